@@ -0,0 +1,4 @@
+// 埋め込みマイグレーション（`sqlx::migrate!`）を migrations/ の変更時に再ビルドする
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
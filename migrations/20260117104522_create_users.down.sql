@@ -0,0 +1,2 @@
+-- users テーブル削除
+DROP TABLE users;
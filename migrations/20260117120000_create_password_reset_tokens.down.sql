@@ -0,0 +1,2 @@
+-- password_reset_tokens テーブル削除
+DROP TABLE password_reset_tokens;
@@ -0,0 +1,2 @@
+-- user_2fa_secrets テーブル削除
+DROP TABLE user_2fa_secrets;
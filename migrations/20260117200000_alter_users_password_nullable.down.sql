@@ -0,0 +1,3 @@
+-- password_hash を NOT NULL に戻す
+-- パスワード未設定のユーザー（ソーシャルログインのみ）がいる場合は失敗する
+ALTER TABLE users ALTER COLUMN password_hash SET NOT NULL;
@@ -0,0 +1,2 @@
+-- user_social_accounts テーブル削除
+DROP TABLE user_social_accounts;
@@ -0,0 +1,2 @@
+-- users.deletion_scheduled_at 削除（インデックスも削除される）
+ALTER TABLE users DROP COLUMN deletion_scheduled_at;
@@ -0,0 +1,10 @@
+-- users テーブルにアカウント削除予約日時を追加
+-- 猶予期間の経過後、バックグラウンドジョブがユーザーを削除する（NULL = 削除予約なし）
+-- 子テーブルは ON DELETE CASCADE により同時に削除される
+
+ALTER TABLE users ADD COLUMN deletion_scheduled_at TIMESTAMPTZ;
+
+-- 削除対象ユーザー検索用の部分インデックス
+CREATE INDEX idx_users_deletion_scheduled_at
+    ON users(deletion_scheduled_at)
+    WHERE deletion_scheduled_at IS NOT NULL;
@@ -0,0 +1,2 @@
+-- audit_events テーブル削除
+DROP TABLE audit_events;
@@ -0,0 +1,25 @@
+-- audit_events テーブル作成
+-- ログイン・同意・パスワード変更などのセキュリティ関連イベントを記録する
+-- user_id はユーザー削除時に CASCADE で削除される（存在しないメールアドレスでのログイン失敗などは NULL）
+
+CREATE TABLE audit_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    -- 操作を行った主体（メールアドレス、管理者など。ユーザーを特定できない場合の識別用）
+    actor VARCHAR(255),
+    event_type VARCHAR(64) NOT NULL,
+    outcome VARCHAR(16) NOT NULL,
+    ip_address VARCHAR(45),
+    user_agent TEXT,
+    metadata JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- ユーザーの最近のアクティビティ取得用インデックス
+CREATE INDEX idx_audit_events_user_id_created_at ON audit_events(user_id, created_at DESC);
+
+-- 管理者検索・保持期間経過イベントの削除用インデックス
+CREATE INDEX idx_audit_events_created_at ON audit_events(created_at);
+
+-- イベント種別での検索用インデックス
+CREATE INDEX idx_audit_events_event_type ON audit_events(event_type, created_at DESC);
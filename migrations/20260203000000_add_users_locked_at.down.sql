@@ -0,0 +1,2 @@
+-- users.locked_at 削除
+ALTER TABLE users DROP COLUMN locked_at;
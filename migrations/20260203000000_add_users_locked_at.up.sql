@@ -0,0 +1,4 @@
+-- users テーブルにアカウントロック日時を追加
+-- セキュリティ通知メールの「心当たりがない」リンクからロックされる（NULL = ロックなし）
+
+ALTER TABLE users ADD COLUMN locked_at TIMESTAMPTZ;
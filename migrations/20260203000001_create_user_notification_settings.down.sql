@@ -0,0 +1,2 @@
+-- user_notification_settings テーブル削除
+DROP TABLE user_notification_settings;
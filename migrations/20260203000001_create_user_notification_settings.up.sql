@@ -0,0 +1,14 @@
+-- user_notification_settings テーブル作成
+-- セキュリティ通知メールの種類ごとの受信設定を格納
+-- 行が存在しないユーザーは全ての通知を受信する
+
+CREATE TABLE user_notification_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    new_device_login BOOLEAN NOT NULL DEFAULT TRUE,
+    password_changed BOOLEAN NOT NULL DEFAULT TRUE,
+    two_factor_changed BOOLEAN NOT NULL DEFAULT TRUE,
+    recovery_codes_used BOOLEAN NOT NULL DEFAULT TRUE,
+    social_account_changed BOOLEAN NOT NULL DEFAULT TRUE,
+    email_changed BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+-- account_lock_tokens テーブル削除
+DROP TABLE account_lock_tokens;
@@ -0,0 +1,17 @@
+-- account_lock_tokens テーブル作成
+-- セキュリティ通知メールに含める「心当たりがない」（アカウントロック）リンク用のトークンを格納
+
+CREATE TABLE account_lock_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(255) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- user_id 検索用インデックス（ユーザーのトークン無効化）
+CREATE INDEX idx_account_lock_tokens_user_id ON account_lock_tokens(user_id);
+
+-- token_hash のユニークインデックス（トークン検索 + 重複防止）
+CREATE UNIQUE INDEX idx_account_lock_tokens_token_hash ON account_lock_tokens(token_hash);
@@ -0,0 +1,8 @@
+-- users.status / lock_reason / locked_until 削除（インデックスも削除される）
+-- locked_at はロック状態の判定に使われていたため、ロック中以外のユーザーはクリアする
+UPDATE users SET locked_at = NULL WHERE status <> 'locked';
+
+ALTER TABLE users
+    DROP COLUMN status,
+    DROP COLUMN lock_reason,
+    DROP COLUMN locked_until;
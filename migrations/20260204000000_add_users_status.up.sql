@@ -0,0 +1,13 @@
+-- アカウント状態（active / locked / disabled / pending_verification）
+ALTER TABLE users
+    ADD COLUMN status VARCHAR(32) NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'locked', 'disabled', 'pending_verification')),
+    -- ロック・無効化の理由
+    ADD COLUMN lock_reason TEXT,
+    -- ロックの有効期限（NULL の場合は解除されるまで）
+    ADD COLUMN locked_until TIMESTAMPTZ;
+
+-- 既存のロック済みアカウントを移行
+UPDATE users SET status = 'locked' WHERE locked_at IS NOT NULL;
+
+CREATE INDEX idx_users_status ON users(status) WHERE status <> 'active';
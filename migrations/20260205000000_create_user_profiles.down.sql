@@ -0,0 +1,2 @@
+-- user_profiles テーブル削除
+DROP TABLE user_profiles;
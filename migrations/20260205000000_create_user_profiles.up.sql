@@ -0,0 +1,17 @@
+-- user_profiles テーブル作成
+-- OIDC の profile クレームに使うプロフィール属性と、拡張用の任意メタデータを格納
+-- 行が存在しないユーザーはプロフィール未設定として扱う
+
+CREATE TABLE user_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    display_name VARCHAR(255),
+    given_name VARCHAR(255),
+    family_name VARCHAR(255),
+    picture_url TEXT,
+    -- BCP 47 言語タグ（例: ja-JP）
+    locale VARCHAR(35),
+    -- IANA タイムゾーン名（例: Asia/Tokyo）
+    zoneinfo VARCHAR(64),
+    metadata JSONB NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+-- email_change_requests テーブル削除
+DROP TABLE email_change_requests;
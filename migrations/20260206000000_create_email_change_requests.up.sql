@@ -0,0 +1,23 @@
+-- email_change_requests テーブル作成
+-- メールアドレス変更の確認リンク（新しいアドレス宛）と取り消しリンク（現在のアドレス宛）のトークンを格納
+
+CREATE TABLE email_change_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    new_email VARCHAR(255) NOT NULL,
+    confirm_token_hash VARCHAR(255) NOT NULL,
+    cancel_token_hash VARCHAR(255) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    confirmed_at TIMESTAMPTZ,
+    cancelled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- user_id 検索用インデックス（ユーザーの未完了リクエストの取り消し）
+CREATE INDEX idx_email_change_requests_user_id ON email_change_requests(user_id);
+
+-- トークンハッシュのユニークインデックス（トークン検索 + 重複防止）
+CREATE UNIQUE INDEX idx_email_change_requests_confirm_token_hash
+    ON email_change_requests(confirm_token_hash);
+CREATE UNIQUE INDEX idx_email_change_requests_cancel_token_hash
+    ON email_change_requests(cancel_token_hash);
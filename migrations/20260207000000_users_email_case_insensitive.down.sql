@@ -0,0 +1,4 @@
+-- 大文字小文字を区別する UNIQUE 制約に戻す（小文字化したメールアドレスは元に戻らない）
+DROP INDEX users_email_key;
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
+CREATE INDEX idx_users_email ON users(email);
@@ -0,0 +1,64 @@
+-- users.email を大文字小文字を区別せず一意にする
+-- 既存のメールアドレスを小文字に正規化し、LOWER(email) のユニークインデックスに置き換える。
+--
+-- SQL で正規化するのは ASCII のみのメールアドレスに限る（IDN ドメインの Punycode 変換や
+-- 非 ASCII 文字の小文字化はアプリケーションの正規化と一致しないため）。
+-- ASCII 以外を含むメールアドレスは書き換えずに WARNING で出力する。
+-- マイグレーション後に `oxgate normalize-emails` で正規化すること。
+--
+-- 正規化すると重複するアカウントがある場合は、重複を全て WARNING で出力してマイグレーションを中断する。
+-- 該当アカウントを統合・変更してから再実行すること。重複の確認クエリ:
+--   SELECT LOWER(TRIM(email)), array_agg(id) FROM users GROUP BY 1 HAVING COUNT(*) > 1;
+
+DO $$
+DECLARE
+    collision RECORD;
+    collisions INTEGER := 0;
+    non_ascii RECORD;
+    non_ascii_count INTEGER := 0;
+BEGIN
+    FOR collision IN
+        SELECT canonical,
+               string_agg(id::text || ' <' || email || '>', ', ' ORDER BY created_at) AS accounts
+        FROM (
+            SELECT id, email, created_at,
+                   CASE WHEN email ~ '^[\x01-\x7f]*$' THEN LOWER(TRIM(email)) ELSE LOWER(email) END
+                       AS canonical
+            FROM users
+        ) AS normalized
+        GROUP BY canonical
+        HAVING COUNT(*) > 1
+    LOOP
+        collisions := collisions + 1;
+        RAISE WARNING 'メールアドレスの重複: % => %', collision.canonical, collision.accounts;
+    END LOOP;
+
+    IF collisions > 0 THEN
+        RAISE EXCEPTION '大文字小文字のみが異なるメールアドレスが % 件あります。該当アカウントを統合・変更してから再実行してください', collisions;
+    END IF;
+
+    FOR non_ascii IN
+        SELECT id, email
+        FROM users
+        WHERE email !~ '^[\x01-\x7f]*$'
+        ORDER BY created_at
+    LOOP
+        non_ascii_count := non_ascii_count + 1;
+        RAISE WARNING 'ASCII 以外を含むメールアドレス: % <%>', non_ascii.id, non_ascii.email;
+    END LOOP;
+
+    IF non_ascii_count > 0 THEN
+        RAISE WARNING 'ASCII 以外を含むメールアドレスが % 件あります。マイグレーション後に oxgate normalize-emails を実行してください', non_ascii_count;
+    END IF;
+END $$;
+
+UPDATE users
+SET email = LOWER(TRIM(email))
+WHERE email <> LOWER(TRIM(email))
+  AND email ~ '^[\x01-\x7f]*$';
+
+-- 大文字小文字を区別しないユニークインデックス
+-- 既存のエラー判定（constraint = "users_email_key"）を維持するため同じ名前を使う
+ALTER TABLE users DROP CONSTRAINT users_email_key;
+DROP INDEX idx_users_email;
+CREATE UNIQUE INDEX users_email_key ON users (LOWER(email));
@@ -0,0 +1,2 @@
+-- scheduled_jobs テーブル削除
+DROP TABLE scheduled_jobs;
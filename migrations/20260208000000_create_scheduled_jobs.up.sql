@@ -0,0 +1,9 @@
+-- scheduled_jobs テーブル作成
+-- バックグラウンドジョブの最終実行日時を格納（複数レプリカで実行間隔を共有する）
+
+CREATE TABLE scheduled_jobs (
+    name VARCHAR(100) PRIMARY KEY,
+    last_started_at TIMESTAMPTZ NOT NULL,
+    last_finished_at TIMESTAMPTZ,
+    last_error TEXT
+);
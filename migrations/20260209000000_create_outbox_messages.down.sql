@@ -0,0 +1,2 @@
+-- outbox_messages テーブル削除
+DROP TABLE outbox_messages;
@@ -0,0 +1,28 @@
+-- outbox_messages テーブル作成
+-- 送信待ちのメッセージ（メールなど）を格納し、ワーカーが再試行付きで配信する
+-- 配信に成功したメッセージは削除し、再試行回数を超えたものは dead として残す
+
+CREATE TABLE outbox_messages (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'dead')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    -- ワーカーが処理中の間は他のワーカーが取得しない
+    locked_until TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- 配信対象の検索用インデックス
+CREATE INDEX idx_outbox_messages_pending
+    ON outbox_messages(next_attempt_at)
+    WHERE status = 'pending';
+
+-- dead メッセージの一覧・削除用インデックス
+CREATE INDEX idx_outbox_messages_dead
+    ON outbox_messages(updated_at)
+    WHERE status = 'dead';
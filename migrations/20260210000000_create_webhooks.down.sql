@@ -0,0 +1,3 @@
+-- webhook_deliveries / webhook_subscriptions テーブル削除
+DROP TABLE webhook_deliveries;
+DROP TABLE webhook_subscriptions;
@@ -0,0 +1,40 @@
+-- webhook_subscriptions / webhook_deliveries テーブル作成
+-- ユーザーのライフサイクルイベントを外部サービスに HMAC-SHA256 署名付きで通知する
+-- 配信は outbox_messages 経由で行い、各配信の結果を webhook_deliveries に記録する
+
+CREATE TABLE webhook_subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    url TEXT NOT NULL,
+    -- 通知するイベント種別（例: user.registered）
+    event_types TEXT[] NOT NULL,
+    -- 署名用シークレット（ENCRYPTION_KEYS で暗号化）
+    secret_encrypted BYTEA NOT NULL,
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE TABLE webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    subscription_id UUID NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+    -- 同じイベントの再送（replay）は同じ event_id を持つ
+    event_id UUID NOT NULL,
+    event_type VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'succeeded', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    response_status INTEGER,
+    last_error TEXT,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- 配信ログの一覧用インデックス
+CREATE INDEX idx_webhook_deliveries_subscription
+    ON webhook_deliveries(subscription_id, created_at DESC);
+
+-- 配信ログの保持期間による削除用インデックス
+CREATE INDEX idx_webhook_deliveries_created_at ON webhook_deliveries(created_at);
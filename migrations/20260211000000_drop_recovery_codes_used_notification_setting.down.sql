@@ -0,0 +1,2 @@
+-- リカバリーコード使用時の通知設定を戻す
+ALTER TABLE user_notification_settings ADD COLUMN recovery_codes_used BOOLEAN NOT NULL DEFAULT TRUE;
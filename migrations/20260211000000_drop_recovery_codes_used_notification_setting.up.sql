@@ -0,0 +1,2 @@
+-- 通知を送る操作がないリカバリーコード使用時の通知設定を削除
+ALTER TABLE user_notification_settings DROP COLUMN recovery_codes_used;
@@ -0,0 +1,82 @@
+//! アクセストークンで認証されたユーザー
+//!
+//! `Authorization: Bearer <access token>` を Hydra のトークンイントロスペクションで検証し、
+//! トークンの subject（ユーザーID）のユーザーを取り出す。
+
+use axum::extract::FromRequestParts;
+use http::HeaderMap;
+use http::header::AUTHORIZATION;
+use http::request::Parts;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::User;
+use crate::services::auth::AuthService;
+use crate::state::AppState;
+
+/// アクセストークンで認証されたユーザー
+#[derive(Debug)]
+pub struct AuthenticatedUser {
+    pub user: User,
+    /// トークンに付与されたスコープ
+    pub scopes: Vec<String>,
+}
+
+impl AuthenticatedUser {
+    /// トークンが指定されたスコープを持つか確認
+    pub fn require_scope(&self, scope: &str) -> Result<(), AppError> {
+        if self.scopes.iter().any(|s| s == scope) {
+            Ok(())
+        } else {
+            Err(AppError::InsufficientScope(scope.to_string()))
+        }
+    }
+}
+
+/// `Authorization: Bearer <token>` のトークン
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty())
+}
+
+impl FromRequestParts<AppState> for AuthenticatedUser {
+    type Rejection = AppError;
+
+    /// # Security
+    /// - アクセストークンはログに出力しない
+    /// - ログインできないアカウント（ロック・削除予定など）のトークンは拒否する
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, AppError> {
+        let token = bearer_token(&parts.headers).ok_or(AppError::AccessTokenInvalid)?;
+
+        let introspected = state.hydra_client.introspect_token(token).await?;
+        if !introspected.active {
+            return Err(AppError::AccessTokenInvalid);
+        }
+
+        // client_credentials のトークンなど、ユーザーに紐付かないトークンは拒否
+        let user_id = introspected
+            .sub
+            .as_deref()
+            .and_then(|sub| Uuid::parse_str(sub).ok())
+            .ok_or(AppError::AccessTokenInvalid)?;
+        let user = state
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::AccessTokenInvalid)?;
+        AuthService::ensure_can_login(&user)?;
+
+        let scopes = introspected
+            .scope
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+
+        Ok(Self { user, scopes })
+    }
+}
@@ -0,0 +1,299 @@
+//! 管理用サブコマンドの実装
+//!
+//! 結果は標準出力、エラーは呼び出し元（main）で標準エラー出力に表示する
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::MigrateCommand;
+use crate::config::Config;
+use crate::database;
+use crate::email_address::normalize_email;
+use crate::error::AppError;
+use crate::handlers::admin::{LockUserRequest, user_admin_service, validate_lock_request};
+use crate::migrations;
+use crate::models::{AuditEventType, NewAuditEvent, User};
+use crate::services::hydra::{CreateOAuth2ClientRequest, HydraClient};
+use crate::services::{
+    AccountService, EmailNormalizationService, HousekeepingService, KeyRotationService,
+};
+use crate::state::AppState;
+
+/// CLI から実行した操作の監査ログの actor
+const CLI_ACTOR: &str = "cli";
+
+/// データベースに接続
+pub async fn connect_database(config: &Config) -> anyhow::Result<PgPool> {
+    database::connect(config).await.map_err(database_error)
+}
+
+/// リードレプリカに接続（未設定の場合は None）
+pub async fn connect_read_replica(config: &Config) -> anyhow::Result<Option<PgPool>> {
+    database::connect_read_replica(config)
+        .await
+        .map_err(database_error)
+}
+
+/// マイグレーション用にデータベースに接続（`statement_timeout` を適用しない）
+pub async fn connect_for_migrations(config: &Config) -> anyhow::Result<PgPool> {
+    database::connect_for_migrations(config)
+        .await
+        .map_err(database_error)
+}
+
+fn database_error(e: sqlx::Error) -> anyhow::Error {
+    tracing::error!(error = ?e, "データベース接続に失敗");
+    anyhow::anyhow!("Failed to connect to database: {}", e)
+}
+
+/// サブコマンド用の AppState を構築
+async fn build_state(config: Config) -> anyhow::Result<AppState> {
+    let db_pool = connect_database(&config).await?;
+    let hydra_client = HydraClient::new(config.hydra_admin_url.clone());
+    AppState::new(db_pool, None, hydra_client, config)
+        .map_err(|e| anyhow::anyhow!("Failed to create AppState: {}", e))
+}
+
+/// `migrate up|status|down`
+pub async fn migrate(config: &Config, command: MigrateCommand) -> anyhow::Result<()> {
+    let pool = connect_for_migrations(config).await?;
+
+    match command {
+        MigrateCommand::Up => {
+            let applied = migrations::run(&pool).await?;
+            if applied.is_empty() {
+                println!("未適用のマイグレーションはありません");
+            }
+            for version in applied {
+                println!("applied  {}", version);
+            }
+        }
+        MigrateCommand::Status => {
+            for status in migrations::status(&pool).await? {
+                let state = match (status.applied, status.modified) {
+                    (true, true) => "modified",
+                    (true, false) => "applied",
+                    (false, _) => "pending",
+                };
+                let description = status
+                    .description
+                    .as_deref()
+                    .unwrap_or("(このバイナリにないマイグレーション)");
+                println!("{:<8} {}  {}", state, status.version, description);
+            }
+        }
+        MigrateCommand::Down { target } => {
+            let reverted = migrations::undo(&pool, target).await?;
+            if reverted.is_empty() {
+                println!("取り消すマイグレーションはありません");
+            }
+            for version in reverted {
+                println!("reverted {}", version);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `create-admin`
+///
+/// 管理 API のスコープ（`ADMIN_TOKEN_SCOPE`）を持つ client_credentials クライアントを
+/// Hydra に作成し、クライアントID とシークレットを表示する
+///
+/// # Security
+/// - client_secret は標準出力にのみ表示し、ログには出力しない
+pub async fn create_admin(config: &Config, name: &str) -> anyhow::Result<()> {
+    let Some(scope) = &config.admin_token_scope else {
+        anyhow::bail!(
+            "ADMIN_TOKEN_SCOPE が設定されていません（管理 API のトークン認証が無効です）"
+        );
+    };
+
+    let hydra_client = HydraClient::new(config.hydra_admin_url.clone());
+    let client = hydra_client
+        .create_oauth2_client(&CreateOAuth2ClientRequest::client_credentials(name, scope))
+        .await?;
+
+    println!("client_id:     {}", client.client_id);
+    println!(
+        "client_secret: {}",
+        client.client_secret.as_deref().unwrap_or("(なし)")
+    );
+    println!("scope:         {}", scope);
+    println!("client_secret は再表示できません。安全な場所に保管してください");
+    Ok(())
+}
+
+/// `user lock`
+pub async fn lock_user(
+    config: Config,
+    user: &str,
+    reason: Option<String>,
+    until: Option<OffsetDateTime>,
+) -> anyhow::Result<()> {
+    let request = LockUserRequest { reason, until };
+    validate_lock_request(&request, OffsetDateTime::now_utc())?;
+
+    let state = build_state(config).await?;
+    let service = user_admin_service(&state);
+
+    let user = find_user(&state, user).await?;
+    let result = service
+        .lock(&user, request.reason.as_deref(), request.until)
+        .await;
+
+    let event = match &result {
+        Ok(_) => NewAuditEvent::success(AuditEventType::AccountLocked),
+        Err(e) => NewAuditEvent::failure(AuditEventType::AccountLocked, e),
+    };
+    state
+        .audit_service
+        .record(event.user(user.id).actor(CLI_ACTOR))
+        .await;
+
+    if result? {
+        println!("ロックしました: {} ({})", user.email, user.id);
+    } else {
+        println!("既にロックされています: {} ({})", user.email, user.id);
+    }
+    Ok(())
+}
+
+/// メールアドレスまたはユーザーIDでユーザーを検索
+async fn find_user(state: &AppState, user: &str) -> Result<User, AppError> {
+    let found = match Uuid::parse_str(user) {
+        Ok(user_id) => state.user_repo.find_by_id(user_id).await?,
+        Err(_) => {
+            state
+                .user_repo
+                .find_by_email(&normalize_email(user)?)
+                .await?
+        }
+    };
+    found.ok_or(AppError::UserNotFound)
+}
+
+/// `rotate-keys`
+///
+/// 旧キーで暗号化された 2FA シークレットを最新キーで再暗号化する
+pub async fn rotate_keys(config: Config) -> anyhow::Result<()> {
+    let state = build_state(config).await?;
+    let service = KeyRotationService::new(state.user_2fa_repo.clone(), state.totp_service.clone());
+
+    let report = service.reencrypt_totp_secrets().await?;
+    println!(
+        "scanned: {}, reencrypted: {}, failed: {}",
+        report.scanned, report.reencrypted, report.failed
+    );
+    if report.failed > 0 {
+        anyhow::bail!(
+            "{} 件のシークレットを復号できませんでした（削除済みのキーで暗号化されています）",
+            report.failed
+        );
+    }
+    Ok(())
+}
+
+/// `normalize-emails`
+///
+/// 保存済みのメールアドレスをアプリケーションと同じ正規化（`normalize_email`）で書き換える。
+/// 重複するもの・形式が不正なものは書き換えずに表示する
+pub async fn normalize_emails(config: Config, dry_run: bool) -> anyhow::Result<()> {
+    let state = build_state(config).await?;
+    let service =
+        EmailNormalizationService::new(state.user_repo.clone(), state.audit_service.clone());
+
+    let report = service.normalize_all(dry_run).await?;
+    for collision in &report.collisions {
+        let conflicting = collision
+            .conflicting_user_id
+            .map_or_else(|| "(不明)".to_string(), |id| id.to_string());
+        println!(
+            "メールアドレスの重複: {} => {} <{}>, {}",
+            collision.canonical, collision.user_id, collision.email, conflicting
+        );
+    }
+    for (user_id, email) in &report.invalid {
+        println!("不正なメールアドレス: {} <{}>", user_id, email);
+    }
+    let label = if dry_run {
+        "to normalize"
+    } else {
+        "normalized"
+    };
+    println!(
+        "scanned: {}, {}: {}, collisions: {}, invalid: {}",
+        report.scanned,
+        label,
+        report.normalized,
+        report.collisions.len(),
+        report.invalid.len()
+    );
+    if !report.collisions.is_empty() || !report.invalid.is_empty() {
+        anyhow::bail!(
+            "{} 件のメールアドレスを正規化できませんでした。該当アカウントを統合・変更してから再実行してください",
+            report.collisions.len() + report.invalid.len()
+        );
+    }
+    Ok(())
+}
+
+/// `cleanup`
+///
+/// 定期ジョブと同じ削除処理（期限切れトークン・監査イベント・削除予定アカウント・
+/// 未確認アカウント・放置された 2FA セットアップ・配信できなかった送信キューのメッセージ・
+/// Webhook の配信ログ）を 1 回実行する
+pub async fn cleanup(config: Config) -> anyhow::Result<()> {
+    let state = build_state(config).await?;
+    let service = housekeeping_service(&state);
+
+    let tokens = service.delete_expired_tokens().await?;
+    println!("password_reset_tokens:  {}", tokens.password_reset_tokens);
+    println!("account_lock_tokens:    {}", tokens.account_lock_tokens);
+    println!("email_change_requests:  {}", tokens.email_change_requests);
+
+    let deleted = service.purge_audit_events().await?;
+    println!("audit_events:           {}", deleted);
+
+    let report = service.purge_scheduled_deletions().await?;
+    println!(
+        "accounts:               {} (failed: {})",
+        report.deleted, report.failed
+    );
+
+    let deleted = service.expire_unverified_accounts().await?;
+    println!("unverified_accounts:    {}", deleted);
+
+    let deleted = service.purge_stale_2fa_setups().await?;
+    println!("pending_2fa_secrets:    {}", deleted);
+
+    let deleted = service.purge_dead_outbox_messages().await?;
+    println!("dead_outbox_messages:   {}", deleted);
+
+    let deleted = service.purge_webhook_deliveries().await?;
+    println!("webhook_deliveries:     {}", deleted);
+    Ok(())
+}
+
+/// 定期ジョブ・`cleanup` で使う HousekeepingService
+pub fn housekeeping_service(state: &AppState) -> HousekeepingService {
+    let account_service = AccountService::new(
+        state.user_repo.clone(),
+        state.user_2fa_repo.clone(),
+        state.social_account_repo.clone(),
+        state.hydra_client.clone(),
+        state.outbox_service.clone(),
+        state.webhook_service.clone(),
+        state.audit_service.clone(),
+        state.profile_service.clone(),
+    );
+    HousekeepingService::new(
+        state.db_pool.clone(),
+        state.audit_service.clone(),
+        account_service,
+        state.outbox_service.clone(),
+        state.webhook_service.clone(),
+        state.config.clone(),
+    )
+}
@@ -0,0 +1,386 @@
+//! コマンドライン引数とサブコマンド
+//!
+//! ```text
+//! oxgate [--config <path>] [serve] [--auto-migrate]
+//! oxgate [--config <path>] --check-config
+//! oxgate [--config <path>] migrate up|status|down [--target <version>]
+//! oxgate [--config <path>] create-admin [--name <client_name>]
+//! oxgate [--config <path>] user lock <email|user_id> [--reason <text>] [--until <rfc3339>]
+//! oxgate [--config <path>] rotate-keys
+//! oxgate [--config <path>] normalize-emails [--dry-run]
+//! oxgate [--config <path>] cleanup
+//! ```
+
+pub mod commands;
+
+use std::path::PathBuf;
+
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+/// `create-admin` で作成するクライアント名（未指定時）
+const DEFAULT_ADMIN_CLIENT_NAME: &str = "oxgate-admin";
+
+/// 使い方
+pub const USAGE: &str = "\
+Usage: oxgate [--config <path>] [COMMAND]
+
+Commands:
+  serve [--auto-migrate]             サーバーを起動（デフォルト）
+  migrate up                         未適用のマイグレーションを適用
+  migrate status                     マイグレーションの適用状況を表示
+  migrate down [--target <version>]  マイグレーションを取り消す（デフォルトは最新の 1 件）
+  create-admin [--name <name>]       管理 API 用の OAuth2 クライアントを Hydra に作成
+  user lock <email|user_id> [--reason <text>] [--until <rfc3339>]
+                                     アカウントをロック
+  rotate-keys                        2FA シークレットを最新の暗号化キーで再暗号化
+  normalize-emails [--dry-run]       保存済みのメールアドレスを正規化（IDN ドメインを含む）
+  cleanup                            期限切れのトークン・監査イベント・削除予定アカウントを削除
+
+Options:
+  --config <path>  設定ファイルのパス
+  --check-config   設定の検証のみ行う
+";
+
+/// コマンドライン引数の解析エラー
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct CliError(String);
+
+/// コマンドライン引数
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliArgs {
+    /// 設定ファイルのパス（`--config <path>`）
+    pub config_path: Option<PathBuf>,
+    pub command: Command,
+}
+
+/// サブコマンド
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// サーバーを起動
+    Serve {
+        /// 起動時に未適用のマイグレーションを適用する（`--auto-migrate`）
+        auto_migrate: bool,
+    },
+    /// 設定の検証のみ行う（`--check-config`）
+    CheckConfig,
+    /// マイグレーション
+    Migrate(MigrateCommand),
+    /// 管理 API 用の OAuth2 クライアントを作成
+    CreateAdmin { name: String },
+    /// アカウントをロック
+    UserLock {
+        /// メールアドレスまたはユーザーID
+        user: String,
+        reason: Option<String>,
+        until: Option<OffsetDateTime>,
+    },
+    /// 2FA シークレットの再暗号化
+    RotateKeys,
+    /// 保存済みメールアドレスの正規化
+    NormalizeEmails {
+        /// 書き換えずに結果のみ表示する（`--dry-run`）
+        dry_run: bool,
+    },
+    /// 期限切れデータの削除
+    Cleanup,
+    /// 使い方を表示（`--help`）
+    Help,
+}
+
+/// `migrate` サブコマンド
+#[derive(Debug, PartialEq, Eq)]
+pub enum MigrateCommand {
+    Up,
+    Status,
+    Down { target: Option<i64> },
+}
+
+impl CliArgs {
+    /// プロセスの引数を解析
+    pub fn from_env() -> Result<Self, CliError> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    /// 引数を解析（プログラム名を除く）
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut config_path = None;
+        let mut check_config = false;
+        let mut help = false;
+        let mut words = Vec::new();
+
+        // グローバルオプションはサブコマンドの前後どちらでも受け付ける
+        let mut iter = args.into_iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--config" => config_path = Some(PathBuf::from(value(&mut iter, "--config")?)),
+                "--check-config" => check_config = true,
+                "-h" | "--help" => help = true,
+                other => match other.strip_prefix("--config=") {
+                    Some(path) => config_path = Some(PathBuf::from(path)),
+                    None => words.push(arg),
+                },
+            }
+        }
+
+        let command = if help {
+            Command::Help
+        } else if check_config {
+            Command::CheckConfig
+        } else {
+            parse_command(words)?
+        };
+
+        Ok(Self {
+            config_path,
+            command,
+        })
+    }
+}
+
+fn parse_command(words: Vec<String>) -> Result<Command, CliError> {
+    let mut iter = words.into_iter();
+    let Some(command) = iter.next() else {
+        return Ok(Command::Serve {
+            auto_migrate: false,
+        });
+    };
+
+    let command = match command.as_str() {
+        "serve" => {
+            let mut auto_migrate = false;
+            for arg in iter.by_ref() {
+                match arg.as_str() {
+                    "--auto-migrate" => auto_migrate = true,
+                    _ => return Err(unexpected(&arg)),
+                }
+            }
+            Command::Serve { auto_migrate }
+        }
+        "migrate" => {
+            let subcommand = iter
+                .next()
+                .ok_or_else(|| CliError("migrate: up / status / down を指定してください".into()))?;
+            match subcommand.as_str() {
+                "up" => Command::Migrate(MigrateCommand::Up),
+                "status" => Command::Migrate(MigrateCommand::Status),
+                "down" => {
+                    let mut target = None;
+                    while let Some(arg) = iter.next() {
+                        match arg.as_str() {
+                            "--target" => {
+                                let version = value(&mut iter, "--target")?;
+                                target = Some(version.parse().map_err(|_| {
+                                    CliError(format!("--target: 不正なバージョン: {}", version))
+                                })?);
+                            }
+                            _ => return Err(unexpected(&arg)),
+                        }
+                    }
+                    Command::Migrate(MigrateCommand::Down { target })
+                }
+                other => return Err(CliError(format!("migrate: 不明なサブコマンド: {}", other))),
+            }
+        }
+        "create-admin" => {
+            let mut name = DEFAULT_ADMIN_CLIENT_NAME.to_string();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--name" => name = value(&mut iter, "--name")?,
+                    _ => return Err(unexpected(&arg)),
+                }
+            }
+            Command::CreateAdmin { name }
+        }
+        "user" => match iter.next().as_deref() {
+            Some("lock") => {
+                let mut user = None;
+                let mut reason = None;
+                let mut until = None;
+                while let Some(arg) = iter.next() {
+                    match arg.as_str() {
+                        "--reason" => reason = Some(value(&mut iter, "--reason")?),
+                        "--until" => {
+                            let value = value(&mut iter, "--until")?;
+                            until =
+                                Some(OffsetDateTime::parse(&value, &Rfc3339).map_err(|_| {
+                                    CliError(format!(
+                                        "--until: RFC 3339 形式で指定してください: {}",
+                                        value
+                                    ))
+                                })?);
+                        }
+                        _ if user.is_none() && !arg.starts_with("--") => user = Some(arg),
+                        _ => return Err(unexpected(&arg)),
+                    }
+                }
+                let user = user.ok_or_else(|| {
+                    CliError("user lock: メールアドレスまたはユーザーIDを指定してください".into())
+                })?;
+                Command::UserLock {
+                    user,
+                    reason,
+                    until,
+                }
+            }
+            Some(other) => return Err(CliError(format!("user: 不明なサブコマンド: {}", other))),
+            None => return Err(CliError("user: lock を指定してください".into())),
+        },
+        "rotate-keys" => Command::RotateKeys,
+        "normalize-emails" => {
+            let mut dry_run = false;
+            for arg in iter.by_ref() {
+                match arg.as_str() {
+                    "--dry-run" => dry_run = true,
+                    _ => return Err(unexpected(&arg)),
+                }
+            }
+            Command::NormalizeEmails { dry_run }
+        }
+        "cleanup" => Command::Cleanup,
+        other => return Err(CliError(format!("不明なコマンド: {}", other))),
+    };
+
+    // 引数を取らないサブコマンドの余分な引数
+    if let Some(arg) = iter.next() {
+        return Err(unexpected(&arg));
+    }
+    Ok(command)
+}
+
+/// オプションの値を取得
+fn value(iter: &mut impl Iterator<Item = String>, option: &str) -> Result<String, CliError> {
+    iter.next()
+        .ok_or_else(|| CliError(format!("{} の値を指定してください", option)))
+}
+
+fn unexpected(arg: &str) -> CliError {
+    CliError(format!("不明な引数: {}", arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, CliError> {
+        CliArgs::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    fn command(args: &[&str]) -> Command {
+        parse(args).unwrap().command
+    }
+
+    #[test]
+    fn test_default_is_serve() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args.config_path, None);
+        assert_eq!(
+            args.command,
+            Command::Serve {
+                auto_migrate: false
+            }
+        );
+        assert_eq!(
+            command(&["serve", "--auto-migrate"]),
+            Command::Serve { auto_migrate: true }
+        );
+    }
+
+    #[test]
+    fn test_global_options() {
+        let args = parse(&["--config", "oxgate.toml", "migrate", "status"]).unwrap();
+        assert_eq!(args.config_path, Some(PathBuf::from("oxgate.toml")));
+        assert_eq!(args.command, Command::Migrate(MigrateCommand::Status));
+
+        let args = parse(&["cleanup", "--config=oxgate.toml"]).unwrap();
+        assert_eq!(args.config_path, Some(PathBuf::from("oxgate.toml")));
+        assert_eq!(args.command, Command::Cleanup);
+
+        assert_eq!(command(&["--check-config"]), Command::CheckConfig);
+        assert_eq!(command(&["--help"]), Command::Help);
+        assert!(parse(&["--config"]).is_err());
+    }
+
+    #[test]
+    fn test_migrate() {
+        assert_eq!(
+            command(&["migrate", "up"]),
+            Command::Migrate(MigrateCommand::Up)
+        );
+        assert_eq!(
+            command(&["migrate", "down"]),
+            Command::Migrate(MigrateCommand::Down { target: None })
+        );
+        assert_eq!(
+            command(&["migrate", "down", "--target", "20260205000000"]),
+            Command::Migrate(MigrateCommand::Down {
+                target: Some(20260205000000)
+            })
+        );
+        assert!(parse(&["migrate"]).is_err());
+        assert!(parse(&["migrate", "sideways"]).is_err());
+        assert!(parse(&["migrate", "down", "--target", "latest"]).is_err());
+        assert!(parse(&["migrate", "up", "extra"]).is_err());
+    }
+
+    #[test]
+    fn test_create_admin() {
+        assert_eq!(
+            command(&["create-admin"]),
+            Command::CreateAdmin {
+                name: "oxgate-admin".to_string()
+            }
+        );
+        assert_eq!(
+            command(&["create-admin", "--name", "ops"]),
+            Command::CreateAdmin {
+                name: "ops".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_user_lock() {
+        assert_eq!(
+            command(&[
+                "user",
+                "lock",
+                "alice@example.com",
+                "--reason",
+                "abuse",
+                "--until",
+                "2030-01-01T00:00:00Z"
+            ]),
+            Command::UserLock {
+                user: "alice@example.com".to_string(),
+                reason: Some("abuse".to_string()),
+                until: Some(OffsetDateTime::parse("2030-01-01T00:00:00Z", &Rfc3339).unwrap()),
+            }
+        );
+        assert!(parse(&["user", "lock"]).is_err());
+        assert!(parse(&["user", "lock", "a@example.com", "b@example.com"]).is_err());
+        assert!(parse(&["user", "lock", "a@example.com", "--until", "tomorrow"]).is_err());
+        assert!(parse(&["user", "delete", "a@example.com"]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_emails() {
+        assert_eq!(
+            command(&["normalize-emails"]),
+            Command::NormalizeEmails { dry_run: false }
+        );
+        assert_eq!(
+            command(&["normalize-emails", "--dry-run"]),
+            Command::NormalizeEmails { dry_run: true }
+        );
+        assert!(parse(&["normalize-emails", "--force"]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        assert!(parse(&["frobnicate"]).is_err());
+        assert!(parse(&["serve", "--port"]).is_err());
+        assert!(parse(&["rotate-keys", "now"]).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! `Accept-Language` ヘッダーのパース
+
+/// 取り出す言語の数の上限
+const MAX_LANGUAGES: usize = 10;
+
+/// 言語タグの最大長
+const MAX_TAG_LEN: usize = 35;
+
+/// 言語タグを優先度（q 値）の高い順に取り出す
+///
+/// 同じ優先度の場合はヘッダーの順序を保つ。ワイルドカード（`*`）、`q=0` の言語、
+/// 不正な要素は除く
+pub fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut languages: Vec<(&str, u16)> = header
+        .split(',')
+        .filter_map(parse_range)
+        .filter(|(tag, quality)| *tag != "*" && *quality > 0)
+        .collect();
+    // 安定ソートのため、同じ優先度の順序は保たれる
+    languages.sort_by_key(|(_, quality)| std::cmp::Reverse(*quality));
+
+    languages
+        .into_iter()
+        .take(MAX_LANGUAGES)
+        .map(|(tag, _)| tag.to_string())
+        .collect()
+}
+
+/// `ja-JP;q=0.8` を（言語タグ, q 値 × 1000）にパース
+fn parse_range(range: &str) -> Option<(&str, u16)> {
+    let mut parts = range.split(';');
+    let tag = parts.next()?.trim();
+    if !is_valid_tag(tag) {
+        return None;
+    }
+
+    let mut quality = 1000;
+    for param in parts {
+        let (key, value) = param.split_once('=')?;
+        if key.trim().eq_ignore_ascii_case("q") {
+            let q = value.trim().parse::<f32>().ok()?;
+            if !(0.0..=1.0).contains(&q) {
+                return None;
+            }
+            quality = (q * 1000.0).round() as u16;
+        }
+    }
+    Some((tag, quality))
+}
+
+fn is_valid_tag(tag: &str) -> bool {
+    tag == "*"
+        || (!tag.is_empty()
+            && tag.len() <= MAX_TAG_LEN
+            && !tag.starts_with('-')
+            && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_by_quality() {
+        assert_eq!(
+            parse_accept_language("en;q=0.5, ja-JP, ja;q=0.9, *;q=0.1, fr;q=0.9"),
+            vec!["ja-JP", "ja", "fr", "en"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_and_excluded_entries() {
+        assert_eq!(
+            parse_accept_language("de;q=0, en;q=abc, <script>, es;q=1.5, , pt-BR"),
+            vec!["pt-BR"]
+        );
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[test]
+    fn test_number_of_languages_is_limited() {
+        let header = (0..20)
+            .map(|i| format!("l{}", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(parse_accept_language(&header).len(), MAX_LANGUAGES);
+    }
+}
@@ -0,0 +1,211 @@
+//! 信頼するリバースプロキシを経由したクライアントの IP アドレスの解決
+//!
+//! 接続元が信頼するプロキシの場合のみ `Forwarded`（RFC 7239）、なければ `X-Forwarded-For` を
+//! 参照する。転送経路を右（接続元に近い側）からたどり、信頼するプロキシではない最初の
+//! アドレスをクライアントとする。左側の値はクライアントが自由に設定できるため、
+//! 信頼するプロキシが付加した値より先は使わない
+
+use std::net::{IpAddr, SocketAddr};
+
+use http::HeaderMap;
+use http::header::{FORWARDED, HeaderName};
+use ipnet::IpNet;
+
+/// `X-Forwarded-For` ヘッダー
+static X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// 転送経路としてたどるアドレス数の上限
+const MAX_HOPS: usize = 32;
+
+/// クライアントの IP アドレスを解決
+///
+/// # Arguments
+/// * `peer` - TCP 接続元の IP アドレス
+/// * `trusted_proxies` - 転送ヘッダーを信頼する接続元（空の場合は常に `peer` を返す）
+pub fn resolve_client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpNet]) -> IpAddr {
+    let peer = peer.to_canonical();
+    if !is_trusted(peer, trusted_proxies) {
+        return peer;
+    }
+
+    let mut client = peer;
+    for hop in forwarded_chain(headers).into_iter().rev().take(MAX_HOPS) {
+        // 解釈できない値（"unknown" や難読化された識別子）より先は信頼できない
+        let Some(ip) = hop else { break };
+        client = ip;
+        if !is_trusted(ip, trusted_proxies) {
+            break;
+        }
+    }
+    client
+}
+
+fn is_trusted(ip: IpAddr, trusted_proxies: &[IpNet]) -> bool {
+    trusted_proxies.iter().any(|net| net.contains(&ip))
+}
+
+/// 転送経路（左がクライアント側）
+///
+/// `Forwarded` がある場合は `X-Forwarded-For` を無視する
+fn forwarded_chain(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let forwarded = header_elements(headers, &FORWARDED);
+    if !forwarded.is_empty() {
+        return forwarded.into_iter().map(forwarded_for).collect();
+    }
+    header_elements(headers, &X_FORWARDED_FOR)
+        .into_iter()
+        .map(parse_node)
+        .collect()
+}
+
+/// 複数行のヘッダーを連結し、カンマ区切りの要素に分割する
+///
+/// 文字列として読めない行は 1 つの不正な要素として扱う（行を読み飛ばすと、
+/// それより左のクライアントが設定した値を信頼することになるため）
+fn header_elements<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Vec<&'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .flat_map(|value| value.to_str().unwrap_or_default().split(','))
+        .collect()
+}
+
+/// `Forwarded` の要素から `for` パラメーターのアドレスを取り出す
+///
+/// 例: `for=192.0.2.60;proto=https`、`for="[2001:db8::17]:4711"`
+fn forwarded_for(element: &str) -> Option<IpAddr> {
+    element.split(';').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("for")
+            .then(|| parse_node(value.trim().trim_matches('"')))
+            .flatten()
+    })
+}
+
+/// アドレス（ポート付き・IPv6 の角括弧付きも可）をパース
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim();
+    let ip = node
+        .parse::<IpAddr>()
+        .or_else(|_| node.parse::<SocketAddr>().map(|addr| addr.ip()))
+        .ok()
+        .or_else(|| {
+            node.strip_prefix('[')?
+                .strip_suffix(']')?
+                .parse::<IpAddr>()
+                .ok()
+        })?;
+    Some(ip.to_canonical())
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    fn trusted() -> Vec<IpNet> {
+        vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "2001:db8:ffff::/48".parse().unwrap(),
+        ]
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_headers() {
+        let headers = headers(&[("x-forwarded-for", "198.51.100.1")]);
+        assert_eq!(
+            resolve_client_ip(ip("203.0.113.7"), &headers, &trusted()),
+            ip("203.0.113.7")
+        );
+        // 信頼するプロキシが未設定の場合も同じ
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &headers, &[]),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn test_spoofed_entries_left_of_client_are_ignored() {
+        // クライアントが偽の値を付けて送り、プロキシが接続元を追記したもの
+        let headers = headers(&[
+            ("x-forwarded-for", "1.2.3.4, 198.51.100.1"),
+            ("x-forwarded-for", "10.0.0.2"),
+        ]);
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+            ip("198.51.100.1")
+        );
+    }
+
+    #[test]
+    fn test_forwarded_takes_precedence() {
+        let headers = headers(&[
+            (
+                "forwarded",
+                r#"for=192.0.2.60;proto=https, For="[2001:db8:cafe::17]:4711""#,
+            ),
+            ("x-forwarded-for", "198.51.100.1"),
+        ]);
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+            ip("2001:db8:cafe::17")
+        );
+    }
+
+    #[test]
+    fn test_unparseable_hop_stops_resolution() {
+        let headers = headers(&[("forwarded", "for=198.51.100.1, for=unknown, for=10.0.0.2")]);
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+            ip("10.0.0.2")
+        );
+    }
+
+    #[test]
+    fn test_all_hops_trusted() {
+        let headers = headers(&[("x-forwarded-for", "10.0.0.3, 10.0.0.2")]);
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &headers, &trusted()),
+            ip("10.0.0.3")
+        );
+        // ヘッダーがない場合は接続元
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.1"), &HeaderMap::new(), &trusted()),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn test_ipv4_mapped_addresses_are_canonicalized() {
+        let headers = headers(&[("x-forwarded-for", "::ffff:198.51.100.1")]);
+        assert_eq!(
+            resolve_client_ip(ip("::ffff:10.0.0.1"), &headers, &trusted()),
+            ip("198.51.100.1")
+        );
+    }
+
+    #[test]
+    fn test_parse_node() {
+        assert_eq!(parse_node("192.0.2.60"), Some(ip("192.0.2.60")));
+        assert_eq!(parse_node(" 192.0.2.60:8080 "), Some(ip("192.0.2.60")));
+        assert_eq!(parse_node("2001:db8::1"), Some(ip("2001:db8::1")));
+        assert_eq!(parse_node("[2001:db8::1]"), Some(ip("2001:db8::1")));
+        assert_eq!(parse_node("[2001:db8::1]:443"), Some(ip("2001:db8::1")));
+        assert_eq!(parse_node("_hidden"), None);
+        assert_eq!(parse_node(""), None);
+    }
+}
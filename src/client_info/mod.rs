@@ -0,0 +1,170 @@
+//! リクエスト元クライアントの情報
+//!
+//! 監査ログ・新しい端末の検出などに使うクライアントの IP アドレス、User-Agent、
+//! `Accept-Language` をリクエストから取り出す。
+//! IP アドレスは TCP 接続元（`ConnectInfo`）を使用し、接続元が信頼するリバースプロキシ
+//! （`TRUSTED_PROXIES`）の場合のみ `Forwarded` / `X-Forwarded-For` から解決する。
+
+mod accept_language;
+mod forwarded;
+mod user_agent;
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use http::header::{ACCEPT_LANGUAGE, USER_AGENT};
+use http::request::Parts;
+use ipnet::IpNet;
+
+use crate::state::AppState;
+
+pub use accept_language::parse_accept_language;
+pub use forwarded::resolve_client_ip;
+pub use user_agent::{DeviceInfo, DeviceKind};
+
+/// 記録する User-Agent の最大長（文字数）
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// リクエスト元クライアントの情報
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientInfo {
+    /// クライアントの IP アドレス（取得できない場合は None）
+    pub ip: Option<IpAddr>,
+    /// User-Agent ヘッダー（長すぎる場合は切り詰める）
+    pub user_agent: Option<String>,
+    /// User-Agent から判定した端末の情報
+    pub device: DeviceInfo,
+    /// `Accept-Language` の言語タグ（優先度の高い順）
+    pub languages: Vec<String>,
+}
+
+impl ClientInfo {
+    /// リクエストからクライアントの情報を取り出す
+    ///
+    /// # Arguments
+    /// * `trusted_proxies` - 転送ヘッダーを信頼する接続元
+    pub fn from_parts(parts: &Parts, trusted_proxies: &[IpNet]) -> Self {
+        let ip = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| resolve_client_ip(addr.ip(), &parts.headers, trusted_proxies));
+
+        let user_agent: Option<String> = parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|ua| ua.chars().take(MAX_USER_AGENT_LEN).collect());
+        let device = user_agent
+            .as_deref()
+            .map(DeviceInfo::parse)
+            .unwrap_or_default();
+
+        let languages = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .map(parse_accept_language)
+            .unwrap_or_default();
+
+        Self {
+            ip,
+            user_agent,
+            device,
+            languages,
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for ClientInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let trusted_proxies = state.config.load().get_trusted_proxies();
+        Ok(Self::from_parts(parts, &trusted_proxies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Request;
+
+    fn extract(request: Request<()>, trusted_proxies: &[IpNet]) -> ClientInfo {
+        let (parts, _) = request.into_parts();
+        ClientInfo::from_parts(&parts, trusted_proxies)
+    }
+
+    fn with_peer(mut request: Request<()>, peer: [u8; 4]) -> Request<()> {
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 54321))));
+        request
+    }
+
+    #[test]
+    fn test_extracts_peer_address_and_user_agent() {
+        let request = Request::builder()
+            .header(USER_AGENT, "Mozilla/5.0")
+            .body(())
+            .unwrap();
+
+        let client = extract(with_peer(request, [203, 0, 113, 7]), &[]);
+        assert_eq!(client.ip, Some(IpAddr::from([203, 0, 113, 7])));
+        assert_eq!(client.user_agent.as_deref(), Some("Mozilla/5.0"));
+    }
+
+    #[test]
+    fn test_missing_connect_info() {
+        let client = extract(Request::builder().body(()).unwrap(), &[]);
+        assert_eq!(client, ClientInfo::default());
+    }
+
+    #[test]
+    fn test_long_user_agent_is_truncated() {
+        let request = Request::builder()
+            .header(USER_AGENT, "a".repeat(MAX_USER_AGENT_LEN * 2))
+            .body(())
+            .unwrap();
+
+        let client = extract(request, &[]);
+        assert_eq!(client.user_agent.unwrap().len(), MAX_USER_AGENT_LEN);
+    }
+
+    #[test]
+    fn test_forwarded_for_only_from_trusted_proxy() {
+        let request = || {
+            Request::builder()
+                .header("x-forwarded-for", "198.51.100.1")
+                .body(())
+                .unwrap()
+        };
+        let trusted = ["10.0.0.0/8".parse().unwrap()];
+
+        let client = extract(with_peer(request(), [10, 0, 0, 1]), &trusted);
+        assert_eq!(client.ip, Some(IpAddr::from([198, 51, 100, 1])));
+
+        let client = extract(with_peer(request(), [203, 0, 113, 7]), &trusted);
+        assert_eq!(client.ip, Some(IpAddr::from([203, 0, 113, 7])));
+    }
+
+    #[test]
+    fn test_parses_device_and_languages() {
+        let request = Request::builder()
+            .header(
+                USER_AGENT,
+                "Mozilla/5.0 (X11; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+            )
+            .header(ACCEPT_LANGUAGE, "en;q=0.8, ja")
+            .body(())
+            .unwrap();
+
+        let client = extract(request, &[]);
+        assert_eq!(client.device.browser, Some("Firefox"));
+        assert_eq!(client.device.kind, DeviceKind::Desktop);
+        assert_eq!(client.languages, vec!["ja", "en"]);
+    }
+}
@@ -0,0 +1,196 @@
+//! User-Agent の簡易パース
+//!
+//! 監査ログやセキュリティ通知で端末を識別するため、主要なブラウザー・OS と端末の種類を判定する。
+//! 網羅的なパーサーではなく、判定できないものは None（種類は `Unknown`）とする
+
+/// 端末の種類
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeviceKind {
+    Desktop,
+    Mobile,
+    Tablet,
+    /// クローラー・コマンドラインツールなど
+    Bot,
+    #[default]
+    Unknown,
+}
+
+/// User-Agent から判定した端末の情報
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// ブラウザー名
+    pub browser: Option<&'static str>,
+    /// ブラウザーのメジャーバージョン
+    pub browser_version: Option<String>,
+    /// OS 名
+    pub os: Option<&'static str>,
+    pub kind: DeviceKind,
+}
+
+/// ブラウザーの判定（上から順に照合する。Chromium 派生は `Chrome/` も含むため先に判定する）
+const BROWSERS: [(&str, &str); 9] = [
+    ("Edg/", "Edge"),
+    ("EdgA/", "Edge"),
+    ("EdgiOS/", "Edge"),
+    ("OPR/", "Opera"),
+    ("SamsungBrowser/", "Samsung Internet"),
+    ("Firefox/", "Firefox"),
+    ("FxiOS/", "Firefox"),
+    ("CriOS/", "Chrome"),
+    ("Chrome/", "Chrome"),
+];
+
+/// OS の判定（上から順に照合する。Android は `Linux` も含むため先に判定する）
+const OPERATING_SYSTEMS: [(&str, &str); 8] = [
+    ("Windows NT", "Windows"),
+    ("iPhone", "iOS"),
+    ("iPod", "iOS"),
+    ("iPad", "iPadOS"),
+    ("Android", "Android"),
+    ("CrOS", "ChromeOS"),
+    ("Macintosh", "macOS"),
+    ("Linux", "Linux"),
+];
+
+/// ボットとみなす User-Agent に含まれる文字列（小文字で照合）
+const BOT_MARKERS: [&str; 8] = [
+    "bot",
+    "crawler",
+    "spider",
+    "headless",
+    "curl/",
+    "wget/",
+    "python-requests/",
+    "go-http-client/",
+];
+
+impl DeviceInfo {
+    /// User-Agent をパース
+    pub fn parse(user_agent: &str) -> Self {
+        let (browser, browser_version) = match BROWSERS
+            .iter()
+            .find(|(token, _)| user_agent.contains(token))
+        {
+            Some((token, name)) => (Some(*name), major_version(user_agent, token)),
+            // Safari はバージョンを `Version/` に持つ
+            None if user_agent.contains("Safari/") && user_agent.contains("Version/") => {
+                (Some("Safari"), major_version(user_agent, "Version/"))
+            }
+            None => (None, None),
+        };
+        let os = OPERATING_SYSTEMS
+            .iter()
+            .find(|(token, _)| user_agent.contains(token))
+            .map(|(_, name)| *name);
+
+        Self {
+            browser,
+            browser_version,
+            os,
+            kind: device_kind(user_agent, os),
+        }
+    }
+}
+
+fn device_kind(user_agent: &str, os: Option<&str>) -> DeviceKind {
+    let lower = user_agent.to_ascii_lowercase();
+    if BOT_MARKERS.iter().any(|marker| lower.contains(marker)) {
+        DeviceKind::Bot
+    } else if user_agent.contains("iPad")
+        || user_agent.contains("Tablet")
+        || (os == Some("Android") && !user_agent.contains("Mobile"))
+    {
+        DeviceKind::Tablet
+    } else if user_agent.contains("Mobi") || os == Some("iOS") {
+        DeviceKind::Mobile
+    } else if os.is_some() {
+        DeviceKind::Desktop
+    } else {
+        DeviceKind::Unknown
+    }
+}
+
+/// `token` に続くバージョンのメジャー番号
+fn major_version(user_agent: &str, token: &str) -> Option<String> {
+    let (_, rest) = user_agent.split_once(token)?;
+    let major: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    (!major.is_empty()).then_some(major)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(
+        browser: &'static str,
+        version: &str,
+        os: &'static str,
+        kind: DeviceKind,
+    ) -> DeviceInfo {
+        DeviceInfo {
+            browser: Some(browser),
+            browser_version: Some(version.to_string()),
+            os: Some(os),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_desktop_browsers() {
+        let cases = [
+            (
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+                device("Chrome", "120", "Windows", DeviceKind::Desktop),
+            ),
+            (
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.2210.91",
+                device("Edge", "120", "Windows", DeviceKind::Desktop),
+            ),
+            (
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+                device("Safari", "17", "macOS", DeviceKind::Desktop),
+            ),
+            (
+                "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+                device("Firefox", "121", "Linux", DeviceKind::Desktop),
+            ),
+        ];
+        for (user_agent, expected) in cases {
+            assert_eq!(DeviceInfo::parse(user_agent), expected, "{}", user_agent);
+        }
+    }
+
+    #[test]
+    fn test_mobile_and_tablet() {
+        let cases = [
+            (
+                "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/120.0.6099.119 Mobile/15E148 Safari/604.1",
+                device("Chrome", "120", "iOS", DeviceKind::Mobile),
+            ),
+            (
+                "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36",
+                device("Chrome", "120", "Android", DeviceKind::Mobile),
+            ),
+            (
+                "Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/23.0 Chrome/115.0.0.0 Safari/537.36",
+                device("Samsung Internet", "23", "Android", DeviceKind::Tablet),
+            ),
+        ];
+        for (user_agent, expected) in cases {
+            assert_eq!(DeviceInfo::parse(user_agent), expected, "{}", user_agent);
+        }
+    }
+
+    #[test]
+    fn test_bots_and_unknown() {
+        assert_eq!(DeviceInfo::parse("curl/8.4.0").kind, DeviceKind::Bot);
+        assert_eq!(
+            DeviceInfo::parse(
+                "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+            )
+            .kind,
+            DeviceKind::Bot
+        );
+        assert_eq!(DeviceInfo::parse("my-app"), DeviceInfo::default());
+    }
+}
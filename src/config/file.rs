@@ -0,0 +1,534 @@
+//! 設定ファイル（TOML / YAML）
+//!
+//! セクション構造の設定ファイルを読み込み、環境変数と同じキー名のマップに変換する。
+//! 変換後のマップは環境変数で上書きされる（環境変数が優先）。
+//!
+//! ```toml
+//! [server]
+//! host = "0.0.0.0"
+//! port = 8080
+//! allowed_origins = ["https://example.com"]
+//!
+//! [providers.google]
+//! client_id = "..."
+//! redirect_uri = "https://auth.example.com/api/oauth/google/callback"
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::ConfigError;
+
+/// 設定ファイルのルート
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub server: ServerSection,
+    #[serde(default)]
+    pub database: DatabaseSection,
+    #[serde(default)]
+    pub hydra: HydraSection,
+    #[serde(default)]
+    pub email: EmailSection,
+    #[serde(default)]
+    pub totp: TotpSection,
+    #[serde(default)]
+    pub encryption: EncryptionSection,
+    #[serde(default)]
+    pub providers: ProvidersSection,
+    #[serde(default)]
+    pub policies: PoliciesSection,
+    #[serde(default)]
+    pub argon2: Argon2Section,
+    #[serde(default)]
+    pub admin: AdminSection,
+    #[serde(default)]
+    pub scheduler: SchedulerSection,
+    #[serde(default)]
+    pub telemetry: TelemetrySection,
+}
+
+/// `[server]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerSection {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    /// 許可するオリジン（未設定の場合は全オリジン許可）
+    pub allowed_origins: Option<Vec<String>>,
+    /// 信頼するリバースプロキシ（CIDR または IP アドレス）
+    pub trusted_proxies: Option<Vec<String>>,
+    pub tls_cert_path: Option<String>,
+    pub tls_key_path: Option<String>,
+}
+
+/// `[database]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatabaseSection {
+    pub url: Option<String>,
+    pub auto_migrate: Option<bool>,
+    pub max_connections: Option<u32>,
+    pub min_connections: Option<u32>,
+    pub acquire_timeout_secs: Option<u64>,
+    pub idle_timeout_secs: Option<u64>,
+    pub max_lifetime_secs: Option<u64>,
+    pub statement_timeout_ms: Option<u64>,
+    pub ssl_mode: Option<String>,
+    pub ssl_root_cert: Option<String>,
+    pub ssl_client_cert: Option<String>,
+    pub ssl_client_key: Option<String>,
+    /// リードレプリカの URL
+    pub read_url: Option<String>,
+}
+
+/// `[hydra]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HydraSection {
+    pub admin_url: Option<String>,
+}
+
+/// `[email]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmailSection {
+    pub smtp_host: Option<String>,
+    pub smtp_port: Option<u16>,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub from_address: Option<String>,
+    pub password_reset_url_base: Option<String>,
+    pub account_lock_url_base: Option<String>,
+    pub email_change_url_base: Option<String>,
+}
+
+/// `[totp]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TotpSection {
+    pub issuer: Option<String>,
+}
+
+/// `[encryption]` セクション
+///
+/// キーリングは `{ "1" = "<base64>", "2" = "<base64>" }` 形式で指定する
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptionSection {
+    pub key: Option<String>,
+    pub keys: Option<BTreeMap<String, String>>,
+    pub oauth_state_secret: Option<String>,
+    pub oauth_state_secrets: Option<BTreeMap<String, String>>,
+}
+
+/// `[argon2]` セクション（パスワードハッシュのコスト）
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Argon2Section {
+    pub memory_kib: Option<u32>,
+    pub iterations: Option<u32>,
+    pub parallelism: Option<u32>,
+}
+
+/// `[admin]` セクション（管理 API）
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdminSection {
+    pub api_key: Option<String>,
+    pub token_scope: Option<String>,
+    /// 管理 API を別のアドレスで待ち受ける
+    pub listen_addr: Option<String>,
+    /// クライアント証明書を発行した CA 証明書（mTLS）
+    pub tls_client_ca_path: Option<String>,
+}
+
+/// `[scheduler]` セクション（バックグラウンドジョブ）
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchedulerSection {
+    pub enabled: Option<bool>,
+}
+
+/// `[telemetry]` セクション（分散トレーシング）
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetrySection {
+    /// OTLP/HTTP コレクターのベース URL
+    pub otlp_endpoint: Option<String>,
+    pub service_name: Option<String>,
+    pub traces_sample_ratio: Option<f64>,
+}
+
+/// `[providers]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProvidersSection {
+    pub google: Option<ProviderSection>,
+    pub github: Option<ProviderSection>,
+}
+
+/// `[providers.<name>]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderSection {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub redirect_uri: Option<String>,
+}
+
+/// `[policies]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoliciesSection {
+    pub password_reset_token_ttl_secs: Option<i64>,
+    pub account_lock_token_ttl_secs: Option<i64>,
+    pub email_change_token_ttl_secs: Option<i64>,
+    pub disposable_email_domains_path: Option<String>,
+    pub account_deletion_grace_period_secs: Option<i64>,
+    pub audit_retention_days: Option<i64>,
+    pub unverified_account_ttl_secs: Option<i64>,
+    pub pending_2fa_ttl_secs: Option<i64>,
+    pub password: Option<PasswordPolicySection>,
+}
+
+/// `[policies.password]` セクション
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PasswordPolicySection {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub require_uppercase: Option<bool>,
+    pub require_lowercase: Option<bool>,
+    pub require_digit: Option<bool>,
+    pub require_symbol: Option<bool>,
+    pub min_strength: Option<u8>,
+    pub breach_check_url: Option<String>,
+    pub breach_dataset_path: Option<String>,
+}
+
+impl ConfigFile {
+    /// ファイルを読み込む（拡張子で TOML / YAML を判定）
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ConfigError::File {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+
+        Self::parse(&content, extension).map_err(|message| ConfigError::File {
+            path: path.display().to_string(),
+            message,
+        })
+    }
+
+    /// 文字列をパース
+    ///
+    /// # Arguments
+    /// * `extension` - `toml` / `yaml` / `yml`
+    pub fn parse(content: &str, extension: &str) -> Result<Self, String> {
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => toml::from_str(content).map_err(|e| e.to_string()),
+            "yaml" | "yml" => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            other => Err(format!(
+                "unsupported config file extension: '{}' (toml / yaml / yml)",
+                other
+            )),
+        }
+    }
+
+    /// 環境変数名（大文字）→ 値 のマップに変換
+    pub fn into_env(self) -> HashMap<String, String> {
+        let mut env = HashMap::new();
+        let mut set = |key: &str, value: Option<String>| {
+            if let Some(value) = value {
+                env.insert(key.to_string(), value);
+            }
+        };
+
+        set("HOST", self.server.host);
+        set("PORT", self.server.port.map(|p| p.to_string()));
+        set(
+            "ALLOWED_ORIGINS",
+            self.server.allowed_origins.map(|o| o.join(",")),
+        );
+        set(
+            "TRUSTED_PROXIES",
+            self.server.trusted_proxies.map(|p| p.join(",")),
+        );
+        set("TLS_CERT_PATH", self.server.tls_cert_path);
+        set("TLS_KEY_PATH", self.server.tls_key_path);
+
+        set("DATABASE_URL", self.database.url);
+        set(
+            "DATABASE_AUTO_MIGRATE",
+            self.database.auto_migrate.map(|v| v.to_string()),
+        );
+        set(
+            "DATABASE_MAX_CONNECTIONS",
+            self.database.max_connections.map(|v| v.to_string()),
+        );
+        set(
+            "DATABASE_MIN_CONNECTIONS",
+            self.database.min_connections.map(|v| v.to_string()),
+        );
+        set(
+            "DATABASE_ACQUIRE_TIMEOUT_SECS",
+            self.database.acquire_timeout_secs.map(|v| v.to_string()),
+        );
+        set(
+            "DATABASE_IDLE_TIMEOUT_SECS",
+            self.database.idle_timeout_secs.map(|v| v.to_string()),
+        );
+        set(
+            "DATABASE_MAX_LIFETIME_SECS",
+            self.database.max_lifetime_secs.map(|v| v.to_string()),
+        );
+        set(
+            "DATABASE_STATEMENT_TIMEOUT_MS",
+            self.database.statement_timeout_ms.map(|v| v.to_string()),
+        );
+        set("DATABASE_SSL_MODE", self.database.ssl_mode);
+        set("DATABASE_SSL_ROOT_CERT", self.database.ssl_root_cert);
+        set("DATABASE_SSL_CLIENT_CERT", self.database.ssl_client_cert);
+        set("DATABASE_SSL_CLIENT_KEY", self.database.ssl_client_key);
+        set("DATABASE_READ_URL", self.database.read_url);
+        set("HYDRA_ADMIN_URL", self.hydra.admin_url);
+
+        set("SMTP_HOST", self.email.smtp_host);
+        set("SMTP_PORT", self.email.smtp_port.map(|p| p.to_string()));
+        set("SMTP_USERNAME", self.email.smtp_username);
+        set("SMTP_PASSWORD", self.email.smtp_password);
+        set("SMTP_FROM_ADDRESS", self.email.from_address);
+        set(
+            "PASSWORD_RESET_URL_BASE",
+            self.email.password_reset_url_base,
+        );
+        set("ACCOUNT_LOCK_URL_BASE", self.email.account_lock_url_base);
+        set("EMAIL_CHANGE_URL_BASE", self.email.email_change_url_base);
+
+        set("TOTP_ISSUER", self.totp.issuer);
+
+        set("ENCRYPTION_KEY", self.encryption.key);
+        set("ENCRYPTION_KEYS", self.encryption.keys.map(keyring_spec));
+        set("OAUTH_STATE_SECRET", self.encryption.oauth_state_secret);
+        set(
+            "OAUTH_STATE_SECRETS",
+            self.encryption.oauth_state_secrets.map(keyring_spec),
+        );
+
+        for (prefix, provider) in [
+            ("GOOGLE", self.providers.google),
+            ("GITHUB", self.providers.github),
+        ] {
+            let provider = provider.unwrap_or_default();
+            set(&format!("{}_CLIENT_ID", prefix), provider.client_id);
+            set(&format!("{}_CLIENT_SECRET", prefix), provider.client_secret);
+            set(&format!("{}_REDIRECT_URI", prefix), provider.redirect_uri);
+        }
+
+        set(
+            "PASSWORD_RESET_TOKEN_TTL_SECS",
+            self.policies
+                .password_reset_token_ttl_secs
+                .map(|t| t.to_string()),
+        );
+
+        set(
+            "ACCOUNT_LOCK_TOKEN_TTL_SECS",
+            self.policies
+                .account_lock_token_ttl_secs
+                .map(|t| t.to_string()),
+        );
+
+        set(
+            "DISPOSABLE_EMAIL_DOMAINS_PATH",
+            self.policies.disposable_email_domains_path,
+        );
+
+        set(
+            "EMAIL_CHANGE_TOKEN_TTL_SECS",
+            self.policies
+                .email_change_token_ttl_secs
+                .map(|t| t.to_string()),
+        );
+
+        set(
+            "ACCOUNT_DELETION_GRACE_PERIOD_SECS",
+            self.policies
+                .account_deletion_grace_period_secs
+                .map(|t| t.to_string()),
+        );
+
+        set(
+            "AUDIT_RETENTION_DAYS",
+            self.policies.audit_retention_days.map(|d| d.to_string()),
+        );
+        set(
+            "UNVERIFIED_ACCOUNT_TTL_SECS",
+            self.policies
+                .unverified_account_ttl_secs
+                .map(|t| t.to_string()),
+        );
+        set(
+            "PENDING_2FA_TTL_SECS",
+            self.policies.pending_2fa_ttl_secs.map(|t| t.to_string()),
+        );
+
+        let password = self.policies.password.unwrap_or_default();
+        set(
+            "PASSWORD_MIN_LENGTH",
+            password.min_length.map(|v| v.to_string()),
+        );
+        set(
+            "PASSWORD_MAX_LENGTH",
+            password.max_length.map(|v| v.to_string()),
+        );
+        set(
+            "PASSWORD_REQUIRE_UPPERCASE",
+            password.require_uppercase.map(|v| v.to_string()),
+        );
+        set(
+            "PASSWORD_REQUIRE_LOWERCASE",
+            password.require_lowercase.map(|v| v.to_string()),
+        );
+        set(
+            "PASSWORD_REQUIRE_DIGIT",
+            password.require_digit.map(|v| v.to_string()),
+        );
+        set(
+            "PASSWORD_REQUIRE_SYMBOL",
+            password.require_symbol.map(|v| v.to_string()),
+        );
+        set(
+            "PASSWORD_MIN_STRENGTH",
+            password.min_strength.map(|v| v.to_string()),
+        );
+        set("PASSWORD_BREACH_CHECK_URL", password.breach_check_url);
+        set("PASSWORD_BREACH_DATASET_PATH", password.breach_dataset_path);
+
+        set(
+            "ARGON2_MEMORY_KIB",
+            self.argon2.memory_kib.map(|v| v.to_string()),
+        );
+        set(
+            "ARGON2_ITERATIONS",
+            self.argon2.iterations.map(|v| v.to_string()),
+        );
+        set(
+            "ARGON2_PARALLELISM",
+            self.argon2.parallelism.map(|v| v.to_string()),
+        );
+
+        set("ADMIN_API_KEY", self.admin.api_key);
+        set("ADMIN_TOKEN_SCOPE", self.admin.token_scope);
+        set("ADMIN_LISTEN_ADDR", self.admin.listen_addr);
+        set("ADMIN_TLS_CLIENT_CA_PATH", self.admin.tls_client_ca_path);
+
+        set(
+            "SCHEDULER_ENABLED",
+            self.scheduler.enabled.map(|v| v.to_string()),
+        );
+
+        set("OTEL_EXPORTER_OTLP_ENDPOINT", self.telemetry.otlp_endpoint);
+        set("OTEL_SERVICE_NAME", self.telemetry.service_name);
+        set(
+            "OTEL_TRACES_SAMPLE_RATIO",
+            self.telemetry.traces_sample_ratio.map(|v| v.to_string()),
+        );
+
+        env
+    }
+}
+
+/// キーリングのマップを `<id>:<base64>` のカンマ区切りに変換
+fn keyring_spec(keys: BTreeMap<String, String>) -> String {
+    keys.into_iter()
+        .map(|(id, key)| format!("{}:{}", id, key))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_toml_sections() {
+        let content = r#"
+            [server]
+            port = 8080
+            allowed_origins = ["https://example.com", "https://admin.example.com"]
+
+            [encryption.keys]
+            1 = "AAAA"
+            2 = "BBBB"
+
+            [providers.github]
+            client_id = "github-id"
+            redirect_uri = "http://localhost:8080/api/oauth/github/callback"
+        "#;
+
+        let env = ConfigFile::parse(content, "toml").unwrap().into_env();
+
+        assert_eq!(env["PORT"], "8080");
+        assert_eq!(
+            env["ALLOWED_ORIGINS"],
+            "https://example.com,https://admin.example.com"
+        );
+        assert_eq!(env["ENCRYPTION_KEYS"], "1:AAAA,2:BBBB");
+        assert_eq!(env["GITHUB_CLIENT_ID"], "github-id");
+        assert!(!env.contains_key("GOOGLE_CLIENT_ID"));
+        assert!(!env.contains_key("HOST"));
+    }
+
+    #[test]
+    fn test_parse_yaml_sections() {
+        let content = r#"
+database:
+  url: postgres://localhost/oxgate
+  max_connections: 20
+  ssl_mode: verify-full
+  read_url: postgres://replica/oxgate
+email:
+  smtp_host: smtp.example.com
+  from_address: noreply@example.com
+policies:
+  password_reset_token_ttl_secs: 900
+  password:
+    min_length: 12
+    require_symbol: true
+"#;
+
+        let env = ConfigFile::parse(content, "yml").unwrap().into_env();
+
+        assert_eq!(env["DATABASE_URL"], "postgres://localhost/oxgate");
+        assert_eq!(env["DATABASE_MAX_CONNECTIONS"], "20");
+        assert_eq!(env["DATABASE_SSL_MODE"], "verify-full");
+        assert_eq!(env["DATABASE_READ_URL"], "postgres://replica/oxgate");
+        assert_eq!(env["SMTP_HOST"], "smtp.example.com");
+        assert_eq!(env["SMTP_FROM_ADDRESS"], "noreply@example.com");
+        assert_eq!(env["PASSWORD_RESET_TOKEN_TTL_SECS"], "900");
+        assert_eq!(env["PASSWORD_MIN_LENGTH"], "12");
+        assert_eq!(env["PASSWORD_REQUIRE_SYMBOL"], "true");
+        assert!(!env.contains_key("PASSWORD_MAX_LENGTH"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_fields() {
+        let result = ConfigFile::parse("[server]\nprot = 8080\n", "toml");
+        assert!(result.unwrap_err().contains("prot"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_extension() {
+        assert!(ConfigFile::parse("", "json").is_err());
+    }
+}
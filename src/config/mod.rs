@@ -0,0 +1,550 @@
+pub mod file;
+pub mod validation;
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use ipnet::IpNet;
+
+use secrecy::{ExposeSecret, SecretBox};
+use serde::Deserialize;
+
+use crate::error::AppError;
+use crate::secrets::{self, SecretError};
+use crate::services::keyring::Keyring;
+
+pub use file::ConfigFile;
+pub use validation::{ValidationErrors, ValidationIssue};
+
+/// 設定ファイルのパスを指定する環境変数
+pub const CONFIG_PATH_ENV: &str = "OXGATE_CONFIG";
+
+/// 必須の設定項目（環境変数名）
+const REQUIRED_KEYS: [&str; 3] = ["DATABASE_URL", "HYDRA_ADMIN_URL", "TOTP_ISSUER"];
+
+/// 設定読み込みエラー
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("環境変数の解析に失敗: {0}")]
+    Env(#[from] envy::Error),
+
+    #[error("シークレットの解決に失敗: {0}")]
+    Secret(#[from] SecretError),
+
+    #[error("設定ファイルの読み込みに失敗: {path}: {message}")]
+    File { path: String, message: String },
+
+    #[error("{0}")]
+    Invalid(#[from] ValidationErrors),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    pub database_url: SecretBox<String>,
+    /// `serve` の起動時に未適用のマイグレーションを適用する
+    #[serde(default)]
+    pub database_auto_migrate: bool,
+    /// 接続プールの最大接続数
+    #[serde(default = "default_database_max_connections")]
+    pub database_max_connections: u32,
+    /// 接続プールで維持する最小接続数
+    #[serde(default)]
+    pub database_min_connections: u32,
+    /// プールから接続を取得するまでの待ち時間の上限（秒）
+    #[serde(default = "default_database_acquire_timeout_secs")]
+    pub database_acquire_timeout_secs: u64,
+    /// アイドル状態の接続を閉じるまでの時間（秒、0 の場合は閉じない）
+    #[serde(default = "default_database_idle_timeout_secs")]
+    pub database_idle_timeout_secs: u64,
+    /// 接続を作り直すまでの時間（秒、0 の場合は作り直さない）
+    #[serde(default = "default_database_max_lifetime_secs")]
+    pub database_max_lifetime_secs: u64,
+    /// クエリのタイムアウト（ミリ秒、Postgres の `statement_timeout`。0 の場合は無制限）
+    /// マイグレーションには適用しない
+    #[serde(default)]
+    pub database_statement_timeout_ms: u64,
+    /// TLS モード（disable / allow / prefer / require / verify-ca / verify-full）
+    /// 未設定の場合は `DATABASE_URL` の `sslmode`（なければ prefer）に従う
+    #[serde(default)]
+    pub database_ssl_mode: Option<String>,
+    /// サーバー証明書を検証する CA 証明書（PEM）のパス
+    #[serde(default)]
+    pub database_ssl_root_cert: Option<String>,
+    /// クライアント証明書（PEM）のパス
+    #[serde(default)]
+    pub database_ssl_client_cert: Option<String>,
+    /// クライアント証明書の秘密鍵（PEM）のパス
+    #[serde(default)]
+    pub database_ssl_client_key: Option<String>,
+    /// 読み取り専用クエリに使うリードレプリカの URL（未設定の場合はプライマリを使う）
+    /// プール・TLS の設定はプライマリと共通
+    pub database_read_url: Option<SecretBox<String>>,
+    pub hydra_admin_url: String,
+    #[serde(default = "default_host")]
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// TLS 証明書チェーン（PEM）のパス（未設定の場合は平文の HTTP で待ち受ける）
+    /// ファイルの更新は再起動せずに反映される
+    #[serde(default)]
+    pub tls_cert_path: Option<String>,
+    /// TLS 秘密鍵（PEM）のパス
+    #[serde(default)]
+    pub tls_key_path: Option<String>,
+
+    // CORS設定
+    /// 許可するオリジン（カンマ区切り、空の場合は全オリジン許可）
+    /// 例: "https://example.com,https://admin.example.com"
+    #[serde(default)]
+    pub allowed_origins: Option<String>,
+
+    // リバースプロキシ設定
+    /// 信頼するリバースプロキシ（CIDR または IP アドレス、カンマ区切り）
+    /// 接続元がこれらに含まれる場合のみ `Forwarded` / `X-Forwarded-For` を信頼する
+    /// 例: "10.0.0.0/8,192.168.1.10"
+    #[serde(default)]
+    pub trusted_proxies: Option<String>,
+
+    // SMTP設定（オプション - email機能有効時のみ使用）
+    #[serde(default)]
+    pub smtp_host: Option<String>,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    pub smtp_username: Option<SecretBox<String>>,
+    pub smtp_password: Option<SecretBox<String>>,
+    #[serde(default)]
+    pub smtp_from_address: Option<String>,
+
+    // パスワードリセット設定
+    #[serde(default)]
+    pub password_reset_url_base: Option<String>,
+    #[serde(default = "default_password_reset_token_ttl_secs")]
+    pub password_reset_token_ttl_secs: i64,
+
+    // セキュリティ通知設定
+    /// 「心当たりがない」リンクの遷移先（フロントエンドのアカウントロックページ）
+    #[serde(default)]
+    pub account_lock_url_base: Option<String>,
+    /// 「心当たりがない」リンクの有効期限（秒）
+    #[serde(default = "default_account_lock_token_ttl_secs")]
+    pub account_lock_token_ttl_secs: i64,
+
+    // メールアドレス設定
+    /// 登録・変更を拒否する使い捨てメールドメインのリスト（1 行 1 ドメイン）
+    #[serde(default)]
+    pub disposable_email_domains_path: Option<String>,
+
+    // メールアドレス変更設定
+    /// 確認・取り消しリンクの遷移先（`{base}/confirm`、`{base}/cancel`）
+    #[serde(default)]
+    pub email_change_url_base: Option<String>,
+    /// 確認・取り消しリンクの有効期限（秒）
+    #[serde(default = "default_email_change_token_ttl_secs")]
+    pub email_change_token_ttl_secs: i64,
+
+    // アカウント削除設定
+    /// 削除リクエストから実際に削除するまでの猶予期間（秒、0 の場合は即時削除）
+    #[serde(default = "default_account_deletion_grace_period_secs")]
+    pub account_deletion_grace_period_secs: i64,
+
+    // 監査ログ設定
+    /// 監査イベントの保持日数（0 の場合は削除しない）
+    #[serde(default = "default_audit_retention_days")]
+    pub audit_retention_days: i64,
+
+    // バックグラウンドジョブ設定
+    /// 定期ジョブ（期限切れデータの削除など）を実行するか
+    #[serde(default = "default_scheduler_enabled")]
+    pub scheduler_enabled: bool,
+    /// 未確認（`pending_verification`）アカウントを削除するまでの期間（秒、0 の場合は削除しない）
+    #[serde(default = "default_unverified_account_ttl_secs")]
+    pub unverified_account_ttl_secs: i64,
+    /// 有効化されていない 2FA シークレットを削除するまでの期間（秒）
+    #[serde(default = "default_pending_2fa_ttl_secs")]
+    pub pending_2fa_ttl_secs: i64,
+
+    // 管理 API 設定
+    /// 管理 API（`/admin/api`）の API キー（未設定の場合は管理 API を無効化）
+    pub admin_api_key: Option<SecretBox<String>>,
+    /// 管理 API で受け付ける Hydra アクセストークンのスコープ
+    /// （未設定の場合はトークンによる認証を無効化）
+    pub admin_token_scope: Option<String>,
+    /// 管理 API を別のアドレスで待ち受ける（例: "127.0.0.1:9443"）
+    /// 未設定の場合は公開 API と同じリスナーで提供する
+    #[serde(default)]
+    pub admin_listen_addr: Option<String>,
+    /// 管理 API のリスナーでクライアント証明書を必須にする（mTLS）
+    /// クライアント証明書を発行した CA 証明書（PEM）のパス
+    #[serde(default)]
+    pub admin_tls_client_ca_path: Option<String>,
+
+    // 分散トレーシング設定
+    /// トレースを送信する OTLP/HTTP コレクターのベース URL（未設定の場合は送信しない）
+    /// 例: "http://localhost:4318"（`/v1/traces` を付けて送信する）
+    #[serde(default)]
+    pub otel_exporter_otlp_endpoint: Option<String>,
+    /// トレースに付けるサービス名
+    #[serde(default = "default_otel_service_name")]
+    pub otel_service_name: String,
+    /// 親のないトレースをサンプリングする割合（0.0-1.0、親がある場合は親の判定に従う）
+    #[serde(default = "default_otel_traces_sample_ratio")]
+    pub otel_traces_sample_ratio: f64,
+
+    // パスワードポリシー設定
+    #[serde(default = "default_password_min_length")]
+    pub password_min_length: usize,
+    #[serde(default = "default_password_max_length")]
+    pub password_max_length: usize,
+    #[serde(default)]
+    pub password_require_uppercase: bool,
+    #[serde(default)]
+    pub password_require_lowercase: bool,
+    #[serde(default)]
+    pub password_require_digit: bool,
+    #[serde(default)]
+    pub password_require_symbol: bool,
+    /// 必要な強度スコア（0-4、0 の場合はチェックしない）
+    #[serde(default)]
+    pub password_min_strength: u8,
+    /// HIBP 互換の漏洩パスワード Range API（例: "https://api.pwnedpasswords.com"）
+    #[serde(default)]
+    pub password_breach_check_url: Option<String>,
+    /// ローカルの漏洩パスワードデータセット（`<SHA-1 先頭5文字>.txt` を格納したディレクトリ）
+    #[serde(default)]
+    pub password_breach_dataset_path: Option<String>,
+
+    // パスワードハッシュ（Argon2id）設定
+    /// メモリコスト（KiB）
+    #[serde(default = "default_argon2_memory_kib")]
+    pub argon2_memory_kib: u32,
+    /// 反復回数
+    #[serde(default = "default_argon2_iterations")]
+    pub argon2_iterations: u32,
+    /// 並列度
+    #[serde(default = "default_argon2_parallelism")]
+    pub argon2_parallelism: u32,
+
+    // 2FA (TOTP) 設定
+    /// TOTP発行者名（認証アプリに表示される）
+    pub totp_issuer: String,
+    /// AES-256暗号化キー（Base64エンコード、32バイト）
+    /// キーリングではキーID 0 として扱う
+    pub encryption_key: Option<SecretBox<String>>,
+    /// バージョン付きAES-256暗号化キーリング（`<id>:<base64>` のカンマ区切り）
+    /// 例: "1:AAAA...,2:BBBB..."（最大IDのキーで暗号化、全キーで復号）
+    pub encryption_keys: Option<SecretBox<String>>,
+
+    // OAuth2 ソーシャルログイン設定
+    /// OAuthステート暗号化用シークレット（32バイト、キーリングではキーID 0）
+    pub oauth_state_secret: Option<SecretBox<String>>,
+    /// OAuthステート暗号化用キーリング（`encryption_keys` と同じ形式）
+    pub oauth_state_secrets: Option<SecretBox<String>>,
+
+    // Google OAuth設定（オプション）
+    #[serde(default)]
+    pub google_client_id: Option<String>,
+    pub google_client_secret: Option<SecretBox<String>>,
+    #[serde(default)]
+    pub google_redirect_uri: Option<String>,
+
+    // GitHub OAuth設定（オプション）
+    #[serde(default)]
+    pub github_client_id: Option<String>,
+    pub github_client_secret: Option<SecretBox<String>>,
+    #[serde(default)]
+    pub github_redirect_uri: Option<String>,
+}
+
+const DEFAULT_HOST: &str = "0.0.0.0";
+const DEFAULT_PORT: u16 = 3000;
+const DEFAULT_SMTP_PORT: u16 = 587;
+const DEFAULT_DATABASE_MAX_CONNECTIONS: u32 = 5;
+const DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_DATABASE_IDLE_TIMEOUT_SECS: u64 = 600;
+const DEFAULT_DATABASE_MAX_LIFETIME_SECS: u64 = 1800;
+const DEFAULT_PASSWORD_RESET_TOKEN_TTL_SECS: i64 = 3600;
+const DEFAULT_ACCOUNT_LOCK_TOKEN_TTL_SECS: i64 = 7 * 24 * 3600;
+const DEFAULT_EMAIL_CHANGE_TOKEN_TTL_SECS: i64 = 24 * 3600;
+const DEFAULT_ACCOUNT_DELETION_GRACE_PERIOD_SECS: i64 = 7 * 24 * 3600;
+const DEFAULT_AUDIT_RETENTION_DAYS: i64 = 365;
+const DEFAULT_UNVERIFIED_ACCOUNT_TTL_SECS: i64 = 7 * 24 * 3600;
+const DEFAULT_PENDING_2FA_TTL_SECS: i64 = 24 * 3600;
+const DEFAULT_OTEL_SERVICE_NAME: &str = "oxgate";
+const DEFAULT_OTEL_TRACES_SAMPLE_RATIO: f64 = 1.0;
+const DEFAULT_PASSWORD_MIN_LENGTH: usize = 8;
+const DEFAULT_PASSWORD_MAX_LENGTH: usize = 128;
+const DEFAULT_ARGON2_MEMORY_KIB: u32 = argon2::Params::DEFAULT_M_COST;
+const DEFAULT_ARGON2_ITERATIONS: u32 = argon2::Params::DEFAULT_T_COST;
+const DEFAULT_ARGON2_PARALLELISM: u32 = argon2::Params::DEFAULT_P_COST;
+
+fn default_host() -> String {
+    DEFAULT_HOST.to_string()
+}
+
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+fn default_smtp_port() -> u16 {
+    DEFAULT_SMTP_PORT
+}
+
+fn default_database_max_connections() -> u32 {
+    DEFAULT_DATABASE_MAX_CONNECTIONS
+}
+
+fn default_database_acquire_timeout_secs() -> u64 {
+    DEFAULT_DATABASE_ACQUIRE_TIMEOUT_SECS
+}
+
+fn default_database_idle_timeout_secs() -> u64 {
+    DEFAULT_DATABASE_IDLE_TIMEOUT_SECS
+}
+
+fn default_database_max_lifetime_secs() -> u64 {
+    DEFAULT_DATABASE_MAX_LIFETIME_SECS
+}
+
+fn default_password_reset_token_ttl_secs() -> i64 {
+    DEFAULT_PASSWORD_RESET_TOKEN_TTL_SECS
+}
+
+fn default_account_lock_token_ttl_secs() -> i64 {
+    DEFAULT_ACCOUNT_LOCK_TOKEN_TTL_SECS
+}
+
+fn default_email_change_token_ttl_secs() -> i64 {
+    DEFAULT_EMAIL_CHANGE_TOKEN_TTL_SECS
+}
+
+fn default_account_deletion_grace_period_secs() -> i64 {
+    DEFAULT_ACCOUNT_DELETION_GRACE_PERIOD_SECS
+}
+
+fn default_audit_retention_days() -> i64 {
+    DEFAULT_AUDIT_RETENTION_DAYS
+}
+
+fn default_scheduler_enabled() -> bool {
+    true
+}
+
+fn default_unverified_account_ttl_secs() -> i64 {
+    DEFAULT_UNVERIFIED_ACCOUNT_TTL_SECS
+}
+
+fn default_pending_2fa_ttl_secs() -> i64 {
+    DEFAULT_PENDING_2FA_TTL_SECS
+}
+
+fn default_otel_service_name() -> String {
+    DEFAULT_OTEL_SERVICE_NAME.to_string()
+}
+
+fn default_otel_traces_sample_ratio() -> f64 {
+    DEFAULT_OTEL_TRACES_SAMPLE_RATIO
+}
+
+fn default_password_min_length() -> usize {
+    DEFAULT_PASSWORD_MIN_LENGTH
+}
+
+fn default_password_max_length() -> usize {
+    DEFAULT_PASSWORD_MAX_LENGTH
+}
+
+fn default_argon2_memory_kib() -> u32 {
+    DEFAULT_ARGON2_MEMORY_KIB
+}
+
+fn default_argon2_iterations() -> u32 {
+    DEFAULT_ARGON2_ITERATIONS
+}
+
+fn default_argon2_parallelism() -> u32 {
+    DEFAULT_ARGON2_PARALLELISM
+}
+
+impl Config {
+    /// 環境変数のみから設定を読み込む
+    pub fn load() -> Result<Self, envy::Error> {
+        envy::from_env()
+    }
+
+    /// 設定ファイル・環境変数・シークレットプロバイダーから設定を読み込み、検証する
+    ///
+    /// # 優先順位（上が優先）
+    /// 1. 環境変数
+    /// 2. シークレットプロバイダー（`*_FILE`、キーストア、Vault。詳細は [`crate::secrets`]）
+    /// 3. 設定ファイル（`path`、未指定時は `OXGATE_CONFIG`）
+    /// 4. デフォルト値
+    ///
+    /// # Errors
+    /// 検証エラーは全項目分をまとめて `ConfigError::Invalid` で返す
+    pub async fn load_layered(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let env: HashMap<String, String> = std::env::vars().collect();
+
+        let mut layered = match Self::file_path(path) {
+            Some(file_path) => {
+                tracing::info!(path = %file_path.display(), "設定ファイルを読み込み");
+                ConfigFile::load(&file_path)?.into_env()
+            }
+            None => HashMap::new(),
+        };
+
+        layered.extend(secrets::resolve_env(env).await?);
+
+        Self::from_env_map(layered)
+    }
+
+    /// 使用する設定ファイルのパス（引数指定を優先し、未指定時は `OXGATE_CONFIG`）
+    pub fn file_path(path: Option<&Path>) -> Option<PathBuf> {
+        path.map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(CONFIG_PATH_ENV).map(PathBuf::from))
+    }
+
+    /// 環境変数名（大文字）→ 値 のマップから設定を構築し、検証する
+    pub fn from_env_map(env: HashMap<String, String>) -> Result<Self, ConfigError> {
+        // envy は最初の欠落項目しか報告しないため、必須項目は先にまとめて確認する
+        let mut missing = ValidationErrors::default();
+        for key in REQUIRED_KEYS {
+            if env.get(key).is_none_or(|v| v.trim().is_empty()) {
+                missing.push(key, "必須項目です");
+            }
+        }
+        missing.into_result()?;
+
+        let config: Self = envy::from_iter(env)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// オリジンが CORS で許可されているか
+    ///
+    /// `allowed_origins` が未設定（または空）の場合は全オリジンを許可する
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        match self.get_allowed_origins() {
+            Some(origins) if !origins.is_empty() => origins.iter().any(|o| o == origin),
+            _ => true,
+        }
+    }
+
+    /// CORS で許可オリジンが明示的に指定されているか（本番設定）
+    pub fn has_allowed_origins(&self) -> bool {
+        self.get_allowed_origins()
+            .is_some_and(|origins| !origins.is_empty())
+    }
+
+    /// 許可するオリジンのリストを取得
+    ///
+    /// - `allowed_origins` が設定されていない場合は `None` を返す（全オリジン許可）
+    /// - 設定されている場合はカンマ区切りでパースしてリストを返す
+    pub fn get_allowed_origins(&self) -> Option<Vec<String>> {
+        self.allowed_origins.as_ref().map(|origins| {
+            origins
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+    }
+
+    /// 信頼するリバースプロキシのネットワークのリストを取得
+    ///
+    /// 解釈できない値は無視する（起動時・リロード時の検証でエラーになる）
+    pub fn get_trusted_proxies(&self) -> Vec<IpNet> {
+        self.trusted_proxies
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| parse_trusted_proxy(s).ok())
+            .collect()
+    }
+
+    /// TOTPシークレット暗号化用キーリングを構築
+    ///
+    /// `ENCRYPTION_KEYS` と `ENCRYPTION_KEY`（キーID 0）を統合する。
+    /// どちらも未設定の場合はエラー
+    pub fn totp_keyring(&self) -> Result<Keyring, AppError> {
+        build_keyring(
+            self.encryption_keys.as_ref(),
+            self.encryption_key.as_ref(),
+            "ENCRYPTION_KEYS / ENCRYPTION_KEY",
+        )
+    }
+
+    /// OAuth state 暗号化用キーリングを構築
+    ///
+    /// `OAUTH_STATE_SECRETS` と `OAUTH_STATE_SECRET`（キーID 0）を統合する
+    pub fn oauth_state_keyring(&self) -> Result<Keyring, AppError> {
+        build_keyring(
+            self.oauth_state_secrets.as_ref(),
+            self.oauth_state_secret.as_ref(),
+            "OAUTH_STATE_SECRETS / OAUTH_STATE_SECRET",
+        )
+    }
+}
+
+/// キーリング設定と単一キー設定からキーリングを構築
+fn build_keyring(
+    keyring: Option<&SecretBox<String>>,
+    single: Option<&SecretBox<String>>,
+    name: &str,
+) -> Result<Keyring, AppError> {
+    let single = single
+        .map(|key| Keyring::from_single(key.expose_secret()))
+        .transpose()?;
+
+    match (keyring, single) {
+        (Some(spec), Some(single)) => Ok(Keyring::parse(spec.expose_secret())?.merge(single)),
+        (Some(spec), None) => Keyring::parse(spec.expose_secret()),
+        (None, Some(single)) => Ok(single),
+        (None, None) => {
+            tracing::error!(setting = %name, "暗号化キーが設定されていません");
+            Err(AppError::Internal(anyhow::anyhow!(
+                "{} must be configured",
+                name
+            )))
+        }
+    }
+}
+
+/// 信頼するリバースプロキシの指定（CIDR または単一の IP アドレス）をパース
+pub(crate) fn parse_trusted_proxy(value: &str) -> Result<IpNet, String> {
+    if let Ok(net) = value.parse::<IpNet>() {
+        return Ok(net.trunc());
+    }
+    value
+        .parse::<IpAddr>()
+        .map(IpNet::from)
+        .map_err(|_| "CIDR または IP アドレスとして解釈できません".to_string())
+}
+
+/// 実行中に差し替え可能な設定（ホットリロード用）
+///
+/// 読み取り側は `load()` で取得したスナップショットを使う。
+/// 差し替えはアトミックに行われ、処理中のリクエストは古いスナップショットを使い続ける
+#[derive(Clone)]
+pub struct SharedConfig(Arc<ArcSwap<Config>>);
+
+impl SharedConfig {
+    /// 新しい SharedConfig を作成
+    pub fn new(config: Config) -> Self {
+        Self(Arc::new(ArcSwap::from_pointee(config)))
+    }
+
+    /// 現在の設定のスナップショットを取得
+    pub fn load(&self) -> Arc<Config> {
+        self.0.load_full()
+    }
+
+    /// 設定を差し替える
+    pub fn store(&self, config: Arc<Config>) {
+        self.0.store(config);
+    }
+}
@@ -0,0 +1,1029 @@
+//! 起動時の設定検証
+//!
+//! 全項目を検証し、問題をまとめて報告する（最初のエラーで停止しない）。
+
+use std::fmt;
+use std::net::SocketAddr;
+
+use reqwest::Url;
+use secrecy::{ExposeSecret, SecretBox};
+use sqlx::postgres::PgSslMode;
+
+use super::{Config, parse_trusted_proxy};
+use crate::error::AppError;
+use crate::services::keyring::Keyring;
+
+/// パスワードリセット・アカウントロックトークン有効期限の許容範囲（秒）
+const MIN_TOKEN_TTL_SECS: i64 = 60;
+const MAX_TOKEN_TTL_SECS: i64 = 7 * 24 * 3600;
+
+/// アカウント削除猶予期間の上限（秒）
+const MAX_DELETION_GRACE_PERIOD_SECS: i64 = 90 * 24 * 3600;
+
+/// 監査イベント保持日数の上限
+const MAX_AUDIT_RETENTION_DAYS: i64 = 10 * 365;
+
+/// 未確認アカウントの保持期間の上限（90日）
+const MAX_UNVERIFIED_ACCOUNT_TTL_SECS: i64 = 90 * 24 * 3600;
+
+/// 管理 API キーの最小長
+const MIN_ADMIN_API_KEY_LEN: usize = 32;
+
+/// パスワード最大長の上限（ハッシュ計算によるリソース枯渇を防ぐ）
+const MAX_PASSWORD_LENGTH_LIMIT: usize = 1024;
+
+/// 強度スコアの上限（zxcvbn 互換の 0-4）
+const MAX_PASSWORD_STRENGTH: u8 = 4;
+
+/// Argon2 メモリコストの上限（KiB、1 リクエストあたりのメモリ使用量）
+const MAX_ARGON2_MEMORY_KIB: u32 = 4 * 1024 * 1024;
+
+/// 設定項目ごとの問題
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// 環境変数名（例: `GOOGLE_REDIRECT_URI`）
+    pub field: String,
+    pub message: String,
+}
+
+/// 設定検証エラー（全ての問題を保持）
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationErrors {
+    /// 問題を追加
+    pub fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    /// 問題がなければ `Ok(())`
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.issues.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// 指定フィールドに問題があるか
+    pub fn has(&self, field: &str) -> bool {
+        self.issues.iter().any(|issue| issue.field == field)
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "設定に {} 件の問題があります", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}: {}", issue.field, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl Config {
+    /// 全ての設定項目を検証
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+
+        self.validate_server(&mut errors);
+        self.validate_database(&mut errors);
+        self.validate_email(&mut errors);
+        self.validate_encryption(&mut errors);
+        self.validate_providers(&mut errors);
+        self.validate_policies(&mut errors);
+        self.validate_argon2(&mut errors);
+        self.validate_admin(&mut errors);
+        self.validate_telemetry(&mut errors);
+
+        errors.into_result()
+    }
+
+    fn validate_server(&self, errors: &mut ValidationErrors) {
+        if self.port == 0 {
+            errors.push("PORT", "1-65535 の範囲で指定してください");
+        }
+        if format!("{}:{}", self.host, self.port)
+            .parse::<SocketAddr>()
+            .is_err()
+        {
+            errors.push(
+                "HOST",
+                format!("IP アドレスとして解釈できません: '{}'", self.host),
+            );
+        }
+
+        if let Some(origins) = self.get_allowed_origins() {
+            for origin in origins {
+                if let Err(message) = validate_origin(&origin) {
+                    errors.push("ALLOWED_ORIGINS", format!("'{}': {}", origin, message));
+                }
+            }
+        }
+
+        for proxy in self
+            .trusted_proxies
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            if let Err(message) = parse_trusted_proxy(proxy) {
+                errors.push("TRUSTED_PROXIES", format!("'{}': {}", proxy, message));
+            }
+        }
+
+        for (field, path) in [
+            ("TLS_CERT_PATH", &self.tls_cert_path),
+            ("TLS_KEY_PATH", &self.tls_key_path),
+        ] {
+            validate_file(errors, field, path.as_deref());
+        }
+        if self.tls_cert_path.is_some() != self.tls_key_path.is_some() {
+            errors.push(
+                "TLS_KEY_PATH",
+                "TLS_CERT_PATH と TLS_KEY_PATH は両方指定してください",
+            );
+        }
+    }
+
+    fn validate_database(&self, errors: &mut ValidationErrors) {
+        validate_postgres_url(errors, "DATABASE_URL", self.database_url.expose_secret());
+        if let Some(read_url) = &self.database_read_url {
+            validate_postgres_url(errors, "DATABASE_READ_URL", read_url.expose_secret());
+        }
+
+        if self.database_max_connections == 0 {
+            errors.push("DATABASE_MAX_CONNECTIONS", "1 以上を指定してください");
+        }
+        if self.database_min_connections > self.database_max_connections {
+            errors.push(
+                "DATABASE_MIN_CONNECTIONS",
+                "DATABASE_MAX_CONNECTIONS 以下を指定してください",
+            );
+        }
+        if self.database_acquire_timeout_secs == 0 {
+            errors.push("DATABASE_ACQUIRE_TIMEOUT_SECS", "1 以上を指定してください");
+        }
+
+        if let Some(mode) = &self.database_ssl_mode
+            && mode.parse::<PgSslMode>().is_err()
+        {
+            errors.push(
+                "DATABASE_SSL_MODE",
+                format!(
+                    "disable / allow / prefer / require / verify-ca / verify-full のいずれかを指定してください: '{}'",
+                    mode
+                ),
+            );
+        }
+        for (field, path) in [
+            ("DATABASE_SSL_ROOT_CERT", &self.database_ssl_root_cert),
+            ("DATABASE_SSL_CLIENT_CERT", &self.database_ssl_client_cert),
+            ("DATABASE_SSL_CLIENT_KEY", &self.database_ssl_client_key),
+        ] {
+            validate_file(errors, field, path.as_deref());
+        }
+        if self.database_ssl_client_cert.is_some() != self.database_ssl_client_key.is_some() {
+            errors.push(
+                "DATABASE_SSL_CLIENT_KEY",
+                "DATABASE_SSL_CLIENT_CERT と DATABASE_SSL_CLIENT_KEY は両方指定してください",
+            );
+        }
+
+        validate_http_url(errors, "HYDRA_ADMIN_URL", &self.hydra_admin_url);
+    }
+
+    fn validate_email(&self, errors: &mut ValidationErrors) {
+        let smtp_in_use = self.smtp_username.is_some()
+            || self.smtp_password.is_some()
+            || self.smtp_from_address.is_some();
+
+        match &self.smtp_host {
+            Some(host) if host.trim().is_empty() => {
+                errors.push("SMTP_HOST", "空文字は指定できません");
+            }
+            Some(_) => {
+                if self.smtp_port == 0 {
+                    errors.push("SMTP_PORT", "1-65535 の範囲で指定してください");
+                }
+                if self.smtp_from_address.is_none() {
+                    errors.push("SMTP_FROM_ADDRESS", "SMTP_HOST を設定する場合は必須です");
+                }
+                if self.smtp_username.is_some() != self.smtp_password.is_some() {
+                    errors.push(
+                        "SMTP_USERNAME",
+                        "SMTP_USERNAME と SMTP_PASSWORD は両方指定してください",
+                    );
+                }
+            }
+            None if smtp_in_use => {
+                errors.push("SMTP_HOST", "SMTP 関連の設定があるため必須です");
+            }
+            None => {}
+        }
+
+        if let Some(from) = &self.smtp_from_address
+            && !looks_like_email(from)
+        {
+            errors.push(
+                "SMTP_FROM_ADDRESS",
+                format!("メールアドレスとして不正です: '{}'", from),
+            );
+        }
+
+        if let Some(base) = &self.password_reset_url_base {
+            validate_http_url(errors, "PASSWORD_RESET_URL_BASE", base);
+        }
+        if let Some(base) = &self.account_lock_url_base {
+            validate_http_url(errors, "ACCOUNT_LOCK_URL_BASE", base);
+        }
+        if let Some(base) = &self.email_change_url_base {
+            validate_http_url(errors, "EMAIL_CHANGE_URL_BASE", base);
+        }
+    }
+
+    fn validate_encryption(&self, errors: &mut ValidationErrors) {
+        if self.totp_issuer.trim().is_empty() {
+            errors.push("TOTP_ISSUER", "空文字は指定できません");
+        } else if self.totp_issuer.contains(':') {
+            errors.push(
+                "TOTP_ISSUER",
+                "':' は使用できません（otpauth URI の区切り文字）",
+            );
+        }
+
+        if let Err(e) = self.totp_keyring() {
+            errors.push("ENCRYPTION_KEYS", keyring_error_message(e));
+        }
+        validate_legacy_key(
+            errors,
+            ("ENCRYPTION_KEY", "ENCRYPTION_KEYS"),
+            self.encryption_keys.as_ref(),
+            self.encryption_key.as_ref(),
+        );
+        validate_legacy_key(
+            errors,
+            ("OAUTH_STATE_SECRET", "OAUTH_STATE_SECRETS"),
+            self.oauth_state_secrets.as_ref(),
+            self.oauth_state_secret.as_ref(),
+        );
+
+        // OAuth state キーはソーシャルログイン有効時のみ必須
+        if (self.google_client_id.is_some() || self.github_client_id.is_some())
+            && let Err(e) = self.oauth_state_keyring()
+        {
+            errors.push("OAUTH_STATE_SECRETS", keyring_error_message(e));
+        }
+    }
+
+    fn validate_providers(&self, errors: &mut ValidationErrors) {
+        validate_provider(
+            errors,
+            "GOOGLE",
+            "google",
+            self.google_client_id.as_deref(),
+            self.google_client_secret.is_some(),
+            self.google_redirect_uri.as_deref(),
+        );
+        validate_provider(
+            errors,
+            "GITHUB",
+            "github",
+            self.github_client_id.as_deref(),
+            self.github_client_secret.is_some(),
+            self.github_redirect_uri.as_deref(),
+        );
+    }
+
+    fn validate_policies(&self, errors: &mut ValidationErrors) {
+        if !(MIN_TOKEN_TTL_SECS..=MAX_TOKEN_TTL_SECS).contains(&self.password_reset_token_ttl_secs)
+        {
+            errors.push(
+                "PASSWORD_RESET_TOKEN_TTL_SECS",
+                format!(
+                    "{}-{} 秒の範囲で指定してください",
+                    MIN_TOKEN_TTL_SECS, MAX_TOKEN_TTL_SECS
+                ),
+            );
+        }
+
+        if !(MIN_TOKEN_TTL_SECS..=MAX_TOKEN_TTL_SECS).contains(&self.account_lock_token_ttl_secs) {
+            errors.push(
+                "ACCOUNT_LOCK_TOKEN_TTL_SECS",
+                format!(
+                    "{}-{} 秒の範囲で指定してください",
+                    MIN_TOKEN_TTL_SECS, MAX_TOKEN_TTL_SECS
+                ),
+            );
+        }
+
+        if !(MIN_TOKEN_TTL_SECS..=MAX_TOKEN_TTL_SECS).contains(&self.email_change_token_ttl_secs) {
+            errors.push(
+                "EMAIL_CHANGE_TOKEN_TTL_SECS",
+                format!(
+                    "{}-{} 秒の範囲で指定してください",
+                    MIN_TOKEN_TTL_SECS, MAX_TOKEN_TTL_SECS
+                ),
+            );
+        }
+
+        if let Some(path) = &self.disposable_email_domains_path
+            && !std::path::Path::new(path).is_file()
+        {
+            errors.push(
+                "DISPOSABLE_EMAIL_DOMAINS_PATH",
+                format!("ファイルが存在しません: '{}'", path),
+            );
+        }
+
+        if !(0..=MAX_DELETION_GRACE_PERIOD_SECS).contains(&self.account_deletion_grace_period_secs)
+        {
+            errors.push(
+                "ACCOUNT_DELETION_GRACE_PERIOD_SECS",
+                format!(
+                    "0-{} 秒の範囲で指定してください",
+                    MAX_DELETION_GRACE_PERIOD_SECS
+                ),
+            );
+        }
+
+        if !(0..=MAX_AUDIT_RETENTION_DAYS).contains(&self.audit_retention_days) {
+            errors.push(
+                "AUDIT_RETENTION_DAYS",
+                format!("0-{} 日の範囲で指定してください", MAX_AUDIT_RETENTION_DAYS),
+            );
+        }
+
+        if !(0..=MAX_UNVERIFIED_ACCOUNT_TTL_SECS).contains(&self.unverified_account_ttl_secs) {
+            errors.push(
+                "UNVERIFIED_ACCOUNT_TTL_SECS",
+                format!(
+                    "0-{} 秒の範囲で指定してください",
+                    MAX_UNVERIFIED_ACCOUNT_TTL_SECS
+                ),
+            );
+        }
+
+        if !(MIN_TOKEN_TTL_SECS..=MAX_TOKEN_TTL_SECS).contains(&self.pending_2fa_ttl_secs) {
+            errors.push(
+                "PENDING_2FA_TTL_SECS",
+                format!(
+                    "{}-{} 秒の範囲で指定してください",
+                    MIN_TOKEN_TTL_SECS, MAX_TOKEN_TTL_SECS
+                ),
+            );
+        }
+
+        if self.password_min_length == 0 {
+            errors.push("PASSWORD_MIN_LENGTH", "1 以上を指定してください");
+        }
+        if self.password_max_length < self.password_min_length {
+            errors.push(
+                "PASSWORD_MAX_LENGTH",
+                "PASSWORD_MIN_LENGTH 以上を指定してください",
+            );
+        }
+        if self.password_max_length > MAX_PASSWORD_LENGTH_LIMIT {
+            errors.push(
+                "PASSWORD_MAX_LENGTH",
+                format!("{} 以下を指定してください", MAX_PASSWORD_LENGTH_LIMIT),
+            );
+        }
+        if self.password_min_strength > MAX_PASSWORD_STRENGTH {
+            errors.push(
+                "PASSWORD_MIN_STRENGTH",
+                format!("0-{} の範囲で指定してください", MAX_PASSWORD_STRENGTH),
+            );
+        }
+
+        if let Some(url) = &self.password_breach_check_url {
+            validate_http_url(errors, "PASSWORD_BREACH_CHECK_URL", url);
+        }
+        if let Some(path) = &self.password_breach_dataset_path {
+            if self.password_breach_check_url.is_some() {
+                errors.push(
+                    "PASSWORD_BREACH_DATASET_PATH",
+                    "PASSWORD_BREACH_CHECK_URL と同時には指定できません",
+                );
+            }
+            if !std::path::Path::new(path).is_dir() {
+                errors.push(
+                    "PASSWORD_BREACH_DATASET_PATH",
+                    format!("ディレクトリが存在しません: '{}'", path),
+                );
+            }
+        }
+    }
+
+    fn validate_argon2(&self, errors: &mut ValidationErrors) {
+        if self.argon2_iterations < argon2::Params::MIN_T_COST {
+            errors.push("ARGON2_ITERATIONS", "1 以上を指定してください");
+        }
+        if !(argon2::Params::MIN_P_COST..=argon2::Params::MAX_P_COST)
+            .contains(&self.argon2_parallelism)
+        {
+            errors.push(
+                "ARGON2_PARALLELISM",
+                format!(
+                    "{}-{} の範囲で指定してください",
+                    argon2::Params::MIN_P_COST,
+                    argon2::Params::MAX_P_COST
+                ),
+            );
+        }
+        let min_memory = self
+            .argon2_parallelism
+            .saturating_mul(8)
+            .max(argon2::Params::MIN_M_COST);
+        if !(min_memory..=MAX_ARGON2_MEMORY_KIB).contains(&self.argon2_memory_kib) {
+            errors.push(
+                "ARGON2_MEMORY_KIB",
+                format!(
+                    "{}-{} の範囲で指定してください（並列度 × 8 以上）",
+                    min_memory, MAX_ARGON2_MEMORY_KIB
+                ),
+            );
+        }
+    }
+
+    fn validate_admin(&self, errors: &mut ValidationErrors) {
+        if let Some(key) = &self.admin_api_key
+            && key.expose_secret().len() < MIN_ADMIN_API_KEY_LEN
+        {
+            // キーは値を出力しない
+            errors.push(
+                "ADMIN_API_KEY",
+                format!("{} 文字以上を指定してください", MIN_ADMIN_API_KEY_LEN),
+            );
+        }
+        if let Some(scope) = &self.admin_token_scope
+            && (scope.is_empty() || scope.contains(char::is_whitespace))
+        {
+            errors.push(
+                "ADMIN_TOKEN_SCOPE",
+                "空白を含まない単一のスコープを指定してください",
+            );
+        }
+
+        if let Some(addr) = &self.admin_listen_addr {
+            match addr.parse::<SocketAddr>() {
+                Ok(addr) if addr.port() == self.port => errors.push(
+                    "ADMIN_LISTEN_ADDR",
+                    "公開 API（PORT）と異なるポートを指定してください",
+                ),
+                Ok(_) => {}
+                Err(_) => errors.push(
+                    "ADMIN_LISTEN_ADDR",
+                    format!(
+                        "<IP アドレス>:<ポート> の形式で指定してください: '{}'",
+                        addr
+                    ),
+                ),
+            }
+        }
+        if let Some(path) = &self.admin_tls_client_ca_path {
+            validate_file(errors, "ADMIN_TLS_CLIENT_CA_PATH", Some(path));
+            // 公開 API と同じリスナーではクライアント証明書を必須にできない
+            if self.admin_listen_addr.is_none() || self.tls_cert_path.is_none() {
+                errors.push(
+                    "ADMIN_TLS_CLIENT_CA_PATH",
+                    "ADMIN_LISTEN_ADDR と TLS_CERT_PATH / TLS_KEY_PATH の設定が必要です",
+                );
+            }
+        }
+    }
+
+    fn validate_telemetry(&self, errors: &mut ValidationErrors) {
+        if let Some(endpoint) = &self.otel_exporter_otlp_endpoint {
+            validate_http_url(errors, "OTEL_EXPORTER_OTLP_ENDPOINT", endpoint);
+        }
+        if self.otel_service_name.trim().is_empty() {
+            errors.push("OTEL_SERVICE_NAME", "空にはできません");
+        }
+        if !(0.0..=1.0).contains(&self.otel_traces_sample_ratio) {
+            errors.push(
+                "OTEL_TRACES_SAMPLE_RATIO",
+                "0.0-1.0 の範囲で指定してください",
+            );
+        }
+    }
+}
+
+/// ソーシャルログインプロバイダーの設定を検証
+///
+/// - client_id / client_secret / redirect_uri は全て指定するか全て省略する
+/// - redirect_uri はそのプロバイダーのコールバックパスを指していること
+fn validate_provider(
+    errors: &mut ValidationErrors,
+    prefix: &str,
+    provider: &str,
+    client_id: Option<&str>,
+    has_client_secret: bool,
+    redirect_uri: Option<&str>,
+) {
+    let configured = [
+        client_id.is_some(),
+        has_client_secret,
+        redirect_uri.is_some(),
+    ];
+    if configured.iter().all(|c| !c) {
+        return;
+    }
+
+    for (suffix, present) in ["CLIENT_ID", "CLIENT_SECRET", "REDIRECT_URI"]
+        .iter()
+        .zip(configured)
+    {
+        if !present {
+            errors.push(
+                format!("{}_{}", prefix, suffix),
+                format!("{} ログインを有効にするには必須です", provider),
+            );
+        }
+    }
+
+    if let Some(client_id) = client_id
+        && client_id.trim().is_empty()
+    {
+        errors.push(format!("{}_CLIENT_ID", prefix), "空文字は指定できません");
+    }
+
+    let Some(redirect_uri) = redirect_uri else {
+        return;
+    };
+    let field = format!("{}_REDIRECT_URI", prefix);
+    let Some(url) = validate_http_url(errors, &field, redirect_uri) else {
+        return;
+    };
+
+    let callback_path = format!("/api/oauth/{}/callback", provider);
+    if !url.path().ends_with(&callback_path) {
+        errors.push(
+            field.clone(),
+            format!("パスが {} で終わる必要があります", callback_path),
+        );
+    }
+    if url.scheme() != "https" && !is_local_host(&url) {
+        errors.push(field, "localhost 以外では https を使用してください");
+    }
+}
+
+/// ファイルが存在するか（未設定の場合は検証しない）
+fn validate_file(errors: &mut ValidationErrors, field: &str, path: Option<&str>) {
+    if let Some(path) = path
+        && !std::path::Path::new(path).is_file()
+    {
+        errors.push(field, format!("ファイルが存在しません: '{}'", path));
+    }
+}
+
+/// Postgres の接続 URL を検証
+///
+/// URL はパスワードを含むため値は出力しない
+fn validate_postgres_url(errors: &mut ValidationErrors, field: &str, value: &str) {
+    match Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "postgres" | "postgresql") => {}
+        Ok(_) => errors.push(
+            field,
+            "postgres:// または postgresql:// で始まる URL を指定してください",
+        ),
+        Err(e) => errors.push(field, format!("URL として不正です: {}", e)),
+    }
+}
+
+/// http(s) URL を検証し、妥当であればパース結果を返す
+fn validate_http_url(errors: &mut ValidationErrors, field: &str, value: &str) -> Option<Url> {
+    match Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host().is_some() => Some(url),
+        Ok(_) => {
+            errors.push(
+                field,
+                format!("http(s) URL を指定してください: '{}'", value),
+            );
+            None
+        }
+        Err(e) => {
+            errors.push(field, format!("URL として不正です: '{}' ({})", value, e));
+            None
+        }
+    }
+}
+
+/// CORS オリジンを検証（`scheme://host[:port]` 形式のみ許可）
+fn validate_origin(origin: &str) -> Result<(), String> {
+    if origin == "*" {
+        return Err("'*' は指定できません（全オリジン許可は未設定にしてください）".to_string());
+    }
+
+    let url = Url::parse(origin).map_err(|e| format!("URL として不正です ({})", e))?;
+
+    if !matches!(url.scheme(), "http" | "https") || url.host().is_none() {
+        return Err("http(s)://host[:port] 形式で指定してください".to_string());
+    }
+    if url.path() != "/"
+        || url.query().is_some()
+        || url.fragment().is_some()
+        || origin.ends_with('/')
+    {
+        return Err("パスや末尾のスラッシュは含めないでください".to_string());
+    }
+    if origin.parse::<http::HeaderValue>().is_err() {
+        return Err("ヘッダー値として使用できない文字が含まれています".to_string());
+    }
+
+    Ok(())
+}
+
+fn is_local_host(url: &Url) -> bool {
+    matches!(
+        url.host_str(),
+        Some("localhost") | Some("127.0.0.1") | Some("[::1]")
+    )
+}
+
+fn looks_like_email(value: &str) -> bool {
+    matches!(value.split_once('@'), Some((local, domain)) if !local.is_empty() && domain.contains('.'))
+}
+
+/// キーリングにキーID 0 がある場合、単一キー設定（キーID 0）と同じキーか確認
+///
+/// 異なる場合は単一キーがキーリングに含まれず、そのキーで暗号化されたデータを復号できなくなる
+/// （形式の誤りはキーリングの構築時に報告する）
+fn validate_legacy_key(
+    errors: &mut ValidationErrors,
+    (field, keyring_field): (&str, &str),
+    keyring: Option<&SecretBox<String>>,
+    single: Option<&SecretBox<String>>,
+) {
+    let (Some(keyring), Some(single)) = (keyring, single) else {
+        return;
+    };
+    let (Ok(keyring), Ok(single)) = (
+        Keyring::parse(keyring.expose_secret()),
+        Keyring::from_single(single.expose_secret()),
+    ) else {
+        return;
+    };
+
+    if !keyring.conflicting_key_ids(&single).is_empty() {
+        errors.push(
+            field,
+            format!(
+                "{} のキーID 0 と異なるキーです（このキーで暗号化されたデータを復号できなくなるため、\
+                 同じキーにするか {} のキーID 0 を別のIDに変更してください）",
+                keyring_field, keyring_field
+            ),
+        );
+    }
+}
+
+/// キーリング構築エラーから設定向けのメッセージを取り出す
+fn keyring_error_message(error: AppError) -> String {
+    match error {
+        AppError::Internal(e) => e.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn base_env() -> HashMap<String, String> {
+        HashMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://oxgate:pw@localhost:5432/oxgate".to_string(),
+            ),
+            (
+                "HYDRA_ADMIN_URL".to_string(),
+                "http://localhost:4445".to_string(),
+            ),
+            ("TOTP_ISSUER".to_string(), "oxgate".to_string()),
+            (
+                "ENCRYPTION_KEY".to_string(),
+                "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+            ),
+        ])
+    }
+
+    fn config_from(env: HashMap<String, String>) -> Config {
+        envy::from_iter(env).unwrap()
+    }
+
+    #[test]
+    fn test_valid_minimal_config() {
+        assert!(config_from(base_env()).validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_origins() {
+        let mut env = base_env();
+        env.insert(
+            "ALLOWED_ORIGINS".to_string(),
+            "https://ok.example.com,https://slash.example.com/,ftp://x.example.com,*".to_string(),
+        );
+
+        let errors = config_from(env).validate().unwrap_err();
+        let origin_issues = errors
+            .issues
+            .iter()
+            .filter(|i| i.field == "ALLOWED_ORIGINS")
+            .count();
+        assert_eq!(origin_issues, 3);
+    }
+
+    #[test]
+    fn test_provider_redirect_uri_must_match_callback() {
+        let mut env = base_env();
+        env.insert("GOOGLE_CLIENT_ID".to_string(), "id".to_string());
+        env.insert("GOOGLE_CLIENT_SECRET".to_string(), "secret".to_string());
+        env.insert(
+            "GOOGLE_REDIRECT_URI".to_string(),
+            "https://auth.example.com/api/oauth/github/callback".to_string(),
+        );
+        env.insert(
+            "OAUTH_STATE_SECRET".to_string(),
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+        );
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("GOOGLE_REDIRECT_URI"));
+    }
+
+    #[test]
+    fn test_legacy_key_must_match_keyring_key_zero() {
+        const OTHER_KEY: &str = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+
+        // キーID 0 が ENCRYPTION_KEY と同じ
+        let mut env = base_env();
+        env.insert(
+            "ENCRYPTION_KEYS".to_string(),
+            format!("0:{},1:{}", env["ENCRYPTION_KEY"], OTHER_KEY),
+        );
+        assert!(config_from(env).validate().is_ok());
+
+        // キーID 0 がない場合は ENCRYPTION_KEY がキーID 0 として追加される
+        let mut env = base_env();
+        env.insert("ENCRYPTION_KEYS".to_string(), format!("1:{}", OTHER_KEY));
+        assert!(config_from(env).validate().is_ok());
+
+        // キーID 0 が ENCRYPTION_KEY と異なる
+        let mut env = base_env();
+        env.insert("ENCRYPTION_KEYS".to_string(), format!("0:{}", OTHER_KEY));
+        env.insert(
+            "OAUTH_STATE_SECRETS".to_string(),
+            format!("0:{}", OTHER_KEY),
+        );
+        env.insert(
+            "OAUTH_STATE_SECRET".to_string(),
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+        );
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("ENCRYPTION_KEY"));
+        assert!(errors.has("OAUTH_STATE_SECRET"));
+    }
+
+    #[test]
+    fn test_partial_provider_and_missing_state_secret() {
+        let mut env = base_env();
+        env.insert("GITHUB_CLIENT_ID".to_string(), "id".to_string());
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("GITHUB_CLIENT_SECRET"));
+        assert!(errors.has("GITHUB_REDIRECT_URI"));
+        assert!(errors.has("OAUTH_STATE_SECRETS"));
+    }
+
+    #[test]
+    fn test_errors_are_aggregated() {
+        let mut env = base_env();
+        env.insert(
+            "DATABASE_URL".to_string(),
+            "mysql://localhost/db".to_string(),
+        );
+        env.insert("HYDRA_ADMIN_URL".to_string(), "not a url".to_string());
+        env.insert("ENCRYPTION_KEY".to_string(), "c2hvcnQ=".to_string());
+        env.insert("SMTP_HOST".to_string(), "smtp.example.com".to_string());
+        env.insert("PASSWORD_RESET_TOKEN_TTL_SECS".to_string(), "0".to_string());
+
+        let errors = config_from(env).validate().unwrap_err();
+        for field in [
+            "DATABASE_URL",
+            "HYDRA_ADMIN_URL",
+            "ENCRYPTION_KEYS",
+            "SMTP_FROM_ADDRESS",
+            "PASSWORD_RESET_TOKEN_TTL_SECS",
+        ] {
+            assert!(errors.has(field), "{} should be reported", field);
+        }
+
+        let message = errors.to_string();
+        assert!(message.contains("5 件"));
+        // DATABASE_URL の値（パスワードを含みうる）は出力しない
+        assert!(!message.contains("mysql://"));
+    }
+
+    #[test]
+    fn test_password_policy_settings() {
+        let mut env = base_env();
+        env.insert("PASSWORD_MIN_LENGTH".to_string(), "16".to_string());
+        env.insert("PASSWORD_MAX_LENGTH".to_string(), "12".to_string());
+        env.insert("PASSWORD_MIN_STRENGTH".to_string(), "5".to_string());
+        env.insert(
+            "PASSWORD_BREACH_CHECK_URL".to_string(),
+            "https://api.pwnedpasswords.com".to_string(),
+        );
+        env.insert(
+            "PASSWORD_BREACH_DATASET_PATH".to_string(),
+            "/nonexistent/pwned".to_string(),
+        );
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("PASSWORD_MAX_LENGTH"));
+        assert!(errors.has("PASSWORD_MIN_STRENGTH"));
+        assert!(!errors.has("PASSWORD_BREACH_CHECK_URL"));
+        assert_eq!(
+            errors
+                .issues
+                .iter()
+                .filter(|i| i.field == "PASSWORD_BREACH_DATASET_PATH")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_argon2_params() {
+        let config = config_from(base_env());
+        assert_eq!(config.argon2_memory_kib, argon2::Params::DEFAULT_M_COST);
+
+        let mut env = base_env();
+        env.insert("ARGON2_ITERATIONS".to_string(), "0".to_string());
+        env.insert("ARGON2_PARALLELISM".to_string(), "4".to_string());
+        env.insert("ARGON2_MEMORY_KIB".to_string(), "16".to_string());
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("ARGON2_ITERATIONS"));
+        assert!(!errors.has("ARGON2_PARALLELISM"));
+        // 並列度 4 には 32 KiB 以上が必要
+        assert!(errors.has("ARGON2_MEMORY_KIB"));
+    }
+
+    #[test]
+    fn test_audit_and_admin_settings() {
+        let config = config_from(base_env());
+        assert_eq!(config.audit_retention_days, 365);
+        assert!(config.admin_api_key.is_none());
+
+        let mut env = base_env();
+        env.insert("AUDIT_RETENTION_DAYS".to_string(), "-1".to_string());
+        env.insert("ADMIN_API_KEY".to_string(), "short".to_string());
+        env.insert(
+            "ADMIN_TOKEN_SCOPE".to_string(),
+            "oxgate:admin extra".to_string(),
+        );
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("AUDIT_RETENTION_DAYS"));
+        assert!(errors.has("ADMIN_API_KEY"));
+        assert!(errors.has("ADMIN_TOKEN_SCOPE"));
+    }
+
+    #[test]
+    fn test_database_settings() {
+        let config = config_from(base_env());
+        assert_eq!(config.database_max_connections, 5);
+        assert_eq!(config.database_statement_timeout_ms, 0);
+        assert!(config.database_read_url.is_none());
+
+        let mut env = base_env();
+        env.insert("DATABASE_MAX_CONNECTIONS".to_string(), "4".to_string());
+        env.insert("DATABASE_MIN_CONNECTIONS".to_string(), "8".to_string());
+        env.insert("DATABASE_SSL_MODE".to_string(), "strict".to_string());
+        env.insert(
+            "DATABASE_SSL_CLIENT_CERT".to_string(),
+            "/nonexistent/client.crt".to_string(),
+        );
+        env.insert(
+            "DATABASE_READ_URL".to_string(),
+            "mysql://replica/db".to_string(),
+        );
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("DATABASE_MIN_CONNECTIONS"));
+        assert!(errors.has("DATABASE_SSL_MODE"));
+        assert!(errors.has("DATABASE_SSL_CLIENT_CERT"));
+        assert!(errors.has("DATABASE_SSL_CLIENT_KEY"));
+        assert!(errors.has("DATABASE_READ_URL"));
+        assert!(!errors.has("DATABASE_URL"));
+        assert!(!errors.to_string().contains("mysql://"));
+    }
+
+    #[test]
+    fn test_trusted_proxies() {
+        let mut env = base_env();
+        env.insert(
+            "TRUSTED_PROXIES".to_string(),
+            "10.0.0.0/8, 192.168.1.10 ,fd00::/8".to_string(),
+        );
+        let config = config_from(env);
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.get_trusted_proxies(),
+            vec![
+                "10.0.0.0/8".parse().unwrap(),
+                "192.168.1.10/32".parse().unwrap(),
+                "fd00::/8".parse().unwrap(),
+            ]
+        );
+
+        let mut env = base_env();
+        env.insert(
+            "TRUSTED_PROXIES".to_string(),
+            "10.0.0.0/33,proxy.internal".to_string(),
+        );
+        let errors = config_from(env).validate().unwrap_err();
+        assert_eq!(
+            errors
+                .issues
+                .iter()
+                .filter(|i| i.field == "TRUSTED_PROXIES")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_tls_and_admin_listener_settings() {
+        let mut env = base_env();
+        env.insert(
+            "TLS_CERT_PATH".to_string(),
+            "/nonexistent/cert.pem".to_string(),
+        );
+        env.insert(
+            "ADMIN_LISTEN_ADDR".to_string(),
+            "localhost:9443".to_string(),
+        );
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("TLS_CERT_PATH"));
+        assert!(errors.has("TLS_KEY_PATH"));
+        assert!(errors.has("ADMIN_LISTEN_ADDR"));
+
+        let mut env = base_env();
+        env.insert(
+            "ADMIN_LISTEN_ADDR".to_string(),
+            "127.0.0.1:3000".to_string(),
+        );
+        env.insert(
+            "ADMIN_TLS_CLIENT_CA_PATH".to_string(),
+            "/nonexistent/ca.pem".to_string(),
+        );
+
+        let errors = config_from(env).validate().unwrap_err();
+        // PORT のデフォルト（3000）と重複
+        assert!(errors.has("ADMIN_LISTEN_ADDR"));
+        // TLS 未設定では mTLS を使えない
+        assert_eq!(
+            errors
+                .issues
+                .iter()
+                .filter(|i| i.field == "ADMIN_TLS_CLIENT_CA_PATH")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_telemetry_settings() {
+        let config = config_from(base_env());
+        assert!(config.otel_exporter_otlp_endpoint.is_none());
+        assert_eq!(config.otel_service_name, "oxgate");
+        assert_eq!(config.otel_traces_sample_ratio, 1.0);
+
+        let mut env = base_env();
+        env.insert(
+            "OTEL_EXPORTER_OTLP_ENDPOINT".to_string(),
+            "localhost:4318".to_string(),
+        );
+        env.insert("OTEL_SERVICE_NAME".to_string(), " ".to_string());
+        env.insert("OTEL_TRACES_SAMPLE_RATIO".to_string(), "1.5".to_string());
+
+        let errors = config_from(env).validate().unwrap_err();
+        assert!(errors.has("OTEL_EXPORTER_OTLP_ENDPOINT"));
+        assert!(errors.has("OTEL_SERVICE_NAME"));
+        assert!(errors.has("OTEL_TRACES_SAMPLE_RATIO"));
+    }
+}
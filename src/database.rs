@@ -0,0 +1,189 @@
+//! データベース接続プール
+//!
+//! 接続プールのサイズ・タイムアウト、`statement_timeout`、TLS（`sslmode`・CA 証明書・
+//! クライアント証明書）を設定から組み立てる。リードレプリカが設定されている場合は
+//! 読み取り専用のプールも作成する（プール・TLS の設定はプライマリと共通）
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use secrecy::ExposeSecret;
+use sqlx::PgPool;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
+
+use crate::config::Config;
+
+/// 接続プールの用途
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PoolKind {
+    /// アプリケーションのクエリ（`statement_timeout` を適用）
+    Primary,
+    /// リードレプリカ（`statement_timeout` に加えて読み取り専用のトランザクションにする）
+    ReadReplica,
+    /// マイグレーション（長時間の DDL があるためタイムアウトを適用しない）
+    Migration,
+}
+
+/// プライマリの接続プールを作成
+pub async fn connect(config: &Config) -> Result<PgPool, sqlx::Error> {
+    connect_pool(
+        config,
+        config.database_url.expose_secret(),
+        PoolKind::Primary,
+    )
+    .await
+}
+
+/// リードレプリカの接続プールを作成（未設定の場合は None）
+pub async fn connect_read_replica(config: &Config) -> Result<Option<PgPool>, sqlx::Error> {
+    let Some(url) = &config.database_read_url else {
+        return Ok(None);
+    };
+    let pool = connect_pool(config, url.expose_secret(), PoolKind::ReadReplica).await?;
+    Ok(Some(pool))
+}
+
+/// マイグレーション用の接続プールを作成（`statement_timeout` を適用しない）
+pub async fn connect_for_migrations(config: &Config) -> Result<PgPool, sqlx::Error> {
+    connect_pool(
+        config,
+        config.database_url.expose_secret(),
+        PoolKind::Migration,
+    )
+    .await
+}
+
+async fn connect_pool(config: &Config, url: &str, kind: PoolKind) -> Result<PgPool, sqlx::Error> {
+    let options = connect_options(config, url, kind)?;
+    pool_options(config, kind).connect_with(options).await
+}
+
+/// 接続プールの設定
+fn pool_options(config: &Config, kind: PoolKind) -> PgPoolOptions {
+    let options = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(config.database_acquire_timeout_secs))
+        .idle_timeout(non_zero_secs(config.database_idle_timeout_secs))
+        .max_lifetime(non_zero_secs(config.database_max_lifetime_secs));
+
+    match kind {
+        // マイグレーションは 1 接続で順に適用する
+        PoolKind::Migration => options.max_connections(1),
+        PoolKind::Primary | PoolKind::ReadReplica => options
+            .max_connections(config.database_max_connections)
+            .min_connections(config.database_min_connections),
+    }
+}
+
+/// 接続ごとの設定（TLS・セッションパラメーター）
+///
+/// TLS の設定は URL のクエリパラメーター（`sslmode` など）より設定項目を優先する
+fn connect_options(
+    config: &Config,
+    url: &str,
+    kind: PoolKind,
+) -> Result<PgConnectOptions, sqlx::Error> {
+    let mut options = PgConnectOptions::from_str(url)?.application_name(env!("CARGO_PKG_NAME"));
+
+    if let Some(mode) = &config.database_ssl_mode {
+        options = options.ssl_mode(mode.parse::<PgSslMode>()?);
+    }
+    if let Some(path) = &config.database_ssl_root_cert {
+        options = options.ssl_root_cert(path);
+    }
+    if let Some(path) = &config.database_ssl_client_cert {
+        options = options.ssl_client_cert(path);
+    }
+    if let Some(path) = &config.database_ssl_client_key {
+        options = options.ssl_client_key(path);
+    }
+
+    let statement_timeout = (kind != PoolKind::Migration
+        && config.database_statement_timeout_ms > 0)
+        .then(|| config.database_statement_timeout_ms.to_string());
+    if let Some(timeout) = &statement_timeout {
+        options = options.options([("statement_timeout", timeout.as_str())]);
+    }
+    if kind == PoolKind::ReadReplica {
+        options = options.options([("default_transaction_read_only", "on")]);
+    }
+
+    Ok(options)
+}
+
+/// 0 を「無効」として扱う秒数
+fn non_zero_secs(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn config(extra: &[(&str, &str)]) -> Config {
+        let mut env = HashMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://oxgate@localhost/oxgate?sslmode=disable".to_string(),
+            ),
+            (
+                "HYDRA_ADMIN_URL".to_string(),
+                "http://localhost:4445".to_string(),
+            ),
+            ("TOTP_ISSUER".to_string(), "oxgate".to_string()),
+        ]);
+        for (key, value) in extra {
+            env.insert(key.to_string(), value.to_string());
+        }
+        envy::from_iter(env).unwrap()
+    }
+
+    fn connect_options_for(config: &Config, kind: PoolKind) -> PgConnectOptions {
+        connect_options(config, config.database_url.expose_secret(), kind).unwrap()
+    }
+
+    #[test]
+    fn test_ssl_mode_overrides_url() {
+        let from_url = config(&[]);
+        let options = connect_options_for(&from_url, PoolKind::Primary);
+        assert!(matches!(options.get_ssl_mode(), PgSslMode::Disable));
+
+        let overridden = config(&[("DATABASE_SSL_MODE", "verify-full")]);
+        let options = connect_options_for(&overridden, PoolKind::Primary);
+        assert!(matches!(options.get_ssl_mode(), PgSslMode::VerifyFull));
+    }
+
+    #[test]
+    fn test_statement_timeout_not_applied_to_migrations() {
+        let config = config(&[("DATABASE_STATEMENT_TIMEOUT_MS", "5000")]);
+
+        let primary = connect_options_for(&config, PoolKind::Primary);
+        assert_eq!(primary.get_options(), Some("-c statement_timeout=5000"));
+
+        let replica = connect_options_for(&config, PoolKind::ReadReplica);
+        assert_eq!(
+            replica.get_options(),
+            Some("-c statement_timeout=5000 -c default_transaction_read_only=on")
+        );
+
+        let migration = connect_options_for(&config, PoolKind::Migration);
+        assert_eq!(migration.get_options(), None);
+    }
+
+    #[test]
+    fn test_pool_options() {
+        let config = config(&[
+            ("DATABASE_MAX_CONNECTIONS", "20"),
+            ("DATABASE_IDLE_TIMEOUT_SECS", "0"),
+        ]);
+
+        let options = pool_options(&config, PoolKind::Primary);
+        assert_eq!(options.get_max_connections(), 20);
+        assert_eq!(options.get_idle_timeout(), None);
+        assert_eq!(options.get_max_lifetime(), Some(Duration::from_secs(1800)));
+
+        let options = pool_options(&config, PoolKind::Migration);
+        assert_eq!(options.get_max_connections(), 1);
+    }
+}
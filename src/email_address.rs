@@ -0,0 +1,240 @@
+//! メールアドレスの検証・正規化
+//!
+//! 保存・検索に使うメールアドレスは全て `normalize_email` で正規化する。
+//! ローカル部は小文字化し、ドメインは IDNA で ASCII（Punycode）に変換する。
+//! 引用符付きのローカル部（`"john doe"@example.com`）と IP アドレスのドメインは扱わない。
+
+use std::collections::HashSet;
+
+use crate::config::Config;
+use crate::error::AppError;
+
+/// メールアドレス全体の最大長（RFC 5321）
+const MAX_EMAIL_LENGTH: usize = 254;
+
+/// ローカル部の最大長（RFC 5321）
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+/// ドメインの最大長
+const MAX_DOMAIN_LENGTH: usize = 253;
+
+/// ドメインラベルの最大長
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// メールアドレスを検証し、正規化した形式を返す
+///
+/// # Errors
+/// メールアドレスの形式が不正な場合は `AppError::Validation`
+pub fn normalize_email(email: &str) -> Result<String, AppError> {
+    let email = email.trim();
+    let (local, domain) = email.rsplit_once('@').ok_or_else(invalid_email)?;
+
+    if !is_valid_local_part(local) {
+        return Err(invalid_email());
+    }
+    let domain = normalize_domain(domain).ok_or_else(invalid_email)?;
+
+    let normalized = format!("{}@{}", local.to_lowercase(), domain);
+    if normalized.len() > MAX_EMAIL_LENGTH {
+        return Err(invalid_email());
+    }
+    Ok(normalized)
+}
+
+/// 正規化済みメールアドレスのドメイン部
+pub fn email_domain(email: &str) -> &str {
+    email.rsplit_once('@').map_or("", |(_, domain)| domain)
+}
+
+fn invalid_email() -> AppError {
+    AppError::Validation("有効なメールアドレスを入力してください".to_string())
+}
+
+/// ローカル部の検証（dot-atom 形式、RFC 6531 の非 ASCII 文字を含む）
+fn is_valid_local_part(local: &str) -> bool {
+    !local.is_empty()
+        && local.len() <= MAX_LOCAL_PART_LENGTH
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || "!#$%&'*+-/=?^_`{|}~.".contains(c)
+                || (!c.is_ascii() && !c.is_control() && !c.is_whitespace())
+        })
+}
+
+/// ドメインを ASCII（Punycode）の小文字に変換して検証
+fn normalize_domain(domain: &str) -> Option<String> {
+    let domain = idna::domain_to_ascii(domain).ok()?;
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let valid = domain.len() <= MAX_DOMAIN_LENGTH
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= MAX_LABEL_LENGTH
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+        // TLD が数字のみの場合は IP アドレスとみなして拒否
+        && labels
+            .last()
+            .is_some_and(|tld| !tld.chars().all(|c| c.is_ascii_digit()));
+
+    valid.then_some(domain)
+}
+
+/// 使い捨てメールアドレスのドメインリスト
+///
+/// `DISPOSABLE_EMAIL_DOMAINS_PATH` のファイル（1 行 1 ドメイン、`#` 以降はコメント）から読み込む。
+/// 登録されたドメインのサブドメインも対象とする
+#[derive(Debug, Default)]
+pub struct DisposableDomains {
+    domains: HashSet<String>,
+}
+
+impl DisposableDomains {
+    /// 設定からドメインリストを読み込む（未設定の場合は空）
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        let Some(path) = &config.disposable_email_domains_path else {
+            return Ok(Self::default());
+        };
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            AppError::Internal(anyhow::anyhow!(
+                "使い捨てメールドメインリストの読み込みに失敗 ({}): {}",
+                path,
+                e
+            ))
+        })?;
+        let domains = Self::parse(&content);
+        tracing::info!(
+            count = domains.len(),
+            "使い捨てメールドメインリストを読み込み"
+        );
+        Ok(domains)
+    }
+
+    /// ドメインリストを解析（不正な行は無視）
+    pub fn parse(content: &str) -> Self {
+        let domains = content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .filter_map(normalize_domain)
+            .collect();
+        Self { domains }
+    }
+
+    /// 登録されたドメイン数
+    pub fn len(&self) -> usize {
+        self.domains.len()
+    }
+
+    /// ドメインリストが空か
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    /// 使い捨てメールアドレスのドメインか（正規化済みのメールアドレスを渡すこと）
+    pub fn contains(&self, email: &str) -> bool {
+        let mut domain = email_domain(email);
+        loop {
+            if self.domains.contains(domain) {
+                return true;
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) => domain = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// 使い捨てメールアドレスを拒否
+    pub fn ensure_allowed(&self, email: &str) -> Result<(), AppError> {
+        if self.contains(email) {
+            tracing::info!(domain = %email_domain(email), "使い捨てメールアドレスを拒否");
+            return Err(AppError::Validation(
+                "このメールアドレスのドメインは使用できません".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_email() {
+        assert_eq!(
+            normalize_email("  Alice@Example.COM ").unwrap(),
+            "alice@example.com"
+        );
+        assert_eq!(
+            normalize_email("first.last+tag@sub.example.co.jp").unwrap(),
+            "first.last+tag@sub.example.co.jp"
+        );
+        // IDN ドメインは Punycode に変換
+        assert_eq!(
+            normalize_email("User@例え.テスト").unwrap(),
+            "user@xn--r8jz45g.xn--zckzah"
+        );
+        assert_eq!(
+            normalize_email("ユーザー@example.com").unwrap(),
+            "ユーザー@example.com"
+        );
+    }
+
+    #[test]
+    fn test_normalize_email_rejects_invalid_syntax() {
+        let invalid = [
+            "",
+            "alice",
+            "@example.com",
+            "alice@",
+            "alice@localhost",
+            "alice@example..com",
+            "alice@-example.com",
+            "alice@example.com.",
+            "alice@192.168.0.1",
+            "alice@exa mple.com",
+            ".alice@example.com",
+            "alice.@example.com",
+            "al..ice@example.com",
+            "al ice@example.com",
+            "\"alice\"@example.com",
+            "alice@example.com\n@evil.com",
+        ];
+        for email in invalid {
+            assert!(normalize_email(email).is_err(), "{:?}", email);
+        }
+
+        let long_local = format!("{}@example.com", "a".repeat(MAX_LOCAL_PART_LENGTH + 1));
+        assert!(normalize_email(&long_local).is_err());
+    }
+
+    #[test]
+    fn test_disposable_domains() {
+        let domains = DisposableDomains::parse(
+            "# disposable domains\nMailinator.com\n\n  tempmail.example # comment\nnot a domain\n",
+        );
+        assert_eq!(domains.len(), 2);
+
+        assert!(domains.contains("alice@mailinator.com"));
+        assert!(domains.contains("alice@eu.tempmail.example"));
+        assert!(!domains.contains("alice@example.com"));
+        assert!(!domains.contains("alice@notmailinator.com"));
+        assert!(domains.ensure_allowed("alice@mailinator.com").is_err());
+        assert!(
+            DisposableDomains::default()
+                .ensure_allowed("alice@mailinator.com")
+                .is_ok()
+        );
+    }
+}
@@ -5,6 +5,8 @@ use axum::{
 };
 use serde::Serialize;
 
+use crate::services::password_policy::{PasswordPolicyViolation, PasswordPolicyViolationResponse};
+
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
     #[error("認証エラー: {0}")]
@@ -51,15 +53,94 @@ pub enum AppError {
 
     #[error("OAuthプロバイダーエラー")]
     OAuthProviderError,
+
+    #[error("パスワードがポリシーを満たしていません")]
+    PasswordPolicy(Vec<PasswordPolicyViolation>),
+
+    #[error("このアカウントは削除予定です")]
+    AccountPendingDeletion,
+
+    #[error("このアカウントはロックされています")]
+    AccountLocked,
+
+    #[error("このアカウントは無効化されています")]
+    AccountDisabled,
+
+    #[error("メールアドレスの確認が完了していません")]
+    AccountNotVerified,
+
+    #[error("管理者認証エラー")]
+    AdminUnauthorized,
+
+    #[error("ユーザーが見つかりません")]
+    UserNotFound,
+
+    #[error("メッセージが見つかりません")]
+    OutboxMessageNotFound,
+
+    #[error("Webhook が見つかりません")]
+    WebhookNotFound,
+
+    #[error("アクセストークンが無効です")]
+    AccessTokenInvalid,
+
+    #[error("スコープが不足しています: {0}")]
+    InsufficientScope(String),
+
+    #[error("再ログインが必要です")]
+    ReauthenticationRequired,
+}
+
+impl AppError {
+    /// 監査ログに記録する失敗理由（機械可読な固定文字列）
+    pub fn reason_code(&self) -> &'static str {
+        match self {
+            Self::Authentication(_) => "invalid_credentials",
+            Self::Validation(_) => "validation_failed",
+            Self::Database(_) => "database_error",
+            Self::Hydra(_) => "hydra_error",
+            Self::Internal(_) => "internal_error",
+            Self::EmailAlreadyExists => "email_already_exists",
+            Self::TokenExpired => "token_expired",
+            Self::TokenNotFound => "token_not_found",
+            Self::TotpInvalid => "totp_invalid",
+            Self::TotpAlreadyEnabled => "totp_already_enabled",
+            Self::TotpNotEnabled => "totp_not_enabled",
+            Self::TotpSetupRequired => "totp_setup_required",
+            Self::OAuthError(_) => "oauth_error",
+            Self::OAuthStateInvalid => "oauth_state_invalid",
+            Self::OAuthProviderError => "oauth_provider_error",
+            Self::PasswordPolicy(_) => "password_policy",
+            Self::AccountPendingDeletion => "account_pending_deletion",
+            Self::AccountLocked => "account_locked",
+            Self::AccountDisabled => "account_disabled",
+            Self::AccountNotVerified => "account_not_verified",
+            Self::AdminUnauthorized => "admin_unauthorized",
+            Self::UserNotFound => "user_not_found",
+            Self::OutboxMessageNotFound => "outbox_message_not_found",
+            Self::WebhookNotFound => "webhook_not_found",
+            Self::AccessTokenInvalid => "access_token_invalid",
+            Self::InsufficientScope(_) => "insufficient_scope",
+            Self::ReauthenticationRequired => "reauthentication_required",
+        }
+    }
 }
 
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
+    /// パスワードポリシー違反の内訳（該当時のみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    violations: Vec<PasswordPolicyViolationResponse>,
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let violations = match &self {
+            Self::PasswordPolicy(violations) => violations.iter().map(Into::into).collect(),
+            _ => Vec::new(),
+        };
+
         let (status, message) = match &self {
             Self::Authentication(_) => (
                 StatusCode::UNAUTHORIZED,
@@ -126,8 +207,62 @@ impl IntoResponse for AppError {
                 StatusCode::BAD_GATEWAY,
                 "外部認証サービスとの通信に失敗しました".to_string(),
             ),
+            Self::PasswordPolicy(_) => (
+                StatusCode::BAD_REQUEST,
+                "パスワードがポリシーを満たしていません".to_string(),
+            ),
+            Self::AccountPendingDeletion => (
+                StatusCode::FORBIDDEN,
+                "このアカウントは削除予定です".to_string(),
+            ),
+            Self::AccountLocked => (
+                StatusCode::FORBIDDEN,
+                "このアカウントはロックされています".to_string(),
+            ),
+            Self::AccountDisabled => (
+                StatusCode::FORBIDDEN,
+                "このアカウントは無効化されています".to_string(),
+            ),
+            Self::AccountNotVerified => (
+                StatusCode::FORBIDDEN,
+                "メールアドレスの確認が完了していません".to_string(),
+            ),
+            Self::AdminUnauthorized => {
+                (StatusCode::UNAUTHORIZED, "管理者認証が必要です".to_string())
+            }
+            Self::UserNotFound => (
+                StatusCode::NOT_FOUND,
+                "ユーザーが見つかりません".to_string(),
+            ),
+            Self::OutboxMessageNotFound => (
+                StatusCode::NOT_FOUND,
+                "メッセージが見つかりません".to_string(),
+            ),
+            Self::WebhookNotFound => (
+                StatusCode::NOT_FOUND,
+                "Webhook が見つかりません".to_string(),
+            ),
+            Self::AccessTokenInvalid => (
+                StatusCode::UNAUTHORIZED,
+                "アクセストークンが無効です".to_string(),
+            ),
+            Self::InsufficientScope(scope) => (
+                StatusCode::FORBIDDEN,
+                format!("スコープ '{}' が必要です", scope),
+            ),
+            Self::ReauthenticationRequired => (
+                StatusCode::UNAUTHORIZED,
+                "もう一度ログインしてから操作してください".to_string(),
+            ),
         };
 
-        (status, Json(ErrorResponse { error: message })).into_response()
+        (
+            status,
+            Json(ErrorResponse {
+                error: message,
+                violations,
+            }),
+        )
+            .into_response()
     }
 }
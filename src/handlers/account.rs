@@ -0,0 +1,565 @@
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::authenticated_user::bearer_token;
+use crate::client_info::ClientInfo;
+use crate::error::AppError;
+use crate::models::{AuditEvent, AuditEventType, NewAuditEvent, User};
+use crate::services::AccountService;
+use crate::services::account::DeletionOutcome;
+use crate::services::auth::{AuthService, PasswordHashService};
+use crate::services::hydra::IntrospectedToken;
+use crate::state::AppState;
+
+/// 本人確認付きリクエスト（エクスポート・削除・削除取り消し・アクティビティ共通）
+#[derive(Debug, Deserialize)]
+pub struct ReauthRequest {
+    pub user_id: Uuid,
+    /// パスワード（パスワード未設定のアカウントは省略し、アクセストークンで確認する）
+    #[serde(default)]
+    pub password: Option<String>,
+    /// TOTPコード（2FA有効時のみ必須）
+    #[serde(default)]
+    pub code: Option<String>,
+}
+
+/// 最近のアクティビティとして返す件数
+const RECENT_ACTIVITY_LIMIT: i64 = 50;
+
+/// パスワード未設定アカウントの本人確認に使うアクセストークンに必要なスコープ
+const ACCOUNT_SCOPE: &str = "account";
+
+/// パスワード未設定アカウントの本人確認で、直近のログインとみなす期間（分）
+const RECENT_LOGIN_MAX_AGE_MINUTES: i64 = 10;
+
+/// 本人確認に使う資格情報
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Credentials<'a> {
+    /// パスワード
+    Password(&'a str),
+    /// アクセストークン（パスワード未設定のアカウントのみ）
+    AccessToken(&'a str),
+}
+
+impl<'a> Credentials<'a> {
+    /// リクエストのパスワード、省略時は `Authorization: Bearer` のアクセストークン
+    fn from_request(request: &'a ReauthRequest, headers: &'a HeaderMap) -> Result<Self, AppError> {
+        match request.password.as_deref().filter(|p| !p.is_empty()) {
+            Some(password) => Ok(Self::Password(password)),
+            None => bearer_token(headers)
+                .map(Self::AccessToken)
+                .ok_or_else(|| AppError::Validation("パスワードは必須です".to_string())),
+        }
+    }
+}
+
+// === データエクスポート ===
+
+/// POST /api/account/export
+///
+/// 保持している個人データを JSON アーカイブとして返す
+///
+/// # Security
+/// - パスワード確認必須（2FA有効時はTOTPコードも必須）
+/// - パスワード未設定のアカウントは直近のログインで発行されたアクセストークン（`account` スコープ）で確認
+pub async fn export_account(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    headers: HeaderMap,
+    Json(request): Json<ReauthRequest>,
+) -> Result<Response, AppError> {
+    validate_reauth_request(&request)?;
+    let user = reauthenticate(
+        &state,
+        &client,
+        AuditEventType::AccountExported,
+        request.user_id,
+        Credentials::from_request(&request, &headers)?,
+        request.code.clone(),
+    )
+    .await?;
+
+    let export = account_service(&state).export(&user).await?;
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::AccountExported)
+                .user(user.id)
+                .client(&client),
+        )
+        .await;
+
+    let disposition = format!("attachment; filename=\"oxgate-export-{}.json\"", user.id);
+    Ok(([(header::CONTENT_DISPOSITION, disposition)], Json(export)).into_response())
+}
+
+// === アカウント削除 ===
+
+#[derive(Debug, Serialize)]
+pub struct DeleteAccountResponse {
+    pub deleted: bool,
+    /// 削除予定日時（猶予期間中のみ）
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub deletion_scheduled_at: Option<OffsetDateTime>,
+}
+
+/// POST /api/account/delete
+///
+/// アカウント削除をリクエスト
+///
+/// Hydra のログインセッションと同意を即時に失効させ、猶予期間
+/// （`ACCOUNT_DELETION_GRACE_PERIOD_SECS`）の経過後に全データを削除する。
+/// 猶予期間中はログインできず、削除の取り消しのみ可能
+///
+/// # Security
+/// - パスワード確認必須（2FA有効時はTOTPコードも必須）
+/// - パスワード未設定のアカウントは直近のログインで発行されたアクセストークン（`account` スコープ）で確認
+pub async fn delete_account(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    headers: HeaderMap,
+    Json(request): Json<ReauthRequest>,
+) -> Result<Json<DeleteAccountResponse>, AppError> {
+    validate_reauth_request(&request)?;
+    let user = reauthenticate(
+        &state,
+        &client,
+        AuditEventType::AccountDeletionRequested,
+        request.user_id,
+        Credentials::from_request(&request, &headers)?,
+        request.code.clone(),
+    )
+    .await?;
+
+    let grace_period_secs = state.config.load().account_deletion_grace_period_secs;
+    let outcome = account_service(&state)
+        .request_deletion(&user, grace_period_secs)
+        .await?;
+
+    state
+        .audit_service
+        .record(
+            deletion_requested_event(user.id, &outcome)
+                .client(&client)
+                .with("grace_period_secs", grace_period_secs),
+        )
+        .await;
+
+    Ok(Json(match outcome {
+        DeletionOutcome::Scheduled { deletion_at } => DeleteAccountResponse {
+            deleted: false,
+            deletion_scheduled_at: Some(deletion_at),
+        },
+        DeletionOutcome::Deleted => DeleteAccountResponse {
+            deleted: true,
+            deletion_scheduled_at: None,
+        },
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct CancelDeletionResponse {
+    pub cancelled: bool,
+}
+
+/// POST /api/account/delete/cancel
+///
+/// 猶予期間中のアカウント削除を取り消す
+///
+/// # Security
+/// - パスワード確認必須（2FA有効時はTOTPコードも必須）
+/// - パスワード未設定のアカウントは直近のログインで発行されたアクセストークン（`account` スコープ）で確認
+pub async fn cancel_account_deletion(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    headers: HeaderMap,
+    Json(request): Json<ReauthRequest>,
+) -> Result<Json<CancelDeletionResponse>, AppError> {
+    validate_reauth_request(&request)?;
+    let user = reauthenticate(
+        &state,
+        &client,
+        AuditEventType::AccountDeletionCancelled,
+        request.user_id,
+        Credentials::from_request(&request, &headers)?,
+        request.code.clone(),
+    )
+    .await?;
+
+    let cancelled = account_service(&state).cancel_deletion(&user).await?;
+
+    if cancelled {
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::success(AuditEventType::AccountDeletionCancelled)
+                    .user(user.id)
+                    .client(&client),
+            )
+            .await;
+    }
+
+    Ok(Json(CancelDeletionResponse { cancelled }))
+}
+
+// === 最近のアクティビティ ===
+
+#[derive(Debug, Serialize)]
+pub struct ActivityResponse {
+    pub events: Vec<ActivityEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityEntry {
+    pub event_type: String,
+    pub outcome: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub metadata: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<AuditEvent> for ActivityEntry {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            event_type: event.event_type,
+            outcome: event.outcome,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            metadata: event.metadata,
+            created_at: event.created_at,
+        }
+    }
+}
+
+/// POST /api/account/activity
+///
+/// 最近のログイン・セキュリティ関連の操作履歴（新しい順）を返す
+///
+/// # Security
+/// - パスワード確認必須（2FA有効時はTOTPコードも必須）
+/// - パスワード未設定のアカウントは直近のログインで発行されたアクセストークン（`account` スコープ）で確認
+pub async fn account_activity(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    headers: HeaderMap,
+    Json(request): Json<ReauthRequest>,
+) -> Result<Json<ActivityResponse>, AppError> {
+    validate_reauth_request(&request)?;
+    let user = reauthenticate(
+        &state,
+        &client,
+        AuditEventType::ActivityViewed,
+        request.user_id,
+        Credentials::from_request(&request, &headers)?,
+        request.code.clone(),
+    )
+    .await?;
+
+    let events = state
+        .audit_service
+        .recent_activity(user.id, RECENT_ACTIVITY_LIMIT)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::ActivityViewed)
+                .user(user.id)
+                .client(&client),
+        )
+        .await;
+
+    Ok(Json(ActivityResponse { events }))
+}
+
+// === Helper Functions ===
+
+fn account_service(state: &AppState) -> AccountService {
+    AccountService::new(
+        state.user_repo.clone(),
+        state.user_2fa_repo.clone(),
+        state.social_account_repo.clone(),
+        state.hydra_client.clone(),
+        state.outbox_service.clone(),
+        state.webhook_service.clone(),
+        state.audit_service.clone(),
+        state.profile_service.clone(),
+    )
+}
+
+/// 本人確認リクエストのバリデーション（パスワードの有無は `Credentials::from_request` で確認する）
+fn validate_reauth_request(request: &ReauthRequest) -> Result<(), AppError> {
+    if let Some(code) = &request.code {
+        validate_totp_code(code)?;
+    }
+    Ok(())
+}
+
+/// TOTPコードの形式チェック
+pub(crate) fn validate_totp_code(code: &str) -> Result<(), AppError> {
+    if code.len() != 6 || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppError::Validation(
+            "認証コードは6桁の数字で入力してください".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 機密操作の前に本人確認を行う
+///
+/// パスワード（パスワード未設定のアカウントは直近のログインで発行されたアクセストークン）を
+/// 検証し、2FA有効時はTOTPコードも検証する。
+/// 削除予定アカウントも確認できる（削除の取り消しに必要なため）。
+/// 失敗時は `event_type` の失敗イベントとして監査ログに記録する
+///
+/// # Security
+/// - パスワード・コード・アクセストークンはログに出力しない
+pub(crate) async fn reauthenticate(
+    state: &AppState,
+    client: &ClientInfo,
+    event_type: AuditEventType,
+    user_id: Uuid,
+    credentials: Credentials<'_>,
+    code: Option<String>,
+) -> Result<User, AppError> {
+    let result = verify_credentials(state, user_id, credentials, code).await;
+
+    if let Err(e) = &result {
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::failure(event_type, e)
+                    .user(user_id)
+                    .client(client),
+            )
+            .await;
+    }
+
+    result
+}
+
+/// パスワード（またはアクセストークン）・TOTPコードを検証
+async fn verify_credentials(
+    state: &AppState,
+    user_id: Uuid,
+    credentials: Credentials<'_>,
+    code: Option<String>,
+) -> Result<User, AppError> {
+    let user = state
+        .user_repo
+        .find_by_id(user_id)
+        .await?
+        .ok_or_else(|| AppError::Authentication("user not found".to_string()))?;
+
+    let user = match credentials {
+        Credentials::Password(password) => {
+            let auth_service = AuthService::new(
+                state.user_repo.clone(),
+                PasswordHashService::from_config(&state.config.load())?,
+            );
+            auth_service.authenticate(&user.email, password).await?
+        }
+        Credentials::AccessToken(token) => {
+            let introspected = state.hydra_client.introspect_token(token).await?;
+            verify_access_token(&user, &introspected)?;
+            ensure_recent_login(state, &user).await?;
+            user
+        }
+    };
+
+    if let Some(user_2fa) = state.user_2fa_repo.find_by_user_id(user.id).await?
+        && user_2fa.enabled
+    {
+        let code = code.ok_or_else(|| AppError::Validation("認証コードは必須です".to_string()))?;
+        let secret = state
+            .totp_service
+            .decrypt_secret(&user_2fa.secret_encrypted)?;
+        if !state.totp_service.verify_code(&secret, &code)? {
+            return Err(AppError::TotpInvalid);
+        }
+    }
+
+    Ok(user)
+}
+
+/// パスワード未設定アカウントのアクセストークンを検証
+///
+/// パスワードを設定済みのアカウントはアクセストークンでは確認しない（パスワードを求める）。
+/// 第三者のアプリケーションに発行されたトークンで操作されないよう、`account` スコープを必須とする
+fn verify_access_token(user: &User, introspected: &IntrospectedToken) -> Result<(), AppError> {
+    if user.password_hash.is_some() {
+        return Err(AppError::Authentication(
+            "password required for account with password".to_string(),
+        ));
+    }
+    if !introspected.active || introspected.sub.as_deref() != Some(user.id.to_string().as_str()) {
+        return Err(AppError::AccessTokenInvalid);
+    }
+    if !introspected.has_scope(ACCOUNT_SCOPE) {
+        return Err(AppError::InsufficientScope(ACCOUNT_SCOPE.to_string()));
+    }
+    AuthService::ensure_active(user)
+}
+
+/// 直近（`RECENT_LOGIN_MAX_AGE_MINUTES` 以内）にログイン（ソーシャルログインを含む）したか確認
+async fn ensure_recent_login(state: &AppState, user: &User) -> Result<(), AppError> {
+    let since = OffsetDateTime::now_utc() - time::Duration::minutes(RECENT_LOGIN_MAX_AGE_MINUTES);
+    if state
+        .audit_service
+        .recent_logins(user.id, since, 1)
+        .await?
+        .is_empty()
+    {
+        return Err(AppError::ReauthenticationRequired);
+    }
+    Ok(())
+}
+
+/// アカウント削除リクエストの監査イベント
+///
+/// 即時削除の場合はユーザーが既に存在せず外部キーで参照できないため、
+/// ユーザーに紐付けずにメタデータにユーザーIDを記録する
+fn deletion_requested_event(user_id: Uuid, outcome: &DeletionOutcome) -> NewAuditEvent {
+    let event = NewAuditEvent::success(AuditEventType::AccountDeletionRequested)
+        .actor(user_id.to_string())
+        .with("user_id", user_id.to_string());
+    match outcome {
+        DeletionOutcome::Scheduled { .. } => event.user(user_id),
+        DeletionOutcome::Deleted => event,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(password: &str, code: Option<&str>) -> ReauthRequest {
+        ReauthRequest {
+            user_id: Uuid::new_v4(),
+            password: Some(password.to_string()),
+            code: code.map(str::to_string),
+        }
+    }
+
+    fn social_user() -> User {
+        let now = OffsetDateTime::now_utc();
+        User {
+            id: Uuid::new_v4(),
+            email: "user@example.com".to_string(),
+            password_hash: None,
+            created_at: now,
+            updated_at: now,
+            deletion_scheduled_at: None,
+            locked_at: None,
+            status: "active".to_string(),
+            lock_reason: None,
+            locked_until: None,
+        }
+    }
+
+    fn token(sub: Uuid, scope: &str) -> IntrospectedToken {
+        IntrospectedToken {
+            active: true,
+            sub: Some(sub.to_string()),
+            client_id: Some("app".to_string()),
+            scope: Some(scope.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_credentials_from_request() {
+        let mut headers = HeaderMap::new();
+        assert!(Credentials::from_request(&request("", None), &headers).is_err());
+
+        headers.insert(
+            header::AUTHORIZATION,
+            "Bearer access-token".parse().unwrap(),
+        );
+        assert_eq!(
+            Credentials::from_request(&request("", None), &headers).unwrap(),
+            Credentials::AccessToken("access-token")
+        );
+        // パスワードがある場合はパスワードで確認する
+        assert_eq!(
+            Credentials::from_request(&request("password123", None), &headers).unwrap(),
+            Credentials::Password("password123")
+        );
+    }
+
+    #[test]
+    fn test_verify_access_token() {
+        let user = social_user();
+        assert!(verify_access_token(&user, &token(user.id, "openid account")).is_ok());
+
+        // 他のユーザーのトークン・無効なトークン
+        assert!(matches!(
+            verify_access_token(&user, &token(Uuid::new_v4(), "account")),
+            Err(AppError::AccessTokenInvalid)
+        ));
+        let mut inactive = token(user.id, "account");
+        inactive.active = false;
+        assert!(matches!(
+            verify_access_token(&user, &inactive),
+            Err(AppError::AccessTokenInvalid)
+        ));
+
+        // account スコープのないトークン（第三者のアプリケーションなど）
+        assert!(matches!(
+            verify_access_token(&user, &token(user.id, "openid profile")),
+            Err(AppError::InsufficientScope(_))
+        ));
+
+        // パスワードを設定済みのアカウントはパスワードが必要
+        let mut with_password = social_user();
+        with_password.password_hash = Some("hash".to_string());
+        assert!(matches!(
+            verify_access_token(&with_password, &token(with_password.id, "account")),
+            Err(AppError::Authentication(_))
+        ));
+
+        // ロック中のアカウント
+        let mut locked = social_user();
+        locked.status = "locked".to_string();
+        assert!(verify_access_token(&locked, &token(locked.id, "account")).is_err());
+    }
+
+    #[test]
+    fn test_validate_invalid_code() {
+        assert!(validate_reauth_request(&request("password123", Some("abc"))).is_err());
+        assert!(validate_reauth_request(&request("password123", Some("1234567"))).is_err());
+    }
+
+    #[test]
+    fn test_validate_valid_request() {
+        assert!(validate_reauth_request(&request("password123", None)).is_ok());
+        assert!(validate_reauth_request(&request("password123", Some("123456"))).is_ok());
+    }
+
+    #[test]
+    fn test_immediate_deletion_event_is_not_linked_to_user() {
+        let user_id = Uuid::new_v4();
+
+        let event = deletion_requested_event(user_id, &DeletionOutcome::Deleted);
+        assert_eq!(event.user_id, None);
+        assert_eq!(event.metadata["user_id"], user_id.to_string());
+
+        let scheduled = DeletionOutcome::Scheduled {
+            deletion_at: OffsetDateTime::now_utc(),
+        };
+        let event = deletion_requested_event(user_id, &scheduled);
+        assert_eq!(event.user_id, Some(user_id));
+    }
+}
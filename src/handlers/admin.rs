@@ -0,0 +1,1035 @@
+//! 管理 API（`/admin/api`）
+//!
+//! `Authorization: Bearer <token>` で認証する。token には `ADMIN_API_KEY`、
+//! または `ADMIN_TOKEN_SCOPE` のスコープを持つ Hydra のアクセストークンを指定できる。
+//! どちらも未設定の場合は全てのリクエストを拒否する。
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use http::header::AUTHORIZATION;
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::client_info::ClientInfo;
+use crate::error::AppError;
+use crate::models::{
+    AuditEvent, AuditEventType, AuditOutcome, NewAuditEvent, OutboxMessage, UserStatus,
+    WebhookDelivery, WebhookEvent, WebhookEventType, WebhookSubscription,
+};
+use crate::repositories::AuditEventFilter;
+use crate::services::user_admin::{AdminUserDetail, AdminUserSummary};
+use crate::services::{PasswordResetService, SecurityEvent, UserAdminService};
+use crate::state::AppState;
+
+/// 一覧取得のデフォルト件数
+const DEFAULT_PAGE_LIMIT: i64 = 100;
+
+/// 一覧取得の最大件数
+const MAX_PAGE_LIMIT: i64 = 1000;
+
+/// ロック・無効化の理由の最大文字数
+const MAX_LOCK_REASON_LENGTH: usize = 500;
+
+/// Webhook の説明の最大文字数
+const MAX_WEBHOOK_DESCRIPTION_LENGTH: usize = 500;
+
+/// 認証済みの管理者
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminPrincipal {
+    /// `ADMIN_API_KEY` で認証
+    ApiKey,
+    /// Hydra のアクセストークンで認証（subject、なければ client_id）
+    Token { subject: String },
+}
+
+impl AdminPrincipal {
+    /// 監査ログの actor
+    pub fn actor(&self) -> String {
+        match self {
+            Self::ApiKey => "admin:api_key".to_string(),
+            Self::Token { subject } => format!("admin:{}", subject),
+        }
+    }
+}
+
+/// 管理 API の認証ミドルウェア
+///
+/// 認証に成功した場合は `AdminPrincipal` をリクエストの Extension に設定する
+///
+/// # Security
+/// - API キーは定数時間で比較する
+/// - API キー・トークンはログに出力しない
+pub async fn require_admin(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let provided = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+
+    let principal = match provided {
+        Some(provided) => authenticate_admin(&state, &provided).await?,
+        None => None,
+    };
+
+    let Some(principal) = principal else {
+        tracing::warn!(client_ip = ?client.ip, path = %request.uri().path(), "管理 API の認証に失敗");
+        return Err(AppError::AdminUnauthorized);
+    };
+
+    request.extensions_mut().insert(principal);
+    Ok(next.run(request).await)
+}
+
+/// API キー、または Hydra のアクセストークンで管理者を認証
+async fn authenticate_admin(
+    state: &AppState,
+    provided: &str,
+) -> Result<Option<AdminPrincipal>, AppError> {
+    let scope = {
+        let config = state.config.load();
+        if let Some(expected) = &config.admin_api_key
+            && bool::from(
+                expected
+                    .expose_secret()
+                    .as_bytes()
+                    .ct_eq(provided.as_bytes()),
+            )
+        {
+            return Ok(Some(AdminPrincipal::ApiKey));
+        }
+        config.admin_token_scope.clone()
+    };
+
+    let Some(scope) = scope else {
+        return Ok(None);
+    };
+
+    let token = state.hydra_client.introspect_token(provided).await?;
+    if !token.has_scope(&scope) {
+        return Ok(None);
+    }
+
+    let subject = token
+        .sub
+        .filter(|sub| !sub.is_empty())
+        .or(token.client_id)
+        .unwrap_or_default();
+    Ok(Some(AdminPrincipal::Token { subject }))
+}
+
+// === 監査イベント検索 ===
+
+/// 監査イベント検索クエリ（未指定の項目は絞り込まない）
+#[derive(Debug, Deserialize)]
+pub struct AuditEventQuery {
+    pub user_id: Option<Uuid>,
+    pub actor: Option<String>,
+    pub event_type: Option<AuditEventType>,
+    pub outcome: Option<AuditOutcome>,
+    pub ip_address: Option<String>,
+    /// この日時以降（RFC 3339）
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub since: Option<OffsetDateTime>,
+    /// この日時より前（RFC 3339）
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub until: Option<OffsetDateTime>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditEventListResponse {
+    pub events: Vec<AuditEvent>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// GET /admin/api/audit-events
+///
+/// 監査イベントを新しい順に検索
+pub async fn list_audit_events(
+    State(state): State<AppState>,
+    Query(query): Query<AuditEventQuery>,
+) -> Result<Json<AuditEventListResponse>, AppError> {
+    let (limit, offset) = validate_pagination(query.limit, query.offset)?;
+
+    let filter = AuditEventFilter {
+        user_id: query.user_id,
+        actor: query.actor,
+        event_type: query.event_type,
+        outcome: query.outcome,
+        ip_address: query.ip_address,
+        since: query.since,
+        until: query.until,
+    };
+    let events = state.audit_service.search(&filter, limit, offset).await?;
+
+    Ok(Json(AuditEventListResponse {
+        events,
+        limit,
+        offset,
+    }))
+}
+
+// === 送信キュー ===
+
+/// dead メッセージ一覧のクエリ
+#[derive(Debug, Deserialize)]
+pub struct OutboxQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutboxMessageListResponse {
+    /// メッセージ（payload は含めない）
+    pub messages: Vec<OutboxMessage>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// GET /admin/api/outbox/dead
+///
+/// 再試行回数を超えて配信できなかったメッセージを新しい順に取得
+pub async fn list_dead_outbox_messages(
+    State(state): State<AppState>,
+    Query(query): Query<OutboxQuery>,
+) -> Result<Json<OutboxMessageListResponse>, AppError> {
+    let (limit, offset) = validate_pagination(query.limit, query.offset)?;
+    let messages = state.outbox_service.list_dead(limit, offset).await?;
+
+    Ok(Json(OutboxMessageListResponse {
+        messages,
+        limit,
+        offset,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct RetryOutboxMessageResponse {
+    pub requeued: bool,
+}
+
+/// POST /admin/api/outbox/{message_id}/retry
+///
+/// dead メッセージを再試行対象に戻す（試行回数はリセット）
+pub async fn retry_outbox_message(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(message_id): Path<Uuid>,
+) -> Result<Json<RetryOutboxMessageResponse>, AppError> {
+    let result = state.outbox_service.requeue_dead(message_id).await;
+
+    let event = match &result {
+        Ok(()) => NewAuditEvent::success(AuditEventType::OutboxMessageRequeued),
+        Err(e) => NewAuditEvent::failure(AuditEventType::OutboxMessageRequeued, e),
+    };
+    state
+        .audit_service
+        .record(
+            event
+                .actor(admin.actor())
+                .client(&client)
+                .with("message_id", message_id.to_string()),
+        )
+        .await;
+
+    result?;
+    Ok(Json(RetryOutboxMessageResponse { requeued: true }))
+}
+
+// === Webhook ===
+
+#[derive(Debug, Serialize)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<WebhookSubscription>,
+}
+
+/// GET /admin/api/webhooks
+///
+/// Webhook の購読設定の一覧（署名用シークレットは含めない）
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+) -> Result<Json<WebhookListResponse>, AppError> {
+    let webhooks = state.webhook_service.list_subscriptions().await?;
+    Ok(Json(WebhookListResponse { webhooks }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub event_types: Vec<WebhookEventType>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateWebhookResponse {
+    pub webhook: WebhookSubscription,
+    /// 署名用シークレット（このレスポンスでのみ返却）
+    pub secret: String,
+}
+
+/// POST /admin/api/webhooks
+///
+/// Webhook の購読設定を作成し、署名用シークレットを返す
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<Json<CreateWebhookResponse>, AppError> {
+    let description = request
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty());
+    if description.is_some_and(|d| d.chars().count() > MAX_WEBHOOK_DESCRIPTION_LENGTH) {
+        return Err(AppError::Validation(format!(
+            "説明は {} 文字以内で入力してください",
+            MAX_WEBHOOK_DESCRIPTION_LENGTH
+        )));
+    }
+
+    let created = state
+        .webhook_service
+        .create_subscription(request.url.trim(), &request.event_types, description)
+        .await?;
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::WebhookCreated)
+                .actor(admin.actor())
+                .client(&client)
+                .with("webhook_id", created.subscription.id.to_string())
+                .with("url", created.subscription.url.as_str()),
+        )
+        .await;
+
+    Ok(Json(CreateWebhookResponse {
+        webhook: created.subscription,
+        secret: created.secret,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    pub enabled: bool,
+}
+
+/// PATCH /admin/api/webhooks/{webhook_id}
+///
+/// Webhook の購読設定を有効・無効にする
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(webhook_id): Path<Uuid>,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookSubscription>, AppError> {
+    let webhook = state
+        .webhook_service
+        .set_enabled(webhook_id, request.enabled)
+        .await?;
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::WebhookUpdated)
+                .actor(admin.actor())
+                .client(&client)
+                .with("webhook_id", webhook_id.to_string())
+                .with("enabled", request.enabled),
+        )
+        .await;
+
+    Ok(Json(webhook))
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteWebhookResponse {
+    pub deleted: bool,
+}
+
+/// DELETE /admin/api/webhooks/{webhook_id}
+///
+/// Webhook の購読設定と配信ログを削除
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<DeleteWebhookResponse>, AppError> {
+    state
+        .webhook_service
+        .delete_subscription(webhook_id)
+        .await?;
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::WebhookDeleted)
+                .actor(admin.actor())
+                .client(&client)
+                .with("webhook_id", webhook_id.to_string()),
+        )
+        .await;
+
+    Ok(Json(DeleteWebhookResponse { deleted: true }))
+}
+
+/// 配信ログの検索クエリ
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveryQuery {
+    /// 購読設定で絞り込む
+    pub webhook_id: Option<Uuid>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryListResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// GET /admin/api/webhooks/deliveries
+///
+/// Webhook の配信ログを新しい順に取得
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    Query(query): Query<WebhookDeliveryQuery>,
+) -> Result<Json<WebhookDeliveryListResponse>, AppError> {
+    let (limit, offset) = validate_pagination(query.limit, query.offset)?;
+    let deliveries = state
+        .webhook_service
+        .list_deliveries(query.webhook_id, limit, offset)
+        .await?;
+
+    Ok(Json(WebhookDeliveryListResponse {
+        deliveries,
+        limit,
+        offset,
+    }))
+}
+
+/// POST /admin/api/webhooks/deliveries/{delivery_id}/replay
+///
+/// 配信ログのイベントを同じイベントIDで再送する
+pub async fn replay_webhook_delivery(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    let delivery = state.webhook_service.replay(delivery_id).await?;
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::WebhookReplayed)
+                .actor(admin.actor())
+                .client(&client)
+                .with("delivery_id", delivery_id.to_string())
+                .with("replay_id", delivery.id.to_string()),
+        )
+        .await;
+
+    Ok(Json(delivery))
+}
+
+// === ユーザー管理 ===
+
+/// ユーザー検索クエリ
+#[derive(Debug, Deserialize)]
+pub struct UserQuery {
+    /// メールアドレスの部分一致またはユーザーID（未指定の場合は全件）
+    pub q: Option<String>,
+    /// アカウント状態で絞り込む
+    pub status: Option<UserStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserListResponse {
+    pub users: Vec<AdminUserSummary>,
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// GET /admin/api/users
+///
+/// ユーザーを作成日時の新しい順に検索
+pub async fn list_users(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Query(query): Query<UserQuery>,
+) -> Result<Json<UserListResponse>, AppError> {
+    let (limit, offset) = validate_pagination(query.limit, query.offset)?;
+    let search = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+
+    let page = user_admin_service(&state)
+        .search(search, query.status, limit, offset)
+        .await?;
+
+    let mut event = NewAuditEvent::success(AuditEventType::UsersSearched)
+        .actor(admin.actor())
+        .client(&client);
+    if let Some(search) = search {
+        event = event.with("query", search);
+    }
+    if let Some(status) = query.status {
+        event = event.with("status", status.as_str());
+    }
+    state.audit_service.record(event).await;
+
+    Ok(Json(UserListResponse {
+        users: page.users,
+        total: page.total,
+        limit,
+        offset,
+    }))
+}
+
+/// GET /admin/api/users/{user_id}
+///
+/// ユーザー詳細（2FA・ソーシャルアカウントの連携状態を含む）
+pub async fn get_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<AdminUserDetail>, AppError> {
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        service.detail(&user).await
+    }
+    .await;
+
+    record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::UserViewed,
+        user_id,
+        result,
+    )
+    .await
+    .map(Json)
+}
+
+/// ロックリクエスト（ボディは省略可）
+#[derive(Debug, Default, Deserialize)]
+pub struct LockUserRequest {
+    pub reason: Option<String>,
+    /// 自動で解除する日時（RFC 3339、未指定の場合は解除されるまで）
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub until: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LockUserResponse {
+    /// 今回ロックした場合は true（既にロック済みの場合は false）
+    pub locked: bool,
+}
+
+/// POST /admin/api/users/{user_id}/lock
+///
+/// アカウントをロックし、ログインセッションと同意を失効させる。
+/// `until` を指定した場合はその日時に自動で解除される
+pub async fn lock_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+    request: Option<Json<LockUserRequest>>,
+) -> Result<Json<LockUserResponse>, AppError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    validate_lock_request(&request, OffsetDateTime::now_utc())?;
+
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        service
+            .lock(&user, request.reason.as_deref(), request.until)
+            .await
+    }
+    .await;
+
+    let locked = record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::AccountLocked,
+        user_id,
+        result,
+    )
+    .await?;
+    Ok(Json(LockUserResponse { locked }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnlockUserResponse {
+    /// 今回解除した場合は true（ロックされていない場合は false）
+    pub unlocked: bool,
+}
+
+/// POST /admin/api/users/{user_id}/unlock
+///
+/// アカウントのロックを解除
+pub async fn unlock_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<UnlockUserResponse>, AppError> {
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        service.unlock(&user).await
+    }
+    .await;
+
+    let unlocked = record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::AccountUnlocked,
+        user_id,
+        result,
+    )
+    .await?;
+    Ok(Json(UnlockUserResponse { unlocked }))
+}
+
+/// 無効化リクエスト（ボディは省略可）
+#[derive(Debug, Default, Deserialize)]
+pub struct DisableUserRequest {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DisableUserResponse {
+    /// 今回無効化した場合は true（既に無効化済みの場合は false）
+    pub disabled: bool,
+}
+
+/// POST /admin/api/users/{user_id}/disable
+///
+/// アカウントを無効化し（退職・規約違反など）、ログインセッションと同意を失効させる
+pub async fn disable_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+    request: Option<Json<DisableUserRequest>>,
+) -> Result<Json<DisableUserResponse>, AppError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    validate_lock_reason(request.reason.as_deref())?;
+
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        service.disable(&user, request.reason.as_deref()).await
+    }
+    .await;
+
+    let disabled = record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::AccountDisabled,
+        user_id,
+        result,
+    )
+    .await?;
+    Ok(Json(DisableUserResponse { disabled }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnableUserResponse {
+    /// 今回有効化した場合は true（無効化されていない場合は false）
+    pub enabled: bool,
+}
+
+/// POST /admin/api/users/{user_id}/enable
+///
+/// 無効化したアカウントを有効に戻す
+pub async fn enable_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<EnableUserResponse>, AppError> {
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        service.enable(&user).await
+    }
+    .await;
+
+    let enabled = record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::AccountEnabled,
+        user_id,
+        result,
+    )
+    .await?;
+    Ok(Json(EnableUserResponse { enabled }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct RevokeSessionsResponse {
+    pub revoked: bool,
+}
+
+/// POST /admin/api/users/{user_id}/logout
+///
+/// Hydra のログインセッションと同意を全て失効させる（強制ログアウト）
+pub async fn revoke_user_sessions(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<RevokeSessionsResponse>, AppError> {
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        service.revoke_sessions(&user).await
+    }
+    .await;
+
+    record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::SessionsRevoked,
+        user_id,
+        result,
+    )
+    .await?;
+    Ok(Json(RevokeSessionsResponse { revoked: true }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResetTwoFactorResponse {
+    /// 2FA が設定されていた場合は true
+    pub reset: bool,
+}
+
+/// POST /admin/api/users/{user_id}/2fa/reset
+///
+/// 2FA をリセットする（端末を紛失したユーザーの復旧用）。
+/// ユーザーにはセキュリティ通知を送信する
+pub async fn reset_user_2fa(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ResetTwoFactorResponse>, AppError> {
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        let reset = service.reset_two_factor(&user).await?;
+        Ok((user, reset))
+    }
+    .await;
+
+    let (user, reset) = record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::TwoFactorReset,
+        user_id,
+        result,
+    )
+    .await?;
+
+    if reset {
+        state
+            .security_notifications
+            .notify(&user, SecurityEvent::TwoFactorDisabled)
+            .await;
+    }
+
+    Ok(Json(ResetTwoFactorResponse { reset }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ForcePasswordResetResponse {
+    pub email_sent: bool,
+}
+
+/// POST /admin/api/users/{user_id}/password-reset
+///
+/// 現在のパスワードを無効化してセッションを失効させ、パスワードリセットメールを送信する
+pub async fn force_password_reset(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ForcePasswordResetResponse>, AppError> {
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        service.force_password_reset(&user).await
+    }
+    .await;
+
+    record_admin_action(
+        &state,
+        &admin,
+        &client,
+        AuditEventType::PasswordResetForced,
+        user_id,
+        result,
+    )
+    .await?;
+    Ok(Json(ForcePasswordResetResponse { email_sent: true }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeleteUserResponse {
+    pub deleted: bool,
+}
+
+/// DELETE /admin/api/users/{user_id}
+///
+/// アカウントを猶予期間なしで即時削除する
+pub async fn delete_user(
+    State(state): State<AppState>,
+    Extension(admin): Extension<AdminPrincipal>,
+    client: ClientInfo,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<DeleteUserResponse>, AppError> {
+    let service = user_admin_service(&state);
+    let result = async {
+        let user = service.find_user(user_id).await?;
+        let deleted = service.delete(&user).await?;
+        Ok::<_, AppError>((user, deleted))
+    }
+    .await;
+
+    state
+        .audit_service
+        .record(
+            deleted_user_event(&result, user_id)
+                .actor(admin.actor())
+                .client(&client),
+        )
+        .await;
+
+    let (user, deleted) = result?;
+    if deleted {
+        state
+            .webhook_service
+            .emit(
+                WebhookEvent::new(WebhookEventType::UserDeleted, user.id)
+                    .email(user.email.as_str())
+                    .reason("admin"),
+            )
+            .await;
+    }
+    Ok(Json(DeleteUserResponse { deleted }))
+}
+
+// === Helper Functions ===
+
+pub(crate) fn user_admin_service(state: &AppState) -> UserAdminService {
+    UserAdminService::new(
+        state.user_repo.clone(),
+        state.user_2fa_repo.clone(),
+        state.social_account_repo.clone(),
+        state.hydra_client.clone(),
+        state.outbox_service.clone(),
+        PasswordResetService::new(
+            state.user_repo.clone(),
+            state.token_repo.clone(),
+            state.password_policy.clone(),
+            state.config.load(),
+        ),
+    )
+}
+
+/// アカウント削除の監査イベント
+///
+/// ユーザーの監査イベントは CASCADE で削除され、削除後のユーザーは外部キーで参照できないため、
+/// ユーザーに紐付けずにメタデータにユーザーIDを記録する
+fn deleted_user_event<T>(result: &Result<T, AppError>, user_id: Uuid) -> NewAuditEvent {
+    let event = match result {
+        Ok(_) => NewAuditEvent::success(AuditEventType::AccountDeleted),
+        Err(e) => NewAuditEvent::failure(AuditEventType::AccountDeleted, e),
+    };
+    event.with("user_id", user_id.to_string())
+}
+
+/// 対象ユーザーへの管理操作を監査ログに記録し、結果をそのまま返す
+async fn record_admin_action<T>(
+    state: &AppState,
+    admin: &AdminPrincipal,
+    client: &ClientInfo,
+    event_type: AuditEventType,
+    user_id: Uuid,
+    result: Result<T, AppError>,
+) -> Result<T, AppError> {
+    let event = match &result {
+        Ok(_) => NewAuditEvent::success(event_type),
+        Err(e) => NewAuditEvent::failure(event_type, e),
+    };
+    state
+        .audit_service
+        .record(event.user(user_id).actor(admin.actor()).client(client))
+        .await;
+    result
+}
+
+/// ロックリクエストのバリデーション
+pub(crate) fn validate_lock_request(
+    request: &LockUserRequest,
+    now: OffsetDateTime,
+) -> Result<(), AppError> {
+    validate_lock_reason(request.reason.as_deref())?;
+    if request.until.is_some_and(|until| until <= now) {
+        return Err(AppError::Validation(
+            "until には未来の日時を指定してください".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// ロック・無効化の理由のバリデーション
+fn validate_lock_reason(reason: Option<&str>) -> Result<(), AppError> {
+    if reason.is_some_and(|reason| reason.chars().count() > MAX_LOCK_REASON_LENGTH) {
+        return Err(AppError::Validation(format!(
+            "reason は {} 文字以内で指定してください",
+            MAX_LOCK_REASON_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// ページング指定のバリデーション（未指定時はデフォルト値）
+fn validate_pagination(limit: Option<i64>, offset: Option<i64>) -> Result<(i64, i64), AppError> {
+    let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+        return Err(AppError::Validation(format!(
+            "limit は 1-{} の範囲で指定してください",
+            MAX_PAGE_LIMIT
+        )));
+    }
+
+    let offset = offset.unwrap_or(0);
+    if offset < 0 {
+        return Err(AppError::Validation(
+            "offset は 0 以上を指定してください".to_string(),
+        ));
+    }
+
+    Ok((limit, offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_pagination_defaults() {
+        assert_eq!(
+            validate_pagination(None, None).unwrap(),
+            (DEFAULT_PAGE_LIMIT, 0)
+        );
+    }
+
+    #[test]
+    fn test_validate_pagination_out_of_range() {
+        assert!(validate_pagination(Some(0), None).is_err());
+        assert!(validate_pagination(Some(MAX_PAGE_LIMIT + 1), None).is_err());
+        assert!(validate_pagination(None, Some(-1)).is_err());
+    }
+
+    #[test]
+    fn test_admin_principal_actor() {
+        assert_eq!(AdminPrincipal::ApiKey.actor(), "admin:api_key");
+        let principal = AdminPrincipal::Token {
+            subject: "ops@example.com".to_string(),
+        };
+        assert_eq!(principal.actor(), "admin:ops@example.com");
+    }
+
+    #[test]
+    fn test_user_query_parses() {
+        let uri = "/admin/api/users?q=alice%40example.com&status=locked&limit=20&offset=40"
+            .parse()
+            .unwrap();
+        let Query(query) = Query::<UserQuery>::try_from_uri(&uri).unwrap();
+
+        assert_eq!(query.q.as_deref(), Some("alice@example.com"));
+        assert_eq!(query.status, Some(UserStatus::Locked));
+        assert_eq!(
+            validate_pagination(query.limit, query.offset).unwrap(),
+            (20, 40)
+        );
+    }
+
+    #[test]
+    fn test_deleted_user_event_is_not_linked_to_user() {
+        let user_id = Uuid::new_v4();
+        for result in [Ok(()), Err(AppError::UserNotFound)] {
+            let event = deleted_user_event(&result, user_id);
+            assert_eq!(event.user_id, None);
+            assert_eq!(event.metadata["user_id"], user_id.to_string());
+        }
+    }
+
+    #[test]
+    fn test_validate_lock_request() {
+        let now = OffsetDateTime::now_utc();
+        assert!(validate_lock_request(&LockUserRequest::default(), now).is_ok());
+
+        let past = LockUserRequest {
+            reason: None,
+            until: Some(now - time::Duration::hours(1)),
+        };
+        assert!(validate_lock_request(&past, now).is_err());
+
+        let long_reason = LockUserRequest {
+            reason: Some("a".repeat(MAX_LOCK_REASON_LENGTH + 1)),
+            until: Some(now + time::Duration::hours(1)),
+        };
+        assert!(validate_lock_request(&long_reason, now).is_err());
+    }
+
+    #[test]
+    fn test_query_parses_filters() {
+        let uri = "/admin/api/audit-events?event_type=login&outcome=failure&since=2026-01-01T00:00:00Z&limit=10"
+            .parse()
+            .unwrap();
+        let Query(query) = Query::<AuditEventQuery>::try_from_uri(&uri).unwrap();
+
+        assert_eq!(query.event_type, Some(AuditEventType::Login));
+        assert_eq!(query.outcome, Some(AuditOutcome::Failure));
+        assert!(query.since.is_some());
+        assert_eq!(query.limit, Some(10));
+    }
+}
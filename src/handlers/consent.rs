@@ -1,7 +1,10 @@
 use axum::{Json, extract::State};
 use serde::{Deserialize, Serialize};
 
+use super::login::subject_login_denial;
+use crate::client_info::ClientInfo;
 use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent};
 use crate::state::AppState;
 
 /// 同意リクエスト
@@ -26,13 +29,14 @@ pub struct ConsentResponse {
 ///
 /// 処理フロー:
 /// 1. リクエストバリデーション
-/// 2. Hydra でチャレンジ検証
+/// 2. Hydra でチャレンジ検証（有効でないアカウントは拒否）
 /// 3. skip=true なら以前の同意を再利用
 /// 4. grant_scope のバリデーション（requested_scope のサブセットか）
-/// 5. Hydra で同意承認
+/// 5. Hydra で同意承認（profile スコープの場合はプロフィールのクレームを含める）
 /// 6. リダイレクトURLを返却
 pub async fn consent(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(request): Json<ConsentRequest>,
 ) -> Result<Json<ConsentResponse>, AppError> {
     // 1. リクエストバリデーション
@@ -44,8 +48,32 @@ pub async fn consent(
         .get_consent_request(&request.consent_challenge)
         .await?;
 
+    // ロック・無効化されたアカウントには同意させない（ログインセッションの再利用対策）
+    if let Some(e) = subject_login_denial(&state, &consent_info.subject).await? {
+        let redirect_to = state
+            .hydra_client
+            .reject_consent(&request.consent_challenge, "access_denied", &e.to_string())
+            .await?;
+
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::failure(AuditEventType::Consent, &e)
+                    .subject(&consent_info.subject)
+                    .client(&client)
+                    .with("client_id", consent_info.client.client_id.clone()),
+            )
+            .await;
+
+        return Ok(Json(ConsentResponse { redirect_to }));
+    }
+
     // 3. skip=true の場合は以前の同意を再利用
     if consent_info.skip {
+        let session = state
+            .profile_service
+            .consent_session(&consent_info.subject, &consent_info.requested_scope)
+            .await?;
         let redirect_to = state
             .hydra_client
             .accept_consent(
@@ -54,7 +82,7 @@ pub async fn consent(
                 consent_info.requested_access_token_audience.clone(),
                 true,
                 3600,
-                None,
+                session,
             )
             .await?;
 
@@ -64,13 +92,40 @@ pub async fn consent(
             "同意スキップ（以前の同意を再利用）"
         );
 
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::success(AuditEventType::Consent)
+                    .subject(&consent_info.subject)
+                    .client(&client)
+                    .with("client_id", consent_info.client.client_id.clone())
+                    .with("granted_scopes", consent_info.requested_scope.clone())
+                    .with("skip", true),
+            )
+            .await;
+
         return Ok(Json(ConsentResponse { redirect_to }));
     }
 
     // 4. grant_scope のバリデーション（requested_scope のサブセットか）
-    validate_grant_scope(&request.grant_scope, &consent_info.requested_scope)?;
+    if let Err(e) = validate_grant_scope(&request.grant_scope, &consent_info.requested_scope) {
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::failure(AuditEventType::Consent, &e)
+                    .subject(&consent_info.subject)
+                    .client(&client)
+                    .with("client_id", consent_info.client.client_id.clone()),
+            )
+            .await;
+        return Err(e);
+    }
 
-    // 5. Hydra で同意承認
+    // 5. Hydra で同意承認（profile スコープを許可した場合はプロフィールを ID トークンに含める）
+    let session = state
+        .profile_service
+        .consent_session(&consent_info.subject, &request.grant_scope)
+        .await?;
     let redirect_to = state
         .hydra_client
         .accept_consent(
@@ -79,7 +134,7 @@ pub async fn consent(
             consent_info.requested_access_token_audience.clone(),
             true,
             3600,
-            None,
+            session,
         )
         .await?;
 
@@ -90,6 +145,18 @@ pub async fn consent(
         "同意承認完了"
     );
 
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::Consent)
+                .subject(&consent_info.subject)
+                .client(&client)
+                .with("client_id", consent_info.client.client_id.clone())
+                .with("granted_scopes", request.grant_scope.clone())
+                .with("skip", false),
+        )
+        .await;
+
     // 6. リダイレクトURLを返却
     Ok(Json(ConsentResponse { redirect_to }))
 }
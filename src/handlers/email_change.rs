@@ -0,0 +1,272 @@
+use axum::{Json, extract::State};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::account::{Credentials, reauthenticate, validate_totp_code};
+use crate::client_info::ClientInfo;
+use crate::email_address::normalize_email;
+use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent, WebhookEvent, WebhookEventType};
+use crate::repositories::EmailChangeRequestRepository;
+use crate::services::{EmailChangeService, SecurityEvent};
+use crate::state::AppState;
+
+// === メールアドレス変更リクエスト ===
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeEmailRequest {
+    pub user_id: Uuid,
+    pub password: String,
+    /// TOTPコード（2FA有効時のみ必須）
+    #[serde(default)]
+    pub code: Option<String>,
+    pub new_email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailChangeResponse {
+    /// 確認リンクの有効期限
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+/// POST /api/account/email
+///
+/// メールアドレスの変更をリクエスト
+///
+/// 新しいアドレスに確認リンク、現在のアドレスに取り消しリンクを送信する。
+/// メールアドレスは確認リンクが開かれるまで変更しない
+///
+/// # Security
+/// - パスワード確認必須（2FA有効時はTOTPコードも必須）
+pub async fn request_email_change(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(request): Json<ChangeEmailRequest>,
+) -> Result<Json<EmailChangeResponse>, AppError> {
+    validate_email_change_request(&request)?;
+    let user = reauthenticate(
+        &state,
+        &client,
+        AuditEventType::EmailChangeRequested,
+        request.user_id,
+        Credentials::Password(&request.password),
+        request.code.clone(),
+    )
+    .await?;
+
+    let new_email = normalize_email(&request.new_email)?;
+    state.disposable_domains.load().ensure_allowed(&new_email)?;
+    let change = match email_change_service(&state)
+        .request_change(&user, &new_email)
+        .await
+    {
+        Ok(change) => change,
+        Err(e) => {
+            state
+                .audit_service
+                .record(
+                    NewAuditEvent::failure(AuditEventType::EmailChangeRequested, &e)
+                        .user(user.id)
+                        .client(&client),
+                )
+                .await;
+            return Err(e);
+        }
+    };
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::EmailChangeRequested)
+                .user(user.id)
+                .client(&client)
+                .with("new_email", new_email.as_str()),
+        )
+        .await;
+
+    Ok(Json(EmailChangeResponse {
+        expires_at: change.expires_at,
+    }))
+}
+
+// === 確認・取り消し（メール内のリンク） ===
+
+#[derive(Debug, Deserialize)]
+pub struct EmailChangeTokenRequest {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailChangeConfirmResponse {
+    pub email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EmailChangeCancelResponse {
+    pub cancelled: bool,
+}
+
+/// POST /api/account/email/confirm
+///
+/// 新しいアドレスに送った確認リンクからメールアドレスの変更を確定する。
+/// 変更前のアドレスにセキュリティ通知を送信する
+///
+/// # Security
+/// - token はログに出力しない
+pub async fn confirm_email_change(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(request): Json<EmailChangeTokenRequest>,
+) -> Result<Json<EmailChangeConfirmResponse>, AppError> {
+    validate_token(&request.token)?;
+
+    let changed = match email_change_service(&state).confirm(&request.token).await {
+        Ok(changed) => changed,
+        Err(e) => {
+            state
+                .audit_service
+                .record(NewAuditEvent::failure(AuditEventType::EmailChanged, &e).client(&client))
+                .await;
+            return Err(e);
+        }
+    };
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::EmailChanged)
+                .user(changed.user.id)
+                .client(&client)
+                .with("old_email", changed.user.email.as_str())
+                .with("new_email", changed.new_email.as_str()),
+        )
+        .await;
+
+    state
+        .security_notifications
+        .notify(
+            &changed.user,
+            SecurityEvent::EmailChanged {
+                new_email: changed.new_email.clone(),
+            },
+        )
+        .await;
+    state
+        .webhook_service
+        .emit(
+            WebhookEvent::new(WebhookEventType::UserEmailChanged, changed.user.id)
+                .email(changed.new_email.as_str())
+                .previous_email(changed.user.email.as_str()),
+        )
+        .await;
+
+    Ok(Json(EmailChangeConfirmResponse {
+        email: changed.new_email,
+    }))
+}
+
+/// POST /api/account/email/cancel
+///
+/// 現在のアドレスに送った取り消しリンクからメールアドレスの変更を取り消す
+///
+/// # Security
+/// - token はログに出力しない
+pub async fn cancel_email_change(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(request): Json<EmailChangeTokenRequest>,
+) -> Result<Json<EmailChangeCancelResponse>, AppError> {
+    validate_token(&request.token)?;
+
+    let user_id = match email_change_service(&state).cancel(&request.token).await {
+        Ok(user_id) => user_id,
+        Err(e) => {
+            state
+                .audit_service
+                .record(
+                    NewAuditEvent::failure(AuditEventType::EmailChangeCancelled, &e)
+                        .client(&client),
+                )
+                .await;
+            return Err(e);
+        }
+    };
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::EmailChangeCancelled)
+                .user(user_id)
+                .client(&client),
+        )
+        .await;
+
+    Ok(Json(EmailChangeCancelResponse { cancelled: true }))
+}
+
+fn email_change_service(state: &AppState) -> EmailChangeService {
+    EmailChangeService::new(
+        state.user_repo.clone(),
+        EmailChangeRequestRepository::new(state.db_pool.clone()),
+        state.token_repo.clone(),
+        state.config.clone(),
+    )
+}
+
+/// メールアドレス変更リクエストのバリデーション
+fn validate_email_change_request(request: &ChangeEmailRequest) -> Result<(), AppError> {
+    if request.password.is_empty() {
+        return Err(AppError::Validation("パスワードは必須です".to_string()));
+    }
+    if let Some(code) = &request.code {
+        validate_totp_code(code)?;
+    }
+
+    if request.new_email.trim().is_empty() {
+        return Err(AppError::Validation("メールアドレスは必須です".to_string()));
+    }
+    normalize_email(&request.new_email)?;
+    Ok(())
+}
+
+fn validate_token(token: &str) -> Result<(), AppError> {
+    if token.trim().is_empty() {
+        return Err(AppError::Validation("トークンは必須です".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(new_email: &str) -> ChangeEmailRequest {
+        ChangeEmailRequest {
+            user_id: Uuid::new_v4(),
+            password: "password123".to_string(),
+            code: None,
+            new_email: new_email.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_email_change_request() {
+        assert!(validate_email_change_request(&request("new@example.com")).is_ok());
+        assert!(validate_email_change_request(&request("  ")).is_err());
+        assert!(validate_email_change_request(&request("not-an-email")).is_err());
+
+        let request = ChangeEmailRequest {
+            password: "".to_string(),
+            ..request("new@example.com")
+        };
+        assert!(validate_email_change_request(&request).is_err());
+    }
+
+    #[test]
+    fn test_validate_token() {
+        assert!(validate_token("abc").is_ok());
+        assert!(validate_token(" ").is_err());
+    }
+}
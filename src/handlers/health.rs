@@ -1,6 +1,11 @@
-use axum::Json;
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode};
 use serde::Serialize;
 
+use crate::services::readiness::{ReadinessReport, ReadinessStatus};
+use crate::state::AppState;
+
 /// ヘルスチェックレスポンス
 #[derive(Debug, Serialize)]
 pub struct HealthResponse {
@@ -8,11 +13,11 @@ pub struct HealthResponse {
     pub version: &'static str,
 }
 
-/// ヘルスチェックハンドラー
+/// ヘルスチェックハンドラー（ライブネス）
 ///
-/// GET /api/health
+/// GET /api/health, GET /api/health/live
 ///
-/// サービスの稼働状況を返す。
+/// プロセスが応答できるかだけを返す（依存先は確認しない）。
 /// ロードバランサーやモニタリングツールから呼び出される。
 pub async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
@@ -21,6 +26,24 @@ pub async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+/// レディネスハンドラー
+///
+/// GET /api/health/ready
+///
+/// 依存先（Postgres・Hydra・SMTP・ソーシャルログインプロバイダー）の状態を返す。
+/// 必須の依存先が利用できない場合は 503、任意の依存先のみの場合は degraded で 200 を返す
+pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<Arc<ReadinessReport>>) {
+    let report = state.readiness.check().await;
+    (status_code(report.status), Json(report))
+}
+
+fn status_code(status: ReadinessStatus) -> StatusCode {
+    match status {
+        ReadinessStatus::Ok | ReadinessStatus::Degraded => StatusCode::OK,
+        ReadinessStatus::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,4 +54,14 @@ mod tests {
         assert_eq!(response.status, "ok");
         assert_eq!(response.version, env!("CARGO_PKG_VERSION"));
     }
+
+    #[test]
+    fn test_readiness_status_code() {
+        assert_eq!(status_code(ReadinessStatus::Ok), StatusCode::OK);
+        assert_eq!(status_code(ReadinessStatus::Degraded), StatusCode::OK);
+        assert_eq!(
+            status_code(ReadinessStatus::Unavailable),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
 }
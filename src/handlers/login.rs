@@ -1,9 +1,15 @@
 use axum::{Json, extract::State};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use crate::client_info::ClientInfo;
+use crate::email_address::normalize_email;
 use crate::error::AppError;
-use crate::repositories::{User2faSecretRepository, UserRepository};
-use crate::services::auth::AuthService;
+use crate::metrics::METRICS;
+use crate::models::{AuditEventType, NewAuditEvent};
+use crate::repositories::User2faSecretRepository;
+use crate::services::SecurityEvent;
+use crate::services::auth::{AuthService, PasswordHashService};
 use crate::state::AppState;
 
 /// ログインリクエスト
@@ -47,10 +53,12 @@ pub struct LoginResponse {
 /// 7. リダイレクトURLを返却
 pub async fn login(
     State(state): State<AppState>,
-    Json(request): Json<LoginRequest>,
+    client: ClientInfo,
+    Json(mut request): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
     // 1. リクエストバリデーション
     validate_login_request(&request)?;
+    request.email = normalize_email(&request.email)?;
 
     // 2. Hydra でチャレンジ検証
     let login_info = state
@@ -60,11 +68,44 @@ pub async fn login(
 
     // skip=true の場合は以前の認証を再利用
     if login_info.skip {
+        // ロック・無効化後に残っているセッションは再利用させない
+        if let Some(e) = subject_login_denial(&state, &login_info.subject).await? {
+            let redirect_to = reject_inactive_login(&state, &request.login_challenge, &e).await?;
+
+            state
+                .audit_service
+                .record(
+                    NewAuditEvent::failure(AuditEventType::Login, &e)
+                        .subject(&login_info.subject)
+                        .client(&client)
+                        .with("method", "session")
+                        .with("client_id", login_info.client.client_id.clone()),
+                )
+                .await;
+
+            return Ok(Json(LoginResponse {
+                redirect_to: Some(redirect_to),
+                requires_2fa: None,
+                user_id: None,
+            }));
+        }
+
         let redirect_to = state
             .hydra_client
             .accept_login(&request.login_challenge, &login_info.subject, true, 3600)
             .await?;
 
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::success(AuditEventType::Login)
+                    .subject(&login_info.subject)
+                    .client(&client)
+                    .with("method", "session")
+                    .with("client_id", login_info.client.client_id.clone()),
+            )
+            .await;
+
         return Ok(Json(LoginResponse {
             redirect_to: Some(redirect_to),
             requires_2fa: None,
@@ -73,12 +114,36 @@ pub async fn login(
     }
 
     // 3. ユーザー認証（DB照合）
-    let user_repo = UserRepository::new(state.db_pool.clone());
-    let auth_service = AuthService::new(user_repo);
+    let hash_service = PasswordHashService::from_config(&state.config.load())?;
+    let auth_service = AuthService::new(state.user_repo.clone(), hash_service);
 
-    let user = auth_service
+    let user = match auth_service
         .authenticate(&request.email, &request.password)
-        .await?;
+        .await
+        .and_then(|user| AuthService::ensure_can_login(&user).map(|()| user))
+    {
+        Ok(user) => user,
+        Err(e) => {
+            // 存在するユーザーであれば紐付けて記録（ブルートフォースの検知用）
+            let user_id = state
+                .user_repo
+                .find_by_email(&request.email)
+                .await
+                .ok()
+                .flatten()
+                .map(|user| user.id);
+            let mut event = NewAuditEvent::failure(AuditEventType::Login, &e)
+                .actor(request.email.clone())
+                .client(&client)
+                .with("method", "password");
+            if let Some(user_id) = user_id {
+                event = event.user(user_id);
+            }
+            METRICS.record_login("password", false);
+            state.audit_service.record(event).await;
+            return Err(e);
+        }
+    };
 
     // 4. 2FA有効チェック
     let user_2fa_repo = User2faSecretRepository::new(state.db_pool.clone());
@@ -94,6 +159,16 @@ pub async fn login(
                 validate_totp_code(code)?;
                 let secret = state.totp_service.decrypt_secret(&tfa.secret_encrypted)?;
                 if !state.totp_service.verify_code(&secret, code)? {
+                    METRICS.record_login("totp", false);
+                    state
+                        .audit_service
+                        .record(
+                            NewAuditEvent::failure(AuditEventType::Login, &AppError::TotpInvalid)
+                                .user(user.id)
+                                .client(&client)
+                                .with("method", "password"),
+                        )
+                        .await;
                     return Err(AppError::TotpInvalid);
                 }
                 // コード検証成功、ログイン続行
@@ -115,6 +190,36 @@ pub async fn login(
         .accept_login(&request.login_challenge, &user.id.to_string(), true, 3600)
         .await?;
 
+    // 2FA を通過したログインは totp として数える
+    let two_factor = user_2fa.is_some_and(|tfa| tfa.enabled);
+    METRICS.record_login(if two_factor { "totp" } else { "password" }, true);
+
+    // 新しい端末の判定は今回のログインを記録する前に行う
+    let new_device = state
+        .security_notifications
+        .is_new_device(user.id, &client)
+        .await;
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::Login)
+                .user(user.id)
+                .client(&client)
+                .with("method", "password")
+                .with("two_factor", two_factor)
+                .with("client_id", login_info.client.client_id.clone())
+                .with("new_device", new_device),
+        )
+        .await;
+
+    if new_device {
+        state
+            .security_notifications
+            .notify(&user, SecurityEvent::new_device_login(&client))
+            .await;
+    }
+
     // 7. リダイレクトURLを返却
     Ok(Json(LoginResponse {
         redirect_to: Some(redirect_to),
@@ -133,6 +238,40 @@ fn validate_totp_code(code: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// 以前のログインセッションを再利用する subject がログイン可能か確認
+///
+/// # Returns
+/// ログインを拒否する理由（ログイン可能な場合は None）
+pub(crate) async fn subject_login_denial(
+    state: &AppState,
+    subject: &str,
+) -> Result<Option<AppError>, AppError> {
+    let user = match Uuid::parse_str(subject) {
+        Ok(user_id) => state.user_repo.find_by_id(user_id).await?,
+        Err(_) => None,
+    };
+
+    Ok(match user {
+        Some(user) => AuthService::ensure_can_login(&user).err(),
+        None => Some(AppError::UserNotFound),
+    })
+}
+
+/// 有効でないアカウントのログインを Hydra で拒否
+///
+/// # Returns
+/// Hydra から返却されたリダイレクト先URL（クライアントにエラーを返す）
+pub(crate) async fn reject_inactive_login(
+    state: &AppState,
+    login_challenge: &str,
+    error: &AppError,
+) -> Result<String, AppError> {
+    state
+        .hydra_client
+        .reject_login(login_challenge, "access_denied", &error.to_string())
+        .await
+}
+
 /// ログインリクエストのバリデーション
 fn validate_login_request(request: &LoginRequest) -> Result<(), AppError> {
     // login_challenge: 必須、空文字不可
@@ -147,12 +286,7 @@ fn validate_login_request(request: &LoginRequest) -> Result<(), AppError> {
         return Err(AppError::Validation("メールアドレスは必須です".to_string()));
     }
 
-    // 簡易的なメール形式チェック（@ が含まれているか）
-    if !request.email.contains('@') {
-        return Err(AppError::Validation(
-            "有効なメールアドレスを入力してください".to_string(),
-        ));
-    }
+    normalize_email(&request.email)?;
 
     // password: 必須、8文字以上
     if request.password.is_empty() {
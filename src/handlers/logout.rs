@@ -1,7 +1,9 @@
 use axum::{Json, extract::State};
 use serde::{Deserialize, Serialize};
 
+use crate::client_info::ClientInfo;
 use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent};
 use crate::state::AppState;
 
 /// ログアウトリクエスト
@@ -29,6 +31,7 @@ pub struct LogoutResponse {
 /// 4. リダイレクトURLを返却
 pub async fn logout(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(request): Json<LogoutRequest>,
 ) -> Result<Json<LogoutResponse>, AppError> {
     // 1. リクエストバリデーション
@@ -51,6 +54,15 @@ pub async fn logout(
         "ログアウト完了"
     );
 
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::Logout)
+                .subject(&logout_info.subject)
+                .client(&client),
+        )
+        .await;
+
     // 4. リダイレクトURLを返却
     Ok(Json(LogoutResponse { redirect_to }))
 }
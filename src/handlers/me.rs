@@ -0,0 +1,130 @@
+use axum::{Json, extract::State};
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::authenticated_user::AuthenticatedUser;
+use crate::client_info::ClientInfo;
+use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent, User, UserProfile};
+use crate::services::profile::ProfileUpdate;
+use crate::state::AppState;
+
+/// プロフィールの参照・更新に必要なスコープ
+const PROFILE_SCOPE: &str = "profile";
+
+/// ログイン中ユーザーの情報
+#[derive(Debug, Serialize)]
+pub struct MeResponse {
+    pub id: Uuid,
+    pub email: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(flatten)]
+    pub profile: UserProfile,
+}
+
+impl MeResponse {
+    fn new(user: &User, profile: UserProfile) -> Self {
+        Self {
+            id: user.id,
+            email: user.email.clone(),
+            created_at: user.created_at,
+            profile,
+        }
+    }
+}
+
+/// GET /api/me
+///
+/// ログイン中ユーザーのプロフィールを返す
+///
+/// # Security
+/// - Hydra が発行したアクセストークン（`profile` スコープ）必須
+pub async fn get_me(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+) -> Result<Json<MeResponse>, AppError> {
+    auth.require_scope(PROFILE_SCOPE)?;
+
+    let profile = state.profile_service.profile(auth.user.id).await?;
+    Ok(Json(MeResponse::new(&auth.user, profile)))
+}
+
+/// PATCH /api/me
+///
+/// ログイン中ユーザーのプロフィールを部分更新
+///
+/// # Security
+/// - Hydra が発行したアクセストークン（`profile` スコープ）必須
+/// - メールアドレスは変更できない
+pub async fn update_me(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    client: ClientInfo,
+    Json(update): Json<ProfileUpdate>,
+) -> Result<Json<MeResponse>, AppError> {
+    auth.require_scope(PROFILE_SCOPE)?;
+
+    let profile = state.profile_service.update(auth.user.id, update).await?;
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::ProfileUpdated)
+                .user(auth.user.id)
+                .client(&client),
+        )
+        .await;
+
+    Ok(Json(MeResponse::new(&auth.user, profile)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> User {
+        let now = OffsetDateTime::now_utc();
+        User {
+            id: Uuid::new_v4(),
+            email: "user@example.com".to_string(),
+            password_hash: Some("hash".to_string()),
+            created_at: now,
+            updated_at: now,
+            deletion_scheduled_at: None,
+            locked_at: None,
+            status: "active".to_string(),
+            lock_reason: None,
+            locked_until: None,
+        }
+    }
+
+    #[test]
+    fn test_me_response_flattens_profile() {
+        let user = user();
+        let mut profile = UserProfile::empty(user.id);
+        profile.display_name = Some("Taro".to_string());
+
+        let json = serde_json::to_value(MeResponse::new(&user, profile)).unwrap();
+
+        assert_eq!(json["email"], "user@example.com");
+        assert_eq!(json["display_name"], "Taro");
+        assert!(json["metadata"].is_object());
+        assert!(json.get("password_hash").is_none());
+        assert!(json.get("user_id").is_none());
+    }
+
+    #[test]
+    fn test_require_scope() {
+        let auth = AuthenticatedUser {
+            user: user(),
+            scopes: vec!["openid".to_string(), "profile".to_string()],
+        };
+        assert!(auth.require_scope(PROFILE_SCOPE).is_ok());
+        assert!(matches!(
+            auth.require_scope("email"),
+            Err(AppError::InsufficientScope(_))
+        ));
+    }
+}
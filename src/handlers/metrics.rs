@@ -0,0 +1,20 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::metrics::METRICS;
+use crate::state::AppState;
+
+/// Prometheus のテキスト形式の Content-Type
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// メトリクスハンドラー
+///
+/// GET /metrics
+///
+/// Prometheus からスクレイプされる。認証は行わないため、
+/// 外部に公開しない（リバースプロキシなどで内部ネットワークに限定する）こと
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        METRICS.render(&state.db_pool),
+    )
+}
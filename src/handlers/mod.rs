@@ -1,17 +1,36 @@
+pub mod account;
+pub mod admin;
 pub mod consent;
+pub mod email_change;
 pub mod health;
 pub mod login;
 pub mod logout;
+pub mod me;
+pub mod metrics;
+pub mod notifications;
 pub mod oauth;
+pub mod password_change;
 pub mod password_reset;
 pub mod register;
 pub mod two_factor;
 
+pub use account::{account_activity, cancel_account_deletion, delete_account, export_account};
+pub use admin::{
+    create_webhook, delete_user, delete_webhook, disable_user, enable_user, force_password_reset,
+    get_user, list_audit_events, list_dead_outbox_messages, list_users, list_webhook_deliveries,
+    list_webhooks, lock_user, replay_webhook_delivery, require_admin, reset_user_2fa,
+    retry_outbox_message, revoke_user_sessions, unlock_user, update_webhook,
+};
 pub use consent::consent;
-pub use health::health_check;
+pub use email_change::{cancel_email_change, confirm_email_change, request_email_change};
+pub use health::{health_check, readiness};
 pub use login::login;
 pub use logout::logout;
+pub use me::{get_me, update_me};
+pub use metrics::metrics;
+pub use notifications::{lock_account, notification_settings};
 pub use oauth::{github_auth, github_callback, google_auth, google_callback};
+pub use password_change::change_password;
 pub use password_reset::{request_password_reset, reset_password};
 pub use register::register;
 pub use two_factor::{disable_2fa, setup_2fa, verify_2fa};
@@ -0,0 +1,226 @@
+use axum::{Json, extract::State};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::account::{Credentials, reauthenticate, validate_totp_code};
+use crate::client_info::ClientInfo;
+use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent, UserNotificationSettings};
+use crate::state::AppState;
+
+// === 通知設定 ===
+
+/// 通知設定リクエスト（変更する項目のみ指定、全て省略した場合は取得のみ）
+#[derive(Debug, Deserialize)]
+pub struct NotificationSettingsRequest {
+    pub user_id: Uuid,
+    pub password: String,
+    /// TOTPコード（2FA有効時のみ必須）
+    #[serde(default)]
+    pub code: Option<String>,
+    pub new_device_login: Option<bool>,
+    pub password_changed: Option<bool>,
+    pub two_factor_changed: Option<bool>,
+    pub social_account_changed: Option<bool>,
+    pub email_changed: Option<bool>,
+}
+
+impl NotificationSettingsRequest {
+    /// 変更する項目があるか
+    fn has_changes(&self) -> bool {
+        [
+            self.new_device_login,
+            self.password_changed,
+            self.two_factor_changed,
+            self.social_account_changed,
+            self.email_changed,
+        ]
+        .iter()
+        .any(Option::is_some)
+    }
+
+    /// 指定された項目を設定に反映
+    fn apply(&self, settings: &mut UserNotificationSettings) {
+        let fields = [
+            (&mut settings.new_device_login, self.new_device_login),
+            (&mut settings.password_changed, self.password_changed),
+            (&mut settings.two_factor_changed, self.two_factor_changed),
+            (
+                &mut settings.social_account_changed,
+                self.social_account_changed,
+            ),
+            (&mut settings.email_changed, self.email_changed),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+    }
+}
+
+/// POST /api/account/notifications
+///
+/// セキュリティ通知メールの受信設定を取得・変更
+///
+/// # Security
+/// - パスワード確認必須（2FA有効時はTOTPコードも必須）
+pub async fn notification_settings(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(request): Json<NotificationSettingsRequest>,
+) -> Result<Json<UserNotificationSettings>, AppError> {
+    validate_notification_settings_request(&request)?;
+    let user = reauthenticate(
+        &state,
+        &client,
+        AuditEventType::NotificationSettingsUpdated,
+        request.user_id,
+        Credentials::Password(&request.password),
+        request.code.clone(),
+    )
+    .await?;
+
+    let mut settings = state.security_notifications.settings(user.id).await?;
+    if !request.has_changes() {
+        return Ok(Json(settings));
+    }
+
+    request.apply(&mut settings);
+    let settings = state
+        .security_notifications
+        .update_settings(&settings)
+        .await?;
+
+    tracing::info!(user_id = %user.id, "通知設定を更新");
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::NotificationSettingsUpdated)
+                .user(user.id)
+                .client(&client),
+        )
+        .await;
+
+    Ok(Json(settings))
+}
+
+// === アカウントロック（「心当たりがない」リンク） ===
+
+#[derive(Debug, Deserialize)]
+pub struct LockAccountRequest {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LockAccountResponse {
+    pub locked: bool,
+}
+
+/// POST /api/account/lock
+///
+/// セキュリティ通知メールの「心当たりがない」リンクからアカウントをロックする。
+/// ログインセッションと同意も失効させる
+///
+/// # Security
+/// - token はログに出力しない
+pub async fn lock_account(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(request): Json<LockAccountRequest>,
+) -> Result<Json<LockAccountResponse>, AppError> {
+    if request.token.trim().is_empty() {
+        return Err(AppError::Validation("トークンは必須です".to_string()));
+    }
+
+    let user_id = match state
+        .security_notifications
+        .lock_account(&request.token)
+        .await
+    {
+        Ok(user_id) => user_id,
+        Err(e) => {
+            state
+                .audit_service
+                .record(NewAuditEvent::failure(AuditEventType::AccountLocked, &e).client(&client))
+                .await;
+            return Err(e);
+        }
+    };
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::AccountLocked)
+                .user(user_id)
+                .client(&client)
+                .with("source", "notification_link"),
+        )
+        .await;
+
+    Ok(Json(LockAccountResponse { locked: true }))
+}
+
+/// 通知設定リクエストのバリデーション
+fn validate_notification_settings_request(
+    request: &NotificationSettingsRequest,
+) -> Result<(), AppError> {
+    if request.password.is_empty() {
+        return Err(AppError::Validation("パスワードは必須です".to_string()));
+    }
+    if let Some(code) = &request.code {
+        validate_totp_code(code)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> NotificationSettingsRequest {
+        NotificationSettingsRequest {
+            user_id: Uuid::new_v4(),
+            password: "password123".to_string(),
+            code: None,
+            new_device_login: None,
+            password_changed: None,
+            two_factor_changed: None,
+            social_account_changed: None,
+            email_changed: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_empty_password() {
+        let request = NotificationSettingsRequest {
+            password: "".to_string(),
+            ..request()
+        };
+        assert!(validate_notification_settings_request(&request).is_err());
+    }
+
+    #[test]
+    fn test_no_changes() {
+        assert!(!request().has_changes());
+    }
+
+    #[test]
+    fn test_apply_only_specified_fields() {
+        let request = NotificationSettingsRequest {
+            new_device_login: Some(false),
+            email_changed: Some(true),
+            ..request()
+        };
+        assert!(request.has_changes());
+
+        let mut settings = UserNotificationSettings::defaults(request.user_id);
+        settings.email_changed = false;
+        request.apply(&mut settings);
+
+        assert!(!settings.new_device_login);
+        assert!(settings.email_changed);
+        assert!(settings.password_changed);
+    }
+}
@@ -14,7 +14,15 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
+use super::login::reject_inactive_login;
+use crate::client_info::ClientInfo;
+use crate::email_address::normalize_email;
 use crate::error::AppError;
+use crate::metrics::METRICS;
+use crate::models::{AuditEventType, NewAuditEvent, User, WebhookEvent, WebhookEventType};
+use crate::services::SecurityEvent;
+use crate::services::auth::AuthService;
+use crate::services::oauth::OAuthUserInfo;
 use crate::state::AppState;
 
 /// OAuth 認証開始時のクエリパラメータ
@@ -67,7 +75,8 @@ pub async fn google_auth(
 ) -> Result<Json<OAuthAuthResponse>, AppError> {
     tracing::info!("Google OAuth 認証開始");
 
-    let oauth_service = state.google_oauth_service.as_ref().ok_or_else(|| {
+    let providers = state.oauth_providers.load();
+    let oauth_service = providers.google.as_ref().ok_or_else(|| {
         tracing::warn!("Google OAuth が設定されていません");
         AppError::OAuthError("Google OAuth is not configured".to_string())
     })?;
@@ -94,39 +103,39 @@ pub async fn google_auth(
 /// 6. redirect_to にリダイレクト
 pub async fn google_callback(
     State(state): State<AppState>,
+    client: ClientInfo,
     Query(query): Query<OAuthCallbackQuery>,
 ) -> Result<Redirect, AppError> {
     tracing::info!("Google OAuth コールバック受信");
 
-    let oauth_service = state.google_oauth_service.as_ref().ok_or_else(|| {
+    let providers = state.oauth_providers.load();
+    let oauth_service = providers.google.as_ref().ok_or_else(|| {
         tracing::warn!("Google OAuth が設定されていません");
         AppError::OAuthError("Google OAuth is not configured".to_string())
     })?;
 
-    // 1. state をデコードして login_challenge を復元
-    let login_challenge = oauth_service.decode_state(&query.state)?;
-    tracing::debug!("state デコード成功");
+    let result = async {
+        // 1. state をデコードして login_challenge を復元
+        let login_challenge = oauth_service.decode_state(&query.state)?;
+        tracing::debug!("state デコード成功");
 
-    // 2. code でトークン交換
-    let token_response = oauth_service.exchange_code(&query.code).await?;
-    tracing::debug!("トークン交換成功");
-    // Note: access_token はログに出力しない
+        // 2. code でトークン交換
+        let token_response = oauth_service.exchange_code(&query.code).await?;
+        tracing::debug!("トークン交換成功");
+        // Note: access_token はログに出力しない
 
-    // 3. access_token でユーザー情報取得
-    let user_info = oauth_service
-        .get_user_info(&token_response.access_token)
-        .await?;
-    tracing::info!(provider = "google", "OAuth ユーザー情報取得成功");
-
-    // 4-6. ユーザー処理と Hydra accept
-    let redirect_to = process_oauth_callback(
-        &state,
-        "google",
-        &user_info.id,
-        &user_info.email,
-        &login_challenge,
-    )
-    .await?;
+        // 3. access_token でユーザー情報取得
+        let user_info = oauth_service
+            .get_user_info(&token_response.access_token)
+            .await?;
+        tracing::info!(provider = "google", "OAuth ユーザー情報取得成功");
+
+        // 4-6. ユーザー処理と Hydra accept
+        process_oauth_callback(&state, &client, "google", &user_info, &login_challenge).await
+    }
+    .await;
+
+    let redirect_to = record_oauth_failure(&state, &client, "google", result).await?;
 
     Ok(Redirect::to(&redirect_to))
 }
@@ -151,7 +160,8 @@ pub async fn github_auth(
 ) -> Result<Json<OAuthAuthResponse>, AppError> {
     tracing::info!("GitHub OAuth 認証開始");
 
-    let oauth_service = state.github_oauth_service.as_ref().ok_or_else(|| {
+    let providers = state.oauth_providers.load();
+    let oauth_service = providers.github.as_ref().ok_or_else(|| {
         tracing::warn!("GitHub OAuth が設定されていません");
         AppError::OAuthError("GitHub OAuth is not configured".to_string())
     })?;
@@ -168,39 +178,39 @@ pub async fn github_auth(
 /// Google OAuth と同様
 pub async fn github_callback(
     State(state): State<AppState>,
+    client: ClientInfo,
     Query(query): Query<OAuthCallbackQuery>,
 ) -> Result<Redirect, AppError> {
     tracing::info!("GitHub OAuth コールバック受信");
 
-    let oauth_service = state.github_oauth_service.as_ref().ok_or_else(|| {
+    let providers = state.oauth_providers.load();
+    let oauth_service = providers.github.as_ref().ok_or_else(|| {
         tracing::warn!("GitHub OAuth が設定されていません");
         AppError::OAuthError("GitHub OAuth is not configured".to_string())
     })?;
 
-    // 1. state をデコードして login_challenge を復元
-    let login_challenge = oauth_service.decode_state(&query.state)?;
-    tracing::debug!("state デコード成功");
+    let result = async {
+        // 1. state をデコードして login_challenge を復元
+        let login_challenge = oauth_service.decode_state(&query.state)?;
+        tracing::debug!("state デコード成功");
 
-    // 2. code でトークン交換
-    let token_response = oauth_service.exchange_code(&query.code).await?;
-    tracing::debug!("トークン交換成功");
-    // Note: access_token はログに出力しない
+        // 2. code でトークン交換
+        let token_response = oauth_service.exchange_code(&query.code).await?;
+        tracing::debug!("トークン交換成功");
+        // Note: access_token はログに出力しない
 
-    // 3. access_token でユーザー情報取得
-    let user_info = oauth_service
-        .get_user_info(&token_response.access_token)
-        .await?;
-    tracing::info!(provider = "github", "OAuth ユーザー情報取得成功");
-
-    // 4-6. ユーザー処理と Hydra accept
-    let redirect_to = process_oauth_callback(
-        &state,
-        "github",
-        &user_info.id,
-        &user_info.email,
-        &login_challenge,
-    )
-    .await?;
+        // 3. access_token でユーザー情報取得
+        let user_info = oauth_service
+            .get_user_info(&token_response.access_token)
+            .await?;
+        tracing::info!(provider = "github", "OAuth ユーザー情報取得成功");
+
+        // 4-6. ユーザー処理と Hydra accept
+        process_oauth_callback(&state, &client, "github", &user_info, &login_challenge).await
+    }
+    .await;
+
+    let redirect_to = record_oauth_failure(&state, &client, "github", result).await?;
 
     Ok(Redirect::to(&redirect_to))
 }
@@ -222,18 +232,27 @@ pub async fn github_callback(
 /// 3. redirect_to を返す
 async fn process_oauth_callback(
     state: &AppState,
+    client: &ClientInfo,
     provider: &str,
-    provider_id: &str,
-    email: &str,
+    user_info: &OAuthUserInfo,
     login_challenge: &str,
 ) -> Result<String, AppError> {
+    let provider_id = user_info.id.as_str();
+    // プロバイダーのメールアドレスも正規化してから検索・保存する
+    let email = normalize_email(&user_info.email)
+        .map_err(|_| AppError::OAuthError("invalid email from provider".to_string()))?;
+    let email = email.as_str();
+
     // 4. provider_id で user_social_accounts 検索
     let existing_social_account = state
         .social_account_repo
         .find_by_provider_and_id(provider, provider_id)
         .await?;
 
-    let user_id = match existing_social_account {
+    // 監査ログ用: 既存の紐付け（existing）/ 既存ユーザーへの紐付け（linked）/ 新規作成（created）
+    let mut link = "existing";
+
+    let user = match existing_social_account {
         Some(social_account) => {
             // 既存のソーシャルアカウントが見つかった
             tracing::info!(
@@ -241,20 +260,37 @@ async fn process_oauth_callback(
                 user_id = %social_account.user_id,
                 "既存ソーシャルアカウントでログイン"
             );
-            social_account.user_id
+            state
+                .user_repo
+                .find_by_id(social_account.user_id)
+                .await?
+                .ok_or_else(|| AppError::OAuthError("linked user not found".to_string()))?
         }
         None => {
             // ソーシャルアカウントが見つからない - ユーザーを検索または作成
             tracing::debug!(provider = %provider, "ソーシャルアカウント未登録 - ユーザー検索");
 
-            let user = match state.user_repo.find_by_email(email).await? {
+            let user = match state.user_repo.find_by_email_for_auth(email).await? {
                 Some(existing_user) => {
                     // メールアドレスで既存ユーザーが見つかった - 紐付け
+                    // （削除予定・有効でないアカウントには紐付けない）
+                    if let Err(e) = AuthService::ensure_can_login(&existing_user) {
+                        return reject_oauth_login(
+                            state,
+                            client,
+                            provider,
+                            &existing_user,
+                            login_challenge,
+                            e,
+                        )
+                        .await;
+                    }
                     tracing::info!(
                         provider = %provider,
                         user_id = %existing_user.id,
                         "既存ユーザーにソーシャルアカウントを紐付け"
                     );
+                    link = "linked";
                     existing_user
                 }
                 None => {
@@ -263,6 +299,7 @@ async fn process_oauth_callback(
                         provider = %provider,
                         "新規ソーシャルユーザーを作成"
                     );
+                    link = "created";
                     state.user_repo.create_social_user(email).await?
                 }
             };
@@ -274,9 +311,21 @@ async fn process_oauth_callback(
                 .await?;
             tracing::debug!(provider = %provider, "ソーシャルアカウント紐付け完了");
 
-            user.id
+            user
         }
     };
+    if let Err(e) = AuthService::ensure_can_login(&user) {
+        return reject_oauth_login(state, client, provider, &user, login_challenge, e).await;
+    }
+    let user_id = user.id;
+
+    // 新規作成・紐付け時はプロバイダーのプロフィールで未設定の項目を補完
+    if link != "existing" {
+        state
+            .profile_service
+            .fill_from_oauth(user_id, user_info)
+            .await;
+    }
 
     // 5. Hydra login accept を呼び出し
     let redirect_to = state
@@ -295,6 +344,113 @@ async fn process_oauth_callback(
         "OAuth ログイン成功"
     );
 
+    // 新しい端末の判定は今回のログインを記録する前に行う（新規作成ユーザーは対象外）
+    let new_device = link != "created"
+        && state
+            .security_notifications
+            .is_new_device(user_id, client)
+            .await;
+
+    METRICS.record_login(provider, true);
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::OauthLogin)
+                .user(user_id)
+                .client(client)
+                .with("provider", provider)
+                .with("link", link)
+                .with("new_device", new_device),
+        )
+        .await;
+
+    if link == "created" {
+        state
+            .webhook_service
+            .emit(
+                WebhookEvent::new(WebhookEventType::UserRegistered, user_id)
+                    .email(email)
+                    .provider(provider),
+            )
+            .await;
+    }
+    if link != "existing" {
+        state
+            .webhook_service
+            .emit(
+                WebhookEvent::new(WebhookEventType::UserProviderLinked, user_id)
+                    .email(email)
+                    .provider(provider),
+            )
+            .await;
+    }
+    if link == "linked" {
+        state
+            .security_notifications
+            .notify(
+                &user,
+                SecurityEvent::SocialAccountLinked {
+                    provider: provider.to_string(),
+                },
+            )
+            .await;
+    }
+    if new_device {
+        state
+            .security_notifications
+            .notify(&user, SecurityEvent::new_device_login(client))
+            .await;
+    }
+
     // 6. redirect_to を返す
     Ok(redirect_to)
 }
+
+/// ログインできないアカウントの OAuth ログインを Hydra で拒否し、監査ログに記録
+///
+/// # Returns
+/// Hydra から返却されたリダイレクト先URL（クライアントにエラーを返す）
+async fn reject_oauth_login(
+    state: &AppState,
+    client: &ClientInfo,
+    provider: &str,
+    user: &User,
+    login_challenge: &str,
+    error: AppError,
+) -> Result<String, AppError> {
+    let redirect_to = reject_inactive_login(state, login_challenge, &error).await?;
+
+    METRICS.record_login(provider, false);
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::failure(AuditEventType::OauthLogin, &error)
+                .user(user.id)
+                .client(client)
+                .with("provider", provider),
+        )
+        .await;
+
+    Ok(redirect_to)
+}
+
+/// OAuth ログインの失敗を監査ログに記録
+async fn record_oauth_failure(
+    state: &AppState,
+    client: &ClientInfo,
+    provider: &str,
+    result: Result<String, AppError>,
+) -> Result<String, AppError> {
+    if let Err(e) = &result {
+        METRICS.record_login(provider, false);
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::failure(AuditEventType::OauthLogin, e)
+                    .client(client)
+                    .with("provider", provider),
+            )
+            .await;
+    }
+    result
+}
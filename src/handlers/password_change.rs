@@ -0,0 +1,217 @@
+use axum::{Json, extract::State};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::account::{Credentials, reauthenticate, validate_totp_code};
+use crate::client_info::ClientInfo;
+use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent};
+use crate::services::auth::PasswordHashService;
+use crate::services::{PasswordPolicy, SecurityEvent};
+use crate::state::AppState;
+
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub user_id: Uuid,
+    pub current_password: String,
+    pub new_password: String,
+    /// TOTPコード（2FA有効時のみ必須）
+    #[serde(default)]
+    pub code: Option<String>,
+    /// 呼び出し元の Hydra ログインセッション ID（ID トークンの `sid`）
+    /// 指定した場合はこのセッションを残し、他のセッションだけを失効させる
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangePasswordResponse {
+    pub message: String,
+    /// Hydra のログインセッション・同意を失効できたか
+    pub sessions_revoked: bool,
+    /// 呼び出し元のセッションを残したか（`session_id` 未指定の場合は全セッションを失効させる）
+    pub current_session_kept: bool,
+}
+
+/// POST /api/password/change
+///
+/// ログイン中ユーザーのパスワード変更
+///
+/// 成功時は未使用のリセットトークンを無効化し、呼び出し元以外の Hydra のログインセッションと
+/// 記憶された同意を失効させ（他の端末は再ログインが必要）、通知メールを送信する。
+/// `session_id` が指定されていない場合は呼び出し元を特定できないため、全て失効させる
+///
+/// # Security
+/// - 現在のパスワード確認必須（2FA有効時はTOTPコードも必須）
+/// - パスワード・コードはログに出力しない
+pub async fn change_password(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(request): Json<ChangePasswordRequest>,
+) -> Result<Json<ChangePasswordResponse>, AppError> {
+    // バリデーション（メールアドレスの照合・漏洩チェックは本人確認後に行う）
+    validate_change_password_request(&request, &state.password_policy.policy())?;
+
+    // 現在のパスワード確認（2FA有効時はTOTPコードも確認）
+    let user = reauthenticate(
+        &state,
+        &client,
+        AuditEventType::PasswordChanged,
+        request.user_id,
+        Credentials::Password(&request.current_password),
+        request.code.clone(),
+    )
+    .await?;
+
+    // パスワードポリシー（メールアドレスの構成要素・漏洩チェックを含む）
+    if let Err(e) = state
+        .password_policy
+        .validate(&request.new_password, Some(&user.email))
+        .await
+    {
+        state
+            .audit_service
+            .record(
+                NewAuditEvent::failure(AuditEventType::PasswordChanged, &e)
+                    .user(user.id)
+                    .client(&client),
+            )
+            .await;
+        return Err(e);
+    }
+
+    // パスワードを更新し、残っているリセットリンクを無効化
+    let password_hash =
+        PasswordHashService::from_config(&state.config.load())?.hash(&request.new_password)?;
+    state
+        .user_repo
+        .update_password(user.id, &password_hash)
+        .await?;
+    state.token_repo.invalidate_all_for_user(user.id).await?;
+
+    tracing::info!(user_id = %user.id, "パスワード変更完了");
+
+    // 変更は確定済みのため、以降の失敗はログに残してレスポンスで伝える
+    let subject = user.id.to_string();
+    let current_session = request.session_id.as_deref().filter(|sid| !sid.is_empty());
+    let revoked = match current_session {
+        Some(sid) => {
+            state
+                .hydra_client
+                .revoke_other_sessions(&subject, sid)
+                .await
+        }
+        None => state.hydra_client.revoke_subject_sessions(&subject).await,
+    };
+    let current_session_kept = current_session.is_some();
+    let sessions_revoked = match revoked {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::error!(user_id = %user.id, error = ?e, "パスワード変更後のセッション失効に失敗");
+            false
+        }
+    };
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::PasswordChanged)
+                .user(user.id)
+                .client(&client)
+                .with("sessions_revoked", sessions_revoked)
+                .with("current_session_kept", current_session_kept),
+        )
+        .await;
+
+    state
+        .security_notifications
+        .notify(&user, SecurityEvent::PasswordChanged)
+        .await;
+
+    Ok(Json(ChangePasswordResponse {
+        message: "パスワードが変更されました".to_string(),
+        sessions_revoked,
+        current_session_kept,
+    }))
+}
+
+/// パスワード変更リクエストのバリデーション（漏洩チェックを除く）
+fn validate_change_password_request(
+    request: &ChangePasswordRequest,
+    policy: &PasswordPolicy,
+) -> Result<(), AppError> {
+    if request.current_password.is_empty() {
+        return Err(AppError::Validation(
+            "現在のパスワードは必須です".to_string(),
+        ));
+    }
+    if let Some(code) = &request.code {
+        validate_totp_code(code)?;
+    }
+    if request.new_password == request.current_password {
+        return Err(AppError::Validation(
+            "新しいパスワードは現在のパスワードと異なるものを指定してください".to_string(),
+        ));
+    }
+    policy.validate(&request.new_password, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(current: &str, new: &str, code: Option<&str>) -> ChangePasswordRequest {
+        ChangePasswordRequest {
+            user_id: Uuid::new_v4(),
+            current_password: current.to_string(),
+            new_password: new.to_string(),
+            code: code.map(str::to_string),
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_empty_current_password() {
+        let result = validate_change_password_request(
+            &request("", "new-password-123", None),
+            &PasswordPolicy::default(),
+        );
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_validate_same_password() {
+        let result = validate_change_password_request(
+            &request("password123", "password123", None),
+            &PasswordPolicy::default(),
+        );
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_validate_invalid_code() {
+        let result = validate_change_password_request(
+            &request("password123", "new-password-123", Some("12ab")),
+            &PasswordPolicy::default(),
+        );
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_validate_new_password_policy() {
+        let result = validate_change_password_request(
+            &request("password123", "short", None),
+            &PasswordPolicy::default(),
+        );
+        assert!(matches!(result, Err(AppError::PasswordPolicy(_))));
+    }
+
+    #[test]
+    fn test_validate_valid_request() {
+        let result = validate_change_password_request(
+            &request("password123", "new-password-123", Some("123456")),
+            &PasswordPolicy::default(),
+        );
+        assert!(result.is_ok());
+    }
+}
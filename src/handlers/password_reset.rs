@@ -1,8 +1,11 @@
 use axum::{Json, extract::State};
 use serde::{Deserialize, Serialize};
 
+use crate::client_info::ClientInfo;
+use crate::email_address::normalize_email;
 use crate::error::AppError;
-use crate::services::PasswordResetService;
+use crate::models::{AuditEventType, NewAuditEvent};
+use crate::services::{PasswordPolicy, PasswordResetService, SecurityEvent};
 use crate::state::AppState;
 
 // === リセットリクエスト ===
@@ -23,19 +26,29 @@ pub struct ResetRequestResponse {
 /// 常に200を返す（ユーザー存在有無を漏洩しない）
 pub async fn request_password_reset(
     State(state): State<AppState>,
-    Json(request): Json<ResetRequestRequest>,
+    client: ClientInfo,
+    Json(mut request): Json<ResetRequestRequest>,
 ) -> Result<Json<ResetRequestResponse>, AppError> {
     // バリデーション
-    validate_email(&request.email)?;
+    request.email = validate_email(&request.email)?;
 
     // リセット処理（ユーザー不在でもエラーにしない）
     let password_reset_service = PasswordResetService::new(
         state.user_repo.clone(),
         state.token_repo.clone(),
-        state.email_service.clone(),
-        state.config.clone(),
+        state.password_policy.clone(),
+        state.config.load(),
     );
-    password_reset_service.request_reset(&request.email).await?;
+    let user_id = password_reset_service.request_reset(&request.email).await?;
+
+    let mut event = NewAuditEvent::success(AuditEventType::PasswordResetRequested)
+        .actor(request.email.clone())
+        .client(&client)
+        .with("user_found", user_id.is_some());
+    if let Some(user_id) = user_id {
+        event = event.user(user_id);
+    }
+    state.audit_service.record(event).await;
 
     Ok(Json(ResetRequestResponse {
         message: "パスワードリセット手順をメールで送信しました".to_string(),
@@ -61,21 +74,48 @@ pub struct ResetPasswordResponse {
 /// - token, new_password はログに出力しない
 pub async fn reset_password(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(request): Json<ResetPasswordRequest>,
 ) -> Result<Json<ResetPasswordResponse>, AppError> {
-    // バリデーション
-    validate_reset_password_request(&request)?;
+    // バリデーション（メールアドレスの照合・漏洩チェックはトークン検証後に行う）
+    validate_reset_password_request(&request, &state.password_policy.policy())?;
 
     // リセット処理
     let password_reset_service = PasswordResetService::new(
         state.user_repo.clone(),
         state.token_repo.clone(),
-        state.email_service.clone(),
-        state.config.clone(),
+        state.password_policy.clone(),
+        state.config.load(),
     );
-    password_reset_service
+    let user_id = match password_reset_service
         .reset_password(&request.token, &request.new_password)
-        .await?;
+        .await
+    {
+        Ok(user_id) => user_id,
+        Err(e) => {
+            state
+                .audit_service
+                .record(NewAuditEvent::failure(AuditEventType::PasswordReset, &e).client(&client))
+                .await;
+            return Err(e);
+        }
+    };
+
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::PasswordReset)
+                .user(user_id)
+                .client(&client),
+        )
+        .await;
+
+    if let Some(user) = state.user_repo.find_by_id(user_id).await? {
+        state
+            .security_notifications
+            .notify(&user, SecurityEvent::PasswordReset)
+            .await;
+    }
 
     tracing::info!("パスワードリセット完了");
 
@@ -84,27 +124,20 @@ pub async fn reset_password(
     }))
 }
 
-/// メールアドレスのバリデーション
-fn validate_email(email: &str) -> Result<(), AppError> {
-    if email.trim().is_empty() || !email.contains('@') {
-        return Err(AppError::Validation(
-            "有効なメールアドレスを入力してください".to_string(),
-        ));
-    }
-    Ok(())
+/// メールアドレスのバリデーション（正規化したメールアドレスを返す）
+fn validate_email(email: &str) -> Result<String, AppError> {
+    normalize_email(email)
 }
 
 /// リセットパスワードリクエストのバリデーション
-fn validate_reset_password_request(request: &ResetPasswordRequest) -> Result<(), AppError> {
+fn validate_reset_password_request(
+    request: &ResetPasswordRequest,
+    policy: &PasswordPolicy,
+) -> Result<(), AppError> {
     if request.token.trim().is_empty() {
         return Err(AppError::Validation("トークンは必須です".to_string()));
     }
-    if request.new_password.len() < 8 {
-        return Err(AppError::Validation(
-            "パスワードは8文字以上で入力してください".to_string(),
-        ));
-    }
-    Ok(())
+    policy.validate(&request.new_password, None)
 }
 
 #[cfg(test)]
@@ -125,8 +158,8 @@ mod tests {
 
     #[test]
     fn test_validate_valid_email() {
-        let result = validate_email("test@example.com");
-        assert!(result.is_ok());
+        let result = validate_email(" Test@Example.com ");
+        assert_eq!(result.unwrap(), "test@example.com");
     }
 
     #[test]
@@ -135,7 +168,7 @@ mod tests {
             token: "".to_string(),
             new_password: "password123".to_string(),
         };
-        let result = validate_reset_password_request(&request);
+        let result = validate_reset_password_request(&request, &PasswordPolicy::default());
         assert!(result.is_err());
     }
 
@@ -145,7 +178,7 @@ mod tests {
             token: "valid-token".to_string(),
             new_password: "short".to_string(),
         };
-        let result = validate_reset_password_request(&request);
+        let result = validate_reset_password_request(&request, &PasswordPolicy::default());
         assert!(result.is_err());
     }
 
@@ -155,7 +188,7 @@ mod tests {
             token: "valid-token".to_string(),
             new_password: "password123".to_string(),
         };
-        let result = validate_reset_password_request(&request);
+        let result = validate_reset_password_request(&request, &PasswordPolicy::default());
         assert!(result.is_ok());
     }
 }
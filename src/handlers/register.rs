@@ -3,9 +3,13 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::client_info::ClientInfo;
+use crate::email_address::normalize_email;
 use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent, WebhookEvent, WebhookEventType};
 use crate::repositories::UserRepository;
-use crate::services::auth::hash_password;
+use crate::services::PasswordPolicy;
+use crate::services::auth::PasswordHashService;
 use crate::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -29,31 +33,62 @@ pub struct RegisterResponse {
 /// - パスワードは即座にハッシュ化
 pub async fn register(
     State(state): State<AppState>,
-    Json(request): Json<RegisterRequest>,
+    client: ClientInfo,
+    Json(mut request): Json<RegisterRequest>,
 ) -> Result<Json<RegisterResponse>, AppError> {
     // バリデーション
-    validate_register_request(&request)?;
+    let policy = state.password_policy.policy();
+    validate_register_request(&request, &policy)?;
+    request.email = normalize_email(&request.email)?;
+    state
+        .disposable_domains
+        .load()
+        .ensure_allowed(&request.email)?;
+    state
+        .password_policy
+        .ensure_not_breached(&policy, &request.password)
+        .await?;
 
     // パスワードハッシュ化
-    let password_hash = hash_password(&request.password)?;
+    let password_hash =
+        PasswordHashService::from_config(&state.config.load())?.hash(&request.password)?;
 
     // ユーザー作成
     let user_repo = UserRepository::new(state.db_pool.clone());
-    let user = user_repo
-        .create_user(&request.email, &password_hash)
-        .await
-        .map_err(|e| {
-            // UNIQUE制約違反チェック
-            if let sqlx::Error::Database(db_err) = &e
-                && db_err.constraint() == Some("users_email_key")
-            {
-                return AppError::EmailAlreadyExists;
-            }
-            AppError::Database(e)
-        })?;
+    let user = match user_repo.create_user(&request.email, &password_hash).await {
+        Ok(user) => user,
+        // UNIQUE制約違反チェック
+        Err(sqlx::Error::Database(db_err)) if db_err.constraint() == Some("users_email_key") => {
+            state
+                .audit_service
+                .record(
+                    NewAuditEvent::failure(AuditEventType::Register, &AppError::EmailAlreadyExists)
+                        .actor(request.email.clone())
+                        .client(&client),
+                )
+                .await;
+            return Err(AppError::EmailAlreadyExists);
+        }
+        Err(e) => return Err(AppError::Database(e)),
+    };
 
     tracing::info!(email = %request.email, "ユーザー登録成功");
 
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::Register)
+                .user(user.id)
+                .client(&client),
+        )
+        .await;
+    state
+        .webhook_service
+        .emit(
+            WebhookEvent::new(WebhookEventType::UserRegistered, user.id).email(user.email.as_str()),
+        )
+        .await;
+
     Ok(Json(RegisterResponse {
         id: user.id,
         email: user.email,
@@ -61,24 +96,18 @@ pub async fn register(
     }))
 }
 
-/// 登録リクエストのバリデーション
-fn validate_register_request(request: &RegisterRequest) -> Result<(), AppError> {
+/// 登録リクエストのバリデーション（漏洩チェックを除く）
+fn validate_register_request(
+    request: &RegisterRequest,
+    policy: &PasswordPolicy,
+) -> Result<(), AppError> {
     // email: 必須、メール形式
     if request.email.trim().is_empty() {
         return Err(AppError::Validation("メールアドレスは必須です".to_string()));
     }
-    if !request.email.contains('@') {
-        return Err(AppError::Validation(
-            "有効なメールアドレスを入力してください".to_string(),
-        ));
-    }
-    // password: 8文字以上
-    if request.password.len() < 8 {
-        return Err(AppError::Validation(
-            "パスワードは8文字以上で入力してください".to_string(),
-        ));
-    }
-    Ok(())
+    normalize_email(&request.email)?;
+    // password: パスワードポリシー
+    policy.validate(&request.password, Some(&request.email))
 }
 
 #[cfg(test)]
@@ -91,7 +120,7 @@ mod tests {
             email: "".to_string(),
             password: "password123".to_string(),
         };
-        let result = validate_register_request(&request);
+        let result = validate_register_request(&request, &PasswordPolicy::default());
         assert!(result.is_err());
     }
 
@@ -101,7 +130,7 @@ mod tests {
             email: "invalid-email".to_string(),
             password: "password123".to_string(),
         };
-        let result = validate_register_request(&request);
+        let result = validate_register_request(&request, &PasswordPolicy::default());
         assert!(result.is_err());
     }
 
@@ -111,7 +140,7 @@ mod tests {
             email: "test@example.com".to_string(),
             password: "short".to_string(),
         };
-        let result = validate_register_request(&request);
+        let result = validate_register_request(&request, &PasswordPolicy::default());
         assert!(result.is_err());
     }
 
@@ -121,7 +150,17 @@ mod tests {
             email: "test@example.com".to_string(),
             password: "password123".to_string(),
         };
-        let result = validate_register_request(&request);
+        let result = validate_register_request(&request, &PasswordPolicy::default());
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_validate_password_contains_email() {
+        let request = RegisterRequest {
+            email: "yamada@example.com".to_string(),
+            password: "yamada-secret".to_string(),
+        };
+        let result = validate_register_request(&request, &PasswordPolicy::default());
+        assert!(matches!(result, Err(AppError::PasswordPolicy(_))));
+    }
 }
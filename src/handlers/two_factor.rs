@@ -2,10 +2,12 @@ use axum::{Json, extract::State};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::client_info::ClientInfo;
 use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent, WebhookEvent, WebhookEventType};
 use crate::repositories::User2faSecretRepository;
-use crate::services::TotpService;
-use crate::services::auth::AuthService;
+use crate::services::auth::{AuthService, PasswordHashService};
+use crate::services::{SecurityEvent, TotpService};
 use crate::state::AppState;
 
 // === 2FA Setup ===
@@ -31,13 +33,26 @@ pub struct SetupResponse {
 /// - シークレット平文はログ出力禁止
 pub async fn setup_2fa(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(request): Json<SetupRequest>,
 ) -> Result<Json<SetupResponse>, AppError> {
     // バリデーション
     validate_password(&request.password)?;
 
     // パスワード確認
-    let user = verify_user_password(&state, request.user_id, &request.password).await?;
+    let user = match verify_user_password(&state, request.user_id, &request.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            return Err(record_failure(
+                &state,
+                &client,
+                AuditEventType::TwoFactorSetup,
+                request.user_id,
+                e,
+            )
+            .await);
+        }
+    };
 
     // 既に2FA設定済みかチェック
     let user_2fa_repo = User2faSecretRepository::new(state.db_pool.clone());
@@ -61,6 +76,15 @@ pub async fn setup_2fa(
 
     tracing::info!(user_id = %user.id, "2FA設定開始");
 
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::TwoFactorSetup)
+                .user(user.id)
+                .client(&client),
+        )
+        .await;
+
     Ok(Json(SetupResponse {
         secret,
         qr_code: format!("data:image/png;base64,{}", qr_code),
@@ -88,6 +112,7 @@ pub struct VerifyResponse {
 /// - コードはログ出力禁止
 pub async fn verify_2fa(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(request): Json<VerifyRequest>,
 ) -> Result<Json<VerifyResponse>, AppError> {
     // バリデーション
@@ -111,7 +136,14 @@ pub async fn verify_2fa(
 
     // コード検証
     if !state.totp_service.verify_code(&secret, &request.code)? {
-        return Err(AppError::TotpInvalid);
+        return Err(record_failure(
+            &state,
+            &client,
+            AuditEventType::TwoFactorEnabled,
+            request.user_id,
+            AppError::TotpInvalid,
+        )
+        .await);
     }
 
     // 2FAを有効化
@@ -119,6 +151,29 @@ pub async fn verify_2fa(
 
     tracing::info!(user_id = %request.user_id, "2FA有効化完了");
 
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::TwoFactorEnabled)
+                .user(request.user_id)
+                .client(&client),
+        )
+        .await;
+
+    if let Some(user) = state.user_repo.find_by_id(request.user_id).await? {
+        state
+            .security_notifications
+            .notify(&user, SecurityEvent::TwoFactorEnabled)
+            .await;
+        state
+            .webhook_service
+            .emit(
+                WebhookEvent::new(WebhookEventType::UserTwoFactorEnabled, user.id)
+                    .email(user.email.as_str()),
+            )
+            .await;
+    }
+
     Ok(Json(VerifyResponse { enabled: true }))
 }
 
@@ -145,6 +200,7 @@ pub struct DisableResponse {
 /// - TOTPコード確認必須
 pub async fn disable_2fa(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(request): Json<DisableRequest>,
 ) -> Result<Json<DisableResponse>, AppError> {
     // バリデーション
@@ -152,7 +208,19 @@ pub async fn disable_2fa(
     validate_totp_code(&request.code)?;
 
     // パスワード確認
-    let user = verify_user_password(&state, request.user_id, &request.password).await?;
+    let user = match verify_user_password(&state, request.user_id, &request.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            return Err(record_failure(
+                &state,
+                &client,
+                AuditEventType::TwoFactorDisabled,
+                request.user_id,
+                e,
+            )
+            .await);
+        }
+    };
 
     // 2FAシークレット取得
     let user_2fa_repo = User2faSecretRepository::new(state.db_pool.clone());
@@ -172,7 +240,14 @@ pub async fn disable_2fa(
 
     // コード検証
     if !state.totp_service.verify_code(&secret, &request.code)? {
-        return Err(AppError::TotpInvalid);
+        return Err(record_failure(
+            &state,
+            &client,
+            AuditEventType::TwoFactorDisabled,
+            user.id,
+            AppError::TotpInvalid,
+        )
+        .await);
     }
 
     // 2FAを削除
@@ -180,6 +255,20 @@ pub async fn disable_2fa(
 
     tracing::info!(user_id = %user.id, "2FA無効化完了");
 
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::success(AuditEventType::TwoFactorDisabled)
+                .user(user.id)
+                .client(&client),
+        )
+        .await;
+
+    state
+        .security_notifications
+        .notify(&user, SecurityEvent::TwoFactorDisabled)
+        .await;
+
     Ok(Json(DisableResponse { disabled: true }))
 }
 
@@ -225,10 +314,30 @@ async fn verify_user_password(
         .ok_or_else(|| AppError::Authentication("user not found".to_string()))?;
 
     // パスワード検証
-    let auth_service = AuthService::new(state.user_repo.clone());
+    let hash_service = PasswordHashService::from_config(&state.config.load())?;
+    let auth_service = AuthService::new(state.user_repo.clone(), hash_service);
     auth_service.authenticate(&user.email, password).await
 }
 
+/// 失敗を監査ログに記録し、そのエラーを返す
+async fn record_failure(
+    state: &AppState,
+    client: &ClientInfo,
+    event_type: AuditEventType,
+    user_id: Uuid,
+    error: AppError,
+) -> AppError {
+    state
+        .audit_service
+        .record(
+            NewAuditEvent::failure(event_type, &error)
+                .user(user_id)
+                .client(client),
+        )
+        .await;
+    error
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
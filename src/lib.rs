@@ -1,7 +1,18 @@
+pub mod authenticated_user;
+pub mod cli;
+pub mod client_info;
 pub mod config;
+pub mod database;
+pub mod email_address;
 pub mod error;
 pub mod handlers;
+pub mod metrics;
+pub mod migrations;
 pub mod models;
 pub mod repositories;
+pub mod secrets;
+pub mod server;
 pub mod services;
 pub mod state;
+pub mod telemetry;
+pub mod tls;
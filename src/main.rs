@@ -1,33 +1,106 @@
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
 use axum::{
-    Router,
-    routing::{get, post},
+    Router, middleware,
+    routing::{get, patch, post},
 };
 use http::{HeaderValue, Method};
-use secrecy::ExposeSecret;
-use sqlx::postgres::PgPoolOptions;
 use tokio::net::TcpListener;
-use tower_http::cors::{AllowOrigin, Any, CorsLayer};
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
+use tokio::sync::watch;
+use tower_http::cors::{AllowCredentials, AllowHeaders, AllowOrigin, CorsLayer};
 
-use oxgate::{config::Config, handlers, services::hydra::HydraClient, state::AppState};
+use oxgate::{
+    cli::{self, CliArgs, Command, commands},
+    config::{Config, SharedConfig},
+    handlers, metrics, migrations,
+    repositories::OutboxRepository,
+    server,
+    services::{ConfigReloader, KeyRotationService, OutboxWorker, Scheduler, hydra::HydraClient},
+    state::AppState,
+    telemetry::{self, Tracing},
+    tls::Tls,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // ログ初期化（JSON形式、環境変数でレベル制御）
-    init_tracing();
+    let tracing = telemetry::init_tracing();
 
-    tracing::info!("oxgate 起動中...");
+    let args = match CliArgs::from_env() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.command == Command::Help {
+        print!("{}", cli::USAGE);
+        return Ok(());
+    }
 
-    // 設定読み込み
-    let config = Config::load().map_err(|e| {
-        tracing::error!(error = ?e, "設定の読み込みに失敗");
-        anyhow::anyhow!("Failed to load config: {}", e)
-    })?;
+    // 設定読み込み（設定ファイル + 環境変数 + シークレットプロバイダー）と検証
+    let config = match Config::load_layered(args.config_path.as_deref()).await {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!(error = %e, "設定の読み込みに失敗");
+            if !matches!(args.command, Command::Serve { .. }) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Err(anyhow::anyhow!("Failed to load config: {}", e));
+        }
+    };
+
+    let result = match args.command {
+        Command::Serve { auto_migrate } => {
+            return serve(config, args.config_path, auto_migrate, tracing).await;
+        }
+        // --check-config: 検証のみ行い終了
+        Command::CheckConfig => {
+            println!("設定は有効です");
+            Ok(())
+        }
+        Command::Migrate(command) => commands::migrate(&config, command).await,
+        Command::CreateAdmin { name } => commands::create_admin(&config, &name).await,
+        Command::UserLock {
+            user,
+            reason,
+            until,
+        } => commands::lock_user(config, &user, reason, until).await,
+        Command::RotateKeys => commands::rotate_keys(config).await,
+        Command::NormalizeEmails { dry_run } => commands::normalize_emails(config, dry_run).await,
+        Command::Cleanup => commands::cleanup(config).await,
+        Command::Help => unreachable!(),
+    };
+
+    // 管理コマンドのエラーは標準エラー出力に表示して終了
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
+/// サーバーを起動
+///
+/// スキーマがバイナリのマイグレーションと一致しない場合は起動しない
+/// （`auto_migrate` の場合は未適用のマイグレーションを先に適用する）
+async fn serve(
+    config: Config,
+    config_path: Option<PathBuf>,
+    auto_migrate: bool,
+    tracing: Tracing,
+) -> anyhow::Result<()> {
+    tracing::info!("oxgate 起動中...");
     tracing::info!(host = %config.host, port = %config.port, "設定読み込み完了");
 
+    // OTLP トレース送信（設定されている場合のみ）
+    let otlp_exporter = tracing.enable_otlp(&config).map_err(|e| {
+        tracing::error!(error = %e, "OTLP トレース送信の初期化に失敗");
+        anyhow::anyhow!("Failed to initialize OTLP exporter: {}", e)
+    })?;
+
     // サーバーアドレスを先に構築（config が move される前に）
     let addr: SocketAddr = format!("{}:{}", config.host, config.port)
         .parse()
@@ -35,18 +108,66 @@ async fn main() -> anyhow::Result<()> {
             tracing::error!(error = ?e, "アドレスのパースに失敗");
             anyhow::anyhow!("Failed to parse address: {}", e)
         })?;
-
-    // データベース接続プール作成
-    let db_pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(config.database_url.expose_secret())
-        .await
+    let admin_addr: Option<SocketAddr> = config
+        .admin_listen_addr
+        .as_deref()
+        .map(str::parse)
+        .transpose()
         .map_err(|e| {
-            tracing::error!(error = ?e, "データベース接続に失敗");
-            anyhow::anyhow!("Failed to connect to database: {}", e)
+            tracing::error!(error = ?e, "管理 API のアドレスのパースに失敗");
+            anyhow::anyhow!("Failed to parse admin address: {}", e)
+        })?;
+
+    // TLS（設定されている場合のみ、証明書ファイルの更新は再起動せずに反映）
+    let tls = Tls::from_config(&config).map_err(|e| {
+        tracing::error!(error = %e, "TLS 証明書の読み込みに失敗");
+        anyhow::anyhow!("Failed to load TLS certificate: {}", e)
+    })?;
+    let admin_mtls = config.admin_tls_client_ca_path.is_some();
+    let (tls_acceptor, admin_tls_acceptor) = match &tls {
+        Some(tls) => {
+            let acceptor = tls.acceptor().map_err(|e| {
+                tracing::error!(error = %e, "TLS の設定に失敗");
+                anyhow::anyhow!("Failed to configure TLS: {}", e)
+            })?;
+            let admin_acceptor = match &config.admin_tls_client_ca_path {
+                Some(ca_path) => tls.acceptor_with_client_auth(ca_path).map_err(|e| {
+                    tracing::error!(error = %e, "管理 API のクライアント証明書の設定に失敗");
+                    anyhow::anyhow!("Failed to configure admin client authentication: {}", e)
+                })?,
+                None => acceptor.clone(),
+            };
+            tls.spawn_reloader();
+            (Some(acceptor), Some(admin_acceptor))
+        }
+        None => (None, None),
+    };
+
+    // スキーマの更新（statement_timeout を適用しない専用の接続で行う）
+    if auto_migrate || config.database_auto_migrate {
+        let migration_pool = commands::connect_for_migrations(&config).await?;
+        migrations::run(&migration_pool).await.map_err(|e| {
+            tracing::error!(error = %e, "マイグレーションの適用に失敗");
+            anyhow::anyhow!("Failed to run migrations: {}", e)
         })?;
+        migration_pool.close().await;
+    }
+
+    // データベース接続プール作成
+    let db_pool = commands::connect_database(&config).await?;
+    let db_read_pool = commands::connect_read_replica(&config).await?;
 
-    tracing::info!("データベース接続完了");
+    tracing::info!(
+        max_connections = config.database_max_connections,
+        read_replica = db_read_pool.is_some(),
+        "データベース接続完了"
+    );
+
+    // スキーマバージョンの確認
+    migrations::check_schema(&db_pool).await.map_err(|e| {
+        tracing::error!(error = %e, "スキーマがこのバージョンと一致しません");
+        anyhow::anyhow!("Schema mismatch: {}", e)
+    })?;
 
     // Hydra クライアント初期化
     let hydra_client = HydraClient::new(config.hydra_admin_url.clone());
@@ -54,54 +175,126 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!(hydra_url = %config.hydra_admin_url, "Hydra クライアント初期化完了");
 
     // AppState 構築
-    let state = AppState::new(db_pool, hydra_client, config).map_err(|e| {
+    let state = AppState::new(db_pool, db_read_pool, hydra_client, config).map_err(|e| {
         tracing::error!(error = ?e, "AppState の構築に失敗");
         anyhow::anyhow!("Failed to create AppState: {}", e)
     })?;
 
-    // Router 構築
-    let app = create_router(state);
+    // 旧キーで暗号化された 2FA シークレットを最新キーへ移行（バックグラウンド）
+    spawn_reencryption_job(&state);
+
+    // 定期ジョブ（期限切れデータの削除など、レプリカ間はアドバイザリロックで排他）
+    let scheduler = state.config.load().scheduler_enabled.then(|| {
+        commands::housekeeping_service(&state)
+            .schedule(Scheduler::new(state.db_pool.clone()))
+            .start()
+    });
+
+    // 送信キューの配信（レプリカ間は行ロックで排他、スケジューラーの設定に関わらず起動）
+    let outbox_worker = OutboxWorker::new(
+        OutboxRepository::new(state.db_pool.clone()),
+        state.email_service.clone(),
+        state.webhook_service.clone(),
+    )
+    .start();
+
+    // 設定ホットリロード（SIGHUP / 設定ファイル変更）
+    tokio::spawn(ConfigReloader::new(state.clone(), config_path).run());
+
+    // Router 構築（管理 API を別のリスナーで提供する場合は公開 API から除く）
+    let admin_app = admin_addr.map(|_| create_admin_router(state.clone()));
+    let app = create_router(state, admin_app.is_none());
 
     // サーバー起動
-    let listener = TcpListener::bind(&addr).await.map_err(|e| {
-        tracing::error!(error = ?e, addr = %addr, "ポートのバインドに失敗");
-        anyhow::anyhow!("Failed to bind to {}: {}", addr, e)
-    })?;
+    let listener = bind(addr).await?;
+    let admin_listener = match admin_addr {
+        Some(admin_addr) => Some(bind(admin_addr).await?),
+        None => None,
+    };
 
-    tracing::info!(addr = %addr, "サーバー起動");
+    tracing::info!(
+        addr = %addr,
+        admin_addr = ?admin_addr,
+        tls = tls.is_some(),
+        admin_mtls,
+        "サーバー起動"
+    );
 
-    // Graceful shutdown 対応
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "サーバーエラー");
-            anyhow::anyhow!("Server error: {}", e)
-        })?;
+    // Graceful shutdown 対応（シグナルを受けたら全てのリスナーを止める）
+    let (shutdown_tx, shutdown_rx) = watch::channel(());
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        let _ = shutdown_tx.send(());
+    });
+
+    // 監査ログに接続元 IP アドレスを記録するため ConnectInfo を渡す
+    let public_server = server::serve(
+        "public",
+        listener,
+        app,
+        tls_acceptor,
+        wait_for_shutdown(shutdown_rx.clone()),
+    );
+    let admin_server = async {
+        if let (Some(listener), Some(app)) = (admin_listener, admin_app) {
+            server::serve(
+                "admin",
+                listener,
+                app,
+                admin_tls_acceptor,
+                wait_for_shutdown(shutdown_rx),
+            )
+            .await;
+        }
+    };
+    tokio::join!(public_server, admin_server);
+
+    // 実行中のジョブ・配信の完了を待つ
+    if let Some(scheduler) = scheduler {
+        scheduler.shutdown().await;
+    }
+    outbox_worker.shutdown().await;
 
     tracing::info!("サーバー終了");
 
+    if let Some(exporter) = otlp_exporter {
+        exporter.shutdown().await;
+    }
+
     Ok(())
 }
 
-/// tracing の初期化（JSON形式）
-fn init_tracing() {
-    let env_filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info,oxgate=debug"));
+/// 2FA シークレット再暗号化ジョブを起動
+///
+/// キーリングに複数のキーがある（ローテーション中の）場合のみ実行する
+fn spawn_reencryption_job(state: &AppState) {
+    match state.config.load().totp_keyring() {
+        Ok(keyring) if keyring.len() > 1 => {}
+        _ => return,
+    }
+
+    let service = KeyRotationService::new(state.user_2fa_repo.clone(), state.totp_service.clone());
 
-    tracing_subscriber::registry()
-        .with(env_filter)
-        .with(tracing_subscriber::fmt::layer().json())
-        .init();
+    tokio::spawn(async move {
+        if let Err(e) = service.reencrypt_totp_secrets().await {
+            tracing::error!(error = ?e, "2FAシークレットの再暗号化に失敗");
+        }
+    });
 }
 
 /// Router の構築
-fn create_router(state: AppState) -> Router {
+///
+/// # Arguments
+/// * `include_admin` - 管理 API と `/metrics` を含めるか
+///   （`ADMIN_LISTEN_ADDR` で別のリスナーにする場合は false）
+fn create_router(state: AppState, include_admin: bool) -> Router {
     // CORS設定
-    let cors = build_cors_layer(&state);
+    let cors = build_cors_layer(&state.config);
 
-    Router::new()
+    let mut router = Router::new()
         .route("/api/health", get(handlers::health_check))
+        .route("/api/health/live", get(handlers::health_check))
+        .route("/api/health/ready", get(handlers::readiness))
         .route("/api/login", post(handlers::login))
         .route("/api/consent", post(handlers::consent))
         .route("/api/logout", post(handlers::logout))
@@ -112,6 +305,29 @@ fn create_router(state: AppState) -> Router {
             post(handlers::request_password_reset),
         )
         .route("/api/password/reset", post(handlers::reset_password))
+        .route("/api/password/change", post(handlers::change_password))
+        .route("/api/account/export", post(handlers::export_account))
+        .route("/api/account/delete", post(handlers::delete_account))
+        .route(
+            "/api/account/delete/cancel",
+            post(handlers::cancel_account_deletion),
+        )
+        .route("/api/account/activity", post(handlers::account_activity))
+        .route(
+            "/api/account/notifications",
+            post(handlers::notification_settings),
+        )
+        .route("/api/account/lock", post(handlers::lock_account))
+        .route("/api/account/email", post(handlers::request_email_change))
+        .route(
+            "/api/account/email/confirm",
+            post(handlers::confirm_email_change),
+        )
+        .route(
+            "/api/account/email/cancel",
+            post(handlers::cancel_email_change),
+        )
+        .route("/api/me", get(handlers::get_me).patch(handlers::update_me))
         // Phase 5: 二要素認証
         .route("/api/2fa/setup", post(handlers::setup_2fa))
         .route("/api/2fa/verify", post(handlers::verify_2fa))
@@ -120,50 +336,139 @@ fn create_router(state: AppState) -> Router {
         .route("/api/oauth/google", get(handlers::google_auth))
         .route("/api/oauth/google/callback", get(handlers::google_callback))
         .route("/api/oauth/github", get(handlers::github_auth))
-        .route("/api/oauth/github/callback", get(handlers::github_callback))
+        .route("/api/oauth/github/callback", get(handlers::github_callback));
+    if include_admin {
+        router = router.nest("/admin/api", admin_routes(&state));
+    }
+
+    // マッチしたルートごとにリクエスト数・処理時間を記録（/metrics 自体は除く）
+    router = router.route_layer(middleware::from_fn(metrics::track_http_metrics));
+    if include_admin {
+        router = router.route("/metrics", get(handlers::metrics));
+    }
+
+    router
+        .route_layer(middleware::from_fn(telemetry::record_route))
         .layer(cors)
+        // リクエストID・トレースのスパン（CORS のプリフライトや 404 も含めて全リクエストが対象）
+        .layer(middleware::from_fn(telemetry::trace_request))
+        .with_state(state)
+}
+
+/// 管理 API 専用のリスナーの Router を構築
+///
+/// 認証のない `/metrics` も公開リスナーではなくこちらで提供する。
+/// ブラウザーからは呼び出さないため CORS は設定しない
+fn create_admin_router(state: AppState) -> Router {
+    Router::new()
+        .nest("/admin/api", admin_routes(&state))
+        .route_layer(middleware::from_fn(metrics::track_http_metrics))
+        .route("/metrics", get(handlers::metrics))
+        .route_layer(middleware::from_fn(telemetry::record_route))
+        .layer(middleware::from_fn(telemetry::trace_request))
         .with_state(state)
 }
 
+/// 管理 API のルート（API キー・Hydra トークン認証）
+fn admin_routes(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/audit-events", get(handlers::list_audit_events))
+        .route("/outbox/dead", get(handlers::list_dead_outbox_messages))
+        .route(
+            "/outbox/{message_id}/retry",
+            post(handlers::retry_outbox_message),
+        )
+        .route(
+            "/webhooks",
+            get(handlers::list_webhooks).post(handlers::create_webhook),
+        )
+        .route(
+            "/webhooks/deliveries",
+            get(handlers::list_webhook_deliveries),
+        )
+        .route(
+            "/webhooks/deliveries/{delivery_id}/replay",
+            post(handlers::replay_webhook_delivery),
+        )
+        .route(
+            "/webhooks/{webhook_id}",
+            patch(handlers::update_webhook).delete(handlers::delete_webhook),
+        )
+        .route("/users", get(handlers::list_users))
+        .route(
+            "/users/{user_id}",
+            get(handlers::get_user).delete(handlers::delete_user),
+        )
+        .route("/users/{user_id}/lock", post(handlers::lock_user))
+        .route("/users/{user_id}/unlock", post(handlers::unlock_user))
+        .route("/users/{user_id}/disable", post(handlers::disable_user))
+        .route("/users/{user_id}/enable", post(handlers::enable_user))
+        .route(
+            "/users/{user_id}/logout",
+            post(handlers::revoke_user_sessions),
+        )
+        .route("/users/{user_id}/2fa/reset", post(handlers::reset_user_2fa))
+        .route(
+            "/users/{user_id}/password-reset",
+            post(handlers::force_password_reset),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            handlers::require_admin,
+        ))
+}
+
 /// CORS レイヤーを構築
 ///
-/// - `ALLOWED_ORIGINS` が設定されている場合: 指定されたオリジンのみ許可
-/// - 設定されていない場合: 全オリジン許可（開発環境向け）
-fn build_cors_layer(state: &AppState) -> CorsLayer {
+/// 設定リロードに追従するため、リクエストごとに現在の設定で判定する
+///
+/// - `ALLOWED_ORIGINS` が設定されている場合: 指定されたオリジンのみ許可（credentials 許可）
+/// - 設定されていない場合: 全オリジン許可（開発環境向け、credentials 不許可）
+fn build_cors_layer(shared_config: &SharedConfig) -> CorsLayer {
     let allowed_methods = [
         Method::GET,
         Method::POST,
         Method::PUT,
+        Method::PATCH,
         Method::DELETE,
         Method::OPTIONS,
     ];
 
-    match state.config.get_allowed_origins() {
-        Some(origins) if !origins.is_empty() => {
-            // 本番環境: 指定されたオリジンのみ許可
-            let origins: Vec<HeaderValue> = origins
-                .iter()
-                .filter_map(|o| o.parse().ok())
-                .collect();
-
-            tracing::info!(origins = ?state.config.allowed_origins, "CORS: 指定オリジンを許可");
-
-            CorsLayer::new()
-                .allow_origin(AllowOrigin::list(origins))
-                .allow_methods(allowed_methods)
-                .allow_headers(Any)
-                .allow_credentials(true)
-        }
-        _ => {
-            // 開発環境: 全オリジン許可
-            tracing::warn!("CORS: 全オリジン許可（開発環境設定）");
-
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(allowed_methods)
-                .allow_headers(Any)
-        }
+    let config = shared_config.load();
+    if config.has_allowed_origins() {
+        tracing::info!(origins = ?config.allowed_origins, "CORS: 指定オリジンを許可");
+    } else {
+        tracing::warn!("CORS: 全オリジン許可（開発環境設定）");
     }
+
+    let origin_config = shared_config.clone();
+    let credentials_config = shared_config.clone();
+
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| origin_config.load().allows_origin(origin))
+        }))
+        .allow_credentials(AllowCredentials::predicate(move |_, _| {
+            credentials_config.load().has_allowed_origins()
+        }))
+        .allow_methods(allowed_methods)
+        .allow_headers(AllowHeaders::mirror_request())
+}
+
+/// アドレスにバインド
+async fn bind(addr: SocketAddr) -> anyhow::Result<TcpListener> {
+    TcpListener::bind(addr).await.map_err(|e| {
+        tracing::error!(error = ?e, addr = %addr, "ポートのバインドに失敗");
+        anyhow::anyhow!("Failed to bind to {}: {}", addr, e)
+    })
+}
+
+/// 終了の通知を待つ
+async fn wait_for_shutdown(mut shutdown: watch::Receiver<()>) {
+    // 送信側が破棄された場合も終了する
+    let _ = shutdown.changed().await;
 }
 
 /// Graceful shutdown シグナル待機
@@ -198,3 +503,112 @@ async fn shutdown_signal() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use axum::body::Body;
+    use http::{Request, StatusCode, header};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const KEY: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+
+    fn env() -> HashMap<String, String> {
+        HashMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://oxgate@localhost/oxgate".to_string(),
+            ),
+            (
+                "HYDRA_ADMIN_URL".to_string(),
+                "http://localhost:4445".to_string(),
+            ),
+            ("TOTP_ISSUER".to_string(), "oxgate".to_string()),
+            ("ENCRYPTION_KEY".to_string(), KEY.to_string()),
+            ("OAUTH_STATE_SECRET".to_string(), KEY.to_string()),
+        ])
+    }
+
+    fn shared_config(allowed_origins: &str) -> SharedConfig {
+        let mut env = env();
+        env.insert("ALLOWED_ORIGINS".to_string(), allowed_origins.to_string());
+        SharedConfig::new(envy::from_iter(env).unwrap())
+    }
+
+    fn state() -> AppState {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://oxgate@localhost/oxgate")
+            .unwrap();
+        AppState::new(
+            pool,
+            None,
+            HydraClient::new("http://localhost:4445".to_string()),
+            Config::from_env_map(env()).unwrap(),
+        )
+        .unwrap()
+    }
+
+    async fn status(app: &Router, uri: &str) -> StatusCode {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        app.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_metrics_moves_to_admin_listener() {
+        // 管理リスナーなし: 公開リスナーで提供
+        let public = create_router(state(), true);
+        assert_eq!(status(&public, "/metrics").await, StatusCode::OK);
+
+        // 管理リスナーあり: 公開リスナーからは外す
+        let public = create_router(state(), false);
+        let admin = create_admin_router(state());
+        assert_eq!(status(&public, "/metrics").await, StatusCode::NOT_FOUND);
+        assert_eq!(status(&admin, "/metrics").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight_allows_patch() {
+        let app = Router::new()
+            .route("/api/me", patch(|| async {}))
+            .layer(build_cors_layer(&shared_config("https://app.example.com")));
+
+        let preflight = |origin: &str| {
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/api/me")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PATCH")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+        let methods = response.headers()[header::ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap();
+        assert!(methods.split(',').any(|m| m.trim() == "PATCH"));
+
+        // 許可されていないオリジンには CORS ヘッダーを返さない
+        let response = app
+            .oneshot(preflight("https://other.example.com"))
+            .await
+            .unwrap();
+        assert!(
+            !response
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        );
+    }
+}
@@ -0,0 +1,378 @@
+//! Prometheus 形式のメトリクス
+//!
+//! カウンターとヒストグラムをプロセス内に集計し、`GET /metrics` でテキスト形式
+//! （exposition format 0.0.4）として出力する。ラベルの値はルートのテンプレートや
+//! メソッド名など種類の限られたものだけを使うこと（ユーザーごとの値は使わない）
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use sqlx::PgPool;
+
+/// レイテンシのヒストグラムのバケット（秒）
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// プロセス全体のメトリクス
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// 収集するメトリクスの一覧
+pub struct Metrics {
+    http_requests: CounterVec,
+    http_request_duration: HistogramVec,
+    logins: CounterVec,
+    hydra_request_duration: HistogramVec,
+    hydra_errors: CounterVec,
+    emails: CounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        Self {
+            http_requests: CounterVec::new(
+                "oxgate_http_requests_total",
+                "HTTP リクエスト数",
+                &["method", "route", "status"],
+            ),
+            http_request_duration: HistogramVec::new(
+                "oxgate_http_request_duration_seconds",
+                "HTTP リクエストの処理時間",
+                &["method", "route", "status"],
+                LATENCY_BUCKETS,
+            ),
+            logins: CounterVec::new(
+                "oxgate_logins_total",
+                "ログインの試行数",
+                &["method", "result"],
+            ),
+            hydra_request_duration: HistogramVec::new(
+                "oxgate_hydra_request_duration_seconds",
+                "Hydra Admin API の呼び出し時間",
+                &["method"],
+                LATENCY_BUCKETS,
+            ),
+            hydra_errors: CounterVec::new(
+                "oxgate_hydra_errors_total",
+                "Hydra Admin API の呼び出しエラー数（接続エラー・2xx 以外の応答）",
+                &["method"],
+            ),
+            emails: CounterVec::new(
+                "oxgate_emails_sent_total",
+                "メールの送信結果",
+                &["template", "result"],
+            ),
+        }
+    }
+
+    /// HTTP リクエストを記録
+    ///
+    /// # Arguments
+    /// * `route` - マッチしたルートのテンプレート（`/admin/api/users/{user_id}` など）
+    pub fn record_http_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.inc(&labels);
+        self.http_request_duration
+            .observe(&labels, elapsed.as_secs_f64());
+    }
+
+    /// ログインの結果を記録
+    ///
+    /// # Arguments
+    /// * `method` - password / totp / google / github
+    pub fn record_login(&self, method: &str, success: bool) {
+        self.logins.inc(&[method, result_label(success)]);
+    }
+
+    /// Hydra Admin API の呼び出しを記録
+    ///
+    /// # Arguments
+    /// * `method` - `HydraClient` のメソッド名
+    pub fn record_hydra_request(&self, method: &str, elapsed: Duration, success: bool) {
+        self.hydra_request_duration
+            .observe(&[method], elapsed.as_secs_f64());
+        if !success {
+            self.hydra_errors.inc(&[method]);
+        }
+    }
+
+    /// メールの送信結果を記録
+    pub fn record_email(&self, template: &str, success: bool) {
+        self.emails.inc(&[template, result_label(success)]);
+    }
+
+    /// テキスト形式で出力
+    ///
+    /// DB 接続プールの使用状況は出力時点の値を使う
+    pub fn render(&self, pool: &PgPool) -> String {
+        let mut out = String::new();
+        self.http_requests.render(&mut out);
+        self.http_request_duration.render(&mut out);
+        self.logins.render(&mut out);
+        self.hydra_request_duration.render(&mut out);
+        self.hydra_errors.render(&mut out);
+        self.emails.render(&mut out);
+
+        let size = pool.size();
+        let idle = pool.num_idle() as u32;
+        render_gauge(
+            &mut out,
+            "oxgate_db_pool_connections",
+            "DB 接続プールの接続数",
+            &[
+                ("state", "idle", idle),
+                ("state", "in_use", size.saturating_sub(idle)),
+            ],
+        );
+        render_gauge(
+            &mut out,
+            "oxgate_db_pool_max_connections",
+            "DB 接続プールの最大接続数",
+            &[("", "", pool.options().get_max_connections())],
+        );
+        out
+    }
+}
+
+fn result_label(success: bool) -> &'static str {
+    if success { "success" } else { "failure" }
+}
+
+/// HTTP リクエストのメトリクスを記録するミドルウェア
+///
+/// ルートにマッチしたリクエストのみ記録する（`route_layer` で適用する）
+pub async fn track_http_metrics(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    METRICS.record_http_request(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
+
+/// ラベル付きのカウンター
+struct CounterVec {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    values: Mutex<BTreeMap<Vec<String>, u64>>,
+}
+
+impl CounterVec {
+    fn new(name: &'static str, help: &'static str, labels: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn inc(&self, label_values: &[&str]) {
+        let key = label_values.iter().map(|v| v.to_string()).collect();
+        let mut values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        *values.entry(key).or_default() += 1;
+    }
+
+    fn render(&self, out: &mut String) {
+        let values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} counter", self.name);
+        for (label_values, value) in values.iter() {
+            let labels = format_labels(self.labels, label_values, None);
+            let _ = writeln!(out, "{}{} {}", self.name, labels, value);
+        }
+    }
+}
+
+/// ラベル付きのヒストグラム
+struct HistogramVec {
+    name: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+    buckets: &'static [f64],
+    values: Mutex<BTreeMap<Vec<String>, Histogram>>,
+}
+
+#[derive(Default)]
+struct Histogram {
+    /// バケットごとの（累積でない）観測数
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl HistogramVec {
+    fn new(
+        name: &'static str,
+        help: &'static str,
+        labels: &'static [&'static str],
+        buckets: &'static [f64],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            labels,
+            buckets,
+            values: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn observe(&self, label_values: &[&str], value: f64) {
+        let key = label_values.iter().map(|v| v.to_string()).collect();
+        let mut values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        let histogram = values.entry(key).or_insert_with(|| Histogram {
+            counts: vec![0; self.buckets.len()],
+            ..Histogram::default()
+        });
+        if let Some(index) = self.buckets.iter().position(|&bound| value <= bound) {
+            histogram.counts[index] += 1;
+        }
+        histogram.sum += value;
+        histogram.count += 1;
+    }
+
+    fn render(&self, out: &mut String) {
+        let values = self.values.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} histogram", self.name);
+        for (label_values, histogram) in values.iter() {
+            let mut cumulative = 0;
+            for (bound, count) in self.buckets.iter().zip(&histogram.counts) {
+                cumulative += count;
+                let labels =
+                    format_labels(self.labels, label_values, Some(("le", &bound.to_string())));
+                let _ = writeln!(out, "{}_bucket{} {}", self.name, labels, cumulative);
+            }
+            let labels = format_labels(self.labels, label_values, Some(("le", "+Inf")));
+            let _ = writeln!(out, "{}_bucket{} {}", self.name, labels, histogram.count);
+
+            let labels = format_labels(self.labels, label_values, None);
+            let _ = writeln!(out, "{}_sum{} {}", self.name, labels, histogram.sum);
+            let _ = writeln!(out, "{}_count{} {}", self.name, labels, histogram.count);
+        }
+    }
+}
+
+/// ゲージを出力（ラベル名が空の場合はラベルなし）
+fn render_gauge(out: &mut String, name: &str, help: &str, samples: &[(&str, &str, u32)]) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} gauge", name);
+    for (label, label_value, value) in samples {
+        if label.is_empty() {
+            let _ = writeln!(out, "{} {}", name, value);
+        } else {
+            let _ = writeln!(
+                out,
+                "{}{{{}=\"{}\"}} {}",
+                name,
+                label,
+                escape_label_value(label_value),
+                value
+            );
+        }
+    }
+}
+
+/// `{name="value",...}` 形式のラベルを組み立てる
+fn format_labels(names: &[&str], values: &[String], extra: Option<(&str, &str)>) -> String {
+    let pairs: Vec<String> = names
+        .iter()
+        .zip(values)
+        .map(|(name, value)| (*name, value.as_str()))
+        .chain(extra)
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+        .collect();
+
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+/// ラベル値のエスケープ（`\`、`"`、改行）
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_render() {
+        let counter = CounterVec::new("test_total", "テスト", &["method", "result"]);
+        counter.inc(&["password", "success"]);
+        counter.inc(&["password", "success"]);
+        counter.inc(&["github", "failure"]);
+
+        let mut out = String::new();
+        counter.render(&mut out);
+        assert_eq!(
+            out,
+            "# HELP test_total テスト\n\
+             # TYPE test_total counter\n\
+             test_total{method=\"github\",result=\"failure\"} 1\n\
+             test_total{method=\"password\",result=\"success\"} 2\n"
+        );
+    }
+
+    #[test]
+    fn test_histogram_render() {
+        let histogram = HistogramVec::new("test_seconds", "テスト", &["method"], &[0.1, 1.0]);
+        histogram.observe(&["get"], 0.05);
+        histogram.observe(&["get"], 0.5);
+        histogram.observe(&["get"], 3.0);
+
+        let mut out = String::new();
+        histogram.render(&mut out);
+        assert_eq!(
+            out,
+            "# HELP test_seconds テスト\n\
+             # TYPE test_seconds histogram\n\
+             test_seconds_bucket{method=\"get\",le=\"0.1\"} 1\n\
+             test_seconds_bucket{method=\"get\",le=\"1\"} 2\n\
+             test_seconds_bucket{method=\"get\",le=\"+Inf\"} 3\n\
+             test_seconds_sum{method=\"get\"} 3.55\n\
+             test_seconds_count{method=\"get\"} 3\n"
+        );
+    }
+
+    #[test]
+    fn test_escape_label_value() {
+        assert_eq!(escape_label_value("plain"), "plain");
+        assert_eq!(escape_label_value("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[test]
+    fn test_record_login() {
+        METRICS.record_login("totp", false);
+
+        let mut out = String::new();
+        METRICS.logins.render(&mut out);
+        assert!(out.contains("oxgate_logins_total{method=\"totp\",result=\"failure\"}"));
+    }
+}
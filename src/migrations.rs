@@ -0,0 +1,303 @@
+//! 埋め込みデータベースマイグレーション
+//!
+//! `migrations/` の SQL をバイナリに埋め込み、`oxgate migrate` サブコマンドと
+//! 起動時のスキーマ確認（バージョン不一致の場合は起動しない）から使用する。
+
+use std::collections::{BTreeSet, HashMap};
+
+use sqlx::PgPool;
+use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, Migration, Migrator};
+
+/// バイナリに埋め込んだマイグレーション
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// スキーマ確認エラー
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaError {
+    #[error(transparent)]
+    Migrate(#[from] MigrateError),
+
+    #[error(
+        "未適用のマイグレーションがあります: {0:?}（`oxgate migrate up` または `serve --auto-migrate` で適用してください）"
+    )]
+    Pending(Vec<i64>),
+
+    #[error(
+        "このバイナリにないマイグレーションが適用されています: {0:?}（新しいバージョンのバイナリを使用してください）"
+    )]
+    Unknown(Vec<i64>),
+
+    #[error("適用済みのマイグレーションが変更されています: {0:?}")]
+    Modified(Vec<i64>),
+
+    #[error("マイグレーション {0} が途中で失敗しています（`_sqlx_migrations` を確認してください）")]
+    Dirty(i64),
+}
+
+/// マイグレーション 1 件の適用状況
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    /// バイナリにないマイグレーションの場合は None
+    pub description: Option<String>,
+    pub applied: bool,
+    /// 適用後にマイグレーションが変更されたか
+    pub modified: bool,
+}
+
+/// 埋め込みマイグレーションと適用済みマイグレーションの比較結果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SchemaCheck {
+    pub pending: Vec<i64>,
+    pub unknown: Vec<i64>,
+    pub modified: Vec<i64>,
+    pub dirty: Option<i64>,
+}
+
+impl SchemaCheck {
+    /// スキーマがバイナリのマイグレーションと一致するか確認
+    pub fn ensure_current(self) -> Result<(), SchemaError> {
+        if let Some(version) = self.dirty {
+            return Err(SchemaError::Dirty(version));
+        }
+        if !self.modified.is_empty() {
+            return Err(SchemaError::Modified(self.modified));
+        }
+        if !self.unknown.is_empty() {
+            return Err(SchemaError::Unknown(self.unknown));
+        }
+        if !self.pending.is_empty() {
+            return Err(SchemaError::Pending(self.pending));
+        }
+        Ok(())
+    }
+}
+
+/// 適用済みマイグレーションを取得（管理テーブルがなければ作成）
+async fn applied_migrations(
+    pool: &PgPool,
+) -> Result<(Vec<AppliedMigration>, Option<i64>), MigrateError> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+    let dirty = conn.dirty_version().await?;
+    let applied = conn.list_applied_migrations().await?;
+    Ok((applied, dirty))
+}
+
+/// 適用するマイグレーション（down を除く）
+fn up_migrations() -> impl Iterator<Item = &'static Migration> {
+    MIGRATOR
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+}
+
+/// 埋め込みマイグレーションと適用済みマイグレーションを比較
+fn compare<'a>(
+    migrations: impl IntoIterator<Item = &'a Migration>,
+    applied: &[AppliedMigration],
+    dirty: Option<i64>,
+) -> SchemaCheck {
+    let applied: HashMap<i64, &AppliedMigration> = applied.iter().map(|m| (m.version, m)).collect();
+    let mut check = SchemaCheck {
+        dirty,
+        ..SchemaCheck::default()
+    };
+
+    let mut known = BTreeSet::new();
+    for migration in migrations {
+        known.insert(migration.version);
+        match applied.get(&migration.version) {
+            Some(applied) if applied.checksum != migration.checksum => {
+                check.modified.push(migration.version)
+            }
+            Some(_) => {}
+            None => check.pending.push(migration.version),
+        }
+    }
+
+    check.unknown = applied
+        .keys()
+        .filter(|version| !known.contains(version))
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    check
+}
+
+/// スキーマがバイナリのマイグレーションと一致するか確認
+pub async fn check_schema(pool: &PgPool) -> Result<(), SchemaError> {
+    let (applied, dirty) = applied_migrations(pool).await?;
+    compare(up_migrations(), &applied, dirty).ensure_current()
+}
+
+/// 全マイグレーションの適用状況
+pub async fn status(pool: &PgPool) -> Result<Vec<MigrationStatus>, MigrateError> {
+    let (applied, dirty) = applied_migrations(pool).await?;
+    let check = compare(up_migrations(), &applied, dirty);
+
+    let mut statuses: Vec<MigrationStatus> = up_migrations()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            description: Some(m.description.to_string()),
+            applied: !check.pending.contains(&m.version),
+            modified: check.modified.contains(&m.version),
+        })
+        .collect();
+    statuses.extend(check.unknown.iter().map(|&version| MigrationStatus {
+        version,
+        description: None,
+        applied: true,
+        modified: false,
+    }));
+    statuses.sort_by_key(|s| s.version);
+    Ok(statuses)
+}
+
+/// 未適用のマイグレーションを全て適用
+///
+/// # Returns
+/// 適用したマイグレーションのバージョン
+pub async fn run(pool: &PgPool) -> Result<Vec<i64>, MigrateError> {
+    let (applied, dirty) = applied_migrations(pool).await?;
+    let pending = compare(up_migrations(), &applied, dirty).pending;
+
+    MIGRATOR.run(pool).await?;
+    for version in &pending {
+        tracing::info!(version, "マイグレーションを適用");
+    }
+    Ok(pending)
+}
+
+/// マイグレーションを取り消す
+///
+/// # Arguments
+/// * `target` - このバージョンより新しいマイグレーションを取り消す
+///   （None の場合は最新の 1 件のみ）
+///
+/// # Returns
+/// 取り消したマイグレーションのバージョン（新しい順）
+pub async fn undo(pool: &PgPool, target: Option<i64>) -> Result<Vec<i64>, MigrateError> {
+    let (applied, _) = applied_migrations(pool).await?;
+    let mut versions: Vec<i64> = applied.iter().map(|m| m.version).collect();
+    versions.sort_unstable();
+
+    let target = target.unwrap_or_else(|| previous_version(&versions));
+    let reverted: Vec<i64> = versions
+        .iter()
+        .rev()
+        .copied()
+        .filter(|&version| version > target)
+        .collect();
+
+    MIGRATOR.undo(pool, target).await?;
+    for version in &reverted {
+        tracing::info!(version, "マイグレーションを取り消し");
+    }
+    Ok(reverted)
+}
+
+/// 最新の 1 件を取り消す場合の取り消し先（最新の 1 つ前のバージョン、なければ 0）
+fn previous_version(sorted_versions: &[i64]) -> i64 {
+    match sorted_versions {
+        [.., previous, _] => *previous,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use sqlx::migrate::MigrationType;
+
+    use super::*;
+
+    fn migration(version: i64, sql: &'static str) -> Migration {
+        Migration::new(
+            version,
+            Cow::Borrowed("test"),
+            MigrationType::ReversibleUp,
+            Cow::Borrowed(sql),
+            false,
+        )
+    }
+
+    fn applied(migration: &Migration) -> AppliedMigration {
+        AppliedMigration {
+            version: migration.version,
+            checksum: migration.checksum.clone(),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let migrations = [
+            migration(1, "CREATE TABLE a ();"),
+            migration(2, "CREATE TABLE b ();"),
+            migration(3, "CREATE TABLE c ();"),
+        ];
+
+        let current = compare(
+            &migrations,
+            &migrations.iter().map(applied).collect::<Vec<_>>(),
+            None,
+        );
+        assert_eq!(current, SchemaCheck::default());
+        assert!(current.ensure_current().is_ok());
+
+        let check = compare(&migrations, &[applied(&migrations[0])], None);
+        assert_eq!(check.pending, [2, 3]);
+        assert!(matches!(
+            check.ensure_current(),
+            Err(SchemaError::Pending(_))
+        ));
+
+        let newer = AppliedMigration {
+            version: 4,
+            checksum: Cow::Owned(vec![0]),
+        };
+        let modified = applied(&migration(1, "CREATE TABLE x ();"));
+        let check = compare(
+            &migrations,
+            &[
+                modified,
+                applied(&migrations[1]),
+                applied(&migrations[2]),
+                newer,
+            ],
+            None,
+        );
+        assert_eq!(check.modified, [1]);
+        assert_eq!(check.unknown, [4]);
+        assert!(matches!(
+            check.ensure_current(),
+            Err(SchemaError::Modified(_))
+        ));
+    }
+
+    #[test]
+    fn test_dirty_schema_is_rejected() {
+        let check = compare(&[], &[], Some(7));
+        assert!(matches!(check.ensure_current(), Err(SchemaError::Dirty(7))));
+    }
+
+    #[test]
+    fn test_previous_version() {
+        assert_eq!(previous_version(&[]), 0);
+        assert_eq!(previous_version(&[1]), 0);
+        assert_eq!(previous_version(&[1, 2, 3]), 2);
+    }
+
+    #[test]
+    fn test_embedded_migrations_are_reversible() {
+        let ups: Vec<i64> = up_migrations().map(|m| m.version).collect();
+        let downs: Vec<i64> = MIGRATOR
+            .iter()
+            .filter(|m| m.migration_type.is_down_migration())
+            .map(|m| m.version)
+            .collect();
+        assert!(!ups.is_empty());
+        assert_eq!(ups, downs);
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// アカウントロックトークン
+///
+/// セキュリティ通知メールの「心当たりがない」リンクに含める。
+/// パスワードリセットトークンと同様、DBにはハッシュのみ保存する
+#[derive(Debug, FromRow, Serialize)]
+pub struct AccountLockToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    #[serde(skip)]
+    pub token_hash: String,
+    pub expires_at: OffsetDateTime,
+    pub used_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
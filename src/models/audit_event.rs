@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::client_info::ClientInfo;
+use crate::error::AppError;
+
+/// 監査イベント
+#[derive(Debug, FromRow, Serialize)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub actor: Option<String>,
+    pub event_type: String,
+    pub outcome: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub metadata: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+/// 監査イベントの種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventType {
+    Login,
+    Logout,
+    Consent,
+    Register,
+    PasswordResetRequested,
+    PasswordReset,
+    PasswordChanged,
+    TwoFactorSetup,
+    TwoFactorEnabled,
+    TwoFactorDisabled,
+    OauthLogin,
+    AccountExported,
+    AccountDeletionRequested,
+    AccountDeletionCancelled,
+    AccountDeleted,
+    ActivityViewed,
+    AccountLocked,
+    NotificationSettingsUpdated,
+    AccountUnlocked,
+    SessionsRevoked,
+    TwoFactorReset,
+    PasswordResetForced,
+    UsersSearched,
+    UserViewed,
+    AccountDisabled,
+    AccountEnabled,
+    ProfileUpdated,
+    EmailChangeRequested,
+    EmailChanged,
+    EmailChangeCancelled,
+    OutboxMessageRequeued,
+    WebhookCreated,
+    WebhookUpdated,
+    WebhookDeleted,
+    WebhookReplayed,
+}
+
+impl AuditEventType {
+    /// DB に保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Login => "login",
+            Self::Logout => "logout",
+            Self::Consent => "consent",
+            Self::Register => "register",
+            Self::PasswordResetRequested => "password_reset_requested",
+            Self::PasswordReset => "password_reset",
+            Self::PasswordChanged => "password_changed",
+            Self::TwoFactorSetup => "two_factor_setup",
+            Self::TwoFactorEnabled => "two_factor_enabled",
+            Self::TwoFactorDisabled => "two_factor_disabled",
+            Self::OauthLogin => "oauth_login",
+            Self::AccountExported => "account_exported",
+            Self::AccountDeletionRequested => "account_deletion_requested",
+            Self::AccountDeletionCancelled => "account_deletion_cancelled",
+            Self::AccountDeleted => "account_deleted",
+            Self::ActivityViewed => "activity_viewed",
+            Self::AccountLocked => "account_locked",
+            Self::NotificationSettingsUpdated => "notification_settings_updated",
+            Self::AccountUnlocked => "account_unlocked",
+            Self::SessionsRevoked => "sessions_revoked",
+            Self::TwoFactorReset => "two_factor_reset",
+            Self::PasswordResetForced => "password_reset_forced",
+            Self::UsersSearched => "users_searched",
+            Self::UserViewed => "user_viewed",
+            Self::AccountDisabled => "account_disabled",
+            Self::AccountEnabled => "account_enabled",
+            Self::ProfileUpdated => "profile_updated",
+            Self::EmailChangeRequested => "email_change_requested",
+            Self::EmailChanged => "email_changed",
+            Self::EmailChangeCancelled => "email_change_cancelled",
+            Self::OutboxMessageRequeued => "outbox_message_requeued",
+            Self::WebhookCreated => "webhook_created",
+            Self::WebhookUpdated => "webhook_updated",
+            Self::WebhookDeleted => "webhook_deleted",
+            Self::WebhookReplayed => "webhook_replayed",
+        }
+    }
+}
+
+/// 監査イベントの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+impl AuditOutcome {
+    /// DB に保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+        }
+    }
+}
+
+/// 記録する監査イベント
+#[derive(Debug, Clone)]
+pub struct NewAuditEvent {
+    pub user_id: Option<Uuid>,
+    pub actor: Option<String>,
+    pub event_type: AuditEventType,
+    pub outcome: AuditOutcome,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub metadata: serde_json::Value,
+}
+
+impl NewAuditEvent {
+    /// 成功イベント
+    pub fn success(event_type: AuditEventType) -> Self {
+        Self::new(event_type, AuditOutcome::Success)
+    }
+
+    /// 失敗イベント（失敗理由を `metadata.reason` に記録）
+    pub fn failure(event_type: AuditEventType, error: &AppError) -> Self {
+        Self::new(event_type, AuditOutcome::Failure).with("reason", error.reason_code())
+    }
+
+    fn new(event_type: AuditEventType, outcome: AuditOutcome) -> Self {
+        Self {
+            user_id: None,
+            actor: None,
+            event_type,
+            outcome,
+            ip_address: None,
+            user_agent: None,
+            metadata: serde_json::Value::Object(serde_json::Map::new()),
+        }
+    }
+
+    /// 対象ユーザー
+    pub fn user(mut self, user_id: Uuid) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    /// Hydra の subject（ユーザーID）から対象ユーザーを設定
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.user_id = Uuid::parse_str(subject).ok();
+        self
+    }
+
+    /// 操作主体（ユーザーを特定できない場合のメールアドレスなど）
+    pub fn actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// リクエスト元クライアント
+    pub fn client(mut self, client: &ClientInfo) -> Self {
+        self.ip_address = client.ip.map(|ip| ip.to_string());
+        self.user_agent = client.user_agent.clone();
+        self
+    }
+
+    /// メタデータを追加
+    pub fn with(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        if let serde_json::Value::Object(map) = &mut self.metadata {
+            map.insert(key.to_string(), value.into());
+        }
+        self
+    }
+}
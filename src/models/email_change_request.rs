@@ -0,0 +1,30 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// メールアドレス変更リクエスト
+///
+/// 新しいアドレス宛の確認リンクで確定し、現在のアドレス宛の取り消しリンクで取り消す。
+/// パスワードリセットトークンと同様、DBにはトークンのハッシュのみ保存する
+#[derive(Debug, FromRow, Serialize)]
+pub struct EmailChangeRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub new_email: String,
+    #[serde(skip)]
+    pub confirm_token_hash: String,
+    #[serde(skip)]
+    pub cancel_token_hash: String,
+    pub expires_at: OffsetDateTime,
+    pub confirmed_at: Option<OffsetDateTime>,
+    pub cancelled_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+impl EmailChangeRequest {
+    /// 確定・取り消しされておらず、有効期限内か
+    pub fn is_pending(&self, now: OffsetDateTime) -> bool {
+        self.confirmed_at.is_none() && self.cancelled_at.is_none() && self.expires_at > now
+    }
+}
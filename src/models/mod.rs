@@ -1,9 +1,26 @@
+pub mod account_lock_token;
+pub mod audit_event;
+pub mod email_change_request;
+pub mod outbox_message;
 pub mod password_reset_token;
 pub mod user;
 pub mod user_2fa;
+pub mod user_notification_settings;
+pub mod user_profile;
 pub mod user_social_account;
+pub mod webhook;
 
+pub use account_lock_token::AccountLockToken;
+pub use audit_event::{AuditEvent, AuditEventType, AuditOutcome, NewAuditEvent};
+pub use email_change_request::EmailChangeRequest;
+pub use outbox_message::{EmailMessage, OutboxMessage, OutboxPayload, OutboxStatus};
 pub use password_reset_token::PasswordResetToken;
-pub use user::User;
+pub use user::{User, UserStatus};
 pub use user_2fa::User2faSecret;
+pub use user_notification_settings::UserNotificationSettings;
+pub use user_profile::UserProfile;
 pub use user_social_account::UserSocialAccount;
+pub use webhook::{
+    WEBHOOK_SCHEMA_VERSION, WebhookDelivery, WebhookDeliveryStatus, WebhookEvent, WebhookEventData,
+    WebhookEventType, WebhookSubscription,
+};
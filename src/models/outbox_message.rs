@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// 送信キュー（outbox）のメッセージ
+///
+/// # Security
+/// payload にはメール内のリンク（トークン）を含むため、API のレスポンスには含めない
+#[derive(Debug, FromRow, Serialize)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub kind: String,
+    #[serde(skip)]
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_attempt_at: OffsetDateTime,
+    pub last_error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl OutboxMessage {
+    /// payload を復元
+    pub fn decode(&self) -> Result<OutboxPayload, serde_json::Error> {
+        serde_json::from_value(self.payload.clone())
+    }
+}
+
+/// メッセージの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    /// 配信待ち（再試行待ちを含む）
+    Pending,
+    /// 再試行回数を超えて配信を諦めた
+    Dead,
+}
+
+impl OutboxStatus {
+    /// DB に保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Dead => "dead",
+        }
+    }
+}
+
+/// 送信キューに追加するメッセージ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum OutboxPayload {
+    Email(EmailMessage),
+    /// Webhook の配信（内容は `webhook_deliveries` から取得）
+    Webhook {
+        delivery_id: Uuid,
+    },
+}
+
+impl OutboxPayload {
+    /// `outbox_messages.kind` に保存する種別
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Email(_) => "email",
+            Self::Webhook { .. } => "webhook",
+        }
+    }
+}
+
+/// 送信するメール（テンプレートと差し込む値）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "template", rename_all = "snake_case")]
+pub enum EmailMessage {
+    PasswordReset {
+        to: String,
+        reset_url: String,
+    },
+    SecurityNotification {
+        to: String,
+        summary: String,
+        lock_url: String,
+    },
+    EmailChangeConfirmation {
+        to: String,
+        confirm_url: String,
+    },
+    EmailChangeNotice {
+        to: String,
+        new_email: String,
+        cancel_url: String,
+    },
+    AccountDeleted {
+        to: String,
+    },
+}
+
+impl EmailMessage {
+    /// テンプレート名（シリアライズ時の `template` と同じ値）
+    pub fn template(&self) -> &'static str {
+        match self {
+            Self::PasswordReset { .. } => "password_reset",
+            Self::SecurityNotification { .. } => "security_notification",
+            Self::EmailChangeConfirmation { .. } => "email_change_confirmation",
+            Self::EmailChangeNotice { .. } => "email_change_notice",
+            Self::AccountDeleted { .. } => "account_deleted",
+        }
+    }
+}
+
+impl From<EmailMessage> for OutboxPayload {
+    fn from(message: EmailMessage) -> Self {
+        Self::Email(message)
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -11,4 +11,71 @@ pub struct User {
     pub password_hash: Option<String>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
+    /// アカウント削除予定日時（削除予約中のみ）
+    pub deletion_scheduled_at: Option<OffsetDateTime>,
+    /// アカウントロック日時（ロック中のみ）
+    pub locked_at: Option<OffsetDateTime>,
+    /// アカウント状態（`UserStatus` の文字列表現）
+    pub status: String,
+    /// ロック・無効化の理由
+    pub lock_reason: Option<String>,
+    /// ロックの有効期限（NULL の場合は解除されるまで）
+    pub locked_until: Option<OffsetDateTime>,
+}
+
+impl User {
+    /// 現在のアカウント状態
+    ///
+    /// 有効期限を過ぎたロックは `Active` として扱う。
+    /// 不明な値は `Disabled` として扱う（ログインを許可しない）
+    pub fn status_at(&self, now: OffsetDateTime) -> UserStatus {
+        match UserStatus::parse(&self.status) {
+            Some(UserStatus::Locked) if self.locked_until.is_some_and(|until| until <= now) => {
+                UserStatus::Active
+            }
+            Some(status) => status,
+            None => UserStatus::Disabled,
+        }
+    }
+
+    /// 現在のアカウント状態（現在時刻で判定）
+    pub fn current_status(&self) -> UserStatus {
+        self.status_at(OffsetDateTime::now_utc())
+    }
+}
+
+/// アカウント状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    Active,
+    /// 一時的な停止（不正利用の疑いなど、期限付きも可）
+    Locked,
+    /// 恒久的な停止（退職・規約違反など）
+    Disabled,
+    /// メールアドレスの確認待ち
+    PendingVerification,
+}
+
+impl UserStatus {
+    /// DB に保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Locked => "locked",
+            Self::Disabled => "disabled",
+            Self::PendingVerification => "pending_verification",
+        }
+    }
+
+    /// DB の文字列表現から変換
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(Self::Active),
+            "locked" => Some(Self::Locked),
+            "disabled" => Some(Self::Disabled),
+            "pending_verification" => Some(Self::PendingVerification),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,35 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// セキュリティ通知メールの受信設定
+///
+/// 行が存在しないユーザーは全ての通知を受信する（[`UserNotificationSettings::defaults`]）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct UserNotificationSettings {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub new_device_login: bool,
+    pub password_changed: bool,
+    pub two_factor_changed: bool,
+    pub social_account_changed: bool,
+    pub email_changed: bool,
+    #[serde(skip)]
+    pub updated_at: OffsetDateTime,
+}
+
+impl UserNotificationSettings {
+    /// デフォルト設定（全ての通知を受信）
+    pub fn defaults(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            new_device_login: true,
+            password_changed: true,
+            two_factor_changed: true,
+            social_account_changed: true,
+            email_changed: true,
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
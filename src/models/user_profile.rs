@@ -0,0 +1,40 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// ユーザープロフィール（OIDC の profile クレーム）
+///
+/// 行が存在しないユーザーは全て未設定（[`UserProfile::empty`]）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct UserProfile {
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub display_name: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    pub picture_url: Option<String>,
+    pub locale: Option<String>,
+    pub zoneinfo: Option<String>,
+    /// アプリケーション固有の任意の属性（JSON オブジェクト）
+    pub metadata: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl UserProfile {
+    /// 未設定のプロフィール
+    pub fn empty(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            display_name: None,
+            given_name: None,
+            family_name: None,
+            picture_url: None,
+            locale: None,
+            zoneinfo: None,
+            metadata: serde_json::Value::Object(serde_json::Map::new()),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Webhook イベントのスキーマバージョン
+///
+/// フィールドの削除・意味の変更など互換性のない変更を行う場合に上げる
+pub const WEBHOOK_SCHEMA_VERSION: u32 = 1;
+
+/// Webhook の購読設定
+///
+/// # Security
+/// 署名用シークレットは作成時のみ返却し、API のレスポンスには含めない
+#[derive(Debug, FromRow, Serialize)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    #[serde(skip)]
+    pub secret_encrypted: Vec<u8>,
+    pub description: Option<String>,
+    pub enabled: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// Webhook の配信ログ
+#[derive(Debug, FromRow, Serialize)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub delivered_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// 配信の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+    /// 配信待ち（再試行待ちを含む）
+    Pending,
+    /// 2xx の応答を受け取った
+    Succeeded,
+    /// 再試行回数を超えて配信を諦めた
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    /// DB に保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Webhook イベントの種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "user.registered")]
+    UserRegistered,
+    #[serde(rename = "user.provider_linked")]
+    UserProviderLinked,
+    #[serde(rename = "user.two_factor_enabled")]
+    UserTwoFactorEnabled,
+    #[serde(rename = "user.email_changed")]
+    UserEmailChanged,
+    #[serde(rename = "user.deleted")]
+    UserDeleted,
+}
+
+impl WebhookEventType {
+    /// 全てのイベント種別
+    pub const ALL: [Self; 5] = [
+        Self::UserRegistered,
+        Self::UserProviderLinked,
+        Self::UserTwoFactorEnabled,
+        Self::UserEmailChanged,
+        Self::UserDeleted,
+    ];
+
+    /// DB・ペイロードに保存する文字列表現
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UserRegistered => "user.registered",
+            Self::UserProviderLinked => "user.provider_linked",
+            Self::UserTwoFactorEnabled => "user.two_factor_enabled",
+            Self::UserEmailChanged => "user.email_changed",
+            Self::UserDeleted => "user.deleted",
+        }
+    }
+}
+
+/// Webhook で送信するイベント（リクエストボディ）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// イベントID（再送時も同じ値、受信側の重複排除に使う）
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    /// スキーマバージョン（`WEBHOOK_SCHEMA_VERSION`）
+    pub version: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    pub data: WebhookEventData,
+}
+
+/// イベントの対象ユーザーと付随情報（種別ごとに該当する項目のみ設定）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookEventData {
+    pub user_id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// ソーシャルログインのプロバイダー（google / github）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// 変更前のメールアドレス（user.email_changed）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_email: Option<String>,
+    /// 削除理由（user.deleted: user_request / admin / unverified）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl WebhookEvent {
+    /// 新しいイベント
+    pub fn new(event_type: WebhookEventType, user_id: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type,
+            version: WEBHOOK_SCHEMA_VERSION,
+            created_at: OffsetDateTime::now_utc(),
+            data: WebhookEventData {
+                user_id,
+                ..WebhookEventData::default()
+            },
+        }
+    }
+
+    /// メールアドレス
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.data.email = Some(email.into());
+        self
+    }
+
+    /// ソーシャルログインのプロバイダー
+    pub fn provider(mut self, provider: impl Into<String>) -> Self {
+        self.data.provider = Some(provider.into());
+        self
+    }
+
+    /// 変更前のメールアドレス
+    pub fn previous_email(mut self, email: impl Into<String>) -> Self {
+        self.data.previous_email = Some(email.into());
+        self
+    }
+
+    /// 削除理由
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.data.reason = Some(reason.into());
+        self
+    }
+}
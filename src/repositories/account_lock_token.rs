@@ -0,0 +1,134 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::models::{AccountLockToken, OutboxPayload};
+use crate::repositories::outbox;
+
+#[derive(Clone)]
+pub struct AccountLockTokenRepository {
+    pool: PgPool,
+}
+
+impl AccountLockTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 新しいアカウントロックトークンを作成し、通知メールを送信キューに追加
+    ///
+    /// # Arguments
+    /// * `user_id` - 対象ユーザーのID
+    /// * `token_hash` - トークンのSHA256ハッシュ
+    /// * `expires_at` - 有効期限
+    /// * `email` - セキュリティ通知メール（トークンと同じトランザクションで追加）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: OffsetDateTime,
+        email: &OutboxPayload,
+    ) -> Result<AccountLockToken, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let token = sqlx::query_as::<_, AccountLockToken>(
+            r#"
+            INSERT INTO account_lock_tokens (user_id, token_hash, expires_at)
+            VALUES ($1, $2, $3)
+            RETURNING id, user_id, token_hash, expires_at, used_at, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        outbox::insert(&mut tx, email).await?;
+
+        tx.commit().await?;
+        Ok(token)
+    }
+
+    /// トークンハッシュでトークンを検索
+    ///
+    /// # Note
+    /// 有効期限や使用済みフラグの検証は呼び出し側で行う
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<AccountLockToken>, sqlx::Error> {
+        sqlx::query_as::<_, AccountLockToken>(
+            r#"
+            SELECT id, user_id, token_hash, expires_at, used_at, created_at
+            FROM account_lock_tokens
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 未使用かつ有効期限内のトークンを使用済みにする
+    ///
+    /// 確認と使用済みへの更新を 1 つの UPDATE で行うため、同じトークンで同時に
+    /// リクエストされても使用できるのは 1 回だけ
+    ///
+    /// # Returns
+    /// 使用したトークンのユーザーID（存在しない・使用済み・期限切れの場合は None）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn consume(&self, token_hash: &str) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE account_lock_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// ユーザーの未使用トークンを全て使用済みにする
+    ///
+    /// # Returns
+    /// 無効化された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn invalidate_all_for_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE account_lock_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 期限切れトークンを削除
+    ///
+    /// # Returns
+    /// 削除された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_expired(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM account_lock_tokens
+            WHERE expires_at < NOW()
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,170 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::models::{AuditEvent, AuditEventType, AuditOutcome, NewAuditEvent};
+
+/// 監査イベントの検索条件（未指定の項目は絞り込まない）
+#[derive(Debug, Default, Clone)]
+pub struct AuditEventFilter {
+    pub user_id: Option<Uuid>,
+    pub actor: Option<String>,
+    pub event_type: Option<AuditEventType>,
+    pub outcome: Option<AuditOutcome>,
+    pub ip_address: Option<String>,
+    pub since: Option<OffsetDateTime>,
+    pub until: Option<OffsetDateTime>,
+}
+
+#[derive(Clone)]
+pub struct AuditEventRepository {
+    pool: PgPool,
+}
+
+impl AuditEventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 監査イベントを記録
+    ///
+    /// # Note
+    /// 存在しないユーザーID（削除済みユーザーの Hydra subject など）は NULL として記録する
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(&self, event: &NewAuditEvent) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_events
+                (user_id, actor, event_type, outcome, ip_address, user_agent, metadata)
+            VALUES ((SELECT id FROM users WHERE id = $1), $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(event.user_id)
+        .bind(&event.actor)
+        .bind(event.event_type.as_str())
+        .bind(event.outcome.as_str())
+        .bind(&event.ip_address)
+        .bind(&event.user_agent)
+        .bind(&event.metadata)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// ユーザーの監査イベントを新しい順に取得
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, sqlx::Error> {
+        sqlx::query_as::<_, AuditEvent>(
+            r#"
+            SELECT id, user_id, actor, event_type, outcome, ip_address, user_agent, metadata, created_at
+            FROM audit_events
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 指定日時以降の成功したログイン（パスワード・ソーシャル）を新しい順に取得
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_successful_logins(
+        &self,
+        user_id: Uuid,
+        since: OffsetDateTime,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, sqlx::Error> {
+        sqlx::query_as::<_, AuditEvent>(
+            r#"
+            SELECT id, user_id, actor, event_type, outcome, ip_address, user_agent, metadata, created_at
+            FROM audit_events
+            WHERE user_id = $1
+              AND event_type IN ($2, $3)
+              AND outcome = $4
+              AND created_at >= $5
+            ORDER BY created_at DESC
+            LIMIT $6
+            "#,
+        )
+        .bind(user_id)
+        .bind(AuditEventType::Login.as_str())
+        .bind(AuditEventType::OauthLogin.as_str())
+        .bind(AuditOutcome::Success.as_str())
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 条件に一致する監査イベントを新しい順に取得
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn search(
+        &self,
+        filter: &AuditEventFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEvent>, sqlx::Error> {
+        sqlx::query_as::<_, AuditEvent>(
+            r#"
+            SELECT id, user_id, actor, event_type, outcome, ip_address, user_agent, metadata, created_at
+            FROM audit_events
+            WHERE ($1::uuid IS NULL OR user_id = $1)
+              AND ($2::text IS NULL OR actor = $2)
+              AND ($3::text IS NULL OR event_type = $3)
+              AND ($4::text IS NULL OR outcome = $4)
+              AND ($5::text IS NULL OR ip_address = $5)
+              AND ($6::timestamptz IS NULL OR created_at >= $6)
+              AND ($7::timestamptz IS NULL OR created_at < $7)
+            ORDER BY created_at DESC
+            LIMIT $8 OFFSET $9
+            "#,
+        )
+        .bind(filter.user_id)
+        .bind(&filter.actor)
+        .bind(filter.event_type.map(|t| t.as_str()))
+        .bind(filter.outcome.map(|o| o.as_str()))
+        .bind(&filter.ip_address)
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 指定日時より古い監査イベントを削除（最大 `limit` 件）
+    ///
+    /// # Returns
+    /// 削除された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_older_than(
+        &self,
+        cutoff: OffsetDateTime,
+        limit: i64,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM audit_events
+            WHERE id IN (
+                SELECT id FROM audit_events
+                WHERE created_at < $1
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(cutoff)
+        .bind(limit)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
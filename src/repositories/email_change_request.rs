@@ -0,0 +1,207 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::models::{EmailChangeRequest, OutboxPayload};
+use crate::repositories::outbox;
+
+#[derive(Clone)]
+pub struct EmailChangeRequestRepository {
+    pool: PgPool,
+}
+
+impl EmailChangeRequestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 新しいメールアドレス変更リクエストを作成し、確認・予告メールを送信キューに追加
+    ///
+    /// # Arguments
+    /// * `user_id` - 対象ユーザーのID
+    /// * `new_email` - 変更後のメールアドレス
+    /// * `confirm_token_hash` - 確認トークンのSHA256ハッシュ
+    /// * `cancel_token_hash` - 取り消しトークンのSHA256ハッシュ
+    /// * `expires_at` - 有効期限
+    /// * `emails` - 確認・予告メール（リクエストと同じトランザクションで追加）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        new_email: &str,
+        confirm_token_hash: &str,
+        cancel_token_hash: &str,
+        expires_at: OffsetDateTime,
+        emails: &[OutboxPayload],
+    ) -> Result<EmailChangeRequest, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let request = sqlx::query_as::<_, EmailChangeRequest>(
+            r#"
+            INSERT INTO email_change_requests
+                (user_id, new_email, confirm_token_hash, cancel_token_hash, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, user_id, new_email, confirm_token_hash, cancel_token_hash, expires_at,
+                      confirmed_at, cancelled_at, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(new_email)
+        .bind(confirm_token_hash)
+        .bind(cancel_token_hash)
+        .bind(expires_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for email in emails {
+            outbox::insert(&mut tx, email).await?;
+        }
+
+        tx.commit().await?;
+        Ok(request)
+    }
+
+    /// 確認トークンのハッシュでリクエストを検索
+    ///
+    /// # Note
+    /// 有効期限や確定・取り消し済みの検証は呼び出し側で行う
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_confirm_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailChangeRequest>, sqlx::Error> {
+        sqlx::query_as::<_, EmailChangeRequest>(
+            r#"
+            SELECT id, user_id, new_email, confirm_token_hash, cancel_token_hash, expires_at,
+                   confirmed_at, cancelled_at, created_at
+            FROM email_change_requests
+            WHERE confirm_token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 取り消しトークンのハッシュでリクエストを検索
+    ///
+    /// # Note
+    /// 有効期限や確定・取り消し済みの検証は呼び出し側で行う
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_cancel_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<EmailChangeRequest>, sqlx::Error> {
+        sqlx::query_as::<_, EmailChangeRequest>(
+            r#"
+            SELECT id, user_id, new_email, confirm_token_hash, cancel_token_hash, expires_at,
+                   confirmed_at, cancelled_at, created_at
+            FROM email_change_requests
+            WHERE cancel_token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// ユーザーの未完了のリクエストを全て取り消す
+    ///
+    /// # Returns
+    /// 取り消された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn cancel_pending_for_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE email_change_requests
+            SET cancelled_at = NOW()
+            WHERE user_id = $1 AND confirmed_at IS NULL AND cancelled_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 未完了のリクエストを取り消す
+    ///
+    /// # Returns
+    /// 取り消した場合は true（確定・取り消し済みの場合は false）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn cancel(&self, request_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE email_change_requests
+            SET cancelled_at = NOW()
+            WHERE id = $1 AND confirmed_at IS NULL AND cancelled_at IS NULL
+            "#,
+        )
+        .bind(request_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// リクエストを確定し、ユーザーのメールアドレスを変更
+    ///
+    /// 確定とメールアドレスの変更は同一トランザクションで行う。
+    /// 変更後のアドレスが他のユーザーに使われている場合は UNIQUE 制約違反
+    /// （`users_email_key`）となり、リクエストは確定されない
+    ///
+    /// # Returns
+    /// 変更した場合は true（確定・取り消し済み、または期限切れの場合は false）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn confirm(&self, request: &EmailChangeRequest) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let confirmed = sqlx::query(
+            r#"
+            UPDATE email_change_requests
+            SET confirmed_at = NOW()
+            WHERE id = $1 AND confirmed_at IS NULL AND cancelled_at IS NULL AND expires_at > NOW()
+            "#,
+        )
+        .bind(request.id)
+        .execute(&mut *tx)
+        .await?;
+        if confirmed.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET email = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(request.user_id)
+        .bind(&request.new_email)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// 期限切れのリクエストを削除
+    ///
+    /// # Returns
+    /// 削除された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_expired(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM email_change_requests
+            WHERE expires_at < NOW()
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
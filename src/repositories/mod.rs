@@ -1,9 +1,25 @@
+pub mod account_lock_token;
+pub mod audit_event;
+pub mod email_change_request;
+pub mod outbox;
 pub mod password_reset_token;
+pub mod scheduled_job;
 pub mod user;
 pub mod user_2fa;
+pub mod user_notification_settings;
+pub mod user_profile;
 pub mod user_social_account;
+pub mod webhook;
 
+pub use account_lock_token::AccountLockTokenRepository;
+pub use audit_event::{AuditEventFilter, AuditEventRepository};
+pub use email_change_request::EmailChangeRequestRepository;
+pub use outbox::OutboxRepository;
 pub use password_reset_token::PasswordResetTokenRepository;
+pub use scheduled_job::ScheduledJobRepository;
 pub use user::UserRepository;
 pub use user_2fa::User2faSecretRepository;
+pub use user_notification_settings::UserNotificationSettingsRepository;
+pub use user_profile::UserProfileRepository;
 pub use user_social_account::UserSocialAccountRepository;
+pub use webhook::WebhookRepository;
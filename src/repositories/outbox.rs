@@ -0,0 +1,208 @@
+use sqlx::{PgConnection, PgPool};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::models::{OutboxMessage, OutboxPayload, OutboxStatus};
+
+/// 送信キュー（outbox）リポジトリ
+#[derive(Clone)]
+pub struct OutboxRepository {
+    pool: PgPool,
+}
+
+/// メッセージを送信キューに追加（呼び出し側のトランザクション内で実行）
+///
+/// 他のリポジトリが DB の更新と同じトランザクションでメッセージを追加するために使用する
+#[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+pub(crate) async fn insert(
+    conn: &mut PgConnection,
+    payload: &OutboxPayload,
+) -> Result<Uuid, sqlx::Error> {
+    let value = serde_json::to_value(payload).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO outbox_messages (kind, payload)
+        VALUES ($1, $2)
+        RETURNING id
+        "#,
+    )
+    .bind(payload.kind())
+    .bind(value)
+    .fetch_one(conn)
+    .await
+}
+
+impl OutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// メッセージを送信キューに追加
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn enqueue(&self, payload: &OutboxPayload) -> Result<Uuid, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        insert(&mut conn, payload).await
+    }
+
+    /// 配信時刻を過ぎたメッセージを取得し、処理中としてロック
+    ///
+    /// 複数のワーカー（レプリカ）が同じメッセージを取得しないよう `FOR UPDATE SKIP LOCKED` を使う。
+    /// `lease_secs` 以内に結果が記録されない場合（ワーカーの停止など）は再び取得対象になる
+    ///
+    /// # Returns
+    /// 取得したメッセージ（`attempts` は今回の試行を含む）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn claim_due(
+        &self,
+        limit: i64,
+        lease_secs: i64,
+    ) -> Result<Vec<OutboxMessage>, sqlx::Error> {
+        sqlx::query_as::<_, OutboxMessage>(
+            r#"
+            UPDATE outbox_messages
+            SET attempts = attempts + 1,
+                locked_until = NOW() + make_interval(secs => $2),
+                updated_at = NOW()
+            WHERE id IN (
+                SELECT id
+                FROM outbox_messages
+                WHERE status = 'pending'
+                  AND next_attempt_at <= NOW()
+                  AND (locked_until IS NULL OR locked_until < NOW())
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, kind, payload, status, attempts, next_attempt_at, last_error,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_secs as f64)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 配信済みのメッセージを削除
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            DELETE FROM outbox_messages
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 配信に失敗したメッセージの再試行を予約
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn schedule_retry(
+        &self,
+        id: Uuid,
+        next_attempt_at: OffsetDateTime,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE outbox_messages
+            SET next_attempt_at = $2, last_error = $3, locked_until = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(next_attempt_at)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 再試行回数を超えたメッセージを dead にする
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn mark_dead(&self, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE outbox_messages
+            SET status = $2, last_error = $3, locked_until = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(OutboxStatus::Dead.as_str())
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// dead のメッセージを新しい順に取得
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_dead(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<OutboxMessage>, sqlx::Error> {
+        sqlx::query_as::<_, OutboxMessage>(
+            r#"
+            SELECT id, kind, payload, status, attempts, next_attempt_at, last_error,
+                   created_at, updated_at
+            FROM outbox_messages
+            WHERE status = 'dead'
+            ORDER BY updated_at DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// dead のメッセージを再試行対象に戻す（試行回数はリセット）
+    ///
+    /// # Returns
+    /// 戻した場合は true（存在しない・dead でない場合は false）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn requeue_dead(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE outbox_messages
+            SET status = $2, attempts = 0, next_attempt_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status = 'dead'
+            "#,
+        )
+        .bind(id)
+        .bind(OutboxStatus::Pending.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 指定日時より前に dead になったメッセージを削除
+    ///
+    /// # Returns
+    /// 削除された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_dead_before(&self, before: OffsetDateTime) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM outbox_messages
+            WHERE status = 'dead' AND updated_at < $1
+            "#,
+        )
+        .bind(before)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -2,7 +2,8 @@ use sqlx::PgPool;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::models::PasswordResetToken;
+use crate::models::{OutboxPayload, PasswordResetToken};
+use crate::repositories::outbox;
 
 #[derive(Clone)]
 pub struct PasswordResetTokenRepository {
@@ -14,19 +15,24 @@ impl PasswordResetTokenRepository {
         Self { pool }
     }
 
-    /// 新しいパスワードリセットトークンを作成
+    /// 新しいパスワードリセットトークンを作成し、リセットメールを送信キューに追加
     ///
     /// # Arguments
     /// * `user_id` - 対象ユーザーのID
     /// * `token_hash` - トークンのSHA256ハッシュ
     /// * `expires_at` - 有効期限
+    /// * `email` - リセットメール（トークンと同じトランザクションで追加）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create(
         &self,
         user_id: Uuid,
         token_hash: &str,
         expires_at: OffsetDateTime,
+        email: &OutboxPayload,
     ) -> Result<PasswordResetToken, sqlx::Error> {
-        sqlx::query_as::<_, PasswordResetToken>(
+        let mut tx = self.pool.begin().await?;
+
+        let token = sqlx::query_as::<_, PasswordResetToken>(
             r#"
             INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
             VALUES ($1, $2, $3)
@@ -36,14 +42,20 @@ impl PasswordResetTokenRepository {
         .bind(user_id)
         .bind(token_hash)
         .bind(expires_at)
-        .fetch_one(&self.pool)
-        .await
+        .fetch_one(&mut *tx)
+        .await?;
+
+        outbox::insert(&mut tx, email).await?;
+
+        tx.commit().await?;
+        Ok(token)
     }
 
     /// トークンハッシュでトークンを検索
     ///
     /// # Note
     /// 有効期限や使用済みフラグの検証は呼び出し側で行う
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_token_hash(
         &self,
         token_hash: &str,
@@ -63,6 +75,7 @@ impl PasswordResetTokenRepository {
     /// トークンを使用済みにマーク
     ///
     /// used_at を現在時刻に設定
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn mark_as_used(&self, id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -78,10 +91,33 @@ impl PasswordResetTokenRepository {
         Ok(())
     }
 
+    /// ユーザーの未使用トークンを全て使用済みにする
+    ///
+    /// パスワードリセット・変更後に、残っているリセットリンクを無効化する
+    ///
+    /// # Returns
+    /// 無効化された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn invalidate_all_for_user(&self, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// 期限切れトークンを削除
     ///
     /// # Returns
     /// 削除された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn delete_expired(&self) -> Result<u64, sqlx::Error> {
         let result = sqlx::query(
             r#"
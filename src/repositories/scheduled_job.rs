@@ -0,0 +1,72 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+
+/// バックグラウンドジョブの実行記録
+#[derive(Clone)]
+pub struct ScheduledJobRepository {
+    pool: PgPool,
+}
+
+impl ScheduledJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// ジョブの最終開始日時を取得（未実行の場合は None）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn last_started_at(&self, name: &str) -> Result<Option<OffsetDateTime>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT last_started_at
+            FROM scheduled_jobs
+            WHERE name = $1
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// ジョブの開始を記録
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_started(&self, name: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO scheduled_jobs (name, last_started_at)
+            VALUES ($1, NOW())
+            ON CONFLICT (name) DO UPDATE
+            SET last_started_at = NOW(), last_finished_at = NULL, last_error = NULL
+            "#,
+        )
+        .bind(name)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// ジョブの終了を記録
+    ///
+    /// # Arguments
+    /// * `error` - 失敗した場合のエラーメッセージ
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_finished(
+        &self,
+        name: &str,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE scheduled_jobs
+            SET last_finished_at = NOW(), last_error = $2
+            WHERE name = $1
+            "#,
+        )
+        .bind(name)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,28 +1,66 @@
 use sqlx::PgPool;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
-use crate::models::User;
+use crate::models::{User, UserStatus};
 
 #[derive(Clone)]
 pub struct UserRepository {
     pool: PgPool,
+    /// 読み取り専用クエリ用（リードレプリカ未設定の場合は `pool` と同じ）
+    read_pool: PgPool,
 }
 
 impl UserRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            read_pool: pool.clone(),
+            pool,
+        }
     }
 
-    /// メールアドレスでユーザーを検索
+    /// 読み取り専用クエリにリードレプリカを使う
+    pub fn with_read_pool(mut self, read_pool: PgPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
+    /// メールアドレスでユーザーを検索（大文字小文字を区別しない）
     ///
     /// # Note
+    /// email は `normalize_email` で正規化してから渡すこと。
+    /// リードレプリカを使うため、直前の書き込みが反映されていない場合がある
+    /// （一意性は UNIQUE 制約で保証する）。ログインの判定には `find_by_email_for_auth` を使うこと。
     /// DB セットアップ後は `query_as!` マクロに変更してコンパイル時SQL検証を有効にすること
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error> {
         sqlx::query_as::<_, User>(
             r#"
-            SELECT id, email, password_hash, created_at, updated_at
+            SELECT id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
             FROM users
-            WHERE email = $1
+            WHERE LOWER(email) = LOWER($1)
+            "#,
+        )
+        .bind(email)
+        .fetch_optional(&self.read_pool)
+        .await
+    }
+
+    /// 認証用にメールアドレスでユーザーを検索（プライマリを使う）
+    ///
+    /// # Security
+    /// ログインの可否はアカウントの状態（`status`・`locked_until`）とパスワードハッシュで
+    /// 判定するため、レプリケーション遅延のあるリードレプリカは使わない。
+    /// レプリカを使うと、ロック・パスワード変更の直後でも遅延の間は古い状態でログインできてしまう
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_email_for_auth(&self, email: &str) -> Result<Option<User>, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
+            FROM users
+            WHERE LOWER(email) = LOWER($1)
             "#,
         )
         .bind(email)
@@ -31,10 +69,12 @@ impl UserRepository {
     }
 
     /// ユーザーIDでユーザーを検索
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, sqlx::Error> {
         sqlx::query_as::<_, User>(
             r#"
-            SELECT id, email, password_hash, created_at, updated_at
+            SELECT id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
             FROM users
             WHERE id = $1
             "#,
@@ -44,17 +84,19 @@ impl UserRepository {
         .await
     }
 
-    /// 新しいユーザーを作成
+    /// 新しいユーザーを作成（email は正規化済みであること）
     ///
     /// # Errors
     /// - UNIQUE制約違反時: `sqlx::Error::Database` (constraint = "users_email_key")
     ///   呼び出し側で `AppError::EmailAlreadyExists` に変換すること
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create_user(&self, email: &str, password_hash: &str) -> Result<User, sqlx::Error> {
         sqlx::query_as::<_, User>(
             r#"
             INSERT INTO users (email, password_hash)
             VALUES ($1, $2)
-            RETURNING id, email, password_hash, created_at, updated_at
+            RETURNING id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
             "#,
         )
         .bind(email)
@@ -67,6 +109,7 @@ impl UserRepository {
     ///
     /// # Note
     /// password_hash はログに出力しないこと
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn update_password(
         &self,
         user_id: Uuid,
@@ -87,21 +130,423 @@ impl UserRepository {
         Ok(())
     }
 
+    /// パスワードハッシュを差し替える（再ハッシュ用）
+    ///
+    /// 現在のハッシュが `old_password_hash` と一致する場合のみ更新する
+    /// （並行したパスワード変更を上書きしない）。
+    /// ユーザー操作ではないため updated_at は変更しない
+    ///
+    /// # Returns
+    /// 更新した場合は true
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn replace_password_hash(
+        &self,
+        user_id: Uuid,
+        old_password_hash: &str,
+        new_password_hash: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET password_hash = $3
+            WHERE id = $1 AND password_hash = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(old_password_hash)
+        .bind(new_password_hash)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// ソーシャルログイン用ユーザーを作成（パスワードなし）
     ///
     /// # Note
     /// ソーシャルログインのみで登録するユーザー用
     /// 後からパスワードを設定することも可能
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create_social_user(&self, email: &str) -> Result<User, sqlx::Error> {
         sqlx::query_as::<_, User>(
             r#"
             INSERT INTO users (email, password_hash)
             VALUES ($1, NULL)
-            RETURNING id, email, password_hash, created_at, updated_at
+            RETURNING id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
             "#,
         )
         .bind(email)
         .fetch_one(&self.pool)
         .await
     }
+
+    /// アカウント削除を予約
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn schedule_deletion(
+        &self,
+        user_id: Uuid,
+        deletion_at: OffsetDateTime,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET deletion_scheduled_at = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(deletion_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// アカウント削除予約を取り消し
+    ///
+    /// # Returns
+    /// 予約を取り消した場合は true
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn cancel_deletion(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET deletion_scheduled_at = NULL, updated_at = NOW()
+            WHERE id = $1 AND deletion_scheduled_at IS NOT NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 削除予定日時を過ぎたユーザーを取得
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_due_for_deletion(&self, limit: i64) -> Result<Vec<User>, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
+            FROM users
+            WHERE deletion_scheduled_at <= NOW()
+            ORDER BY deletion_scheduled_at
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 削除予定日時を過ぎたユーザーを削除（子テーブルはカスケード削除）
+    ///
+    /// 直前に予約が取り消された場合は削除しない
+    ///
+    /// # Returns
+    /// 削除した場合は true
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_if_due(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM users
+            WHERE id = $1 AND deletion_scheduled_at <= NOW()
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 確認期限を過ぎた未確認（`pending_verification`）のユーザーを削除（子テーブルはカスケード削除）
+    ///
+    /// # Arguments
+    /// * `created_before` - この日時より前に作成されたユーザーを削除
+    /// * `limit` - 1 回に削除する最大件数
+    ///
+    /// # Returns
+    /// 削除したユーザーのID
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_unverified(
+        &self,
+        created_before: OffsetDateTime,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            DELETE FROM users
+            WHERE id IN (
+                SELECT id
+                FROM users
+                WHERE status = 'pending_verification' AND created_at < $1
+                ORDER BY created_at
+                LIMIT $2
+            )
+            RETURNING id
+            "#,
+        )
+        .bind(created_before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// アカウントをロック（`until` が NULL の場合は解除されるまで）
+    ///
+    /// 有効なアカウントと、期限切れのロックのみ対象とする（無効化済みのアカウントは変更しない）
+    ///
+    /// # Returns
+    /// ロックした場合は true（既にロック済みの場合は false）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn lock(
+        &self,
+        user_id: Uuid,
+        reason: Option<&str>,
+        until: Option<OffsetDateTime>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET status = 'locked', locked_at = NOW(), lock_reason = $2, locked_until = $3,
+                updated_at = NOW()
+            WHERE id = $1
+              AND (status = 'active' OR (status = 'locked' AND locked_until <= NOW()))
+            "#,
+        )
+        .bind(user_id)
+        .bind(reason)
+        .bind(until)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// アカウントのロックを解除
+    ///
+    /// # Returns
+    /// 解除した場合は true（ロックされていない場合は false）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn unlock(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET status = 'active', locked_at = NULL, lock_reason = NULL, locked_until = NULL,
+                updated_at = NOW()
+            WHERE id = $1 AND status = 'locked'
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// アカウントを無効化
+    ///
+    /// # Returns
+    /// 無効化した場合は true（既に無効化済みの場合は false）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn disable(&self, user_id: Uuid, reason: Option<&str>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET status = 'disabled', lock_reason = $2, locked_until = NULL, updated_at = NOW()
+            WHERE id = $1 AND status <> 'disabled'
+            "#,
+        )
+        .bind(user_id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 無効化したアカウントを有効に戻す
+    ///
+    /// # Returns
+    /// 有効化した場合は true（無効化されていない場合は false）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn enable(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET status = 'active', locked_at = NULL, lock_reason = NULL, updated_at = NOW()
+            WHERE id = $1 AND status = 'disabled'
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// パスワードを削除（パスワードリセットを強制する）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn clear_password(&self, user_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET password_hash = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// ユーザーを即時削除（子テーブルはカスケード削除）
+    ///
+    /// # Returns
+    /// 削除した場合は true
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM users
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// ユーザーをユーザーID順に一括取得（キーセットページネーション）
+    ///
+    /// # Arguments
+    /// * `after` - 前回バッチの最後のユーザーID（初回は `None`）
+    /// * `limit` - 取得件数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_batch(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<User>, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
+            FROM users
+            WHERE $1::uuid IS NULL OR id > $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// メールアドレスを置き換え（正規化の移行用）
+    ///
+    /// # Note
+    /// 読み取り後に変更された場合に上書きしないよう、旧メールアドレスが一致する場合のみ更新する
+    ///
+    /// # Returns
+    /// 更新された場合は `true`
+    ///
+    /// # Errors
+    /// - UNIQUE制約違反時: `sqlx::Error::Database` (constraint = "users_email_key")
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn replace_email(
+        &self,
+        user_id: Uuid,
+        old_email: &str,
+        new_email: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE users
+            SET email = $3, updated_at = NOW()
+            WHERE id = $1 AND email = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(old_email)
+        .bind(new_email)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// ユーザーを作成日時の新しい順に検索
+    ///
+    /// `query` はメールアドレスの部分一致（大文字小文字を区別しない）またはユーザーIDの完全一致。
+    /// `status` を指定した場合はその状態のユーザーのみ
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn search(
+        &self,
+        query: Option<&str>,
+        status: Option<UserStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<User>, sqlx::Error> {
+        sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, email, password_hash, created_at, updated_at, deletion_scheduled_at, locked_at,
+                   status, lock_reason, locked_until
+            FROM users
+            WHERE ($1::text IS NULL
+                   OR email ILIKE '%' || $2 || '%' ESCAPE '\'
+                   OR id::text = $1)
+              AND ($3::text IS NULL OR status = $3)
+            ORDER BY created_at DESC, id
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(query)
+        .bind(query.map(escape_like))
+        .bind(status.map(|s| s.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 検索条件に一致するユーザー数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn count(
+        &self,
+        query: Option<&str>,
+        status: Option<UserStatus>,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM users
+            WHERE ($1::text IS NULL
+                   OR email ILIKE '%' || $2 || '%' ESCAPE '\'
+                   OR id::text = $1)
+              AND ($3::text IS NULL OR status = $3)
+            "#,
+        )
+        .bind(query)
+        .bind(query.map(escape_like))
+        .bind(status.map(|s| s.as_str()))
+        .fetch_one(&self.pool)
+        .await
+    }
+}
+
+/// LIKE パターンのワイルドカードをエスケープ
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
@@ -1,4 +1,5 @@
 use sqlx::PgPool;
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::models::User2faSecret;
@@ -14,6 +15,7 @@ impl User2faSecretRepository {
     }
 
     /// ユーザーIDで2FAシークレットを検索
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_user_id(
         &self,
         user_id: Uuid,
@@ -35,6 +37,7 @@ impl User2faSecretRepository {
     /// # Note
     /// 作成時は enabled = false
     /// verify 成功後に enable() を呼び出す
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create(
         &self,
         user_id: Uuid,
@@ -54,6 +57,7 @@ impl User2faSecretRepository {
     }
 
     /// 2FAを有効化
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn enable(&self, user_id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -70,6 +74,7 @@ impl User2faSecretRepository {
     }
 
     /// 2FAを無効化
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn disable(&self, user_id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -86,6 +91,7 @@ impl User2faSecretRepository {
     }
 
     /// 2FAシークレットを削除
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn delete(&self, user_id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
@@ -99,4 +105,83 @@ impl User2faSecretRepository {
 
         Ok(())
     }
+
+    /// 有効化されないまま放置された 2FA シークレットを削除
+    ///
+    /// # Arguments
+    /// * `before` - この日時より前に作成・更新されたものを削除
+    ///
+    /// # Returns
+    /// 削除した件数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_stale_pending(&self, before: OffsetDateTime) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM user_2fa_secrets
+            WHERE enabled = false AND updated_at < $1
+            "#,
+        )
+        .bind(before)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// 2FAシークレットをユーザーID順に一括取得（キーセットページネーション）
+    ///
+    /// # Arguments
+    /// * `after` - 前回バッチの最後のユーザーID（初回は `None`）
+    /// * `limit` - 取得件数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_batch(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<User2faSecret>, sqlx::Error> {
+        sqlx::query_as::<_, User2faSecret>(
+            r#"
+            SELECT user_id, secret_encrypted, enabled, created_at, updated_at
+            FROM user_2fa_secrets
+            WHERE $1::uuid IS NULL OR user_id > $1
+            ORDER BY user_id
+            LIMIT $2
+            "#,
+        )
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 暗号化シークレットを置き換え（キーローテーション用）
+    ///
+    /// # Note
+    /// 読み取り後に再設定された場合に上書きしないよう、
+    /// 旧暗号文が一致する場合のみ更新する
+    ///
+    /// # Returns
+    /// 更新された場合は `true`
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn replace_secret(
+        &self,
+        user_id: Uuid,
+        old_secret_encrypted: &[u8],
+        new_secret_encrypted: &[u8],
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_2fa_secrets
+            SET secret_encrypted = $3, updated_at = NOW()
+            WHERE user_id = $1 AND secret_encrypted = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(old_secret_encrypted)
+        .bind(new_secret_encrypted)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
@@ -0,0 +1,70 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::UserNotificationSettings;
+
+#[derive(Clone)]
+pub struct UserNotificationSettingsRepository {
+    pool: PgPool,
+}
+
+impl UserNotificationSettingsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// ユーザーの通知設定を取得
+    ///
+    /// # Note
+    /// 未設定のユーザーは None（全ての通知を受信する）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<UserNotificationSettings>, sqlx::Error> {
+        sqlx::query_as::<_, UserNotificationSettings>(
+            r#"
+            SELECT user_id, new_device_login, password_changed, two_factor_changed,
+                   social_account_changed, email_changed, updated_at
+            FROM user_notification_settings
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 通知設定を保存（未設定の場合は作成）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn upsert(
+        &self,
+        settings: &UserNotificationSettings,
+    ) -> Result<UserNotificationSettings, sqlx::Error> {
+        sqlx::query_as::<_, UserNotificationSettings>(
+            r#"
+            INSERT INTO user_notification_settings
+                (user_id, new_device_login, password_changed, two_factor_changed,
+                 social_account_changed, email_changed)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE
+            SET new_device_login = EXCLUDED.new_device_login,
+                password_changed = EXCLUDED.password_changed,
+                two_factor_changed = EXCLUDED.two_factor_changed,
+                social_account_changed = EXCLUDED.social_account_changed,
+                email_changed = EXCLUDED.email_changed,
+                updated_at = NOW()
+            RETURNING user_id, new_device_login, password_changed, two_factor_changed,
+                      social_account_changed, email_changed, updated_at
+            "#,
+        )
+        .bind(settings.user_id)
+        .bind(settings.new_device_login)
+        .bind(settings.password_changed)
+        .bind(settings.two_factor_changed)
+        .bind(settings.social_account_changed)
+        .bind(settings.email_changed)
+        .fetch_one(&self.pool)
+        .await
+    }
+}
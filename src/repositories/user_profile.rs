@@ -0,0 +1,138 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::UserProfile;
+
+#[derive(Clone)]
+pub struct UserProfileRepository {
+    pool: PgPool,
+}
+
+impl UserProfileRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// ユーザーのプロフィールを取得
+    ///
+    /// # Note
+    /// 未設定のユーザーは None
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserProfile>, sqlx::Error> {
+        sqlx::query_as::<_, UserProfile>(
+            r#"
+            SELECT user_id, display_name, given_name, family_name, picture_url, locale, zoneinfo,
+                   metadata, updated_at
+            FROM user_profiles
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 行ロックを取ってプロフィールを読み込み、変更して保存（未設定の場合は作成）
+    ///
+    /// 読み込みから保存までを 1 つのトランザクションで行うため、同時に更新されても
+    /// 先の更新が失われない。`modify` がエラーを返した場合は保存しない
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn modify<E>(
+        &self,
+        user_id: Uuid,
+        modify: impl FnOnce(&mut UserProfile) -> Result<(), E>,
+    ) -> Result<UserProfile, E>
+    where
+        E: From<sqlx::Error>,
+    {
+        let mut tx = self.pool.begin().await?;
+
+        // 未設定の場合も行ロックを取れるよう、先に空の行を作成する
+        sqlx::query(
+            r#"
+            INSERT INTO user_profiles (user_id)
+            VALUES ($1)
+            ON CONFLICT (user_id) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let mut profile = sqlx::query_as::<_, UserProfile>(
+            r#"
+            SELECT user_id, display_name, given_name, family_name, picture_url, locale, zoneinfo,
+                   metadata, updated_at
+            FROM user_profiles
+            WHERE user_id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        modify(&mut profile)?;
+
+        let profile = sqlx::query_as::<_, UserProfile>(
+            r#"
+            UPDATE user_profiles
+            SET display_name = $2,
+                given_name = $3,
+                family_name = $4,
+                picture_url = $5,
+                locale = $6,
+                zoneinfo = $7,
+                metadata = $8,
+                updated_at = NOW()
+            WHERE user_id = $1
+            RETURNING user_id, display_name, given_name, family_name, picture_url, locale,
+                      zoneinfo, metadata, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(&profile.display_name)
+        .bind(&profile.given_name)
+        .bind(&profile.family_name)
+        .bind(&profile.picture_url)
+        .bind(&profile.locale)
+        .bind(&profile.zoneinfo)
+        .bind(&profile.metadata)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(profile)
+    }
+
+    /// 未設定の項目のみ補完（ソーシャルログインのプロバイダー情報から）
+    ///
+    /// ユーザーが設定済みの項目とメタデータは変更しない
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn fill_missing(&self, profile: &UserProfile) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_profiles
+                (user_id, display_name, given_name, family_name, picture_url, locale)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE
+            SET display_name = COALESCE(user_profiles.display_name, EXCLUDED.display_name),
+                given_name = COALESCE(user_profiles.given_name, EXCLUDED.given_name),
+                family_name = COALESCE(user_profiles.family_name, EXCLUDED.family_name),
+                picture_url = COALESCE(user_profiles.picture_url, EXCLUDED.picture_url),
+                locale = COALESCE(user_profiles.locale, EXCLUDED.locale),
+                updated_at = NOW()
+            "#,
+        )
+        .bind(profile.user_id)
+        .bind(&profile.display_name)
+        .bind(&profile.given_name)
+        .bind(&profile.family_name)
+        .bind(&profile.picture_url)
+        .bind(&profile.locale)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
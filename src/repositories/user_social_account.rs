@@ -16,7 +16,12 @@ impl UserSocialAccountRepository {
     /// プロバイダとプロバイダIDでソーシャルアカウントを検索
     ///
     /// # Note
-    /// ソーシャルログイン時に既存ユーザーを特定するために使用
+    /// ソーシャルログイン時に既存ユーザーを特定するために使用。
+    ///
+    /// # Security
+    /// 結果でユーザー作成・紐付けを判断するため、リードレプリカではなくプライマリから読む
+    /// （レプリケーション遅延で直前の紐付けが見えないと、重複して作成しようとする）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_provider_and_id(
         &self,
         provider: &str,
@@ -36,6 +41,7 @@ impl UserSocialAccountRepository {
     }
 
     /// ユーザーIDに紐付くソーシャルアカウント一覧を取得
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_user_id(
         &self,
         user_id: Uuid,
@@ -56,6 +62,7 @@ impl UserSocialAccountRepository {
     ///
     /// # Errors
     /// - UNIQUE制約違反時: 同一プロバイダ・プロバイダIDの組み合わせが既に存在
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create(
         &self,
         user_id: Uuid,
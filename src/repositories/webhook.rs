@@ -0,0 +1,319 @@
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::models::{
+    OutboxPayload, WebhookDelivery, WebhookDeliveryStatus, WebhookEvent, WebhookSubscription,
+};
+use crate::repositories::outbox;
+
+/// Webhook の購読設定・配信ログのリポジトリ
+#[derive(Clone)]
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 購読設定を作成
+    ///
+    /// # Arguments
+    /// * `event_types` - 通知するイベント種別
+    /// * `secret_encrypted` - 暗号化済みの署名用シークレット
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create_subscription(
+        &self,
+        url: &str,
+        event_types: &[String],
+        secret_encrypted: &[u8],
+        description: Option<&str>,
+    ) -> Result<WebhookSubscription, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            INSERT INTO webhook_subscriptions (url, event_types, secret_encrypted, description)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, url, event_types, secret_encrypted, description, enabled,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(url)
+        .bind(event_types)
+        .bind(secret_encrypted)
+        .bind(description)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// 購読設定を作成日時の新しい順に取得
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            SELECT id, url, event_types, secret_encrypted, description, enabled,
+                   created_at, updated_at
+            FROM webhook_subscriptions
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// IDで購読設定を検索
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_subscription(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<WebhookSubscription>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            SELECT id, url, event_types, secret_encrypted, description, enabled,
+                   created_at, updated_at
+            FROM webhook_subscriptions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 購読設定の有効・無効を切り替え
+    ///
+    /// # Returns
+    /// 更新後の購読設定（存在しない場合は None）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn set_enabled(
+        &self,
+        id: Uuid,
+        enabled: bool,
+    ) -> Result<Option<WebhookSubscription>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            UPDATE webhook_subscriptions
+            SET enabled = $2, updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, url, event_types, secret_encrypted, description, enabled,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(enabled)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 購読設定を削除（配信ログはカスケード削除）
+    ///
+    /// # Returns
+    /// 削除した場合は true
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_subscription(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_subscriptions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// イベントを購読している有効な購読設定ごとに配信ログを作成し、送信キューに追加
+    ///
+    /// # Returns
+    /// 作成した配信ログのID
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create_deliveries(&self, event: &WebhookEvent) -> Result<Vec<Uuid>, sqlx::Error> {
+        let payload = serde_json::to_value(event).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        let mut tx = self.pool.begin().await?;
+
+        let delivery_ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload)
+            SELECT id, $1, $2, $3
+            FROM webhook_subscriptions
+            WHERE enabled AND $2 = ANY(event_types)
+            RETURNING id
+            "#,
+        )
+        .bind(event.id)
+        .bind(event.event_type.as_str())
+        .bind(payload)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for &delivery_id in &delivery_ids {
+            outbox::insert(&mut tx, &OutboxPayload::Webhook { delivery_id }).await?;
+        }
+
+        tx.commit().await?;
+        Ok(delivery_ids)
+    }
+
+    /// 配信ログを同じイベント・購読設定で複製し、送信キューに追加（再送）
+    ///
+    /// # Returns
+    /// 作成した配信ログ（元の配信ログが存在しない場合は None）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn replay_delivery(&self, id: Uuid) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload)
+            SELECT subscription_id, event_id, event_type, payload
+            FROM webhook_deliveries
+            WHERE id = $1
+            RETURNING id, subscription_id, event_id, event_type, payload, status, attempts,
+                      response_status, last_error, delivered_at, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(delivery) = &delivery {
+            outbox::insert(
+                &mut tx,
+                &OutboxPayload::Webhook {
+                    delivery_id: delivery.id,
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(delivery)
+    }
+
+    /// IDで配信ログを検索
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_delivery(&self, id: Uuid) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, subscription_id, event_id, event_type, payload, status, attempts,
+                   response_status, last_error, delivered_at, created_at, updated_at
+            FROM webhook_deliveries
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 配信ログを新しい順に取得
+    ///
+    /// # Arguments
+    /// * `subscription_id` - 購読設定で絞り込む（None の場合は全件）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_deliveries(
+        &self,
+        subscription_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, subscription_id, event_id, event_type, payload, status, attempts,
+                   response_status, last_error, delivered_at, created_at, updated_at
+            FROM webhook_deliveries
+            WHERE ($1::uuid IS NULL OR subscription_id = $1)
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 配信の試行結果を記録
+    ///
+    /// # Arguments
+    /// * `response_status` - 応答の HTTP ステータス（接続エラーなどの場合は None）
+    /// * `error` - 失敗の理由（成功した場合は None）
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_attempt(
+        &self,
+        id: Uuid,
+        response_status: Option<i32>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let status = if error.is_none() {
+            WebhookDeliveryStatus::Succeeded
+        } else {
+            WebhookDeliveryStatus::Pending
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2,
+                attempts = attempts + 1,
+                response_status = $3,
+                last_error = $4,
+                delivered_at = CASE WHEN $4::text IS NULL THEN NOW() ELSE delivered_at END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(status.as_str())
+        .bind(response_status)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 再試行回数を超えた配信を失敗にする
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn mark_failed(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, updated_at = NOW()
+            WHERE id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(id)
+        .bind(WebhookDeliveryStatus::Failed.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 指定日時より前に作成された配信ログを削除
+    ///
+    /// # Returns
+    /// 削除された行数
+    #[tracing::instrument(skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_deliveries_before(
+        &self,
+        before: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_deliveries
+            WHERE created_at < $1 AND status <> 'pending'
+            "#,
+        )
+        .bind(before)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
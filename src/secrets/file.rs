@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use super::{SecretError, SecretFuture, SecretProvider};
+
+/// ファイル参照用の環境変数サフィックス
+const FILE_SUFFIX: &str = "_FILE";
+
+/// ファイルから読み込めるキー（シークレットを受け付ける設定とプロバイダーの認証情報）
+///
+/// `SSL_CERT_FILE` など、他のツールが使う `*_FILE` を読み込まないよう限定する
+const SECRET_KEYS: [&str; 13] = [
+    "DATABASE_URL",
+    "DATABASE_READ_URL",
+    "SMTP_USERNAME",
+    "SMTP_PASSWORD",
+    "ADMIN_API_KEY",
+    "ENCRYPTION_KEY",
+    "ENCRYPTION_KEYS",
+    "OAUTH_STATE_SECRET",
+    "OAUTH_STATE_SECRETS",
+    "GOOGLE_CLIENT_SECRET",
+    "GITHUB_CLIENT_SECRET",
+    "SECRETS_KEYSTORE_KEY",
+    "VAULT_TOKEN",
+];
+
+/// ファイルシークレットプロバイダー
+///
+/// `FOO_FILE=/run/secrets/foo` のような環境変数を見つけ、
+/// ファイルの内容を `FOO` の値として返す（末尾の改行は除去）。
+/// Docker / Kubernetes secrets のマウントに対応する。
+/// 対象はシークレットを受け付けるキー（[`SECRET_KEYS`]）のみ。
+pub struct FileSecretProvider {
+    /// (環境変数名, ファイルパス)
+    files: Vec<(String, String)>,
+}
+
+impl FileSecretProvider {
+    /// 環境変数からシークレットの `*_FILE` を収集して作成
+    pub fn from_env(env: &HashMap<String, String>) -> Self {
+        let files = SECRET_KEYS
+            .iter()
+            .filter_map(|name| {
+                let path = env.get(&format!("{}{}", name, FILE_SUFFIX))?;
+                Some((name.to_string(), path.clone()))
+            })
+            .collect();
+
+        Self { files }
+    }
+}
+
+impl SecretProvider for FileSecretProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn load(&self) -> SecretFuture<'_> {
+        Box::pin(async move {
+            let mut values = HashMap::with_capacity(self.files.len());
+
+            for (name, path) in &self.files {
+                let content =
+                    tokio::fs::read_to_string(path)
+                        .await
+                        .map_err(|source| SecretError::Io {
+                            path: path.clone(),
+                            source,
+                        })?;
+                values.insert(
+                    name.clone(),
+                    content.trim_end_matches(['\r', '\n']).to_string(),
+                );
+            }
+
+            Ok(values)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_file_secret() {
+        let path = std::env::temp_dir().join(format!("oxgate-secret-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "s3cret\n").unwrap();
+
+        let env = HashMap::from([
+            (
+                "GOOGLE_CLIENT_SECRET_FILE".to_string(),
+                path.to_string_lossy().to_string(),
+            ),
+            ("HOST".to_string(), "0.0.0.0".to_string()),
+        ]);
+        let values = FileSecretProvider::from_env(&env).load().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(values["GOOGLE_CLIENT_SECRET"], "s3cret");
+    }
+
+    #[tokio::test]
+    async fn test_unrelated_file_variables_are_ignored() {
+        // 読み込まれるとファイルが存在しないため失敗する
+        let env = HashMap::from([
+            (
+                "SSL_CERT_FILE".to_string(),
+                "/nonexistent/oxgate/cert.pem".to_string(),
+            ),
+            (
+                "GOOGLE_APPLICATION_CREDENTIALS_FILE".to_string(),
+                "/nonexistent/oxgate/credentials.json".to_string(),
+            ),
+        ]);
+        let values = FileSecretProvider::from_env(&env).load().await.unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_file() {
+        let env = HashMap::from([(
+            "DATABASE_URL_FILE".to_string(),
+            "/nonexistent/oxgate/secret".to_string(),
+        )]);
+        let result = FileSecretProvider::from_env(&env).load().await;
+        assert!(matches!(result, Err(SecretError::Io { .. })));
+    }
+}
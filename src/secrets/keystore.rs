@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+use super::{SecretError, SecretFuture, SecretProvider};
+use crate::services::keyring::Keyring;
+
+/// 暗号化ローカルキーストアプロバイダー
+///
+/// キーストアファイルは JSON オブジェクト（環境変数名 → 値）を
+/// AES-256-GCM（キーリング形式）で暗号化し、Base64 エンコードしたもの。
+///
+/// # 設定
+/// - `SECRETS_KEYSTORE_PATH` - キーストアファイルのパス
+/// - `SECRETS_KEYSTORE_KEY` - 復号キー（Base64 の32バイト、または `<id>:<base64>` のキーリング）
+pub struct KeystoreSecretProvider {
+    path: String,
+    keyring: Keyring,
+}
+
+impl KeystoreSecretProvider {
+    /// 新しい KeystoreSecretProvider を作成
+    pub fn new(path: String, keyring: Keyring) -> Self {
+        Self { path, keyring }
+    }
+
+    /// 環境変数から作成（`SECRETS_KEYSTORE_PATH` 未設定時は `None`）
+    pub fn from_env(env: &HashMap<String, String>) -> Result<Option<Self>, SecretError> {
+        let Some(path) = env.get("SECRETS_KEYSTORE_PATH") else {
+            return Ok(None);
+        };
+
+        let key = env.get("SECRETS_KEYSTORE_KEY").ok_or_else(|| {
+            SecretError::Configuration("SECRETS_KEYSTORE_KEY is required".to_string())
+        })?;
+
+        let keyring = if key.contains(':') {
+            Keyring::parse(key)
+        } else {
+            Keyring::from_single(key)
+        }
+        .map_err(|_| SecretError::Configuration("invalid SECRETS_KEYSTORE_KEY".to_string()))?;
+
+        Ok(Some(Self::new(path.clone(), keyring)))
+    }
+
+    /// 値のマップを暗号化してキーストアファイルの内容を生成
+    pub fn seal(
+        values: &HashMap<String, String>,
+        keyring: &Keyring,
+    ) -> Result<String, SecretError> {
+        let json = serde_json::to_vec(values)
+            .map_err(|e| SecretError::Keystore(format!("serialize error: {}", e)))?;
+        let encrypted = keyring
+            .encrypt(&json)
+            .map_err(|_| SecretError::Keystore("encryption error".to_string()))?;
+        Ok(STANDARD.encode(encrypted))
+    }
+
+    /// キーストアファイルの内容を復号
+    pub fn open(content: &str, keyring: &Keyring) -> Result<HashMap<String, String>, SecretError> {
+        let encrypted = STANDARD
+            .decode(content.trim())
+            .map_err(|_| SecretError::Keystore("invalid base64".to_string()))?;
+        let (json, _) = keyring
+            .decrypt(&encrypted)
+            .ok_or_else(|| SecretError::Keystore("decryption failed".to_string()))?;
+        serde_json::from_slice(&json)
+            .map_err(|e| SecretError::Keystore(format!("invalid content: {}", e)))
+    }
+}
+
+impl SecretProvider for KeystoreSecretProvider {
+    fn name(&self) -> &'static str {
+        "keystore"
+    }
+
+    fn load(&self) -> SecretFuture<'_> {
+        Box::pin(async move {
+            let content = tokio::fs::read_to_string(&self.path)
+                .await
+                .map_err(|source| SecretError::Io {
+                    path: self.path.clone(),
+                    source,
+                })?;
+            Self::open(&content, &self.keyring)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> String {
+        STANDARD.encode([7u8; 32])
+    }
+
+    #[tokio::test]
+    async fn test_load_keystore() {
+        let keyring = Keyring::from_single(&test_key()).unwrap();
+        let values = HashMap::from([("ENCRYPTION_KEY".to_string(), "value".to_string())]);
+        let content = KeystoreSecretProvider::seal(&values, &keyring).unwrap();
+
+        let path = std::env::temp_dir().join(format!("oxgate-keystore-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, content).unwrap();
+
+        let env = HashMap::from([
+            (
+                "SECRETS_KEYSTORE_PATH".to_string(),
+                path.to_string_lossy().to_string(),
+            ),
+            ("SECRETS_KEYSTORE_KEY".to_string(), test_key()),
+        ]);
+        let provider = KeystoreSecretProvider::from_env(&env).unwrap().unwrap();
+        let loaded = provider.load().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, values);
+    }
+
+    #[test]
+    fn test_open_with_wrong_key() {
+        let keyring = Keyring::from_single(&test_key()).unwrap();
+        let content = KeystoreSecretProvider::seal(&HashMap::new(), &keyring).unwrap();
+
+        let wrong = Keyring::from_single(&STANDARD.encode([8u8; 32])).unwrap();
+        let result = KeystoreSecretProvider::open(&content, &wrong);
+        assert!(matches!(result, Err(SecretError::Keystore(_))));
+    }
+
+    #[test]
+    fn test_from_env_requires_key() {
+        let env = HashMap::from([(
+            "SECRETS_KEYSTORE_PATH".to_string(),
+            "/tmp/keystore".to_string(),
+        )]);
+        let result = KeystoreSecretProvider::from_env(&env);
+        assert!(matches!(result, Err(SecretError::Configuration(_))));
+
+        assert!(
+            KeystoreSecretProvider::from_env(&HashMap::new())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
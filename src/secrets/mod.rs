@@ -0,0 +1,138 @@
+//! シークレットプロバイダー
+//!
+//! 設定値（`DATABASE_URL`、`ENCRYPTION_KEY`、クライアントシークレットなど）を
+//! 環境変数以外のバックエンドから解決する。
+//!
+//! # 解決順序（先に見つかった値を優先）
+//! 1. 環境変数
+//! 2. `*_FILE` で指定されたファイル（Docker / Kubernetes secrets、シークレットのキーのみ）
+//! 3. 暗号化ローカルキーストア（`SECRETS_KEYSTORE_PATH`）
+//! 4. Vault 互換 HTTP KV API（`VAULT_ADDR`）
+//!
+//! # Security
+//! シークレットの値はログに出力しない（キー名のみ）
+
+pub mod file;
+pub mod keystore;
+pub mod vault;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+pub use file::FileSecretProvider;
+pub use keystore::KeystoreSecretProvider;
+pub use vault::VaultSecretProvider;
+
+/// シークレット解決エラー
+#[derive(Debug, thiserror::Error)]
+pub enum SecretError {
+    #[error("シークレットファイルの読み込みに失敗: {path}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("キーストアが不正です: {0}")]
+    Keystore(String),
+
+    #[error("Vault との通信に失敗")]
+    VaultRequest(#[from] reqwest::Error),
+
+    #[error("Vault がエラーを返しました: {0}")]
+    VaultResponse(String),
+
+    #[error("シークレットプロバイダーの設定が不正です: {0}")]
+    Configuration(String),
+}
+
+/// プロバイダーが返す非同期処理
+pub type SecretFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<HashMap<String, String>, SecretError>> + Send + 'a>>;
+
+/// シークレットプロバイダー
+///
+/// 環境変数名（大文字）→ 値 のマップを返す
+pub trait SecretProvider: Send + Sync {
+    /// プロバイダー名（ログ出力用）
+    fn name(&self) -> &'static str;
+
+    /// シークレットを読み込む
+    fn load(&self) -> SecretFuture<'_>;
+}
+
+/// 環境変数とシークレットプロバイダーから設定値のマップを構築
+///
+/// プロバイダーの設定自体（`VAULT_TOKEN` など）も `*_FILE` から解決できるよう、
+/// ファイルプロバイダーを先に適用してから後続のプロバイダーを構築する
+pub async fn resolve_env(
+    env: HashMap<String, String>,
+) -> Result<HashMap<String, String>, SecretError> {
+    let mut env = env;
+
+    let file_provider = FileSecretProvider::from_env(&env);
+    merge(&mut env, file_provider.name(), file_provider.load().await?);
+
+    let mut providers: Vec<Box<dyn SecretProvider>> = Vec::new();
+    if let Some(provider) = KeystoreSecretProvider::from_env(&env)? {
+        providers.push(Box::new(provider));
+    }
+    if let Some(provider) = VaultSecretProvider::from_env(&env)? {
+        providers.push(Box::new(provider));
+    }
+
+    for provider in &providers {
+        let values = provider.load().await?;
+        merge(&mut env, provider.name(), values);
+    }
+
+    Ok(env)
+}
+
+/// 未設定のキーのみ追加（既存の値を優先）
+fn merge(env: &mut HashMap<String, String>, provider: &str, values: HashMap<String, String>) {
+    for (key, value) in values {
+        let key = key.to_ascii_uppercase();
+        if env.contains_key(&key) {
+            tracing::debug!(provider = %provider, key = %key, "既に設定済みのためスキップ");
+            continue;
+        }
+        tracing::debug!(provider = %provider, key = %key, "シークレットを解決");
+        env.insert(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_keeps_existing_values() {
+        let mut env = HashMap::from([("DATABASE_URL".to_string(), "from-env".to_string())]);
+        let values = HashMap::from([
+            ("database_url".to_string(), "from-provider".to_string()),
+            ("encryption_key".to_string(), "key".to_string()),
+        ]);
+
+        merge(&mut env, "test", values);
+
+        assert_eq!(env["DATABASE_URL"], "from-env");
+        assert_eq!(env["ENCRYPTION_KEY"], "key");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_env_reads_file_secrets() {
+        let path = std::env::temp_dir().join(format!("oxgate-secret-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "postgres://from-file\n").unwrap();
+
+        let env = HashMap::from([(
+            "DATABASE_URL_FILE".to_string(),
+            path.to_string_lossy().to_string(),
+        )]);
+        let resolved = resolve_env(env).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(resolved["DATABASE_URL"], "postgres://from-file");
+    }
+}
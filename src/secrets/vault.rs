@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+
+use super::{SecretError, SecretFuture, SecretProvider};
+
+const DEFAULT_KV_MOUNT: &str = "secret";
+
+/// Vault KV v2 のレスポンス
+#[derive(Debug, Deserialize)]
+struct KvV2Response {
+    data: KvV2Data,
+}
+
+#[derive(Debug, Deserialize)]
+struct KvV2Data {
+    data: HashMap<String, serde_json::Value>,
+}
+
+/// Vault 互換 HTTP KV (v2) シークレットプロバイダー
+///
+/// `GET {VAULT_ADDR}/v1/{mount}/data/{path}` の `data.data` を読み込む。
+///
+/// # 設定
+/// - `VAULT_ADDR` - Vault のアドレス（例: `https://vault.example.com:8200`）
+/// - `VAULT_TOKEN` - アクセストークン（`VAULT_TOKEN_FILE` でも指定可）
+/// - `VAULT_KV_MOUNT` - KV エンジンのマウント名（デフォルト: `secret`）
+/// - `VAULT_KV_PATH` - シークレットのパス（例: `oxgate`）
+/// - `VAULT_NAMESPACE` - Vault Enterprise の名前空間（オプション）
+///
+/// # Security
+/// トークンはログに出力しない
+pub struct VaultSecretProvider {
+    client: reqwest::Client,
+    url: String,
+    token: SecretString,
+    namespace: Option<String>,
+}
+
+impl VaultSecretProvider {
+    /// 新しい VaultSecretProvider を作成
+    pub fn new(
+        addr: &str,
+        token: SecretString,
+        mount: &str,
+        path: &str,
+        namespace: Option<String>,
+    ) -> Self {
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            addr.trim_end_matches('/'),
+            mount.trim_matches('/'),
+            path.trim_matches('/')
+        );
+
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            token,
+            namespace,
+        }
+    }
+
+    /// 環境変数から作成（`VAULT_ADDR` 未設定時は `None`）
+    pub fn from_env(env: &HashMap<String, String>) -> Result<Option<Self>, SecretError> {
+        let Some(addr) = env.get("VAULT_ADDR") else {
+            return Ok(None);
+        };
+
+        let token = env
+            .get("VAULT_TOKEN")
+            .ok_or_else(|| SecretError::Configuration("VAULT_TOKEN is required".to_string()))?;
+        let path = env
+            .get("VAULT_KV_PATH")
+            .ok_or_else(|| SecretError::Configuration("VAULT_KV_PATH is required".to_string()))?;
+        let mount = env
+            .get("VAULT_KV_MOUNT")
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_KV_MOUNT);
+
+        Ok(Some(Self::new(
+            addr,
+            SecretString::from(token.clone()),
+            mount,
+            path,
+            env.get("VAULT_NAMESPACE").cloned(),
+        )))
+    }
+}
+
+impl SecretProvider for VaultSecretProvider {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    fn load(&self) -> SecretFuture<'_> {
+        Box::pin(async move {
+            let mut request = self
+                .client
+                .get(&self.url)
+                .header("X-Vault-Token", self.token.expose_secret());
+            if let Some(namespace) = &self.namespace {
+                request = request.header("X-Vault-Namespace", namespace);
+            }
+
+            let response = request.send().await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                tracing::error!(status = %status, "Vault シークレット取得失敗");
+                return Err(SecretError::VaultResponse(format!("status {}", status)));
+            }
+
+            let body: KvV2Response = response.json().await?;
+
+            Ok(body
+                .data
+                .data
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_json::Value::String(s) => s,
+                        serde_json::Value::Null => return None,
+                        other => other.to_string(),
+                    };
+                    Some((key.to_ascii_uppercase(), value))
+                })
+                .collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, http::HeaderMap, http::StatusCode, routing::get};
+
+    /// テスト用の Vault スタンドイン（KV v2 のレスポンス形式のみ再現）
+    async fn spawn_stand_in() -> String {
+        async fn read_secret(headers: HeaderMap) -> Result<Json<serde_json::Value>, StatusCode> {
+            if headers.get("X-Vault-Token").and_then(|v| v.to_str().ok()) != Some("test-token") {
+                return Err(StatusCode::FORBIDDEN);
+            }
+            Ok(Json(serde_json::json!({
+                "data": {
+                    "data": {
+                        "database_url": "postgres://from-vault",
+                        "SMTP_PORT": 2525,
+                        "unused": null
+                    },
+                    "metadata": { "version": 3 }
+                }
+            })))
+        }
+
+        let app = Router::new().route("/v1/secret/data/oxgate", get(read_secret));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_load_from_stand_in() {
+        let addr = spawn_stand_in().await;
+        let env = HashMap::from([
+            ("VAULT_ADDR".to_string(), addr),
+            ("VAULT_TOKEN".to_string(), "test-token".to_string()),
+            ("VAULT_KV_PATH".to_string(), "oxgate".to_string()),
+        ]);
+
+        let provider = VaultSecretProvider::from_env(&env).unwrap().unwrap();
+        let values = provider.load().await.unwrap();
+
+        assert_eq!(values["DATABASE_URL"], "postgres://from-vault");
+        assert_eq!(values["SMTP_PORT"], "2525");
+        assert!(!values.contains_key("UNUSED"));
+    }
+
+    #[tokio::test]
+    async fn test_load_with_invalid_token() {
+        let addr = spawn_stand_in().await;
+        let provider = VaultSecretProvider::new(
+            &addr,
+            SecretString::from("wrong-token".to_string()),
+            "secret",
+            "oxgate",
+            None,
+        );
+
+        let result = provider.load().await;
+        assert!(matches!(result, Err(SecretError::VaultResponse(_))));
+    }
+
+    #[test]
+    fn test_from_env_requires_token() {
+        let env = HashMap::from([(
+            "VAULT_ADDR".to_string(),
+            "http://127.0.0.1:8200".to_string(),
+        )]);
+        let result = VaultSecretProvider::from_env(&env);
+        assert!(matches!(result, Err(SecretError::Configuration(_))));
+
+        assert!(
+            VaultSecretProvider::from_env(&HashMap::new())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
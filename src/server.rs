@@ -0,0 +1,117 @@
+//! HTTP サーバー（HTTP/1.1・HTTP/2）
+//!
+//! TLS の場合は ALPN で、平文の場合は接続の先頭（HTTP/2 prior knowledge）で
+//! プロトコルを判定する。接続元アドレスは `ConnectInfo<SocketAddr>` としてハンドラーに渡す
+
+use std::future::Future;
+use std::time::Duration;
+
+use axum::{Router, extract::ConnectInfo};
+use http::Request;
+use hyper::body::Incoming;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{conn::auto, graceful::GracefulShutdown},
+    service::TowerToHyperService,
+};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+
+/// TLS ハンドシェイクのタイムアウト
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 接続の受け付けに失敗した場合（ファイルディスクリプタ不足など）の待ち時間
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+/// 終了時に処理中の接続の完了を待つ時間の上限
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 接続を受け付けて処理する
+///
+/// `shutdown` が完了すると新しい接続の受け付けを止め、処理中のリクエストの完了を待って返る
+///
+/// # Arguments
+/// * `name` - ログに出力するリスナー名
+/// * `tls` - None の場合は平文の HTTP
+pub async fn serve(
+    name: &'static str,
+    listener: TcpListener,
+    app: Router,
+    tls: Option<TlsAcceptor>,
+    shutdown: impl Future<Output = ()>,
+) {
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let graceful = GracefulShutdown::new();
+    tokio::pin!(shutdown);
+
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            result = listener.accept() => match result {
+                Ok(connection) => connection,
+                Err(e) => {
+                    tracing::warn!(listener = name, error = %e, "接続の受け付けに失敗");
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            },
+            () = &mut shutdown => break,
+        };
+
+        let service = TowerToHyperService::new(app.clone().map_request(
+            move |mut request: Request<Incoming>| {
+                request.extensions_mut().insert(ConnectInfo(remote_addr));
+                request
+            },
+        ));
+        let builder = builder.clone();
+        let watcher = graceful.watcher();
+        let tls = tls.clone();
+
+        // TLS ハンドシェイクは受け付けループを止めないよう接続ごとのタスクで行う
+        tokio::spawn(async move {
+            let result = match tls {
+                Some(acceptor) => match handshake(&acceptor, stream).await {
+                    Ok(stream) => {
+                        let connection =
+                            builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+                        watcher.watch(connection.into_owned()).await
+                    }
+                    Err(e) => {
+                        tracing::debug!(
+                            remote_addr = %remote_addr,
+                            error = %e,
+                            "TLS ハンドシェイクに失敗"
+                        );
+                        return;
+                    }
+                },
+                None => {
+                    let connection =
+                        builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+                    watcher.watch(connection.into_owned()).await
+                }
+            };
+            if let Err(e) = result {
+                tracing::debug!(remote_addr = %remote_addr, error = %e, "接続エラー");
+            }
+        });
+    }
+
+    drop(listener);
+    if tokio::time::timeout(SHUTDOWN_TIMEOUT, graceful.shutdown())
+        .await
+        .is_err()
+    {
+        tracing::warn!(listener = name, "処理中の接続の完了待ちがタイムアウト");
+    }
+}
+
+async fn handshake(
+    acceptor: &TlsAcceptor,
+    stream: TcpStream,
+) -> std::io::Result<tokio_rustls::server::TlsStream<TcpStream>> {
+    tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "handshake timed out"))?
+}
@@ -0,0 +1,341 @@
+use serde::Serialize;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{
+    AuditEvent, AuditEventType, EmailMessage, NewAuditEvent, User, UserProfile, WebhookEvent,
+    WebhookEventType,
+};
+use crate::repositories::{User2faSecretRepository, UserRepository, UserSocialAccountRepository};
+use crate::services::hydra::HydraClient;
+use crate::services::{AuditService, OutboxService, ProfileService, WebhookService};
+
+/// 削除ジョブが 1 回に処理するユーザー数
+const PURGE_BATCH_SIZE: i64 = 100;
+
+/// エクスポートに含める監査イベントの最大件数
+const EXPORT_AUDIT_EVENT_LIMIT: i64 = 10_000;
+
+/// アカウントデータのエクスポート（GDPR データポータビリティ）
+#[derive(Debug, Serialize)]
+pub struct AccountExport {
+    #[serde(with = "time::serde::rfc3339")]
+    pub exported_at: OffsetDateTime,
+    pub profile: ExportedProfile,
+    pub user_profile: UserProfile,
+    pub social_accounts: Vec<ExportedSocialAccount>,
+    pub two_factor: ExportedTwoFactor,
+    pub consents: Vec<ExportedConsent>,
+    pub activity: Vec<ExportedAuditEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedProfile {
+    pub id: Uuid,
+    pub email: String,
+    pub has_password: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub deletion_scheduled_at: Option<OffsetDateTime>,
+    pub status: String,
+    pub lock_reason: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub locked_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub locked_until: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedSocialAccount {
+    pub provider: String,
+    pub email: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub linked_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedTwoFactor {
+    pub enabled: bool,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub configured_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedConsent {
+    pub client_id: Option<String>,
+    pub client_name: Option<String>,
+    pub granted_scopes: Vec<String>,
+    pub granted_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedAuditEvent {
+    pub event_type: String,
+    pub outcome: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub metadata: serde_json::Value,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<AuditEvent> for ExportedAuditEvent {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            event_type: event.event_type,
+            outcome: event.outcome,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            metadata: event.metadata,
+            created_at: event.created_at,
+        }
+    }
+}
+
+/// アカウント削除リクエストの結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeletionOutcome {
+    /// 猶予期間後に削除される
+    Scheduled { deletion_at: OffsetDateTime },
+    /// 即時削除された（猶予期間 0）
+    Deleted,
+}
+
+/// 削除ジョブの実行結果
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PurgeReport {
+    pub deleted: usize,
+    pub failed: usize,
+}
+
+/// アカウント管理サービス（データエクスポート・削除）
+#[derive(Clone)]
+pub struct AccountService {
+    user_repo: UserRepository,
+    user_2fa_repo: User2faSecretRepository,
+    social_account_repo: UserSocialAccountRepository,
+    hydra_client: HydraClient,
+    outbox_service: OutboxService,
+    webhook_service: WebhookService,
+    audit_service: AuditService,
+    profile_service: ProfileService,
+}
+
+impl AccountService {
+    /// 新しい AccountService を作成
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repo: UserRepository,
+        user_2fa_repo: User2faSecretRepository,
+        social_account_repo: UserSocialAccountRepository,
+        hydra_client: HydraClient,
+        outbox_service: OutboxService,
+        webhook_service: WebhookService,
+        audit_service: AuditService,
+        profile_service: ProfileService,
+    ) -> Self {
+        Self {
+            user_repo,
+            user_2fa_repo,
+            social_account_repo,
+            hydra_client,
+            outbox_service,
+            webhook_service,
+            audit_service,
+            profile_service,
+        }
+    }
+
+    /// ユーザーの保持データをエクスポート
+    ///
+    /// # Security
+    /// - パスワードハッシュ・2FAシークレットは含めない
+    pub async fn export(&self, user: &User) -> Result<AccountExport, AppError> {
+        let social_accounts = self
+            .social_account_repo
+            .find_by_user_id(user.id)
+            .await?
+            .into_iter()
+            .map(|account| ExportedSocialAccount {
+                provider: account.provider,
+                email: account.email,
+                linked_at: account.created_at,
+            })
+            .collect();
+
+        let two_factor = match self.user_2fa_repo.find_by_user_id(user.id).await? {
+            Some(secret) => ExportedTwoFactor {
+                enabled: secret.enabled,
+                configured_at: Some(secret.created_at),
+            },
+            None => ExportedTwoFactor {
+                enabled: false,
+                configured_at: None,
+            },
+        };
+
+        let consents = self
+            .hydra_client
+            .list_consent_sessions(&user.id.to_string())
+            .await?
+            .into_iter()
+            .map(|session| {
+                let client = session.consent_request.and_then(|r| r.client);
+                ExportedConsent {
+                    client_id: client.as_ref().map(|c| c.client_id.clone()),
+                    client_name: client.and_then(|c| c.client_name),
+                    granted_scopes: session.grant_scope,
+                    granted_at: session.handled_at,
+                }
+            })
+            .collect();
+
+        let user_profile = self.profile_service.profile(user.id).await?;
+
+        let activity = self
+            .audit_service
+            .recent_activity(user.id, EXPORT_AUDIT_EVENT_LIMIT)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        tracing::info!(user_id = %user.id, "アカウントデータをエクスポート");
+
+        Ok(AccountExport {
+            exported_at: OffsetDateTime::now_utc(),
+            profile: ExportedProfile {
+                id: user.id,
+                email: user.email.clone(),
+                has_password: user.password_hash.is_some(),
+                created_at: user.created_at,
+                updated_at: user.updated_at,
+                deletion_scheduled_at: user.deletion_scheduled_at,
+                status: user.status.clone(),
+                lock_reason: user.lock_reason.clone(),
+                locked_at: user.locked_at,
+                locked_until: user.locked_until,
+            },
+            user_profile,
+            social_accounts,
+            two_factor,
+            consents,
+            activity,
+        })
+    }
+
+    /// アカウント削除をリクエスト
+    ///
+    /// Hydra のログインセッションと同意を失効させた上で削除を予約する。
+    /// 猶予期間が 0 の場合は即時削除する
+    pub async fn request_deletion(
+        &self,
+        user: &User,
+        grace_period_secs: i64,
+    ) -> Result<DeletionOutcome, AppError> {
+        // 失効に失敗した場合は予約せずにエラーを返す（再試行可能）
+        self.hydra_client
+            .revoke_subject_sessions(&user.id.to_string())
+            .await?;
+
+        let deletion_at = OffsetDateTime::now_utc() + Duration::seconds(grace_period_secs);
+        self.user_repo
+            .schedule_deletion(user.id, deletion_at)
+            .await?;
+
+        if grace_period_secs == 0 && self.purge_user(user).await? {
+            return Ok(DeletionOutcome::Deleted);
+        }
+
+        tracing::info!(user_id = %user.id, deletion_at = %deletion_at, "アカウント削除を予約");
+        Ok(DeletionOutcome::Scheduled { deletion_at })
+    }
+
+    /// アカウント削除予約を取り消し
+    ///
+    /// # Returns
+    /// 予約を取り消した場合は true
+    pub async fn cancel_deletion(&self, user: &User) -> Result<bool, AppError> {
+        let cancelled = self.user_repo.cancel_deletion(user.id).await?;
+        if cancelled {
+            tracing::info!(user_id = %user.id, "アカウント削除予約を取り消し");
+        }
+        Ok(cancelled)
+    }
+
+    /// 削除予定日時を過ぎたアカウントを削除
+    pub async fn purge_due(&self) -> Result<PurgeReport, AppError> {
+        let mut report = PurgeReport::default();
+
+        for user in self
+            .user_repo
+            .find_due_for_deletion(PURGE_BATCH_SIZE)
+            .await?
+        {
+            match self.purge_user(&user).await {
+                Ok(true) => report.deleted += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::error!(user_id = %user.id, error = ?e, "アカウント削除に失敗");
+                    report.failed += 1;
+                }
+            }
+        }
+
+        if report.deleted > 0 || report.failed > 0 {
+            tracing::info!(
+                deleted = report.deleted,
+                failed = report.failed,
+                "削除予定アカウントの削除完了"
+            );
+        }
+        Ok(report)
+    }
+
+    /// アカウントを削除し、最終確認メールを送信
+    ///
+    /// Hydra の失効に失敗した場合は削除しない（トークンが残らないよう次回再試行する）
+    async fn purge_user(&self, user: &User) -> Result<bool, AppError> {
+        self.hydra_client
+            .revoke_subject_sessions(&user.id.to_string())
+            .await?;
+
+        if !self.user_repo.delete_if_due(user.id).await? {
+            return Ok(false);
+        }
+
+        tracing::info!(user_id = %user.id, "アカウントを削除");
+
+        // ユーザーの監査イベントは CASCADE で削除されるため、ユーザーに紐付けずに記録する
+        self.audit_service
+            .record(
+                NewAuditEvent::success(AuditEventType::AccountDeleted)
+                    .actor("system")
+                    .with("user_id", user.id.to_string()),
+            )
+            .await;
+        self.webhook_service
+            .emit(
+                WebhookEvent::new(WebhookEventType::UserDeleted, user.id)
+                    .email(user.email.as_str())
+                    .reason("user_request"),
+            )
+            .await;
+
+        if let Err(e) = self
+            .outbox_service
+            .enqueue(EmailMessage::AccountDeleted {
+                to: user.email.clone(),
+            })
+            .await
+        {
+            tracing::warn!(user_id = %user.id, error = ?e, "アカウント削除完了メールの送信キューへの追加に失敗");
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,145 @@
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{AuditEvent, NewAuditEvent};
+use crate::repositories::{AuditEventFilter, AuditEventRepository};
+
+/// 保持期間経過イベントの削除で 1 回に削除する件数
+const PURGE_BATCH_SIZE: i64 = 1000;
+
+/// 監査ログサービス
+#[derive(Clone)]
+pub struct AuditService {
+    repo: AuditEventRepository,
+}
+
+impl AuditService {
+    /// 新しい AuditService を作成
+    pub fn new(repo: AuditEventRepository) -> Self {
+        Self { repo }
+    }
+
+    /// 監査イベントを記録
+    ///
+    /// 記録の失敗で本来の処理を失敗させないよう、エラーはログに残して無視する
+    pub async fn record(&self, event: NewAuditEvent) {
+        if let Err(e) = self.repo.create(&event).await {
+            tracing::error!(
+                error = ?e,
+                event_type = event.event_type.as_str(),
+                outcome = event.outcome.as_str(),
+                "監査イベントの記録に失敗"
+            );
+        }
+    }
+
+    /// ユーザーの最近のアクティビティ（新しい順）
+    pub async fn recent_activity(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, AppError> {
+        Ok(self.repo.find_by_user_id(user_id, limit).await?)
+    }
+
+    /// 指定日時以降の成功したログイン（新しい順）
+    pub async fn recent_logins(
+        &self,
+        user_id: Uuid,
+        since: OffsetDateTime,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, AppError> {
+        Ok(self
+            .repo
+            .find_successful_logins(user_id, since, limit)
+            .await?)
+    }
+
+    /// 監査イベントを検索（管理者向け）
+    pub async fn search(
+        &self,
+        filter: &AuditEventFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AuditEvent>, AppError> {
+        Ok(self.repo.search(filter, limit, offset).await?)
+    }
+
+    /// 保持期間を過ぎた監査イベントを削除
+    ///
+    /// # Arguments
+    /// * `retention_days` - 保持日数（0 の場合は削除しない）
+    ///
+    /// # Returns
+    /// 削除した件数
+    pub async fn purge_expired(&self, retention_days: i64) -> Result<u64, AppError> {
+        let Some(cutoff) = retention_cutoff(OffsetDateTime::now_utc(), retention_days) else {
+            return Ok(0);
+        };
+
+        let mut deleted = 0;
+        loop {
+            let batch = self
+                .repo
+                .delete_older_than(cutoff, PURGE_BATCH_SIZE)
+                .await?;
+            deleted += batch;
+            if batch < PURGE_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+
+        if deleted > 0 {
+            tracing::info!(
+                deleted,
+                retention_days,
+                "保持期間を過ぎた監査イベントを削除"
+            );
+        }
+        Ok(deleted)
+    }
+}
+
+/// 保持期間の起点（これより古いイベントを削除する、0 日の場合は None）
+fn retention_cutoff(now: OffsetDateTime, retention_days: i64) -> Option<OffsetDateTime> {
+    (retention_days > 0).then(|| now - Duration::days(retention_days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use crate::models::{AuditEventType, AuditOutcome};
+
+    #[test]
+    fn test_retention_cutoff() {
+        let now = OffsetDateTime::now_utc();
+        assert_eq!(retention_cutoff(now, 30), Some(now - Duration::days(30)));
+        assert_eq!(retention_cutoff(now, 0), None);
+    }
+
+    #[test]
+    fn test_failure_event_records_reason() {
+        let event = NewAuditEvent::failure(AuditEventType::Login, &AppError::TotpInvalid)
+            .actor("user@example.com")
+            .with("method", "password");
+
+        assert_eq!(event.outcome, AuditOutcome::Failure);
+        assert_eq!(event.actor.as_deref(), Some("user@example.com"));
+        assert_eq!(
+            event.metadata,
+            serde_json::json!({ "reason": "totp_invalid", "method": "password" })
+        );
+    }
+
+    #[test]
+    fn test_subject_parses_user_id() {
+        let user_id = Uuid::new_v4();
+        let event = NewAuditEvent::success(AuditEventType::Logout).subject(&user_id.to_string());
+        assert_eq!(event.user_id, Some(user_id));
+
+        let event = NewAuditEvent::success(AuditEventType::Logout).subject("not-a-uuid");
+        assert_eq!(event.user_id, None);
+    }
+}
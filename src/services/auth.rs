@@ -1,41 +1,189 @@
-use argon2::password_hash::SaltString;
 use argon2::password_hash::rand_core::OsRng;
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::{PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, PasswordHash, Version};
 
+use crate::config::Config;
 use crate::error::AppError;
-use crate::models::User;
+use crate::models::{User, UserStatus};
 use crate::repositories::UserRepository;
 
-/// パスワードをargon2idでハッシュ化
-pub fn hash_password(password: &str) -> Result<String, AppError> {
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let hash = argon2
-        .hash_password(password.as_bytes(), &salt)
+/// タイミング攻撃対策のダミー検証に使うソルト
+const DUMMY_SALT: &str = "c29tZXNhbHQ";
+
+/// bcrypt（Modular Crypt Format）のプレフィックス
+const BCRYPT_PREFIXES: [&str; 4] = ["$2a$", "$2b$", "$2x$", "$2y$"];
+
+/// パスワード検証結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordVerification {
+    /// 不一致
+    Invalid,
+    /// 一致（`needs_rehash` が true の場合は現在の設定で再ハッシュすべき）
+    Valid { needs_rehash: bool },
+}
+
+/// パスワードハッシュサービス
+///
+/// 新規ハッシュは設定された Argon2id パラメータで生成する。
+/// 検証は移行用に bcrypt / scrypt / PBKDF2 のハッシュも受け付け、
+/// Argon2id 以外・パラメータが古いハッシュは再ハッシュ対象として報告する
+#[derive(Clone)]
+pub struct PasswordHashService {
+    params: Params,
+}
+
+impl PasswordHashService {
+    /// パラメータを指定して PasswordHashService を作成
+    pub fn new(params: Params) -> Self {
+        Self { params }
+    }
+
+    /// 設定から PasswordHashService を作成
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        let params = Params::new(
+            config.argon2_memory_kib,
+            config.argon2_iterations,
+            config.argon2_parallelism,
+            None,
+        )
         .map_err(|e| {
-            tracing::error!(error = ?e, "パスワードハッシュ生成エラー");
-            AppError::Internal(anyhow::anyhow!("password hash error"))
+            tracing::error!(error = %e, "Argon2 パラメータが不正");
+            AppError::Internal(anyhow::anyhow!("invalid argon2 params: {}", e))
+        })?;
+        Ok(Self::new(params))
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    /// パスワードを Argon2id でハッシュ化
+    pub fn hash(&self, password: &str) -> Result<String, AppError> {
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = self
+            .argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| {
+                tracing::error!(error = ?e, "パスワードハッシュ生成エラー");
+                AppError::Internal(anyhow::anyhow!("password hash error"))
+            })?;
+        Ok(hash.to_string())
+    }
+
+    /// パスワードを検証
+    ///
+    /// # Errors
+    /// ハッシュの形式が不正・未対応の場合
+    pub fn verify(&self, password: &str, hash: &str) -> Result<PasswordVerification, AppError> {
+        // bcrypt は PHC 形式ではないため個別に判定
+        if BCRYPT_PREFIXES
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+        {
+            let valid = bcrypt::verify(password, hash).map_err(|e| {
+                tracing::error!(error = ?e, "bcrypt ハッシュの検証エラー");
+                AppError::Internal(anyhow::anyhow!("bcrypt hash error"))
+            })?;
+            return Ok(Self::legacy_result(valid));
+        }
+
+        let parsed_hash = PasswordHash::new(hash).map_err(|e| {
+            tracing::error!(error = ?e, "パスワードハッシュのパースエラー");
+            AppError::Internal(anyhow::anyhow!("password hash parse error"))
         })?;
-    Ok(hash.to_string())
+
+        let algorithm = parsed_hash.algorithm.as_str();
+        let password = password.as_bytes();
+        match algorithm {
+            // Argon2 はハッシュに含まれるパラメータで検証する
+            "argon2id" | "argon2i" | "argon2d" => {
+                if Argon2::default()
+                    .verify_password(password, &parsed_hash)
+                    .is_err()
+                {
+                    return Ok(PasswordVerification::Invalid);
+                }
+                Ok(PasswordVerification::Valid {
+                    needs_rehash: !self.is_current(&parsed_hash),
+                })
+            }
+            "scrypt" => Ok(Self::legacy_result(
+                scrypt::Scrypt
+                    .verify_password(password, &parsed_hash)
+                    .is_ok(),
+            )),
+            "pbkdf2" | "pbkdf2-sha256" | "pbkdf2-sha512" => Ok(Self::legacy_result(
+                pbkdf2::Pbkdf2
+                    .verify_password(password, &parsed_hash)
+                    .is_ok(),
+            )),
+            other => {
+                tracing::error!(algorithm = %other, "未対応のパスワードハッシュ形式");
+                Err(AppError::Internal(anyhow::anyhow!(
+                    "unsupported password hash algorithm"
+                )))
+            }
+        }
+    }
+
+    /// ダミーのハッシュ計算を行う（タイミング攻撃対策）
+    ///
+    /// 実際の検証と同じパラメータで計算し、ユーザーの存在有無を応答時間から推測させない
+    pub fn dummy_verify(&self, password: &str) {
+        if let Ok(salt) = SaltString::from_b64(DUMMY_SALT) {
+            let _ = self.argon2().hash_password(password.as_bytes(), &salt);
+        }
+    }
+
+    /// 現在の設定（Argon2id・v19・同一パラメータ）で生成されたハッシュか
+    fn is_current(&self, parsed_hash: &PasswordHash<'_>) -> bool {
+        if parsed_hash.algorithm != argon2::ARGON2ID_IDENT
+            || parsed_hash.version != Some(Version::V0x13.into())
+        {
+            return false;
+        }
+        Params::try_from(parsed_hash).is_ok_and(|params| {
+            params.m_cost() == self.params.m_cost()
+                && params.t_cost() == self.params.t_cost()
+                && params.p_cost() == self.params.p_cost()
+        })
+    }
+
+    /// 移行元形式（bcrypt / scrypt / PBKDF2）の検証結果（一致した場合は常に再ハッシュ）
+    fn legacy_result(valid: bool) -> PasswordVerification {
+        if valid {
+            PasswordVerification::Valid { needs_rehash: true }
+        } else {
+            PasswordVerification::Invalid
+        }
+    }
 }
 
 /// 認証サービス
 #[derive(Clone)]
 pub struct AuthService {
     user_repo: UserRepository,
+    hash_service: PasswordHashService,
 }
 
 impl AuthService {
     /// 新しい AuthService を作成
-    pub fn new(user_repo: UserRepository) -> Self {
-        Self { user_repo }
+    pub fn new(user_repo: UserRepository, hash_service: PasswordHashService) -> Self {
+        Self {
+            user_repo,
+            hash_service,
+        }
     }
 
     /// ユーザー認証を実行
     ///
     /// タイミング攻撃対策: ユーザーが存在しない場合もダミーのパスワード検証を実行
+    ///
+    /// 認証成功時、ハッシュが古い形式・パラメータであれば現在の設定で再ハッシュする。
+    /// 有効でないアカウント（ロック・無効化・確認待ち）はパスワードが正しい場合のみ
+    /// その状態のエラーを返す（パスワードを知らない相手に状態を漏らさない）
     pub async fn authenticate(&self, email: &str, password: &str) -> Result<User, AppError> {
-        let user = self.user_repo.find_by_email(email).await?;
+        let user = self.user_repo.find_by_email_for_auth(email).await?;
 
         match user {
             Some(user) => {
@@ -44,56 +192,289 @@ impl AuthService {
                     Some(hash) => hash,
                     None => {
                         // タイミング攻撃対策: ダミーのパスワード検証を実行
-                        let dummy_hash = "$argon2id$v=19$m=19456,t=2,p=1$c29tZXNhbHQ$RWh6";
-                        let _ = self.verify_password(password, dummy_hash);
+                        self.hash_service.dummy_verify(password);
                         tracing::warn!(email = %email, "認証失敗: ソーシャルログインユーザー");
                         return Err(AppError::Authentication("invalid_credentials".to_string()));
                     }
                 };
 
-                if self.verify_password(password, password_hash)? {
-                    tracing::info!(email = %email, "認証成功");
-                    Ok(user)
-                } else {
-                    tracing::warn!(email = %email, "認証失敗: パスワード不一致");
-                    Err(AppError::Authentication("invalid_credentials".to_string()))
+                match self.hash_service.verify(password, password_hash)? {
+                    PasswordVerification::Valid { needs_rehash } => {
+                        Self::ensure_active(&user)?;
+                        tracing::info!(email = %email, "認証成功");
+                        if needs_rehash {
+                            self.upgrade_password_hash(&user, password_hash, password)
+                                .await;
+                        }
+                        Ok(user)
+                    }
+                    PasswordVerification::Invalid => {
+                        tracing::warn!(email = %email, "認証失敗: パスワード不一致");
+                        Err(AppError::Authentication("invalid_credentials".to_string()))
+                    }
                 }
             }
             None => {
                 // タイミング攻撃対策: ユーザーが存在しない場合もダミーのパスワード検証を実行
                 // これにより、ユーザーの存在有無を応答時間から推測できなくなる
-                let dummy_hash = "$argon2id$v=19$m=19456,t=2,p=1$c29tZXNhbHQ$RWh6";
-                let _ = self.verify_password(password, dummy_hash);
+                self.hash_service.dummy_verify(password);
                 tracing::warn!(email = %email, "認証失敗: ユーザー不在");
                 Err(AppError::Authentication("invalid_credentials".to_string()))
             }
         }
     }
 
-    /// パスワードを検証
-    fn verify_password(&self, password: &str, hash: &str) -> Result<bool, AppError> {
-        let parsed_hash = PasswordHash::new(hash).map_err(|e| {
-            tracing::error!(error = ?e, "パスワードハッシュのパースエラー");
-            AppError::Internal(anyhow::anyhow!("password hash parse error"))
-        })?;
+    /// ログインを許可できるユーザーか確認
+    ///
+    /// パスワード・ソーシャルログインなど全てのログイン経路で呼び出すこと
+    pub fn ensure_can_login(user: &User) -> Result<(), AppError> {
+        Self::ensure_active(user)?;
+        if user.deletion_scheduled_at.is_some() {
+            tracing::warn!(user_id = %user.id, "ログイン拒否: 削除予定アカウント");
+            return Err(AppError::AccountPendingDeletion);
+        }
+        Ok(())
+    }
 
-        Ok(Argon2::default()
-            .verify_password(password.as_bytes(), &parsed_hash)
-            .is_ok())
+    /// アカウント状態が有効か確認
+    pub fn ensure_active(user: &User) -> Result<(), AppError> {
+        let error = match user.current_status() {
+            UserStatus::Active => return Ok(()),
+            UserStatus::Locked => AppError::AccountLocked,
+            UserStatus::Disabled => AppError::AccountDisabled,
+            UserStatus::PendingVerification => AppError::AccountNotVerified,
+        };
+        tracing::warn!(user_id = %user.id, status = %user.status, "ログイン拒否: 有効でないアカウント");
+        Err(error)
+    }
+
+    /// パスワードハッシュを現在の設定で再ハッシュ
+    ///
+    /// 失敗してもログインは継続する（次回ログイン時に再試行される）
+    async fn upgrade_password_hash(&self, user: &User, old_hash: &str, password: &str) {
+        let new_hash = match self.hash_service.hash(password) {
+            Ok(hash) => hash,
+            Err(_) => return,
+        };
+
+        match self
+            .user_repo
+            .replace_password_hash(user.id, old_hash, &new_hash)
+            .await
+        {
+            Ok(true) => tracing::info!(user_id = %user.id, "パスワードハッシュを更新"),
+            // 並行してパスワードが変更された場合は何もしない
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!(user_id = %user.id, error = ?e, "パスワードハッシュの更新に失敗")
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    /// テスト用の軽量パラメータ
+    fn test_service(m_cost: u32, t_cost: u32) -> PasswordHashService {
+        PasswordHashService::new(Params::new(m_cost, t_cost, 1, None).unwrap())
+    }
+
     /// パスワード検証ロジックのユニットテスト
     /// AuthService のインスタンス化には PgPool が必要なため、
-    /// argon2 を直接テスト
+    /// PasswordHashService を直接テスト
     #[test]
     fn test_verify_password_logic() {
-        // argon2 のパスワード検証ロジックをテスト
         // 無効なハッシュ形式でエラーハンドリングを確認
-        let invalid_hash = "invalid_hash_format";
-        let parsed = argon2::PasswordHash::new(invalid_hash);
-        assert!(parsed.is_err());
+        let service = test_service(64, 1);
+        assert!(service.verify("password", "invalid_hash_format").is_err());
+    }
+
+    #[test]
+    fn test_hash_and_verify_with_current_params() {
+        let service = test_service(64, 1);
+        let hash = service.hash("correct-password").unwrap();
+
+        assert!(hash.starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+        assert_eq!(
+            service.verify("correct-password", &hash).unwrap(),
+            PasswordVerification::Valid {
+                needs_rehash: false
+            }
+        );
+        assert_eq!(
+            service.verify("wrong-password", &hash).unwrap(),
+            PasswordVerification::Invalid
+        );
+    }
+
+    #[test]
+    fn test_outdated_params_need_rehash() {
+        let hash = test_service(64, 1).hash("correct-password").unwrap();
+
+        // パラメータが変わっても古いハッシュで検証でき、再ハッシュ対象になる
+        assert_eq!(
+            test_service(128, 2)
+                .verify("correct-password", &hash)
+                .unwrap(),
+            PasswordVerification::Valid { needs_rehash: true }
+        );
+    }
+
+    #[test]
+    fn test_argon2i_needs_rehash() {
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = Argon2::new(
+            Algorithm::Argon2i,
+            Version::V0x13,
+            Params::new(64, 1, 1, None).unwrap(),
+        )
+        .hash_password(b"correct-password", &salt)
+        .unwrap()
+        .to_string();
+
+        assert_eq!(
+            test_service(64, 1)
+                .verify("correct-password", &hash)
+                .unwrap(),
+            PasswordVerification::Valid { needs_rehash: true }
+        );
+    }
+
+    #[test]
+    fn test_verify_legacy_bcrypt() {
+        let hash = bcrypt::hash("correct-password", 4).unwrap();
+        let service = test_service(64, 1);
+
+        assert_eq!(
+            service.verify("correct-password", &hash).unwrap(),
+            PasswordVerification::Valid { needs_rehash: true }
+        );
+        assert_eq!(
+            service.verify("wrong-password", &hash).unwrap(),
+            PasswordVerification::Invalid
+        );
+    }
+
+    #[test]
+    fn test_verify_legacy_scrypt() {
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = scrypt::Scrypt
+            .hash_password_customized(
+                b"correct-password",
+                None,
+                None,
+                scrypt::Params::new(4, 8, 1, 32).unwrap(),
+                &salt,
+            )
+            .unwrap()
+            .to_string();
+        let service = test_service(64, 1);
+
+        assert_eq!(
+            service.verify("correct-password", &hash).unwrap(),
+            PasswordVerification::Valid { needs_rehash: true }
+        );
+        assert_eq!(
+            service.verify("wrong-password", &hash).unwrap(),
+            PasswordVerification::Invalid
+        );
+    }
+
+    #[test]
+    fn test_verify_legacy_pbkdf2() {
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = pbkdf2::Pbkdf2
+            .hash_password_customized(
+                b"correct-password",
+                Some(pbkdf2::Algorithm::PBKDF2_SHA256_IDENT),
+                None,
+                pbkdf2::Params {
+                    rounds: 1000,
+                    output_length: 32,
+                },
+                &salt,
+            )
+            .unwrap()
+            .to_string();
+        let service = test_service(64, 1);
+
+        assert!(hash.starts_with("$pbkdf2-sha256$"));
+        assert_eq!(
+            service.verify("correct-password", &hash).unwrap(),
+            PasswordVerification::Valid { needs_rehash: true }
+        );
+        assert_eq!(
+            service.verify("wrong-password", &hash).unwrap(),
+            PasswordVerification::Invalid
+        );
+    }
+
+    #[test]
+    fn test_unsupported_algorithm() {
+        let service = test_service(64, 1);
+        assert!(service.verify("password", "$md5$c29tZXNhbHQ$RWh6").is_err());
+    }
+
+    fn user(status: &str) -> User {
+        let now = time::OffsetDateTime::now_utc();
+        User {
+            id: uuid::Uuid::new_v4(),
+            email: "user@example.com".to_string(),
+            password_hash: None,
+            created_at: now,
+            updated_at: now,
+            deletion_scheduled_at: None,
+            locked_at: None,
+            status: status.to_string(),
+            lock_reason: None,
+            locked_until: None,
+        }
+    }
+
+    #[test]
+    fn test_ensure_can_login_by_status() {
+        assert!(AuthService::ensure_can_login(&user("active")).is_ok());
+        assert!(matches!(
+            AuthService::ensure_can_login(&user("locked")),
+            Err(AppError::AccountLocked)
+        ));
+        assert!(matches!(
+            AuthService::ensure_can_login(&user("disabled")),
+            Err(AppError::AccountDisabled)
+        ));
+        assert!(matches!(
+            AuthService::ensure_can_login(&user("pending_verification")),
+            Err(AppError::AccountNotVerified)
+        ));
+        // 不明な状態はログインを許可しない
+        assert!(matches!(
+            AuthService::ensure_can_login(&user("unknown")),
+            Err(AppError::AccountDisabled)
+        ));
+    }
+
+    #[test]
+    fn test_expired_lock_allows_login() {
+        let now = time::OffsetDateTime::now_utc();
+
+        let mut expired = user("locked");
+        expired.locked_until = Some(now - time::Duration::minutes(1));
+        assert!(AuthService::ensure_can_login(&expired).is_ok());
+
+        let mut active_lock = user("locked");
+        active_lock.locked_until = Some(now + time::Duration::minutes(1));
+        assert!(AuthService::ensure_can_login(&active_lock).is_err());
+    }
+
+    #[test]
+    fn test_pending_deletion_is_rejected() {
+        let mut pending = user("active");
+        pending.deletion_scheduled_at = Some(time::OffsetDateTime::now_utc());
+        assert!(matches!(
+            AuthService::ensure_can_login(&pending),
+            Err(AppError::AccountPendingDeletion)
+        ));
     }
 }
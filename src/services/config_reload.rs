@@ -0,0 +1,387 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use secrecy::{ExposeSecret, SecretBox};
+
+use crate::config::{Config, ConfigError};
+use crate::email_address::DisposableDomains;
+use crate::error::AppError;
+use crate::state::{AppState, OAuthProviders};
+
+/// 設定ファイルの変更確認間隔
+const FILE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 設定リロードエラー
+#[derive(Debug, thiserror::Error)]
+pub enum ReloadError {
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+
+    #[error("プロバイダーの初期化に失敗: {0}")]
+    Providers(#[from] AppError),
+
+    #[error("使い捨てメールドメインリストの読み込みに失敗: {0}")]
+    DisposableDomains(AppError),
+}
+
+/// 設定リロードの結果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    /// 反映したセクション
+    pub reloaded: Vec<&'static str>,
+    /// 変更されたが再起動まで反映されないセクション
+    pub requires_restart: Vec<&'static str>,
+}
+
+/// 設定ホットリロード
+///
+/// SIGHUP 受信時または設定ファイルの更新時に設定を再読み込みし、
+/// リロード可能なセクション（CORS、プロバイダー、ポリシー、メール）を
+/// `AppState` にアトミックに反映する。
+///
+/// - 検証に失敗した設定は反映しない（現在の設定を維持）
+/// - サーバー・DB・Hydra・暗号化キーの変更は再起動が必要
+#[derive(Clone)]
+pub struct ConfigReloader {
+    state: AppState,
+    path: Option<PathBuf>,
+}
+
+impl ConfigReloader {
+    /// 新しい ConfigReloader を作成
+    ///
+    /// # Arguments
+    /// * `path` - 設定ファイルのパス（`None` の場合は `OXGATE_CONFIG`、なければ環境変数のみ）
+    pub fn new(state: AppState, path: Option<PathBuf>) -> Self {
+        let path = Config::file_path(path.as_deref());
+        Self { state, path }
+    }
+
+    /// 設定を再読み込みして反映
+    pub async fn reload(&self) -> Result<ReloadReport, ReloadError> {
+        let config = Config::load_layered(self.path.as_deref()).await?;
+        self.apply(config)
+    }
+
+    /// 検証済みの設定を反映
+    pub fn apply(&self, config: Config) -> Result<ReloadReport, ReloadError> {
+        let current = self.state.config.load();
+        let report = diff_sections(&current, &config);
+
+        // プロバイダー・ドメインリストは先に構築し、失敗した場合は何も差し替えない
+        let providers = if report.reloaded.contains(&"providers") {
+            Some(OAuthProviders::from_config(&config)?)
+        } else {
+            None
+        };
+
+        // リストファイルの内容の変更も反映するため、毎回再読み込みする
+        let disposable_domains =
+            DisposableDomains::from_config(&config).map_err(ReloadError::DisposableDomains)?;
+
+        self.state.config.store(Arc::new(config));
+        if let Some(providers) = providers {
+            self.state.oauth_providers.store(Arc::new(providers));
+        }
+        self.state
+            .disposable_domains
+            .store(Arc::new(disposable_domains));
+
+        Ok(report)
+    }
+
+    /// SIGHUP と設定ファイルの変更を監視し、リロードを繰り返す
+    pub async fn run(self) {
+        #[cfg(unix)]
+        let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        {
+            Ok(signal) => Some(signal),
+            Err(e) => {
+                tracing::error!(error = ?e, "SIGHUP ハンドラーのインストールに失敗");
+                None
+            }
+        };
+
+        let mut interval = tokio::time::interval(FILE_POLL_INTERVAL);
+        let mut last_modified = self.modified_at();
+
+        loop {
+            #[cfg(unix)]
+            let hangup_received = async {
+                match hangup.as_mut() {
+                    Some(signal) => {
+                        signal.recv().await;
+                    }
+                    None => std::future::pending::<()>().await,
+                }
+            };
+
+            #[cfg(not(unix))]
+            let hangup_received = std::future::pending::<()>();
+
+            tokio::select! {
+                _ = hangup_received => {
+                    tracing::info!("SIGHUP received, reloading configuration");
+                }
+                _ = interval.tick(), if self.path.is_some() => {
+                    let modified = self.modified_at();
+                    if modified == last_modified {
+                        continue;
+                    }
+                    last_modified = modified;
+                    tracing::info!("設定ファイルの変更を検知");
+                }
+            }
+
+            match self.reload().await {
+                Ok(report) => {
+                    tracing::info!(
+                        reloaded = ?report.reloaded,
+                        requires_restart = ?report.requires_restart,
+                        "設定リロード完了"
+                    );
+                    if !report.requires_restart.is_empty() {
+                        tracing::warn!(
+                            sections = ?report.requires_restart,
+                            "再起動するまで反映されない設定変更があります"
+                        );
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "設定リロードを拒否（現在の設定を維持）");
+                }
+            }
+        }
+    }
+
+    /// 設定ファイルの更新時刻（シンボリックリンクの差し替えにも追従）
+    fn modified_at(&self) -> Option<SystemTime> {
+        let path = self.path.as_ref()?;
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+}
+
+/// 変更されたセクションを判定
+fn diff_sections(old: &Config, new: &Config) -> ReloadReport {
+    let mut report = ReloadReport::default();
+
+    // リロード可能なセクション
+    if old.allowed_origins != new.allowed_origins {
+        report.reloaded.push("cors");
+    }
+    if old.trusted_proxies != new.trusted_proxies {
+        report.reloaded.push("trusted_proxies");
+    }
+    if old.google_client_id != new.google_client_id
+        || !secret_eq(&old.google_client_secret, &new.google_client_secret)
+        || old.google_redirect_uri != new.google_redirect_uri
+        || old.github_client_id != new.github_client_id
+        || !secret_eq(&old.github_client_secret, &new.github_client_secret)
+        || old.github_redirect_uri != new.github_redirect_uri
+        || !secret_eq(&old.oauth_state_secret, &new.oauth_state_secret)
+        || !secret_eq(&old.oauth_state_secrets, &new.oauth_state_secrets)
+    {
+        report.reloaded.push("providers");
+    }
+    if old.password_reset_token_ttl_secs != new.password_reset_token_ttl_secs
+        || old.account_lock_token_ttl_secs != new.account_lock_token_ttl_secs
+        || old.email_change_token_ttl_secs != new.email_change_token_ttl_secs
+        || old.disposable_email_domains_path != new.disposable_email_domains_path
+        || old.account_deletion_grace_period_secs != new.account_deletion_grace_period_secs
+        || old.audit_retention_days != new.audit_retention_days
+        || old.unverified_account_ttl_secs != new.unverified_account_ttl_secs
+        || old.pending_2fa_ttl_secs != new.pending_2fa_ttl_secs
+        || old.password_min_length != new.password_min_length
+        || old.password_max_length != new.password_max_length
+        || old.password_require_uppercase != new.password_require_uppercase
+        || old.password_require_lowercase != new.password_require_lowercase
+        || old.password_require_digit != new.password_require_digit
+        || old.password_require_symbol != new.password_require_symbol
+        || old.password_min_strength != new.password_min_strength
+        || old.password_breach_check_url != new.password_breach_check_url
+        || old.password_breach_dataset_path != new.password_breach_dataset_path
+    {
+        report.reloaded.push("policies");
+    }
+    if old.argon2_memory_kib != new.argon2_memory_kib
+        || old.argon2_iterations != new.argon2_iterations
+        || old.argon2_parallelism != new.argon2_parallelism
+    {
+        report.reloaded.push("argon2");
+    }
+    if !secret_eq(&old.admin_api_key, &new.admin_api_key)
+        || old.admin_token_scope != new.admin_token_scope
+    {
+        report.reloaded.push("admin");
+    }
+    if old.smtp_host != new.smtp_host
+        || old.smtp_port != new.smtp_port
+        || !secret_eq(&old.smtp_username, &new.smtp_username)
+        || !secret_eq(&old.smtp_password, &new.smtp_password)
+        || old.smtp_from_address != new.smtp_from_address
+        || old.password_reset_url_base != new.password_reset_url_base
+        || old.account_lock_url_base != new.account_lock_url_base
+        || old.email_change_url_base != new.email_change_url_base
+    {
+        report.reloaded.push("email");
+    }
+
+    // 再起動が必要なセクション
+    if old.host != new.host
+        || old.port != new.port
+        || old.tls_cert_path != new.tls_cert_path
+        || old.tls_key_path != new.tls_key_path
+        || old.admin_listen_addr != new.admin_listen_addr
+        || old.admin_tls_client_ca_path != new.admin_tls_client_ca_path
+    {
+        report.requires_restart.push("server");
+    }
+    if old.database_url.expose_secret() != new.database_url.expose_secret()
+        || old.database_auto_migrate != new.database_auto_migrate
+        || old.database_max_connections != new.database_max_connections
+        || old.database_min_connections != new.database_min_connections
+        || old.database_acquire_timeout_secs != new.database_acquire_timeout_secs
+        || old.database_idle_timeout_secs != new.database_idle_timeout_secs
+        || old.database_max_lifetime_secs != new.database_max_lifetime_secs
+        || old.database_statement_timeout_ms != new.database_statement_timeout_ms
+        || old.database_ssl_mode != new.database_ssl_mode
+        || old.database_ssl_root_cert != new.database_ssl_root_cert
+        || old.database_ssl_client_cert != new.database_ssl_client_cert
+        || old.database_ssl_client_key != new.database_ssl_client_key
+        || !secret_eq(&old.database_read_url, &new.database_read_url)
+    {
+        report.requires_restart.push("database");
+    }
+    if old.hydra_admin_url != new.hydra_admin_url {
+        report.requires_restart.push("hydra");
+    }
+    if old.scheduler_enabled != new.scheduler_enabled {
+        report.requires_restart.push("scheduler");
+    }
+    if old.otel_exporter_otlp_endpoint != new.otel_exporter_otlp_endpoint
+        || old.otel_service_name != new.otel_service_name
+        || old.otel_traces_sample_ratio != new.otel_traces_sample_ratio
+    {
+        report.requires_restart.push("telemetry");
+    }
+    if old.totp_issuer != new.totp_issuer
+        || !secret_eq(&old.encryption_key, &new.encryption_key)
+        || !secret_eq(&old.encryption_keys, &new.encryption_keys)
+    {
+        report.requires_restart.push("totp");
+    }
+
+    report
+}
+
+fn secret_eq(a: &Option<SecretBox<String>>, b: &Option<SecretBox<String>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.expose_secret() == b.expose_secret(),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sqlx::postgres::PgPoolOptions;
+
+    use super::*;
+    use crate::services::hydra::HydraClient;
+
+    const KEY: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+
+    fn env() -> HashMap<String, String> {
+        HashMap::from([
+            (
+                "DATABASE_URL".to_string(),
+                "postgres://oxgate@localhost/oxgate".to_string(),
+            ),
+            (
+                "HYDRA_ADMIN_URL".to_string(),
+                "http://localhost:4445".to_string(),
+            ),
+            ("TOTP_ISSUER".to_string(), "oxgate".to_string()),
+            ("ENCRYPTION_KEY".to_string(), KEY.to_string()),
+            ("OAUTH_STATE_SECRET".to_string(), KEY.to_string()),
+        ])
+    }
+
+    fn with_github(mut env: HashMap<String, String>) -> HashMap<String, String> {
+        env.insert("GITHUB_CLIENT_ID".to_string(), "id".to_string());
+        env.insert("GITHUB_CLIENT_SECRET".to_string(), "secret".to_string());
+        env.insert(
+            "GITHUB_REDIRECT_URI".to_string(),
+            "http://localhost:8080/api/oauth/github/callback".to_string(),
+        );
+        env
+    }
+
+    fn reloader() -> ConfigReloader {
+        let pool = PgPoolOptions::new()
+            .connect_lazy("postgres://oxgate@localhost/oxgate")
+            .unwrap();
+        let config = Config::from_env_map(env()).unwrap();
+        let state = AppState::new(
+            pool,
+            None,
+            HydraClient::new("http://localhost:4445".to_string()),
+            config,
+        )
+        .unwrap();
+        ConfigReloader { state, path: None }
+    }
+
+    #[tokio::test]
+    async fn test_apply_swaps_reloadable_sections() {
+        let reloader = reloader();
+        assert!(reloader.state.oauth_providers.load().github.is_none());
+
+        let mut new_env = with_github(env());
+        new_env.insert(
+            "ALLOWED_ORIGINS".to_string(),
+            "https://example.com".to_string(),
+        );
+        new_env.insert("PORT".to_string(), "9090".to_string());
+
+        let report = reloader
+            .apply(Config::from_env_map(new_env).unwrap())
+            .unwrap();
+
+        assert_eq!(report.reloaded, vec!["cors", "providers"]);
+        assert_eq!(report.requires_restart, vec!["server"]);
+        assert!(reloader.state.oauth_providers.load().github.is_some());
+        assert!(
+            !reloader
+                .state
+                .config
+                .load()
+                .allows_origin("https://other.com")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_without_changes() {
+        let reloader = reloader();
+        let report = reloader
+            .apply(Config::from_env_map(env()).unwrap())
+            .unwrap();
+        assert_eq!(report, ReloadReport::default());
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected_before_apply() {
+        let mut new_env = env();
+        new_env.insert(
+            "ALLOWED_ORIGINS".to_string(),
+            "https://example.com/".to_string(),
+        );
+        assert!(matches!(
+            Config::from_env_map(new_env),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+}
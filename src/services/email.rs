@@ -1,28 +1,63 @@
-use std::sync::Arc;
-
-use crate::config::Config;
+use crate::config::SharedConfig;
 use crate::error::AppError;
+use crate::metrics::METRICS;
+use crate::models::EmailMessage;
 
 /// メール送信サービス（開発環境: スタブ実装）
 #[derive(Clone)]
 pub struct EmailService {
-    config: Arc<Config>,
+    config: SharedConfig,
 }
 
 impl EmailService {
     /// 新しい EmailService を作成
-    pub fn new(config: Arc<Config>) -> Self {
+    pub fn new(config: SharedConfig) -> Self {
         Self { config }
     }
 
+    /// 送信キューのメールを送信
+    ///
+    /// 送信キュー（`OutboxWorker`）から呼び出される。失敗した場合は再試行される
+    pub async fn deliver(&self, message: &EmailMessage) -> Result<(), AppError> {
+        let result = self.send(message).await;
+        METRICS.record_email(message.template(), result.is_ok());
+        result
+    }
+
+    /// テンプレートごとのメールを送信
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        match message {
+            EmailMessage::PasswordReset { to, reset_url } => {
+                self.send_password_reset_email(to, reset_url).await
+            }
+            EmailMessage::SecurityNotification {
+                to,
+                summary,
+                lock_url,
+            } => {
+                self.send_security_notification_email(to, summary, lock_url)
+                    .await
+            }
+            EmailMessage::EmailChangeConfirmation { to, confirm_url } => {
+                self.send_email_change_confirmation_email(to, confirm_url)
+                    .await
+            }
+            EmailMessage::EmailChangeNotice {
+                to,
+                new_email,
+                cancel_url,
+            } => {
+                self.send_email_change_notice_email(to, new_email, cancel_url)
+                    .await
+            }
+            EmailMessage::AccountDeleted { to } => self.send_account_deleted_email(to).await,
+        }
+    }
+
     /// パスワードリセットメールを送信（開発環境: ログ出力のみ）
     ///
     /// 本番環境では lettre クレートを使用してメール送信を実装予定
-    pub async fn send_password_reset_email(
-        &self,
-        to: &str,
-        reset_url: &str,
-    ) -> Result<(), AppError> {
+    async fn send_password_reset_email(&self, to: &str, reset_url: &str) -> Result<(), AppError> {
         // 開発モード: メール送信せずログ出力のみ
         tracing::info!(
             to = %to,
@@ -31,11 +66,12 @@ impl EmailService {
         tracing::info!("リセットURL: {}", reset_url);
 
         // 本番環境では lettre を使用してメール送信
-        // SMTP設定が存在するか確認
-        let _smtp_configured = self.config.smtp_host.is_some()
-            && self.config.smtp_username.is_some()
-            && self.config.smtp_password.is_some()
-            && self.config.smtp_from_address.is_some();
+        // SMTP設定が存在するか確認（リロードに追従するため送信時に取得）
+        let config = self.config.load();
+        let _smtp_configured = config.smtp_host.is_some()
+            && config.smtp_username.is_some()
+            && config.smtp_password.is_some()
+            && config.smtp_from_address.is_some();
 
         // TODO: 本番実装時は以下のような形式で lettre を使用
         // if smtp_configured {
@@ -45,4 +81,72 @@ impl EmailService {
 
         Ok(())
     }
+
+    /// セキュリティ通知メールを送信（開発環境: ログ出力のみ）
+    ///
+    /// 本人以外による操作に気付けるよう、操作完了後に送信する
+    ///
+    /// # Arguments
+    /// * `summary` - 通知内容（例: 「パスワードが変更されました」）
+    /// * `lock_url` - 「心当たりがない」場合のアカウントロック URL
+    async fn send_security_notification_email(
+        &self,
+        to: &str,
+        summary: &str,
+        lock_url: &str,
+    ) -> Result<(), AppError> {
+        tracing::info!(
+            to = %to,
+            summary = %summary,
+            "セキュリティ通知メール送信（開発モード）"
+        );
+        tracing::info!("アカウントロックURL: {}", lock_url);
+        Ok(())
+    }
+
+    /// メールアドレス変更の確認メールを新しいアドレスに送信（開発環境: ログ出力のみ）
+    ///
+    /// # Arguments
+    /// * `confirm_url` - 変更を確定する URL
+    async fn send_email_change_confirmation_email(
+        &self,
+        to: &str,
+        confirm_url: &str,
+    ) -> Result<(), AppError> {
+        tracing::info!(
+            to = %to,
+            "メールアドレス変更確認メール送信（開発モード）"
+        );
+        tracing::info!("確認URL: {}", confirm_url);
+        Ok(())
+    }
+
+    /// メールアドレス変更の予告メールを現在のアドレスに送信（開発環境: ログ出力のみ）
+    ///
+    /// # Arguments
+    /// * `new_email` - 変更後のメールアドレス
+    /// * `cancel_url` - 変更を取り消す URL
+    async fn send_email_change_notice_email(
+        &self,
+        to: &str,
+        new_email: &str,
+        cancel_url: &str,
+    ) -> Result<(), AppError> {
+        tracing::info!(
+            to = %to,
+            new_email = %new_email,
+            "メールアドレス変更予告メール送信（開発モード）"
+        );
+        tracing::info!("取り消しURL: {}", cancel_url);
+        Ok(())
+    }
+
+    /// アカウント削除完了メールを送信（開発環境: ログ出力のみ）
+    async fn send_account_deleted_email(&self, to: &str) -> Result<(), AppError> {
+        tracing::info!(
+            to = %to,
+            "アカウント削除完了メール送信（開発モード）"
+        );
+        Ok(())
+    }
 }
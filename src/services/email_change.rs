@@ -0,0 +1,272 @@
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::config::SharedConfig;
+use crate::error::AppError;
+use crate::models::{EmailChangeRequest, EmailMessage, User};
+use crate::repositories::{
+    EmailChangeRequestRepository, PasswordResetTokenRepository, UserRepository,
+};
+use crate::services::auth::AuthService;
+
+/// 確認・取り消しリンクの遷移先（未設定時）
+const DEFAULT_EMAIL_CHANGE_URL_BASE: &str = "http://localhost:3000/account/email";
+
+/// 確定したメールアドレス変更
+#[derive(Debug)]
+pub struct EmailChanged {
+    /// 変更前のユーザー（通知は変更前のアドレスに送る）
+    pub user: User,
+    pub new_email: String,
+}
+
+/// メールアドレス変更サービス
+///
+/// 新しいアドレス宛の確認リンクと現在のアドレス宛の取り消しリンクを送信し、
+/// 確認リンクが開かれた時点でメールアドレスを変更する
+#[derive(Clone)]
+pub struct EmailChangeService {
+    user_repo: UserRepository,
+    request_repo: EmailChangeRequestRepository,
+    reset_token_repo: PasswordResetTokenRepository,
+    config: SharedConfig,
+}
+
+impl EmailChangeService {
+    /// 新しい EmailChangeService を作成
+    pub fn new(
+        user_repo: UserRepository,
+        request_repo: EmailChangeRequestRepository,
+        reset_token_repo: PasswordResetTokenRepository,
+        config: SharedConfig,
+    ) -> Self {
+        Self {
+            user_repo,
+            request_repo,
+            reset_token_repo,
+            config,
+        }
+    }
+
+    /// メールアドレスの変更をリクエスト
+    ///
+    /// 未完了の以前のリクエストは取り消す。本人確認とメールアドレスの正規化は呼び出し側で行うこと
+    ///
+    /// # Security
+    /// - トークン（平文）はログに出力しない
+    pub async fn request_change(
+        &self,
+        user: &User,
+        new_email: &str,
+    ) -> Result<EmailChangeRequest, AppError> {
+        if new_email.eq_ignore_ascii_case(&user.email) {
+            return Err(AppError::Validation(
+                "現在と同じメールアドレスです".to_string(),
+            ));
+        }
+        if self.user_repo.find_by_email(new_email).await?.is_some() {
+            return Err(AppError::EmailAlreadyExists);
+        }
+
+        self.request_repo.cancel_pending_for_user(user.id).await?;
+
+        let confirm_token = generate_token();
+        let cancel_token = generate_token();
+        let config = self.config.load();
+        let expires_at =
+            OffsetDateTime::now_utc() + Duration::seconds(config.email_change_token_ttl_secs);
+
+        // リクエストの保存と両方のメールの送信キューへの追加を同じトランザクションで行う
+        let base = config.email_change_url_base.as_deref();
+        let emails = [
+            EmailMessage::EmailChangeConfirmation {
+                to: new_email.to_string(),
+                confirm_url: build_url(base, "confirm", &confirm_token),
+            }
+            .into(),
+            EmailMessage::EmailChangeNotice {
+                to: user.email.clone(),
+                new_email: new_email.to_string(),
+                cancel_url: build_url(base, "cancel", &cancel_token),
+            }
+            .into(),
+        ];
+        let request = self
+            .request_repo
+            .create(
+                user.id,
+                new_email,
+                &hash_token(&confirm_token),
+                &hash_token(&cancel_token),
+                expires_at,
+                &emails,
+            )
+            .await?;
+
+        tracing::info!(user_id = %user.id, "メールアドレス変更をリクエスト");
+        Ok(request)
+    }
+
+    /// 確認トークンでメールアドレスの変更を確定
+    ///
+    /// 変更後は、変更前のアドレスに送ったパスワードリセットトークンを無効化する
+    ///
+    /// # Security
+    /// - トークンはログに出力しない
+    /// - ログインできないアカウント（ロック・削除予定など）は変更しない
+    pub async fn confirm(&self, token: &str) -> Result<EmailChanged, AppError> {
+        let request = self
+            .request_repo
+            .find_by_confirm_token_hash(&hash_token(token))
+            .await?
+            .ok_or(AppError::TokenNotFound)?;
+        ensure_pending(&request)?;
+
+        let user = self
+            .user_repo
+            .find_by_id(request.user_id)
+            .await?
+            .ok_or(AppError::TokenNotFound)?;
+        AuthService::ensure_can_login(&user)?;
+
+        match self.request_repo.confirm(&request).await {
+            Ok(true) => {}
+            Ok(false) => return Err(AppError::TokenExpired),
+            // 確認待ちの間に他のユーザーが同じアドレスを登録した
+            Err(sqlx::Error::Database(db_err))
+                if db_err.constraint() == Some("users_email_key") =>
+            {
+                return Err(AppError::EmailAlreadyExists);
+            }
+            Err(e) => return Err(AppError::Database(e)),
+        }
+
+        self.reset_token_repo
+            .invalidate_all_for_user(user.id)
+            .await?;
+
+        tracing::info!(user_id = %user.id, "メールアドレスを変更");
+        Ok(EmailChanged {
+            user,
+            new_email: request.new_email,
+        })
+    }
+
+    /// 取り消しトークンでメールアドレスの変更を取り消す
+    ///
+    /// # Returns
+    /// リクエストしたユーザーのID
+    ///
+    /// # Security
+    /// - トークンはログに出力しない
+    pub async fn cancel(&self, token: &str) -> Result<Uuid, AppError> {
+        let request = self
+            .request_repo
+            .find_by_cancel_token_hash(&hash_token(token))
+            .await?
+            .ok_or(AppError::TokenNotFound)?;
+        ensure_pending(&request)?;
+
+        if !self.request_repo.cancel(request.id).await? {
+            return Err(AppError::TokenExpired);
+        }
+
+        tracing::info!(user_id = %request.user_id, "メールアドレス変更を取り消し");
+        Ok(request.user_id)
+    }
+}
+
+/// 確定・取り消し済み、または期限切れのリクエストを拒否
+fn ensure_pending(request: &EmailChangeRequest) -> Result<(), AppError> {
+    if !request.is_pending(OffsetDateTime::now_utc()) {
+        tracing::warn!(request_id = %request.id, "完了済みまたは期限切れのメールアドレス変更リクエスト");
+        return Err(AppError::TokenExpired);
+    }
+    Ok(())
+}
+
+/// 32バイトのランダムトークンを生成
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// トークンをSHA256でハッシュ化
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// 確認・取り消しURLを構築
+fn build_url(base: Option<&str>, action: &str, token: &str) -> String {
+    let base = base.unwrap_or(DEFAULT_EMAIL_CHANGE_URL_BASE);
+    format!("{}/{}?token={}", base.trim_end_matches('/'), action, token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(now: OffsetDateTime) -> EmailChangeRequest {
+        EmailChangeRequest {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            new_email: "new@example.com".to_string(),
+            confirm_token_hash: hash_token("confirm"),
+            cancel_token_hash: hash_token("cancel"),
+            expires_at: now + Duration::hours(1),
+            confirmed_at: None,
+            cancelled_at: None,
+            created_at: now,
+        }
+    }
+
+    #[test]
+    fn test_ensure_pending() {
+        let now = OffsetDateTime::now_utc();
+        assert!(ensure_pending(&request(now)).is_ok());
+
+        let expired = EmailChangeRequest {
+            expires_at: now - Duration::seconds(1),
+            ..request(now)
+        };
+        assert!(matches!(
+            ensure_pending(&expired),
+            Err(AppError::TokenExpired)
+        ));
+
+        let confirmed = EmailChangeRequest {
+            confirmed_at: Some(now),
+            ..request(now)
+        };
+        assert!(ensure_pending(&confirmed).is_err());
+
+        let cancelled = EmailChangeRequest {
+            cancelled_at: Some(now),
+            ..request(now)
+        };
+        assert!(ensure_pending(&cancelled).is_err());
+    }
+
+    #[test]
+    fn test_build_url() {
+        assert_eq!(
+            build_url(Some("https://example.com/account/email/"), "confirm", "abc"),
+            "https://example.com/account/email/confirm?token=abc"
+        );
+        assert_eq!(
+            build_url(None, "cancel", "abc"),
+            "http://localhost:3000/account/email/cancel?token=abc"
+        );
+    }
+
+    #[test]
+    fn test_confirm_and_cancel_tokens_differ() {
+        let (confirm, cancel) = (generate_token(), generate_token());
+        assert_ne!(hash_token(&confirm), hash_token(&cancel));
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::email_address::normalize_email;
+use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent, User};
+use crate::repositories::UserRepository;
+use crate::services::AuditService;
+
+/// 1バッチあたりの処理件数
+const NORMALIZATION_BATCH_SIZE: i64 = 500;
+
+/// 正規化すると他のアカウントと重複するメールアドレス
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailCollision {
+    /// 正規化後のメールアドレス
+    pub canonical: String,
+    pub user_id: Uuid,
+    /// 保存されているメールアドレス
+    pub email: String,
+    /// 正規化後のメールアドレスを使っているアカウント（特定できない場合は None）
+    pub conflicting_user_id: Option<Uuid>,
+}
+
+/// メールアドレスの正規化の結果
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EmailNormalizationReport {
+    /// 走査した件数
+    pub scanned: u64,
+    /// 正規化した（`dry_run` の場合は正規化が必要な）件数
+    pub normalized: u64,
+    /// 重複するためスキップしたもの
+    pub collisions: Vec<EmailCollision>,
+    /// 形式が不正で正規化できずスキップしたもの（ユーザーID, メールアドレス）
+    pub invalid: Vec<(Uuid, String)>,
+}
+
+/// 保存済みメールアドレスの正規化サービス
+///
+/// `normalize_email` 導入前に保存されたメールアドレスを同じ正規化で書き換える。
+/// マイグレーション（SQL の `LOWER`）では IDNA の変換や非 ASCII 文字の小文字化が
+/// Rust の正規化と一致しないため、非 ASCII を含むメールアドレスはこのサービスで移行する。
+#[derive(Clone)]
+pub struct EmailNormalizationService {
+    user_repo: UserRepository,
+    audit_service: AuditService,
+}
+
+impl EmailNormalizationService {
+    /// 新しい EmailNormalizationService を作成
+    pub fn new(user_repo: UserRepository, audit_service: AuditService) -> Self {
+        Self {
+            user_repo,
+            audit_service,
+        }
+    }
+
+    /// `users.email` を `normalize_email` の形式に書き換える
+    ///
+    /// # Arguments
+    /// * `dry_run` - 書き換えずに結果のみ報告する
+    ///
+    /// # Note
+    /// - 重複するもの・形式が不正なものは書き換えずに報告する（該当アカウントは手動で統合・変更すること）
+    /// - 読み取り後に変更された行は上書きしない
+    pub async fn normalize_all(&self, dry_run: bool) -> Result<EmailNormalizationReport, AppError> {
+        let mut report = EmailNormalizationReport::default();
+        // この実行で正規化した（dry_run の場合は正規化する予定の）メールアドレス
+        let mut claimed: HashMap<String, Uuid> = HashMap::new();
+        let mut after = None;
+
+        loop {
+            let batch = self
+                .user_repo
+                .find_batch(after, NORMALIZATION_BATCH_SIZE)
+                .await?;
+
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after = Some(last.id);
+
+            for user in &batch {
+                report.scanned += 1;
+
+                let canonical = match normalized_change(&user.email) {
+                    Ok(Some(canonical)) => canonical,
+                    Ok(None) => continue,
+                    Err(_) => {
+                        report.invalid.push((user.id, user.email.clone()));
+                        continue;
+                    }
+                };
+
+                if let Some(conflicting_user_id) =
+                    self.find_conflict(user, &canonical, &claimed).await?
+                {
+                    report
+                        .collisions
+                        .push(collision(user, canonical, Some(conflicting_user_id)));
+                    continue;
+                }
+
+                if !dry_run {
+                    match self
+                        .user_repo
+                        .replace_email(user.id, &user.email, &canonical)
+                        .await
+                    {
+                        Ok(true) => self.record(user, &canonical).await,
+                        Ok(false) => continue,
+                        Err(sqlx::Error::Database(db_err))
+                            if db_err.constraint() == Some("users_email_key") =>
+                        {
+                            report.collisions.push(collision(user, canonical, None));
+                            continue;
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                claimed.insert(canonical, user.id);
+                report.normalized += 1;
+            }
+        }
+
+        tracing::info!(
+            scanned = report.scanned,
+            normalized = report.normalized,
+            collisions = report.collisions.len(),
+            invalid = report.invalid.len(),
+            dry_run,
+            "メールアドレスの正規化完了"
+        );
+
+        Ok(report)
+    }
+
+    /// 正規化後のメールアドレスを使っている他のアカウント
+    async fn find_conflict(
+        &self,
+        user: &User,
+        canonical: &str,
+        claimed: &HashMap<String, Uuid>,
+    ) -> Result<Option<Uuid>, AppError> {
+        if let Some(&user_id) = claimed.get(canonical) {
+            return Ok(Some(user_id));
+        }
+        Ok(self
+            .user_repo
+            .find_by_email(canonical)
+            .await?
+            .map(|existing| existing.id)
+            .filter(|&id| id != user.id))
+    }
+
+    async fn record(&self, user: &User, canonical: &str) {
+        tracing::info!(user_id = %user.id, "メールアドレスを正規化");
+        self.audit_service
+            .record(
+                NewAuditEvent::success(AuditEventType::EmailChanged)
+                    .user(user.id)
+                    .actor("system")
+                    .with("reason", "normalization")
+                    .with("old_email", user.email.as_str())
+                    .with("new_email", canonical),
+            )
+            .await;
+    }
+}
+
+fn collision(user: &User, canonical: String, conflicting_user_id: Option<Uuid>) -> EmailCollision {
+    EmailCollision {
+        canonical,
+        user_id: user.id,
+        email: user.email.clone(),
+        conflicting_user_id,
+    }
+}
+
+/// 正規化後のメールアドレス（既に正規化済みの場合は None）
+fn normalized_change(email: &str) -> Result<Option<String>, AppError> {
+    let canonical = normalize_email(email)?;
+    Ok((canonical != email).then_some(canonical))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_change() {
+        assert_eq!(normalized_change("alice@example.com").unwrap(), None);
+        assert_eq!(
+            normalized_change(" Alice@Example.COM").unwrap().as_deref(),
+            Some("alice@example.com")
+        );
+        // SQL の LOWER では変換できない IDN ドメイン
+        assert_eq!(
+            normalized_change("user@BÜCHER.example").unwrap().as_deref(),
+            Some("user@xn--bcher-kva.example")
+        );
+        assert_eq!(
+            normalized_change("ÉLODIE@example.com").unwrap().as_deref(),
+            Some("élodie@example.com")
+        );
+        assert!(normalized_change("not-an-email").is_err());
+    }
+}
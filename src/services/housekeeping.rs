@@ -0,0 +1,289 @@
+use std::time::Duration as StdDuration;
+
+use sqlx::PgPool;
+use time::{Duration, OffsetDateTime};
+
+use crate::config::SharedConfig;
+use crate::error::AppError;
+use crate::models::{AuditEventType, NewAuditEvent, WebhookEvent, WebhookEventType};
+use crate::repositories::{
+    AccountLockTokenRepository, EmailChangeRequestRepository, PasswordResetTokenRepository,
+    User2faSecretRepository, UserRepository,
+};
+use crate::services::account::PurgeReport;
+use crate::services::scheduler::Scheduler;
+use crate::services::{AccountService, AuditService, OutboxService, WebhookService};
+
+/// 期限切れトークンの削除ジョブの実行間隔
+const EXPIRED_TOKENS_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// 保持期間を過ぎた監査イベントの削除ジョブの実行間隔
+const AUDIT_RETENTION_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// 削除予定アカウントの削除ジョブの実行間隔
+const ACCOUNT_PURGE_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// 未確認アカウントの削除ジョブの実行間隔
+const UNVERIFIED_ACCOUNTS_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// 放置された 2FA セットアップの削除ジョブの実行間隔
+const STALE_2FA_SETUPS_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// 配信できなかった送信キューのメッセージの削除ジョブの実行間隔
+const OUTBOX_RETENTION_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// 配信できなかった（dead の）メッセージの保持期間
+const OUTBOX_DEAD_RETENTION: Duration = Duration::days(30);
+
+/// Webhook の配信ログの削除ジョブの実行間隔
+const WEBHOOK_DELIVERY_RETENTION_INTERVAL: StdDuration = StdDuration::from_secs(3600);
+
+/// Webhook の配信ログの保持期間
+const WEBHOOK_DELIVERY_RETENTION: Duration = Duration::days(30);
+
+/// 未確認アカウントを 1 回に削除する最大件数
+const UNVERIFIED_PURGE_BATCH_SIZE: i64 = 100;
+
+/// 期限切れトークンの削除件数
+#[derive(Debug, Default)]
+pub struct ExpiredTokensReport {
+    pub password_reset_tokens: u64,
+    pub account_lock_tokens: u64,
+    pub email_change_requests: u64,
+}
+
+/// 定期的なデータ削除（期限切れトークン・監査ログ・未確認アカウントなど）
+///
+/// `Scheduler` の定期ジョブと `oxgate cleanup` から使用する
+#[derive(Clone)]
+pub struct HousekeepingService {
+    user_repo: UserRepository,
+    user_2fa_repo: User2faSecretRepository,
+    reset_token_repo: PasswordResetTokenRepository,
+    lock_token_repo: AccountLockTokenRepository,
+    email_change_repo: EmailChangeRequestRepository,
+    audit_service: AuditService,
+    account_service: AccountService,
+    outbox_service: OutboxService,
+    webhook_service: WebhookService,
+    config: SharedConfig,
+}
+
+impl HousekeepingService {
+    /// 新しい HousekeepingService を作成
+    pub fn new(
+        pool: PgPool,
+        audit_service: AuditService,
+        account_service: AccountService,
+        outbox_service: OutboxService,
+        webhook_service: WebhookService,
+        config: SharedConfig,
+    ) -> Self {
+        Self {
+            user_repo: UserRepository::new(pool.clone()),
+            user_2fa_repo: User2faSecretRepository::new(pool.clone()),
+            reset_token_repo: PasswordResetTokenRepository::new(pool.clone()),
+            lock_token_repo: AccountLockTokenRepository::new(pool.clone()),
+            email_change_repo: EmailChangeRequestRepository::new(pool),
+            audit_service,
+            account_service,
+            outbox_service,
+            webhook_service,
+            config,
+        }
+    }
+
+    /// 定期ジョブを登録
+    pub fn schedule(&self, scheduler: Scheduler) -> Scheduler {
+        let service = self.clone();
+        let expired_tokens = move || {
+            let service = service.clone();
+            async move { service.delete_expired_tokens().await.map(|_| ()) }
+        };
+        let service = self.clone();
+        let audit_retention = move || {
+            let service = service.clone();
+            async move { service.purge_audit_events().await.map(|_| ()) }
+        };
+        let service = self.clone();
+        let account_purge = move || {
+            let service = service.clone();
+            async move { service.purge_scheduled_deletions().await.map(|_| ()) }
+        };
+        let service = self.clone();
+        let unverified_accounts = move || {
+            let service = service.clone();
+            async move { service.expire_unverified_accounts().await.map(|_| ()) }
+        };
+        let service = self.clone();
+        let stale_2fa_setups = move || {
+            let service = service.clone();
+            async move { service.purge_stale_2fa_setups().await.map(|_| ()) }
+        };
+        let service = self.clone();
+        let outbox_retention = move || {
+            let service = service.clone();
+            async move { service.purge_dead_outbox_messages().await.map(|_| ()) }
+        };
+        let service = self.clone();
+        let webhook_delivery_retention = move || {
+            let service = service.clone();
+            async move { service.purge_webhook_deliveries().await.map(|_| ()) }
+        };
+
+        scheduler
+            .job("expired_tokens", EXPIRED_TOKENS_INTERVAL, expired_tokens)
+            .job("audit_retention", AUDIT_RETENTION_INTERVAL, audit_retention)
+            .job("account_purge", ACCOUNT_PURGE_INTERVAL, account_purge)
+            .job(
+                "unverified_accounts",
+                UNVERIFIED_ACCOUNTS_INTERVAL,
+                unverified_accounts,
+            )
+            .job(
+                "stale_2fa_setups",
+                STALE_2FA_SETUPS_INTERVAL,
+                stale_2fa_setups,
+            )
+            .job(
+                "outbox_retention",
+                OUTBOX_RETENTION_INTERVAL,
+                outbox_retention,
+            )
+            .job(
+                "webhook_delivery_retention",
+                WEBHOOK_DELIVERY_RETENTION_INTERVAL,
+                webhook_delivery_retention,
+            )
+    }
+
+    /// 期限切れのパスワードリセット・アカウントロック・メールアドレス変更トークンを削除
+    pub async fn delete_expired_tokens(&self) -> Result<ExpiredTokensReport, AppError> {
+        let report = ExpiredTokensReport {
+            password_reset_tokens: self.reset_token_repo.delete_expired().await?,
+            account_lock_tokens: self.lock_token_repo.delete_expired().await?,
+            email_change_requests: self.email_change_repo.delete_expired().await?,
+        };
+
+        tracing::info!(
+            password_reset_tokens = report.password_reset_tokens,
+            account_lock_tokens = report.account_lock_tokens,
+            email_change_requests = report.email_change_requests,
+            "期限切れトークンを削除"
+        );
+        Ok(report)
+    }
+
+    /// 保持期間（`AUDIT_RETENTION_DAYS`）を過ぎた監査イベントを削除
+    pub async fn purge_audit_events(&self) -> Result<u64, AppError> {
+        let retention_days = self.config.load().audit_retention_days;
+        self.audit_service.purge_expired(retention_days).await
+    }
+
+    /// 削除予定日時を過ぎたアカウントを削除
+    pub async fn purge_scheduled_deletions(&self) -> Result<PurgeReport, AppError> {
+        self.account_service.purge_due().await
+    }
+
+    /// 確認期限（`UNVERIFIED_ACCOUNT_TTL_SECS`）を過ぎた未確認アカウントを削除
+    ///
+    /// # Returns
+    /// 削除した件数
+    pub async fn expire_unverified_accounts(&self) -> Result<usize, AppError> {
+        let ttl_secs = self.config.load().unverified_account_ttl_secs;
+        let Some(cutoff) = ttl_cutoff(OffsetDateTime::now_utc(), ttl_secs) else {
+            return Ok(0);
+        };
+
+        let mut deleted = 0;
+        loop {
+            let user_ids = self
+                .user_repo
+                .delete_unverified(cutoff, UNVERIFIED_PURGE_BATCH_SIZE)
+                .await?;
+
+            // ユーザーの監査イベントは CASCADE で削除されるため、ユーザーに紐付けずに記録する
+            for user_id in &user_ids {
+                self.audit_service
+                    .record(
+                        NewAuditEvent::success(AuditEventType::AccountDeleted)
+                            .actor("system")
+                            .with("user_id", user_id.to_string())
+                            .with("reason", "unverified"),
+                    )
+                    .await;
+                self.webhook_service
+                    .emit(
+                        WebhookEvent::new(WebhookEventType::UserDeleted, *user_id)
+                            .reason("unverified"),
+                    )
+                    .await;
+            }
+
+            deleted += user_ids.len();
+            if (user_ids.len() as i64) < UNVERIFIED_PURGE_BATCH_SIZE {
+                break;
+            }
+        }
+
+        if deleted > 0 {
+            tracing::info!(deleted, "未確認アカウントを削除");
+        }
+        Ok(deleted)
+    }
+
+    /// 有効化されないまま `PENDING_2FA_TTL_SECS` を過ぎた 2FA シークレットを削除
+    ///
+    /// # Returns
+    /// 削除した件数
+    pub async fn purge_stale_2fa_setups(&self) -> Result<u64, AppError> {
+        let ttl_secs = self.config.load().pending_2fa_ttl_secs;
+        let Some(cutoff) = ttl_cutoff(OffsetDateTime::now_utc(), ttl_secs) else {
+            return Ok(0);
+        };
+
+        let deleted = self.user_2fa_repo.delete_stale_pending(cutoff).await?;
+        if deleted > 0 {
+            tracing::info!(deleted, "有効化されていない 2FA シークレットを削除");
+        }
+        Ok(deleted)
+    }
+
+    /// 配信できないまま保持期間（30 日）を過ぎた送信キューのメッセージを削除
+    ///
+    /// # Returns
+    /// 削除した件数
+    pub async fn purge_dead_outbox_messages(&self) -> Result<u64, AppError> {
+        self.outbox_service
+            .purge_dead(OffsetDateTime::now_utc() - OUTBOX_DEAD_RETENTION)
+            .await
+    }
+
+    /// 保持期間（30 日）を過ぎた Webhook の配信ログを削除（配信待ちのものは残す）
+    ///
+    /// # Returns
+    /// 削除した件数
+    pub async fn purge_webhook_deliveries(&self) -> Result<u64, AppError> {
+        self.webhook_service
+            .purge_deliveries(OffsetDateTime::now_utc() - WEBHOOK_DELIVERY_RETENTION)
+            .await
+    }
+}
+
+/// 保持期間から削除対象の基準日時を計算（0 以下の場合は削除しない）
+fn ttl_cutoff(now: OffsetDateTime, ttl_secs: i64) -> Option<OffsetDateTime> {
+    (ttl_secs > 0).then(|| now - Duration::seconds(ttl_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_cutoff() {
+        let now = OffsetDateTime::now_utc();
+        assert_eq!(ttl_cutoff(now, 0), None);
+        assert_eq!(ttl_cutoff(now, -1), None);
+        assert_eq!(ttl_cutoff(now, 3600), Some(now - Duration::hours(1)));
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
+use tracing::Instrument;
 
 /// Hydra からのログインリクエスト情報
 #[derive(Debug, Deserialize)]
@@ -89,6 +91,45 @@ pub struct RejectConsentRequest {
     pub error_description: String,
 }
 
+/// 記憶された同意セッション（Hydra → oxgate）
+#[derive(Debug, Deserialize)]
+pub struct HydraPreviousConsentSession {
+    /// 同意リクエストの ID（Hydra v2.2 以降）
+    pub consent_request_id: Option<String>,
+    #[serde(default)]
+    pub grant_scope: Vec<String>,
+    pub consent_request: Option<HydraPreviousConsentRequest>,
+    /// 同意日時（RFC 3339）
+    pub handled_at: Option<String>,
+}
+
+/// 記憶された同意セッションの元リクエスト
+#[derive(Debug, Deserialize)]
+pub struct HydraPreviousConsentRequest {
+    pub challenge: Option<String>,
+    pub client: Option<HydraClientInfo>,
+    /// 同意したときのログインセッション ID（ID トークンの `sid`）
+    pub login_session_id: Option<String>,
+}
+
+impl HydraPreviousConsentSession {
+    /// 同意したときのログインセッション ID
+    pub fn login_session_id(&self) -> Option<&str> {
+        self.consent_request
+            .as_ref()
+            .and_then(|r| r.login_session_id.as_deref())
+    }
+
+    /// 失効の指定に使う同意リクエストの ID
+    fn consent_request_id(&self) -> Option<&str> {
+        self.consent_request_id.as_deref().or_else(|| {
+            self.consent_request
+                .as_ref()
+                .and_then(|r| r.challenge.as_deref())
+        })
+    }
+}
+
 // ============================================================================
 // Logout (ログアウト) フロー関連 DTO
 // ============================================================================
@@ -113,7 +154,71 @@ pub struct RejectLogoutRequest {
     pub error_description: String,
 }
 
+// ============================================================================
+// トークンイントロスペクション関連 DTO
+// ============================================================================
+
+/// Hydra のトークンイントロスペクション結果
+#[derive(Debug, Deserialize)]
+pub struct IntrospectedToken {
+    pub active: bool,
+    pub sub: Option<String>,
+    pub client_id: Option<String>,
+    /// スペース区切りのスコープ
+    pub scope: Option<String>,
+}
+
+impl IntrospectedToken {
+    /// 有効なトークンで、指定されたスコープを含むか
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.active
+            && self
+                .scope
+                .as_deref()
+                .is_some_and(|scopes| scopes.split_whitespace().any(|s| s == scope))
+    }
+}
+
+// ============================================================================
+// OAuth2 クライアント管理関連 DTO
+// ============================================================================
+
+/// OAuth2 クライアント作成リクエスト（oxgate → Hydra）
+#[derive(Debug, Serialize)]
+pub struct CreateOAuth2ClientRequest {
+    pub client_name: String,
+    pub grant_types: Vec<String>,
+    /// スペース区切りのスコープ
+    pub scope: String,
+    pub token_endpoint_auth_method: String,
+}
+
+impl CreateOAuth2ClientRequest {
+    /// client_credentials グラントのみを許可するマシン用クライアント
+    pub fn client_credentials(client_name: &str, scope: &str) -> Self {
+        Self {
+            client_name: client_name.to_string(),
+            grant_types: vec!["client_credentials".to_string()],
+            scope: scope.to_string(),
+            token_endpoint_auth_method: "client_secret_basic".to_string(),
+        }
+    }
+}
+
+/// 作成された OAuth2 クライアント（Hydra → oxgate）
+///
+/// `client_secret` は作成時のレスポンスにのみ含まれる
+#[derive(Debug, Deserialize)]
+pub struct HydraOAuth2Client {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub client_name: Option<String>,
+    pub scope: Option<String>,
+}
+
 use crate::error::AppError;
+use crate::metrics::METRICS;
+use crate::telemetry;
 
 /// Hydra Admin API クライアント
 #[derive(Clone)]
@@ -140,7 +245,9 @@ impl HydraClient {
             self.admin_url, challenge
         );
 
-        let response: reqwest::Response = self.client.get(&url).send().await?;
+        let response = self
+            .send("get_login_request", self.client.get(&url))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -181,7 +288,9 @@ impl HydraClient {
             remember_for: Some(remember_for),
         };
 
-        let response: reqwest::Response = self.client.put(&url).json(&body).send().await?;
+        let response = self
+            .send("accept_login", self.client.put(&url).json(&body))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -220,7 +329,9 @@ impl HydraClient {
             error_description: description.to_string(),
         };
 
-        let response: reqwest::Response = self.client.put(&url).json(&body).send().await?;
+        let response = self
+            .send("reject_login", self.client.put(&url).json(&body))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -256,7 +367,9 @@ impl HydraClient {
             self.admin_url, challenge
         );
 
-        let response: reqwest::Response = self.client.get(&url).send().await?;
+        let response = self
+            .send("get_consent_request", self.client.get(&url))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -301,7 +414,9 @@ impl HydraClient {
             session,
         };
 
-        let response: reqwest::Response = self.client.put(&url).json(&body).send().await?;
+        let response = self
+            .send("accept_consent", self.client.put(&url).json(&body))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -340,7 +455,9 @@ impl HydraClient {
             error_description: description.to_string(),
         };
 
-        let response: reqwest::Response = self.client.put(&url).json(&body).send().await?;
+        let response = self
+            .send("reject_consent", self.client.put(&url).json(&body))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -376,7 +493,9 @@ impl HydraClient {
             self.admin_url, challenge
         );
 
-        let response: reqwest::Response = self.client.get(&url).send().await?;
+        let response = self
+            .send("get_logout_request", self.client.get(&url))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -407,7 +526,9 @@ impl HydraClient {
 
         let body = AcceptLogoutRequest {};
 
-        let response: reqwest::Response = self.client.put(&url).json(&body).send().await?;
+        let response = self
+            .send("accept_logout", self.client.put(&url).json(&body))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -446,7 +567,9 @@ impl HydraClient {
             error_description: description.to_string(),
         };
 
-        let response: reqwest::Response = self.client.put(&url).json(&body).send().await?;
+        let response = self
+            .send("reject_logout", self.client.put(&url).json(&body))
+            .await?;
 
         if !response.status().is_success() {
             let status = response.status();
@@ -465,4 +588,557 @@ impl HydraClient {
         tracing::info!("Hydra logout reject 成功");
         Ok(redirect.redirect_to)
     }
+
+    // ========================================================================
+    // トークンイントロスペクション
+    // ========================================================================
+
+    /// アクセストークンをイントロスペクション
+    ///
+    /// 無効・期限切れのトークンも `active: false` として正常に返る
+    ///
+    /// # Security
+    /// - トークンはログに出力しない
+    pub async fn introspect_token(&self, token: &str) -> Result<IntrospectedToken, AppError> {
+        let url = format!("{}/admin/oauth2/introspect", self.admin_url);
+
+        let request = self
+            .client
+            .post(&url)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            )
+            .body(format!("token={}", urlencoding::encode(token)));
+        let response = self.send("introspect_token", request).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Hydra トークンイントロスペクション失敗");
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra introspect returned status: {}",
+                status
+            )));
+        }
+
+        let introspected = response.json().await.map_err(|e| {
+            tracing::error!(error = ?e, "Hydra レスポンスのパースエラー");
+            AppError::Internal(anyhow::anyhow!("Failed to parse Hydra response"))
+        })?;
+
+        tracing::debug!("Hydra トークンイントロスペクション成功");
+        Ok(introspected)
+    }
+
+    // ========================================================================
+    // OAuth2 クライアント管理
+    // ========================================================================
+
+    /// OAuth2 クライアントを作成
+    ///
+    /// # Security
+    /// - client_secret はログに出力しない
+    pub async fn create_oauth2_client(
+        &self,
+        request: &CreateOAuth2ClientRequest,
+    ) -> Result<HydraOAuth2Client, AppError> {
+        let url = format!("{}/admin/clients", self.admin_url);
+
+        let response = self
+            .send("create_oauth2_client", self.client.post(&url).json(request))
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Hydra OAuth2 クライアント作成失敗");
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra create client returned status: {}",
+                status
+            )));
+        }
+
+        let client: HydraOAuth2Client = response.json().await.map_err(|e| {
+            tracing::error!(error = ?e, "Hydra レスポンスのパースエラー");
+            AppError::Internal(anyhow::anyhow!("Failed to parse Hydra response"))
+        })?;
+
+        tracing::info!(client_id = %client.client_id, "Hydra OAuth2 クライアント作成成功");
+        Ok(client)
+    }
+
+    // ========================================================================
+    // セッション管理
+    // ========================================================================
+
+    /// サブジェクトが記憶した同意の一覧を取得
+    pub async fn list_consent_sessions(
+        &self,
+        subject: &str,
+    ) -> Result<Vec<HydraPreviousConsentSession>, AppError> {
+        let url = format!(
+            "{}/admin/oauth2/auth/sessions/consent?subject={}",
+            self.admin_url,
+            urlencoding::encode(subject)
+        );
+
+        let response = self
+            .send("list_consent_sessions", self.client.get(&url))
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Hydra 同意セッション一覧取得失敗");
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra list consent sessions returned status: {}",
+                status
+            )));
+        }
+
+        let sessions = response.json().await.map_err(|e| {
+            tracing::error!(error = ?e, "Hydra レスポンスのパースエラー");
+            AppError::Internal(anyhow::anyhow!("Failed to parse Hydra response"))
+        })?;
+
+        tracing::debug!("Hydra 同意セッション一覧取得成功");
+        Ok(sessions)
+    }
+
+    /// サブジェクトの全ログインセッションを失効
+    ///
+    /// 以降の認可リクエストでは再ログイン（skip=false）が必要になる
+    pub async fn revoke_login_sessions(&self, subject: &str) -> Result<(), AppError> {
+        let url = format!(
+            "{}/admin/oauth2/auth/sessions/login?subject={}",
+            self.admin_url,
+            urlencoding::encode(subject)
+        );
+
+        let response = self
+            .send("revoke_login_sessions", self.client.delete(&url))
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Hydra ログインセッション失効失敗");
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra revoke login sessions returned status: {}",
+                status
+            )));
+        }
+
+        tracing::info!("Hydra ログインセッション失効成功");
+        Ok(())
+    }
+
+    /// サブジェクトが記憶した全クライアントへの同意を失効
+    ///
+    /// 発行済みのアクセストークン・リフレッシュトークンも無効になる
+    pub async fn revoke_consent_sessions(&self, subject: &str) -> Result<(), AppError> {
+        let url = format!(
+            "{}/admin/oauth2/auth/sessions/consent?subject={}&all=true",
+            self.admin_url,
+            urlencoding::encode(subject)
+        );
+
+        let response = self
+            .send("revoke_consent_sessions", self.client.delete(&url))
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Hydra 同意セッション失効失敗");
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra revoke consent sessions returned status: {}",
+                status
+            )));
+        }
+
+        tracing::info!("Hydra 同意セッション失効成功");
+        Ok(())
+    }
+
+    /// サブジェクトのログインセッションと同意を全て失効
+    ///
+    /// 両方を試行し、失敗があれば最初のエラーを返す
+    pub async fn revoke_subject_sessions(&self, subject: &str) -> Result<(), AppError> {
+        let login = self.revoke_login_sessions(subject).await;
+        let consent = self.revoke_consent_sessions(subject).await;
+        login.and(consent)
+    }
+
+    /// ログインセッションを 1 つ失効
+    pub async fn revoke_login_session(&self, sid: &str) -> Result<(), AppError> {
+        let url = format!(
+            "{}/admin/oauth2/auth/sessions/login?sid={}",
+            self.admin_url,
+            urlencoding::encode(sid)
+        );
+
+        let response = self
+            .send("revoke_login_session", self.client.delete(&url))
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Hydra ログインセッション失効失敗");
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra revoke login session returned status: {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+
+    /// 同意リクエスト 1 件から発行されたトークンと、記憶された同意を失効
+    pub async fn revoke_consent_request(
+        &self,
+        subject: &str,
+        consent_request_id: &str,
+    ) -> Result<(), AppError> {
+        let url = format!(
+            "{}/admin/oauth2/auth/sessions/consent?subject={}&consent_challenge_id={}",
+            self.admin_url,
+            urlencoding::encode(subject),
+            urlencoding::encode(consent_request_id)
+        );
+
+        let response = self
+            .send("revoke_consent_request", self.client.delete(&url))
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            tracing::error!(status = %status, "Hydra 同意セッション失効失敗");
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra revoke consent request returned status: {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+
+    /// 指定したログインセッション以外のログインセッションと同意を失効
+    ///
+    /// Hydra にはログインセッションの一覧 API がないため、記憶された同意の
+    /// `login_session_id` から対象のセッションを求める（同意に至っていない
+    /// ログインセッションは対象にならない）。全て試行し、失敗があれば最初のエラーを返す
+    ///
+    /// # Arguments
+    /// * `keep_sid` - 失効させないログインセッション ID（呼び出し元の ID トークンの `sid`）
+    pub async fn revoke_other_sessions(
+        &self,
+        subject: &str,
+        keep_sid: &str,
+    ) -> Result<(), AppError> {
+        let sessions = self.list_consent_sessions(subject).await?;
+        let others: Vec<_> = sessions
+            .iter()
+            .filter(|session| session.login_session_id() != Some(keep_sid))
+            .collect();
+
+        let mut result = Ok(());
+        let mut sids: Vec<&str> = others
+            .iter()
+            .filter_map(|session| session.login_session_id())
+            .collect();
+        sids.sort_unstable();
+        sids.dedup();
+        for sid in sids {
+            result = result.and(self.revoke_login_session(sid).await);
+        }
+        for session in &others {
+            let revoked = match session.consent_request_id() {
+                Some(id) => self.revoke_consent_request(subject, id).await,
+                None => Err(AppError::Internal(anyhow::anyhow!(
+                    "Hydra consent session has no consent request id"
+                ))),
+            };
+            result = result.and(revoked);
+        }
+
+        tracing::info!(revoked = others.len(), "Hydra の他のセッションを失効");
+        result
+    }
+
+    // ========================================================================
+    // ヘルスチェック
+    // ========================================================================
+
+    /// Hydra がリクエストを処理できる状態か確認（`/health/ready`）
+    pub async fn check_ready(&self) -> Result<(), AppError> {
+        let url = format!("{}/health/ready", self.admin_url);
+
+        let response = self.send("check_ready", self.client.get(&url)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(AppError::Internal(anyhow::anyhow!(
+                "Hydra health check returned status: {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+
+    /// リクエストを送信し、呼び出し時間とエラー（接続エラー・2xx 以外の応答）を記録
+    ///
+    /// 呼び出しは子スパンとして記録し、Hydra にトレースコンテキストを引き継ぐ
+    async fn send(
+        &self,
+        method: &'static str,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, AppError> {
+        let span = tracing::info_span!(
+            "hydra_request",
+            otel.name = %format!("Hydra {}", method),
+            otel.kind = "client",
+            otel.status_code = tracing::field::Empty,
+            http.response.status_code = tracing::field::Empty,
+        );
+        let request = request.headers(telemetry::trace_context_headers(&span));
+
+        let started = Instant::now();
+        let result = request.send().instrument(span.clone()).await;
+        let success = match &result {
+            Ok(response) => {
+                span.record("http.response.status_code", response.status().as_u16());
+                response.status().is_success()
+            }
+            Err(_) => false,
+        };
+        if !success {
+            span.record("otel.status_code", "ERROR");
+        }
+        METRICS.record_hydra_request(method, started.elapsed(), success);
+        Ok(result?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        Json, Router,
+        extract::{Query, State},
+        http::StatusCode,
+        routing::{delete, get},
+    };
+
+    use super::*;
+
+    type Calls = Arc<Mutex<Vec<(String, std::collections::HashMap<String, String>)>>>;
+
+    async fn spawn_hydra(status: StatusCode) -> (String, Calls) {
+        let calls: Calls = Arc::default();
+        let record = |path: &'static str| {
+            move |State(calls): State<Calls>,
+                  Query(query): Query<std::collections::HashMap<String, String>>| async move {
+                calls.lock().unwrap().push((path.to_string(), query));
+                status
+            }
+        };
+        let app = Router::new()
+            .route("/admin/oauth2/auth/sessions/login", delete(record("login")))
+            .route(
+                "/admin/oauth2/auth/sessions/consent",
+                delete(record("consent")),
+            )
+            .with_state(calls.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}", addr), calls)
+    }
+
+    #[tokio::test]
+    async fn test_revoke_subject_sessions() {
+        let (url, calls) = spawn_hydra(StatusCode::NO_CONTENT).await;
+        let client = HydraClient::new(url);
+
+        client.revoke_subject_sessions("user-1").await.unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].0, "login");
+        assert_eq!(calls[0].1["subject"], "user-1");
+        assert_eq!(calls[1].0, "consent");
+        assert_eq!(calls[1].1["all"], "true");
+    }
+
+    #[tokio::test]
+    async fn test_list_consent_sessions() {
+        let app = Router::new().route(
+            "/admin/oauth2/auth/sessions/consent",
+            get(
+                |Query(query): Query<std::collections::HashMap<String, String>>| async move {
+                    assert_eq!(query["subject"], "user-1");
+                    Json(serde_json::json!([{
+                        "grant_scope": ["openid", "email"],
+                        "handled_at": "2026-01-01T00:00:00Z",
+                        "remember": true,
+                        "consent_request": {
+                            "client": { "client_id": "app", "client_name": "App" }
+                        }
+                    }]))
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let sessions = HydraClient::new(format!("http://{}", addr))
+            .list_consent_sessions("user-1")
+            .await
+            .unwrap();
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].grant_scope, ["openid", "email"]);
+        let client = sessions[0]
+            .consent_request
+            .as_ref()
+            .and_then(|r| r.client.as_ref())
+            .unwrap();
+        assert_eq!(client.client_id, "app");
+    }
+
+    #[tokio::test]
+    async fn test_introspect_token() {
+        let app = Router::new().route(
+            "/admin/oauth2/introspect",
+            axum::routing::post(|body: String| async move {
+                assert_eq!(body, "token=abc%2Fdef");
+                Json(serde_json::json!({
+                    "active": true,
+                    "sub": "ops",
+                    "client_id": "admin-cli",
+                    "scope": "openid oxgate:admin"
+                }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let token = HydraClient::new(format!("http://{}", addr))
+            .introspect_token("abc/def")
+            .await
+            .unwrap();
+
+        assert!(token.has_scope("oxgate:admin"));
+        assert!(!token.has_scope("oxgate"));
+        assert_eq!(token.sub.as_deref(), Some("ops"));
+    }
+
+    #[tokio::test]
+    async fn test_create_oauth2_client() {
+        let app = Router::new().route(
+            "/admin/clients",
+            axum::routing::post(|Json(body): Json<serde_json::Value>| async move {
+                assert_eq!(
+                    body["grant_types"],
+                    serde_json::json!(["client_credentials"])
+                );
+                assert_eq!(body["scope"], "oxgate:admin");
+                (
+                    StatusCode::CREATED,
+                    Json(serde_json::json!({
+                        "client_id": "generated-id",
+                        "client_secret": "generated-secret",
+                        "client_name": body["client_name"],
+                        "scope": body["scope"]
+                    })),
+                )
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = HydraClient::new(format!("http://{}", addr))
+            .create_oauth2_client(&CreateOAuth2ClientRequest::client_credentials(
+                "ops",
+                "oxgate:admin",
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(client.client_id, "generated-id");
+        assert_eq!(client.client_secret.as_deref(), Some("generated-secret"));
+        assert_eq!(client.client_name.as_deref(), Some("ops"));
+    }
+
+    #[test]
+    fn test_inactive_token_has_no_scope() {
+        let token = IntrospectedToken {
+            active: false,
+            sub: None,
+            client_id: None,
+            scope: Some("oxgate:admin".to_string()),
+        };
+        assert!(!token.has_scope("oxgate:admin"));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_other_sessions_keeps_current_session() {
+        let calls: Calls = Arc::default();
+        let record = |path: &'static str| {
+            move |State(calls): State<Calls>,
+                  Query(query): Query<std::collections::HashMap<String, String>>| async move {
+                calls.lock().unwrap().push((path.to_string(), query));
+                StatusCode::NO_CONTENT
+            }
+        };
+        let app = Router::new()
+            .route("/admin/oauth2/auth/sessions/login", delete(record("login")))
+            .route(
+                "/admin/oauth2/auth/sessions/consent",
+                delete(record("consent")).get(|| async {
+                    Json(serde_json::json!([
+                        {
+                            "consent_request_id": "consent-current",
+                            "consent_request": { "login_session_id": "sid-current" }
+                        },
+                        {
+                            "consent_request_id": "consent-other-1",
+                            "consent_request": { "login_session_id": "sid-other" }
+                        },
+                        {
+                            "consent_request": {
+                                "challenge": "consent-other-2",
+                                "login_session_id": "sid-other"
+                            }
+                        }
+                    ]))
+                }),
+            )
+            .with_state(calls.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        HydraClient::new(format!("http://{}", addr))
+            .revoke_other_sessions("user-1", "sid-current")
+            .await
+            .unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].0, "login");
+        assert_eq!(calls[0].1["sid"], "sid-other");
+        assert_eq!(calls[1].1["consent_challenge_id"], "consent-other-1");
+        assert_eq!(calls[2].1["consent_challenge_id"], "consent-other-2");
+        assert!(calls.iter().all(|(_, query)| !query.contains_key("all")));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_subject_sessions_attempts_both_on_failure() {
+        let (url, calls) = spawn_hydra(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let client = HydraClient::new(url);
+
+        assert!(client.revoke_subject_sessions("user-1").await.is_err());
+        assert_eq!(calls.lock().unwrap().len(), 2);
+    }
 }
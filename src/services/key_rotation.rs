@@ -0,0 +1,93 @@
+use crate::error::AppError;
+use crate::repositories::User2faSecretRepository;
+use crate::services::TotpService;
+
+/// 1バッチあたりの処理件数
+const REENCRYPTION_BATCH_SIZE: i64 = 100;
+
+/// 再暗号化ジョブの結果
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReencryptionReport {
+    /// 走査した件数
+    pub scanned: u64,
+    /// 最新キーで再暗号化した件数
+    pub reencrypted: u64,
+    /// 復号できずスキップした件数（キーリングから削除済みのキーなど）
+    pub failed: u64,
+}
+
+/// 暗号化キーローテーションサービス
+///
+/// 保存済みの TOTP シークレットを最新キーで再暗号化する。
+/// 全件の移行が完了すれば、旧キーをキーリングから削除できる。
+#[derive(Clone)]
+pub struct KeyRotationService {
+    user_2fa_repo: User2faSecretRepository,
+    totp_service: TotpService,
+}
+
+impl KeyRotationService {
+    /// 新しい KeyRotationService を作成
+    pub fn new(user_2fa_repo: User2faSecretRepository, totp_service: TotpService) -> Self {
+        Self {
+            user_2fa_repo,
+            totp_service,
+        }
+    }
+
+    /// `user_2fa_secrets.secret_encrypted` を最新キーで再暗号化
+    ///
+    /// # Note
+    /// - 複数レプリカで同時に実行しても、旧暗号文が一致する行のみ更新するため安全
+    /// - 復号できない行はスキップし、件数のみ記録する（シークレットはログ出力禁止）
+    pub async fn reencrypt_totp_secrets(&self) -> Result<ReencryptionReport, AppError> {
+        let mut report = ReencryptionReport::default();
+        let mut after = None;
+
+        loop {
+            let batch = self
+                .user_2fa_repo
+                .find_batch(after, REENCRYPTION_BATCH_SIZE)
+                .await?;
+
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after = Some(last.user_id);
+
+            for row in &batch {
+                report.scanned += 1;
+
+                if !self.totp_service.needs_reencryption(&row.secret_encrypted) {
+                    continue;
+                }
+
+                let reencrypted = match self.totp_service.reencrypt_secret(&row.secret_encrypted) {
+                    Ok(reencrypted) => reencrypted,
+                    Err(_) => {
+                        tracing::warn!(user_id = %row.user_id, "2FAシークレットを復号できないため再暗号化をスキップ");
+                        report.failed += 1;
+                        continue;
+                    }
+                };
+
+                if self
+                    .user_2fa_repo
+                    .replace_secret(row.user_id, &row.secret_encrypted, &reencrypted)
+                    .await?
+                {
+                    report.reencrypted += 1;
+                }
+            }
+        }
+
+        tracing::info!(
+            scanned = report.scanned,
+            reencrypted = report.reencrypted,
+            failed = report.failed,
+            "2FAシークレットの再暗号化完了"
+        );
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,315 @@
+use aes_gcm::{
+    Aes256Gcm, KeyInit, Nonce,
+    aead::{Aead, OsRng},
+};
+use base64::{
+    Engine as _,
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+};
+use rand::RngCore;
+
+use crate::error::AppError;
+
+/// nonce 長（AES-GCM 96ビット）
+const NONCE_LEN: usize = 12;
+
+/// バージョン付き AES-256-GCM キーリング
+///
+/// 暗号文のフォーマット: キーID (1バイト) + nonce (12バイト) + 暗号文
+///
+/// - 暗号化は常に最新（最大ID）のキーで行う
+/// - 復号は先頭バイトのキーIDを優先し、失敗した場合は全キーを試す
+///   （キーID導入前に保存されたプレフィックスなしの暗号文にも対応）
+///
+/// # Security
+/// キー素材はログに出力しない
+#[derive(Clone)]
+pub struct Keyring {
+    /// (キーID, キー) のリスト（キーID昇順）
+    keys: Vec<(u8, [u8; 32])>,
+}
+
+impl Keyring {
+    /// 単一キーからキーリングを作成（キーID 0）
+    ///
+    /// # Arguments
+    /// * `key_base64` - Base64エンコードされた32バイトのキー
+    pub fn from_single(key_base64: &str) -> Result<Self, AppError> {
+        Ok(Self {
+            keys: vec![(0, decode_key(key_base64)?)],
+        })
+    }
+
+    /// キーリング設定文字列をパース
+    ///
+    /// # Arguments
+    /// * `spec` - `"<id>:<base64>"` のカンマ区切り（例: `"1:AAAA...,2:BBBB..."`）
+    pub fn parse(spec: &str) -> Result<Self, AppError> {
+        let mut keys = Vec::new();
+
+        for entry in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (id, key) = entry.split_once(':').ok_or_else(|| {
+                tracing::error!("キーリングのエントリ形式が不正（<id>:<base64> 形式が必要）");
+                AppError::Internal(anyhow::anyhow!("invalid keyring entry format"))
+            })?;
+
+            let id: u8 = id.trim().parse().map_err(|e| {
+                tracing::error!(error = ?e, "キーIDのパースエラー（0-255 の整数が必要）");
+                AppError::Internal(anyhow::anyhow!("invalid key id"))
+            })?;
+
+            if keys.iter().any(|(existing, _)| *existing == id) {
+                tracing::error!(key_id = id, "キーIDが重複している");
+                return Err(AppError::Internal(anyhow::anyhow!("duplicate key id")));
+            }
+
+            keys.push((id, decode_key(key.trim())?));
+        }
+
+        if keys.is_empty() {
+            tracing::error!("キーリングにキーが設定されていない");
+            return Err(AppError::Internal(anyhow::anyhow!("keyring is empty")));
+        }
+
+        keys.sort_by_key(|(id, _)| *id);
+
+        Ok(Self { keys })
+    }
+
+    /// 別のキーリングのキーを追加（同じキーIDが既にある場合は既存を優先）
+    pub fn merge(mut self, other: Keyring) -> Self {
+        for (id, key) in other.keys {
+            if !self.keys.iter().any(|(existing, _)| *existing == id) {
+                self.keys.push((id, key));
+            }
+        }
+        self.keys.sort_by_key(|(id, _)| *id);
+        self
+    }
+
+    /// 両方のキーリングに登録され、キーが異なるキーIDの一覧
+    ///
+    /// `merge` ではこれらのキーIDについて `other` のキーが使われない
+    pub fn conflicting_key_ids(&self, other: &Keyring) -> Vec<u8> {
+        self.keys
+            .iter()
+            .filter(|(id, key)| {
+                other
+                    .keys
+                    .iter()
+                    .any(|(other_id, other_key)| other_id == id && other_key != key)
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// 暗号化に使用する最新キーのID
+    pub fn primary_key_id(&self) -> u8 {
+        self.primary().0
+    }
+
+    /// 登録されているキーの数
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// キーが登録されていないか（パース時に拒否するため通常は false）
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// 最新キーで暗号化
+    ///
+    /// # Returns
+    /// キーID (1バイト) + nonce (12バイト) + 暗号文
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, AppError> {
+        let (key_id, key) = self.primary();
+
+        let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| {
+            tracing::error!(error = ?e, "AES-GCM暗号化器の初期化エラー");
+            AppError::Internal(anyhow::anyhow!("cipher initialization error"))
+        })?;
+
+        // 96ビット (12バイト) のランダムnonce生成
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let ciphertext = cipher.encrypt(nonce, plaintext).map_err(|e| {
+            tracing::error!(error = ?e, "暗号化エラー");
+            AppError::Internal(anyhow::anyhow!("encryption error"))
+        })?;
+
+        let mut result = Vec::with_capacity(1 + NONCE_LEN + ciphertext.len());
+        result.push(*key_id);
+        result.extend_from_slice(&nonce_bytes);
+        result.extend_from_slice(&ciphertext);
+
+        Ok(result)
+    }
+
+    /// 暗号文を復号
+    ///
+    /// # Returns
+    /// 復号結果と、暗号化に使われていたキーID（プレフィックスなしの旧形式は `None`）
+    ///
+    /// # Errors
+    /// どのキーでも復号できない場合は `None` を返す（呼び出し側でエラー種別を決める）
+    pub fn decrypt(&self, encrypted: &[u8]) -> Option<(Vec<u8>, Option<u8>)> {
+        // 1. キーIDプレフィックス付きの形式
+        if encrypted.len() > NONCE_LEN + 1
+            && let Some((key_id, key)) = self.keys.iter().find(|(id, _)| *id == encrypted[0])
+            && let Some(plaintext) = decrypt_with(key, &encrypted[1..])
+        {
+            return Some((plaintext, Some(*key_id)));
+        }
+
+        // 2. プレフィックスなしの旧形式（全キーを新しい順に試す）
+        if encrypted.len() > NONCE_LEN {
+            for (_, key) in self.keys.iter().rev() {
+                if let Some(plaintext) = decrypt_with(key, encrypted) {
+                    return Some((plaintext, None));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 暗号文が最新キー以外で暗号化されているか（再暗号化が必要か）
+    pub fn needs_reencryption(&self, encrypted: &[u8]) -> bool {
+        !matches!(self.decrypt(encrypted), Some((_, Some(id))) if id == self.primary_key_id())
+    }
+
+    fn primary(&self) -> &(u8, [u8; 32]) {
+        // parse / from_single で空のキーリングは作成できない
+        self.keys.last().expect("keyring must not be empty")
+    }
+}
+
+/// nonce + 暗号文 を指定キーで復号
+fn decrypt_with(key: &[u8; 32], data: &[u8]) -> Option<Vec<u8>> {
+    let cipher = Aes256Gcm::new_from_slice(key).ok()?;
+    let (nonce_bytes, ciphertext) = data.split_at(NONCE_LEN);
+    cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .ok()
+}
+
+/// Base64 (STANDARD または URL-safe) エンコードされた32バイトキーをデコード
+fn decode_key(key_base64: &str) -> Result<[u8; 32], AppError> {
+    let key_bytes = STANDARD
+        .decode(key_base64)
+        .or_else(|_| URL_SAFE_NO_PAD.decode(key_base64))
+        .map_err(|e| {
+            tracing::error!(error = ?e, "暗号化キーのBase64デコードエラー");
+            AppError::Internal(anyhow::anyhow!("invalid encryption key format"))
+        })?;
+
+    if key_bytes.len() != 32 {
+        tracing::error!(
+            expected = 32,
+            actual = key_bytes.len(),
+            "暗号化キーの長さが不正"
+        );
+        return Err(AppError::Internal(anyhow::anyhow!(
+            "encryption key must be 32 bytes"
+        )));
+    }
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&key_bytes);
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> String {
+        STANDARD.encode([byte; 32])
+    }
+
+    #[test]
+    fn test_parse_keyring() {
+        let keyring = Keyring::parse(&format!("2:{},1:{}", key(2), key(1))).unwrap();
+        assert_eq!(keyring.len(), 2);
+        assert_eq!(keyring.primary_key_id(), 2);
+    }
+
+    #[test]
+    fn test_parse_invalid_keyring() {
+        assert!(Keyring::parse("").is_err());
+        assert!(Keyring::parse(&key(1)).is_err());
+        assert!(Keyring::parse(&format!("x:{}", key(1))).is_err());
+        assert!(Keyring::parse(&format!("1:{},1:{}", key(1), key(2))).is_err());
+        assert!(Keyring::parse(&format!("1:{}", STANDARD.encode([0u8; 16]))).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_uses_primary_key() {
+        let keyring = Keyring::parse(&format!("1:{},7:{}", key(1), key(7))).unwrap();
+        let encrypted = keyring.encrypt(b"secret").unwrap();
+        assert_eq!(encrypted[0], 7);
+
+        let (plaintext, key_id) = keyring.decrypt(&encrypted).unwrap();
+        assert_eq!(plaintext, b"secret");
+        assert_eq!(key_id, Some(7));
+        assert!(!keyring.needs_reencryption(&encrypted));
+    }
+
+    #[test]
+    fn test_decrypt_with_rotated_key() {
+        let old = Keyring::parse(&format!("1:{}", key(1))).unwrap();
+        let encrypted = old.encrypt(b"secret").unwrap();
+
+        let rotated = Keyring::parse(&format!("1:{},2:{}", key(1), key(2))).unwrap();
+        let (plaintext, key_id) = rotated.decrypt(&encrypted).unwrap();
+        assert_eq!(plaintext, b"secret");
+        assert_eq!(key_id, Some(1));
+        assert!(rotated.needs_reencryption(&encrypted));
+
+        // 旧キーを削除すると復号できない
+        let retired = Keyring::parse(&format!("2:{}", key(2))).unwrap();
+        assert!(retired.decrypt(&encrypted).is_none());
+    }
+
+    #[test]
+    fn test_decrypt_legacy_format() {
+        // キーIDプレフィックスなしの旧形式（nonce + 暗号文）
+        let legacy_key = [3u8; 32];
+        let cipher = Aes256Gcm::new_from_slice(&legacy_key).unwrap();
+        let nonce_bytes = [9u8; NONCE_LEN];
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), b"legacy".as_ref())
+            .unwrap();
+        let mut legacy = nonce_bytes.to_vec();
+        legacy.extend_from_slice(&ciphertext);
+
+        let keyring = Keyring::parse(&format!("0:{},5:{}", key(3), key(5))).unwrap();
+        let (plaintext, key_id) = keyring.decrypt(&legacy).unwrap();
+        assert_eq!(plaintext, b"legacy");
+        assert_eq!(key_id, None);
+        assert!(keyring.needs_reencryption(&legacy));
+    }
+
+    #[test]
+    fn test_merge_prefers_existing_ids() {
+        let keyring = Keyring::parse(&format!("1:{}", key(1)))
+            .unwrap()
+            .merge(Keyring::from_single(&key(0)).unwrap());
+        assert_eq!(keyring.len(), 2);
+        assert_eq!(keyring.primary_key_id(), 1);
+    }
+
+    #[test]
+    fn test_conflicting_key_ids() {
+        let keyring = Keyring::parse(&format!("0:{},1:{}", key(0), key(1))).unwrap();
+
+        let same = Keyring::from_single(&key(0)).unwrap();
+        assert!(keyring.conflicting_key_ids(&same).is_empty());
+
+        let different = Keyring::from_single(&key(9)).unwrap();
+        assert_eq!(keyring.conflicting_key_ids(&different), vec![0]);
+    }
+}
@@ -1,11 +1,43 @@
+pub mod account;
+pub mod audit;
 pub mod auth;
+pub mod config_reload;
 pub mod email;
+pub mod email_change;
+pub mod email_normalization;
+pub mod housekeeping;
 pub mod hydra;
+pub mod key_rotation;
+pub mod keyring;
 pub mod oauth;
+pub mod outbox;
+pub mod password_policy;
 pub mod password_reset;
+pub mod profile;
+pub mod readiness;
+pub mod scheduler;
+pub mod security_notification;
 pub mod totp;
+pub mod user_admin;
+pub mod webhook;
 
+pub use account::AccountService;
+pub use audit::AuditService;
+pub use config_reload::ConfigReloader;
 pub use email::EmailService;
+pub use email_change::EmailChangeService;
+pub use email_normalization::EmailNormalizationService;
+pub use housekeeping::HousekeepingService;
+pub use key_rotation::KeyRotationService;
+pub use keyring::Keyring;
 pub use oauth::{GitHubOAuthService, OAuthService};
+pub use outbox::{OutboxService, OutboxWorker, OutboxWorkerHandle};
+pub use password_policy::{PasswordPolicy, PasswordPolicyService};
 pub use password_reset::PasswordResetService;
+pub use profile::ProfileService;
+pub use readiness::ReadinessService;
+pub use scheduler::{Scheduler, SchedulerHandle};
+pub use security_notification::{SecurityEvent, SecurityNotificationService};
 pub use totp::TotpService;
+pub use user_admin::UserAdminService;
+pub use webhook::WebhookService;
@@ -1,19 +1,18 @@
 use std::sync::Arc;
 
-use aes_gcm::{
-    Aes256Gcm, KeyInit, Nonce,
-    aead::{Aead, OsRng},
-};
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
-use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
 use crate::error::AppError;
+use crate::services::keyring::Keyring;
 
 /// Google OAuth URLs
 const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
 const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
 const GOOGLE_USERINFO_URL: &str = "https://www.googleapis.com/oauth2/v2/userinfo";
+/// Google の OpenID Connect ディスカバリードキュメント（疎通確認に使用）
+pub(crate) const GOOGLE_DISCOVERY_URL: &str =
+    "https://accounts.google.com/.well-known/openid-configuration";
 
 /// OAuth ユーザー情報
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +20,11 @@ pub struct OAuthUserInfo {
     pub id: String,
     pub email: String,
     pub name: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    /// プロフィール画像（アバター）の URL
+    pub picture: Option<String>,
+    pub locale: Option<String>,
 }
 
 /// OAuth トークンレスポンス
@@ -45,6 +49,10 @@ struct GoogleUserInfoResponse {
     id: String,
     email: String,
     name: Option<String>,
+    given_name: Option<String>,
+    family_name: Option<String>,
+    picture: Option<String>,
+    locale: Option<String>,
 }
 
 /// Google OAuth サービス
@@ -59,7 +67,7 @@ pub struct OAuthService {
     /// クライアントシークレット（機密情報 - ログ出力禁止）
     client_secret: Arc<String>,
     redirect_uri: String,
-    state_encryption_key: [u8; 32],
+    state_keyring: Keyring,
     http_client: reqwest::Client,
 }
 
@@ -80,38 +88,31 @@ impl OAuthService {
         redirect_uri: String,
         state_secret_base64: &str,
     ) -> Result<Self, AppError> {
-        let key_bytes = URL_SAFE_NO_PAD
-            .decode(state_secret_base64)
-            .or_else(|_| {
-                // URL_SAFE でデコード失敗した場合、STANDARD を試す
-                base64::engine::general_purpose::STANDARD.decode(state_secret_base64)
-            })
-            .map_err(|e| {
-                tracing::error!(error = ?e, "OAuth state暗号化キーのBase64デコードエラー");
-                AppError::Internal(anyhow::anyhow!("invalid state encryption key format"))
-            })?;
-
-        if key_bytes.len() != 32 {
-            tracing::error!(
-                expected = 32,
-                actual = key_bytes.len(),
-                "OAuth state暗号化キーの長さが不正"
-            );
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "state encryption key must be 32 bytes"
-            )));
-        }
-
-        let mut state_encryption_key = [0u8; 32];
-        state_encryption_key.copy_from_slice(&key_bytes);
+        Ok(Self::with_keyring(
+            client_id,
+            client_secret,
+            redirect_uri,
+            Keyring::from_single(state_secret_base64)?,
+        ))
+    }
 
-        Ok(Self {
+    /// state 暗号化用キーリングを指定して OAuthService を作成
+    ///
+    /// # Arguments
+    /// * `state_keyring` - state 暗号化用キーリング（最新キーで暗号化、全キーで復号）
+    pub fn with_keyring(
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        state_keyring: Keyring,
+    ) -> Self {
+        Self {
             client_id,
             client_secret: Arc::new(client_secret),
             redirect_uri,
-            state_encryption_key,
+            state_keyring,
             http_client: reqwest::Client::new(),
-        })
+        }
     }
 
     /// Google OAuth 認可 URL を生成
@@ -147,6 +148,11 @@ impl OAuthService {
     ///
     /// # Arguments
     /// * `code` - Google から受け取った認可コード
+    #[tracing::instrument(
+        name = "oauth_provider_request",
+        skip_all,
+        fields(otel.name = "Google token", otel.kind = "client")
+    )]
     pub async fn exchange_code(&self, code: &str) -> Result<OAuthTokenResponse, AppError> {
         // application/x-www-form-urlencoded 形式で body を構築
         let body = format!(
@@ -197,6 +203,11 @@ impl OAuthService {
     ///
     /// # Arguments
     /// * `access_token` - Google アクセストークン
+    #[tracing::instrument(
+        name = "oauth_provider_request",
+        skip_all,
+        fields(otel.name = "Google userinfo", otel.kind = "client")
+    )]
     pub async fn get_user_info(&self, access_token: &str) -> Result<OAuthUserInfo, AppError> {
         let response = self
             .http_client
@@ -227,6 +238,10 @@ impl OAuthService {
             id: user_info.id,
             email: user_info.email,
             name: user_info.name,
+            given_name: user_info.given_name,
+            family_name: user_info.family_name,
+            picture: user_info.picture,
+            locale: user_info.locale,
         })
     }
 
@@ -243,63 +258,12 @@ impl OAuthService {
 
     /// login_challenge を AES-256-GCM で暗号化し、Base64 URL-safe エンコード
     fn encrypt_state(&self, login_challenge: &str) -> Result<String, AppError> {
-        let cipher = Aes256Gcm::new_from_slice(&self.state_encryption_key).map_err(|e| {
-            tracing::error!(error = ?e, "AES-GCM暗号化器の初期化エラー");
-            AppError::Internal(anyhow::anyhow!("cipher initialization error"))
-        })?;
-
-        // 96ビット (12バイト) のランダム nonce 生成
-        let mut nonce_bytes = [0u8; 12];
-        OsRng.fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
-
-        let ciphertext = cipher
-            .encrypt(nonce, login_challenge.as_bytes())
-            .map_err(|e| {
-                tracing::error!(error = ?e, "state暗号化エラー");
-                AppError::Internal(anyhow::anyhow!("state encryption error"))
-            })?;
-
-        // nonce + ciphertext を結合して Base64 URL-safe エンコード
-        let mut combined = Vec::with_capacity(12 + ciphertext.len());
-        combined.extend_from_slice(&nonce_bytes);
-        combined.extend_from_slice(&ciphertext);
-
-        Ok(URL_SAFE_NO_PAD.encode(&combined))
+        encrypt_state(&self.state_keyring, login_challenge)
     }
 
     /// 暗号化された state を復号して login_challenge を取得
     fn decrypt_state(&self, encrypted_state: &str) -> Result<String, AppError> {
-        let encrypted = URL_SAFE_NO_PAD.decode(encrypted_state).map_err(|e| {
-            tracing::warn!(error = ?e, "state Base64デコードエラー（改ざんの可能性）");
-            AppError::OAuthStateInvalid
-        })?;
-
-        if encrypted.len() < 12 {
-            tracing::warn!(
-                len = encrypted.len(),
-                "暗号化stateが短すぎる（改ざんの可能性）"
-            );
-            return Err(AppError::OAuthStateInvalid);
-        }
-
-        let cipher = Aes256Gcm::new_from_slice(&self.state_encryption_key).map_err(|e| {
-            tracing::error!(error = ?e, "AES-GCM暗号化器の初期化エラー");
-            AppError::Internal(anyhow::anyhow!("cipher initialization error"))
-        })?;
-
-        let (nonce_bytes, ciphertext) = encrypted.split_at(12);
-        let nonce = Nonce::from_slice(nonce_bytes);
-
-        let plaintext = cipher.decrypt(nonce, ciphertext).map_err(|e| {
-            tracing::warn!(error = ?e, "state復号エラー（改ざんまたは期限切れの可能性）");
-            AppError::OAuthStateInvalid
-        })?;
-
-        String::from_utf8(plaintext).map_err(|e| {
-            tracing::warn!(error = ?e, "復号stateのUTF-8変換エラー");
-            AppError::OAuthStateInvalid
-        })
+        decrypt_state(&self.state_keyring, encrypted_state)
     }
 }
 
@@ -308,7 +272,7 @@ impl OAuthService {
 // =============================================================================
 
 /// GitHub OAuth URLs
-const GITHUB_AUTH_URL: &str = "https://github.com/login/oauth/authorize";
+pub(crate) const GITHUB_AUTH_URL: &str = "https://github.com/login/oauth/authorize";
 const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
 const GITHUB_USERINFO_URL: &str = "https://api.github.com/user";
 
@@ -327,6 +291,7 @@ struct GitHubUserInfoResponse {
     email: Option<String>,
     name: Option<String>,
     login: String,
+    avatar_url: Option<String>,
 }
 
 /// GitHub OAuth サービス
@@ -341,7 +306,7 @@ pub struct GitHubOAuthService {
     /// クライアントシークレット（機密情報 - ログ出力禁止）
     client_secret: Arc<String>,
     redirect_uri: String,
-    state_encryption_key: [u8; 32],
+    state_keyring: Keyring,
     http_client: reqwest::Client,
 }
 
@@ -362,35 +327,31 @@ impl GitHubOAuthService {
         redirect_uri: String,
         state_secret_base64: &str,
     ) -> Result<Self, AppError> {
-        let key_bytes = URL_SAFE_NO_PAD
-            .decode(state_secret_base64)
-            .or_else(|_| base64::engine::general_purpose::STANDARD.decode(state_secret_base64))
-            .map_err(|e| {
-                tracing::error!(error = ?e, "GitHub OAuth state暗号化キーのBase64デコードエラー");
-                AppError::Internal(anyhow::anyhow!("invalid state encryption key format"))
-            })?;
-
-        if key_bytes.len() != 32 {
-            tracing::error!(
-                expected = 32,
-                actual = key_bytes.len(),
-                "GitHub OAuth state暗号化キーの長さが不正"
-            );
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "state encryption key must be 32 bytes"
-            )));
-        }
-
-        let mut state_encryption_key = [0u8; 32];
-        state_encryption_key.copy_from_slice(&key_bytes);
+        Ok(Self::with_keyring(
+            client_id,
+            client_secret,
+            redirect_uri,
+            Keyring::from_single(state_secret_base64)?,
+        ))
+    }
 
-        Ok(Self {
+    /// state 暗号化用キーリングを指定して GitHubOAuthService を作成
+    ///
+    /// # Arguments
+    /// * `state_keyring` - state 暗号化用キーリング（最新キーで暗号化、全キーで復号）
+    pub fn with_keyring(
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        state_keyring: Keyring,
+    ) -> Self {
+        Self {
             client_id,
             client_secret: Arc::new(client_secret),
             redirect_uri,
-            state_encryption_key,
+            state_keyring,
             http_client: reqwest::Client::new(),
-        })
+        }
     }
 
     /// GitHub OAuth 認可 URL を生成
@@ -422,6 +383,11 @@ impl GitHubOAuthService {
     ///
     /// # Arguments
     /// * `code` - GitHub から受け取った認可コード
+    #[tracing::instrument(
+        name = "oauth_provider_request",
+        skip_all,
+        fields(otel.name = "GitHub token", otel.kind = "client")
+    )]
     pub async fn exchange_code(&self, code: &str) -> Result<OAuthTokenResponse, AppError> {
         let body = format!(
             "client_id={}&client_secret={}&code={}&redirect_uri={}",
@@ -472,6 +438,11 @@ impl GitHubOAuthService {
     ///
     /// # Arguments
     /// * `access_token` - GitHub アクセストークン
+    #[tracing::instrument(
+        name = "oauth_provider_request",
+        skip_all,
+        fields(otel.name = "GitHub userinfo", otel.kind = "client")
+    )]
     pub async fn get_user_info(&self, access_token: &str) -> Result<OAuthUserInfo, AppError> {
         let response = self
             .http_client
@@ -509,6 +480,10 @@ impl GitHubOAuthService {
             id: user_info.id.to_string(),
             email,
             name: user_info.name,
+            given_name: None,
+            family_name: None,
+            picture: user_info.avatar_url,
+            locale: None,
         })
     }
 
@@ -519,62 +494,51 @@ impl GitHubOAuthService {
 
     /// login_challenge を AES-256-GCM で暗号化
     fn encrypt_state(&self, login_challenge: &str) -> Result<String, AppError> {
-        let cipher = Aes256Gcm::new_from_slice(&self.state_encryption_key).map_err(|e| {
-            tracing::error!(error = ?e, "AES-GCM暗号化器の初期化エラー");
-            AppError::Internal(anyhow::anyhow!("cipher initialization error"))
-        })?;
-
-        let mut nonce_bytes = [0u8; 12];
-        OsRng.fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
-
-        let ciphertext = cipher
-            .encrypt(nonce, login_challenge.as_bytes())
-            .map_err(|e| {
-                tracing::error!(error = ?e, "state暗号化エラー");
-                AppError::Internal(anyhow::anyhow!("state encryption error"))
-            })?;
-
-        let mut combined = Vec::with_capacity(12 + ciphertext.len());
-        combined.extend_from_slice(&nonce_bytes);
-        combined.extend_from_slice(&ciphertext);
-
-        Ok(URL_SAFE_NO_PAD.encode(&combined))
+        encrypt_state(&self.state_keyring, login_challenge)
     }
 
     /// 暗号化された state を復号
     fn decrypt_state(&self, encrypted_state: &str) -> Result<String, AppError> {
-        let encrypted = URL_SAFE_NO_PAD.decode(encrypted_state).map_err(|e| {
-            tracing::warn!(error = ?e, "state Base64デコードエラー（改ざんの可能性）");
-            AppError::OAuthStateInvalid
-        })?;
+        decrypt_state(&self.state_keyring, encrypted_state)
+    }
+}
 
-        if encrypted.len() < 12 {
-            tracing::warn!(
-                len = encrypted.len(),
-                "暗号化stateが短すぎる（改ざんの可能性）"
-            );
-            return Err(AppError::OAuthStateInvalid);
-        }
+// =============================================================================
+// state 暗号化（Google / GitHub 共通）
+// =============================================================================
 
-        let cipher = Aes256Gcm::new_from_slice(&self.state_encryption_key).map_err(|e| {
-            tracing::error!(error = ?e, "AES-GCM暗号化器の初期化エラー");
-            AppError::Internal(anyhow::anyhow!("cipher initialization error"))
-        })?;
+/// login_challenge をキーリングの最新キーで暗号化し、Base64 URL-safe エンコード
+fn encrypt_state(keyring: &Keyring, login_challenge: &str) -> Result<String, AppError> {
+    let encrypted = keyring.encrypt(login_challenge.as_bytes())?;
+    Ok(URL_SAFE_NO_PAD.encode(&encrypted))
+}
 
-        let (nonce_bytes, ciphertext) = encrypted.split_at(12);
-        let nonce = Nonce::from_slice(nonce_bytes);
+/// 暗号化された state を復号して login_challenge を取得
+///
+/// ローテーション中も発行済みの state を受け付けるため、全キーで復号を試す
+fn decrypt_state(keyring: &Keyring, encrypted_state: &str) -> Result<String, AppError> {
+    let encrypted = URL_SAFE_NO_PAD.decode(encrypted_state).map_err(|e| {
+        tracing::warn!(error = ?e, "state Base64デコードエラー（改ざんの可能性）");
+        AppError::OAuthStateInvalid
+    })?;
+
+    if encrypted.len() < 12 {
+        tracing::warn!(
+            len = encrypted.len(),
+            "暗号化stateが短すぎる（改ざんの可能性）"
+        );
+        return Err(AppError::OAuthStateInvalid);
+    }
 
-        let plaintext = cipher.decrypt(nonce, ciphertext).map_err(|e| {
-            tracing::warn!(error = ?e, "state復号エラー（改ざんまたは期限切れの可能性）");
-            AppError::OAuthStateInvalid
-        })?;
+    let (plaintext, _) = keyring.decrypt(&encrypted).ok_or_else(|| {
+        tracing::warn!("state復号エラー（改ざんまたは期限切れの可能性）");
+        AppError::OAuthStateInvalid
+    })?;
 
-        String::from_utf8(plaintext).map_err(|e| {
-            tracing::warn!(error = ?e, "復号stateのUTF-8変換エラー");
-            AppError::OAuthStateInvalid
-        })
-    }
+    String::from_utf8(plaintext).map_err(|e| {
+        tracing::warn!(error = ?e, "復号stateのUTF-8変換エラー");
+        AppError::OAuthStateInvalid
+    })
 }
 
 #[cfg(test)]
@@ -638,6 +602,35 @@ mod tests {
         assert!(matches!(result, Err(AppError::OAuthStateInvalid)));
     }
 
+    #[test]
+    fn test_decrypt_state_after_key_rotation() {
+        let old_keyring = Keyring::parse(&format!("1:{}", STANDARD.encode([1u8; 32]))).unwrap();
+        let old_service = OAuthService::with_keyring(
+            "test-client-id".to_string(),
+            "test-client-secret".to_string(),
+            "http://localhost:8080/callback".to_string(),
+            old_keyring,
+        );
+        let encrypted = old_service.encrypt_state("rotating-challenge").unwrap();
+
+        let keyring = Keyring::parse(&format!(
+            "1:{},2:{}",
+            STANDARD.encode([1u8; 32]),
+            STANDARD.encode([2u8; 32])
+        ))
+        .unwrap();
+        let service = OAuthService::with_keyring(
+            "test-client-id".to_string(),
+            "test-client-secret".to_string(),
+            "http://localhost:8080/callback".to_string(),
+            keyring,
+        );
+        assert_eq!(
+            service.decrypt_state(&encrypted).unwrap(),
+            "rotating-challenge"
+        );
+    }
+
     #[test]
     fn test_generate_auth_url() {
         let service = create_test_service();
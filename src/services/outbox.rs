@@ -0,0 +1,264 @@
+use std::time::Duration as StdDuration;
+
+use time::{Duration, OffsetDateTime};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{OutboxMessage, OutboxPayload};
+use crate::repositories::OutboxRepository;
+use crate::services::{EmailService, WebhookService};
+
+/// 配信対象のメッセージを確認する間隔
+const POLL_INTERVAL: StdDuration = StdDuration::from_secs(5);
+
+/// 1 回に取得するメッセージ数
+const BATCH_SIZE: i64 = 20;
+
+/// 取得したメッセージを他のワーカーが取得しない期間（秒）
+const LEASE_SECS: i64 = 300;
+
+/// 最大試行回数（超えた場合は dead にする）
+pub const MAX_ATTEMPTS: i32 = 8;
+
+/// 再試行間隔の初期値（秒、試行ごとに倍にする）
+const BASE_BACKOFF_SECS: i64 = 30;
+
+/// 再試行間隔の上限（秒）
+const MAX_BACKOFF_SECS: i64 = 3600;
+
+/// 送信キュー（outbox）サービス
+///
+/// メッセージを追加し、配信できなかった（dead の）メッセージを管理する。
+/// 配信は `OutboxWorker` が行う
+#[derive(Clone)]
+pub struct OutboxService {
+    repo: OutboxRepository,
+}
+
+impl OutboxService {
+    /// 新しい OutboxService を作成
+    pub fn new(repo: OutboxRepository) -> Self {
+        Self { repo }
+    }
+
+    /// メッセージを送信キューに追加
+    ///
+    /// DB の更新と同時に送るメッセージは、各リポジトリの同じトランザクション内で追加すること
+    pub async fn enqueue(&self, payload: impl Into<OutboxPayload>) -> Result<Uuid, AppError> {
+        let payload = payload.into();
+        let id = self.repo.enqueue(&payload).await?;
+        tracing::debug!(message_id = %id, kind = payload.kind(), "送信キューに追加");
+        Ok(id)
+    }
+
+    /// dead のメッセージを新しい順に取得
+    pub async fn list_dead(&self, limit: i64, offset: i64) -> Result<Vec<OutboxMessage>, AppError> {
+        Ok(self.repo.list_dead(limit, offset).await?)
+    }
+
+    /// dead のメッセージを再試行対象に戻す
+    pub async fn requeue_dead(&self, id: Uuid) -> Result<(), AppError> {
+        if !self.repo.requeue_dead(id).await? {
+            return Err(AppError::OutboxMessageNotFound);
+        }
+        tracing::info!(message_id = %id, "dead メッセージを再試行対象に戻しました");
+        Ok(())
+    }
+
+    /// 指定日時より前に dead になったメッセージを削除
+    pub async fn purge_dead(&self, before: OffsetDateTime) -> Result<u64, AppError> {
+        let deleted = self.repo.delete_dead_before(before).await?;
+        if deleted > 0 {
+            tracing::info!(deleted, "古い dead メッセージを削除");
+        }
+        Ok(deleted)
+    }
+}
+
+/// 送信キューの配信ワーカー
+///
+/// 複数のレプリカで起動しても、メッセージは `FOR UPDATE SKIP LOCKED` で 1 つのワーカーだけが取得する。
+/// 失敗したメッセージは指数バックオフで再試行し、`MAX_ATTEMPTS` 回失敗したら dead にする
+#[derive(Clone)]
+pub struct OutboxWorker {
+    repo: OutboxRepository,
+    email_service: EmailService,
+    webhook_service: WebhookService,
+}
+
+/// 起動した配信ワーカー（`shutdown` で停止）
+pub struct OutboxWorkerHandle {
+    shutdown: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl OutboxWorker {
+    /// 新しい OutboxWorker を作成
+    pub fn new(
+        repo: OutboxRepository,
+        email_service: EmailService,
+        webhook_service: WebhookService,
+    ) -> Self {
+        Self {
+            repo,
+            email_service,
+            webhook_service,
+        }
+    }
+
+    /// バックグラウンドで配信を開始
+    pub fn start(self) -> OutboxWorkerHandle {
+        let (shutdown, receiver) = watch::channel(false);
+        let task = tokio::spawn(self.run(receiver));
+        tracing::info!("送信キューの配信ワーカーを起動");
+        OutboxWorkerHandle { shutdown, task }
+    }
+
+    async fn run(self, mut shutdown: watch::Receiver<bool>) {
+        loop {
+            // 取得したメッセージは最後まで処理してから停止する
+            let processed = match self.process_due().await {
+                Ok(processed) => processed,
+                Err(e) => {
+                    tracing::error!(error = ?e, "送信キューの処理に失敗");
+                    0
+                }
+            };
+            if *shutdown.borrow() {
+                break;
+            }
+
+            // バッチが埋まった場合は続けて処理する
+            if processed < BATCH_SIZE as usize {
+                tokio::select! {
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                    _ = shutdown.changed() => break,
+                }
+            }
+        }
+    }
+
+    /// 配信時刻を過ぎたメッセージを 1 バッチ配信
+    ///
+    /// 1 件の結果の記録に失敗しても、残りのメッセージの処理を続ける
+    /// （失敗したメッセージはリースの期限が切れた後に再取得される）
+    ///
+    /// # Returns
+    /// 取得したメッセージ数
+    pub async fn process_due(&self) -> Result<usize, AppError> {
+        let messages = self.repo.claim_due(BATCH_SIZE, LEASE_SECS).await?;
+        for message in &messages {
+            if let Err(e) = self.process(message).await {
+                tracing::error!(
+                    message_id = %message.id,
+                    kind = %message.kind,
+                    error = ?e,
+                    "配信結果の記録に失敗（リースの期限後に再試行）"
+                );
+            }
+        }
+        Ok(messages.len())
+    }
+
+    /// メッセージを配信し、結果を記録
+    async fn process(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        let payload = message.decode();
+        let result = match &payload {
+            Ok(payload) => self.deliver(payload).await,
+            Err(e) => Err(AppError::Internal(anyhow::anyhow!(
+                "Failed to decode outbox payload: {}",
+                e
+            ))),
+        };
+
+        match result {
+            Ok(()) => {
+                self.repo.delete(message.id).await?;
+                tracing::debug!(message_id = %message.id, kind = %message.kind, "メッセージを配信");
+            }
+            Err(e) if message.attempts >= MAX_ATTEMPTS => {
+                tracing::error!(
+                    message_id = %message.id,
+                    kind = %message.kind,
+                    attempts = message.attempts,
+                    error = ?e,
+                    "メッセージの配信を諦めました（dead）"
+                );
+                self.repo.mark_dead(message.id, &e.to_string()).await?;
+                if let Ok(payload) = &payload {
+                    self.on_dead(payload).await?;
+                }
+            }
+            Err(e) => {
+                let next_attempt_at = OffsetDateTime::now_utc() + backoff(message.attempts);
+                tracing::warn!(
+                    message_id = %message.id,
+                    kind = %message.kind,
+                    attempts = message.attempts,
+                    error = ?e,
+                    "メッセージの配信に失敗（再試行予定）"
+                );
+                self.repo
+                    .schedule_retry(message.id, next_attempt_at, &e.to_string())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// メッセージの種別ごとに配信
+    async fn deliver(&self, payload: &OutboxPayload) -> Result<(), AppError> {
+        match payload {
+            OutboxPayload::Email(message) => self.email_service.deliver(message).await,
+            OutboxPayload::Webhook { delivery_id } => {
+                self.webhook_service.deliver(*delivery_id).await
+            }
+        }
+    }
+
+    /// 配信を諦めたメッセージの後処理
+    async fn on_dead(&self, payload: &OutboxPayload) -> Result<(), AppError> {
+        match payload {
+            OutboxPayload::Email(_) => Ok(()),
+            OutboxPayload::Webhook { delivery_id } => {
+                self.webhook_service.mark_failed(*delivery_id).await
+            }
+        }
+    }
+}
+
+impl OutboxWorkerHandle {
+    /// 新しいメッセージの取得を止め、処理中のメッセージの完了を待つ
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        if let Err(e) = self.task.await {
+            tracing::error!(error = ?e, "配信ワーカーが異常終了");
+        }
+        tracing::info!("送信キューの配信ワーカーを停止");
+    }
+}
+
+/// `attempts` 回目の失敗後、次の試行までの待ち時間（指数バックオフ）
+fn backoff(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    let secs = BASE_BACKOFF_SECS.saturating_mul(1 << exponent);
+    Duration::seconds(secs.min(MAX_BACKOFF_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(1), Duration::seconds(30));
+        assert_eq!(backoff(2), Duration::seconds(60));
+        assert_eq!(backoff(3), Duration::seconds(120));
+        assert_eq!(backoff(7), Duration::seconds(1920));
+        assert_eq!(backoff(8), Duration::seconds(3600));
+        assert_eq!(backoff(i32::MAX), Duration::seconds(3600));
+        assert_eq!(backoff(0), Duration::seconds(30));
+    }
+}
@@ -0,0 +1,702 @@
+//! パスワードポリシー
+//!
+//! 設定（`Config`）に基づいてパスワードを検証する。
+//!
+//! - 長さ（最小・最大）
+//! - 文字種（大文字・小文字・数字・記号、それぞれ任意で必須化）
+//! - 強度スコア（zxcvbn と同じ 0-4 の尺度）
+//! - メールアドレスの構成要素を含まないこと
+//! - 漏洩パスワードでないこと（k-匿名性: SHA-1 の先頭 5 文字のみで照会）
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+
+use crate::config::{Config, SharedConfig};
+use crate::error::AppError;
+
+/// 漏洩チェック API のタイムアウト
+const BREACH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 強度スコアの閾値（推測回数の log2。zxcvbn の 10^3, 10^6, 10^8, 10^10 に相当）
+const STRENGTH_THRESHOLDS: [f64; 4] = [10.0, 20.0, 26.6, 33.2];
+
+/// 辞書語 1 語あたりの推測コスト（bit）
+const DICTIONARY_WORD_BITS: f64 = 10.0;
+
+/// 繰り返し・連続文字の推測コスト（bit）
+const PREDICTABLE_CHAR_BITS: f64 = 1.0;
+
+/// ブロックリストに含めるメールアドレス構成要素の最小文字数
+const MIN_EMAIL_PART_LENGTH: usize = 4;
+
+/// そのまま使われることの多いパスワード（完全一致でスコア 0）
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456789",
+    "12345678",
+    "1234567890",
+    "password",
+    "password1",
+    "password123",
+    "passw0rd",
+    "qwerty123",
+    "qwertyuiop",
+    "11111111",
+    "00000000",
+    "iloveyou",
+    "abc12345",
+    "letmein123",
+    "welcome1",
+    "admin123",
+];
+
+/// パスワードに含まれやすい辞書語（部分一致）
+const COMMON_WORDS: &[&str] = &[
+    "password", "passw0rd", "qwerty", "letmein", "welcome", "admin", "login", "dragon", "monkey",
+    "master", "sunshine", "iloveyou", "princess", "football", "baseball", "shadow", "superman",
+    "trustno", "secret", "oxgate",
+];
+
+/// キーボード配列（隣接キーの並びは推測しやすい）
+const KEYBOARD_ROWS: &[&str] = &["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// パスワードポリシー違反
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PasswordPolicyViolation {
+    #[error("パスワードは{min_length}文字以上で入力してください")]
+    TooShort { min_length: usize },
+
+    #[error("パスワードは{max_length}文字以下で入力してください")]
+    TooLong { max_length: usize },
+
+    #[error("パスワードに英大文字を含めてください")]
+    MissingUppercase,
+
+    #[error("パスワードに英小文字を含めてください")]
+    MissingLowercase,
+
+    #[error("パスワードに数字を含めてください")]
+    MissingDigit,
+
+    #[error("パスワードに記号を含めてください")]
+    MissingSymbol,
+
+    #[error("パスワードが推測されやすすぎます")]
+    TooWeak { score: u8, min_score: u8 },
+
+    #[error("パスワードにメールアドレスの一部を含めないでください")]
+    ContainsEmail,
+
+    #[error("このパスワードは過去に漏洩しています。別のパスワードを使用してください")]
+    Breached,
+}
+
+impl PasswordPolicyViolation {
+    /// クライアント向けの機械可読なコード
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::TooShort { .. } => "too_short",
+            Self::TooLong { .. } => "too_long",
+            Self::MissingUppercase => "missing_uppercase",
+            Self::MissingLowercase => "missing_lowercase",
+            Self::MissingDigit => "missing_digit",
+            Self::MissingSymbol => "missing_symbol",
+            Self::TooWeak { .. } => "too_weak",
+            Self::ContainsEmail => "contains_email",
+            Self::Breached => "breached",
+        }
+    }
+}
+
+/// エラーレスポンスに含める違反内容
+#[derive(Debug, Serialize)]
+pub struct PasswordPolicyViolationResponse {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl From<&PasswordPolicyViolation> for PasswordPolicyViolationResponse {
+    fn from(violation: &PasswordPolicyViolation) -> Self {
+        Self {
+            code: violation.code(),
+            message: violation.to_string(),
+        }
+    }
+}
+
+/// 漏洩パスワードの照会先
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreachSource {
+    /// ローカルのハッシュプレフィックスデータセット（`<PREFIX>.txt` を格納したディレクトリ）
+    Dataset(PathBuf),
+    /// HIBP 互換の Range API（`{base}/range/{prefix}`）
+    Api(String),
+}
+
+/// パスワードポリシー（設定のスナップショットから構築）
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// 必要な強度スコア（0-4、0 の場合はチェックしない）
+    pub min_strength: u8,
+    /// 漏洩チェックの照会先（未設定の場合はチェックしない）
+    pub breach_source: Option<BreachSource>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 128,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+            min_strength: 0,
+            breach_source: None,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// 設定からポリシーを構築
+    pub fn from_config(config: &Config) -> Self {
+        let breach_source = match (
+            &config.password_breach_dataset_path,
+            &config.password_breach_check_url,
+        ) {
+            (Some(path), _) => Some(BreachSource::Dataset(PathBuf::from(path))),
+            (None, Some(url)) => Some(BreachSource::Api(url.trim_end_matches('/').to_string())),
+            (None, None) => None,
+        };
+
+        Self {
+            min_length: config.password_min_length,
+            max_length: config.password_max_length,
+            require_uppercase: config.password_require_uppercase,
+            require_lowercase: config.password_require_lowercase,
+            require_digit: config.password_require_digit,
+            require_symbol: config.password_require_symbol,
+            min_strength: config.password_min_strength,
+            breach_source,
+        }
+    }
+
+    /// 漏洩チェック以外のルールで検証し、全ての違反を返す
+    ///
+    /// # Arguments
+    /// * `email` - ユーザーのメールアドレス（分かっている場合のみ）
+    pub fn check(&self, password: &str, email: Option<&str>) -> Vec<PasswordPolicyViolation> {
+        let mut violations = Vec::new();
+
+        // 長さは文字数で判定（マルチバイト文字を 1 文字として数える）
+        let length = password.chars().count();
+        if length < self.min_length {
+            violations.push(PasswordPolicyViolation::TooShort {
+                min_length: self.min_length,
+            });
+        }
+        if length > self.max_length {
+            violations.push(PasswordPolicyViolation::TooLong {
+                max_length: self.max_length,
+            });
+        }
+
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            violations.push(PasswordPolicyViolation::MissingUppercase);
+        }
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            violations.push(PasswordPolicyViolation::MissingLowercase);
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(PasswordPolicyViolation::MissingDigit);
+        }
+        if self.require_symbol && !password.chars().any(is_symbol) {
+            violations.push(PasswordPolicyViolation::MissingSymbol);
+        }
+
+        let email_parts = email.map(email_parts).unwrap_or_default();
+        let lower = password.to_lowercase();
+        if email_parts.iter().any(|part| lower.contains(part.as_str())) {
+            violations.push(PasswordPolicyViolation::ContainsEmail);
+        }
+
+        if self.min_strength > 0 {
+            let score = estimate_strength(password, &email_parts);
+            if score < self.min_strength {
+                violations.push(PasswordPolicyViolation::TooWeak {
+                    score,
+                    min_score: self.min_strength,
+                });
+            }
+        }
+
+        violations
+    }
+
+    /// 漏洩チェック以外のルールで検証
+    pub fn validate(&self, password: &str, email: Option<&str>) -> Result<(), AppError> {
+        let violations = self.check(password, email);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::PasswordPolicy(violations))
+        }
+    }
+}
+
+/// パスワードポリシーサービス
+///
+/// 設定はリロードに追従するため、検証のたびに現在のスナップショットからポリシーを構築する
+#[derive(Clone)]
+pub struct PasswordPolicyService {
+    config: SharedConfig,
+    http_client: reqwest::Client,
+}
+
+impl PasswordPolicyService {
+    /// 新しい PasswordPolicyService を作成
+    pub fn new(config: SharedConfig) -> Self {
+        Self {
+            config,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// 現在の設定のポリシーを取得
+    pub fn policy(&self) -> PasswordPolicy {
+        PasswordPolicy::from_config(&self.config.load())
+    }
+
+    /// 全てのルール（漏洩チェックを含む）で検証
+    ///
+    /// 漏洩チェックは他のルールを満たした場合のみ行う（不要な外部照会を避ける）
+    ///
+    /// # Security
+    /// - パスワードはログに出力しない
+    pub async fn validate(&self, password: &str, email: Option<&str>) -> Result<(), AppError> {
+        let policy = self.policy();
+        policy.validate(password, email)?;
+        self.ensure_not_breached(&policy, password).await
+    }
+
+    /// 漏洩パスワードでないことを確認（照会先が未設定の場合は何もしない）
+    pub async fn ensure_not_breached(
+        &self,
+        policy: &PasswordPolicy,
+        password: &str,
+    ) -> Result<(), AppError> {
+        if let Some(source) = &policy.breach_source
+            && self.is_breached(source, password).await
+        {
+            return Err(AppError::PasswordPolicy(vec![
+                PasswordPolicyViolation::Breached,
+            ]));
+        }
+        Ok(())
+    }
+
+    /// 漏洩パスワードかを照会
+    ///
+    /// 照会先に到達できない場合は登録・リセットを止めないよう `false` を返す（fail-open）
+    async fn is_breached(&self, source: &BreachSource, password: &str) -> bool {
+        let (prefix, suffix) = sha1_prefix_suffix(password);
+
+        let result = match source {
+            BreachSource::Dataset(dir) => read_dataset_range(dir, &prefix).await,
+            BreachSource::Api(base) => self.fetch_api_range(base, &prefix).await,
+        };
+
+        match result {
+            Ok(Some(body)) => range_contains(&body, &suffix),
+            Ok(None) => false,
+            Err(e) => {
+                tracing::warn!(error = %e, "漏洩パスワードチェックに失敗（スキップ）");
+                false
+            }
+        }
+    }
+
+    /// HIBP 互換 API から Range を取得
+    async fn fetch_api_range(&self, base: &str, prefix: &str) -> anyhow::Result<Option<String>> {
+        let body = self
+            .http_client
+            .get(format!("{}/range/{}", base, prefix))
+            // レスポンスサイズから照会内容を推測されないようにする
+            .header("Add-Padding", "true")
+            .timeout(BREACH_CHECK_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(Some(body))
+    }
+}
+
+/// ローカルデータセットから Range を読み込む（ファイルがない場合は `None`）
+async fn read_dataset_range(dir: &Path, prefix: &str) -> anyhow::Result<Option<String>> {
+    let path = dir.join(format!("{}.txt", prefix));
+    match tokio::fs::read_to_string(&path).await {
+        Ok(body) => Ok(Some(body)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            tracing::warn!(path = %path.display(), "漏洩パスワードデータセットにプレフィックスがありません");
+            Ok(None)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// パスワードの SHA-1（大文字 16 進）を先頭 5 文字と残りに分割
+fn sha1_prefix_suffix(password: &str) -> (String, String) {
+    let hash = Sha1::digest(password.as_bytes())
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<String>();
+    let (prefix, suffix) = hash.split_at(5);
+    (prefix.to_string(), suffix.to_string())
+}
+
+/// Range（`SUFFIX:COUNT` の行）にサフィックスが含まれるか
+///
+/// パディング用の `COUNT = 0` の行は無視する
+fn range_contains(body: &str, suffix: &str) -> bool {
+    body.lines().any(|line| {
+        let Some((candidate, count)) = line.trim().split_once(':') else {
+            return false;
+        };
+        candidate.eq_ignore_ascii_case(suffix) && count.trim().parse::<u64>().is_ok_and(|c| c > 0)
+    })
+}
+
+/// 記号（英数字・空白以外の ASCII 文字）か
+fn is_symbol(c: char) -> bool {
+    c.is_ascii_punctuation()
+}
+
+/// メールアドレスをブロックリスト用の構成要素に分割
+///
+/// `taro.yamada@example.co.jp` → `["taro.yamada", "taro", "yamada", "example"]`
+/// （短すぎる要素とトップレベルドメインは除外）
+fn email_parts(email: &str) -> Vec<String> {
+    let email = email.trim().to_lowercase();
+    let (local, domain) = email.split_once('@').unwrap_or((email.as_str(), ""));
+
+    let mut parts = vec![local.to_string()];
+    parts.extend(
+        local
+            .split(|c: char| !c.is_alphanumeric())
+            .map(str::to_string),
+    );
+
+    let mut labels: Vec<&str> = domain.split('.').collect();
+    labels.pop();
+    parts.extend(labels.into_iter().map(str::to_string));
+
+    parts.retain(|part| part.chars().count() >= MIN_EMAIL_PART_LENGTH);
+    parts.dedup();
+    parts
+}
+
+/// パスワード強度を 0-4 で推定（zxcvbn 互換の尺度）
+///
+/// 推測に必要な試行回数（bit）を概算する。
+/// - よく使われるパスワードは 0
+/// - 辞書語・ユーザー情報は 1 語あたり固定コスト
+/// - 直前と同じ文字・連続する文字・隣接キーは低コスト
+/// - それ以外は使用文字種の数から算出
+pub fn estimate_strength(password: &str, user_inputs: &[String]) -> u8 {
+    let lower = password.to_lowercase();
+    if password.is_empty() || COMMON_PASSWORDS.contains(&lower.as_str()) {
+        return 0;
+    }
+
+    let chars: Vec<char> = lower.chars().collect();
+    let mut covered = vec![false; chars.len()];
+    let mut bits = 0.0;
+
+    let words = COMMON_WORDS
+        .iter()
+        .copied()
+        .chain(user_inputs.iter().map(String::as_str));
+    for word in words {
+        let word: Vec<char> = word.chars().collect();
+        if word.is_empty() || word.len() > chars.len() {
+            continue;
+        }
+        for start in 0..=chars.len() - word.len() {
+            let range = start..start + word.len();
+            if chars[range.clone()] == word[..] && !covered[range.clone()].iter().any(|c| *c) {
+                covered[range].iter_mut().for_each(|c| *c = true);
+                bits += DICTIONARY_WORD_BITS;
+            }
+        }
+    }
+
+    let charset_bits = (charset_size(password) as f64).log2();
+    for (i, c) in chars.iter().enumerate() {
+        if covered[i] {
+            continue;
+        }
+        let predictable = i > 0 && is_predictable_pair(chars[i - 1], *c);
+        bits += if predictable {
+            PREDICTABLE_CHAR_BITS
+        } else {
+            charset_bits
+        };
+    }
+
+    STRENGTH_THRESHOLDS
+        .iter()
+        .take_while(|threshold| bits >= **threshold)
+        .count() as u8
+}
+
+/// 使用している文字種から 1 文字あたりの候補数を算出
+fn charset_size(password: &str) -> usize {
+    let mut size = 0;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        size += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        size += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        size += 10;
+    }
+    if password.chars().any(is_symbol) {
+        size += 33;
+    }
+    if !password.is_ascii() {
+        size += 100;
+    }
+    size.max(10)
+}
+
+/// 直前の文字から容易に推測できる並びか（繰り返し・連番・キーボードの隣接キー）
+fn is_predictable_pair(prev: char, current: char) -> bool {
+    if (prev as u32).abs_diff(current as u32) <= 1 {
+        return true;
+    }
+    KEYBOARD_ROWS.iter().any(|row| {
+        let row: Vec<char> = row.chars().collect();
+        row.windows(2).any(|pair| {
+            (pair[0] == prev && pair[1] == current) || (pair[1] == prev && pair[0] == current)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_length() {
+        let policy = PasswordPolicy::default();
+
+        assert_eq!(
+            policy.check("short", None),
+            vec![PasswordPolicyViolation::TooShort { min_length: 8 }]
+        );
+        assert!(policy.check("password123", None).is_empty());
+        assert_eq!(
+            policy.check(&"a".repeat(129), None),
+            vec![PasswordPolicyViolation::TooLong { max_length: 128 }]
+        );
+        // マルチバイト文字は 1 文字として数える
+        assert!(policy.check("パスワード確認用", None).is_empty());
+    }
+
+    #[test]
+    fn test_character_classes_are_all_reported() {
+        let policy = PasswordPolicy {
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: true,
+            ..Default::default()
+        };
+
+        let codes: Vec<_> = policy
+            .check("abcdefghij", None)
+            .iter()
+            .map(|v| v.code())
+            .collect();
+        assert_eq!(
+            codes,
+            ["missing_uppercase", "missing_digit", "missing_symbol"]
+        );
+
+        assert!(policy.check("Abcdefg1!", None).is_empty());
+    }
+
+    #[test]
+    fn test_email_parts_blocklist() {
+        let policy = PasswordPolicy::default();
+        let email = Some("taro.yamada@example.co.jp");
+
+        assert!(
+            policy
+                .check("Yamada-2024-pass", email)
+                .contains(&PasswordPolicyViolation::ContainsEmail)
+        );
+        assert!(
+            policy
+                .check("my-example-site", email)
+                .contains(&PasswordPolicyViolation::ContainsEmail)
+        );
+        // 短い要素・TLD はブロックしない
+        assert!(policy.check("jp-co-unrelated", email).is_empty());
+    }
+
+    #[test]
+    fn test_email_parts() {
+        assert_eq!(
+            email_parts("Taro.Yamada@Example.co.jp"),
+            vec!["taro.yamada", "taro", "yamada", "example"]
+        );
+        assert_eq!(email_parts("bob@ex.com"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_estimate_strength() {
+        assert_eq!(estimate_strength("password123", &[]), 0);
+        assert_eq!(estimate_strength("", &[]), 0);
+        assert!(estimate_strength("aaaaaaaaaaaa", &[]) <= 1);
+        assert!(estimate_strength("qwertyasdfgh", &[]) <= 1);
+        assert!(estimate_strength("Password1", &[]) <= 1);
+        assert_eq!(estimate_strength("correcthorsebatterystaple", &[]), 4);
+        assert_eq!(estimate_strength("Tr0ub4dor&3", &[]), 4);
+
+        // ユーザー情報を含むと弱くなる
+        let inputs = vec!["yamada".to_string()];
+        assert!(estimate_strength("yamada1985", &inputs) < estimate_strength("yqmxdz1985", &[]));
+    }
+
+    #[test]
+    fn test_min_strength() {
+        let policy = PasswordPolicy {
+            min_strength: 3,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            policy.check("password123", None),
+            vec![PasswordPolicyViolation::TooWeak {
+                score: 0,
+                min_score: 3
+            }]
+        );
+        assert!(policy.check("correct horse battery", None).is_empty());
+    }
+
+    #[test]
+    fn test_sha1_prefix_suffix() {
+        // SHA-1("password") = 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8
+        let (prefix, suffix) = sha1_prefix_suffix("password");
+        assert_eq!(prefix, "5BAA6");
+        assert_eq!(suffix, "1E4C9B93F3F0682250B6CF8331B7EE68FD8");
+    }
+
+    #[test]
+    fn test_range_contains() {
+        let body = "0018A45C4D1DEF81644B54AB7F969B88D65:1\r\n\
+                    1E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\r\n\
+                    011053FD0102E94D6AE2F8B83D76FAF94F6:0";
+
+        assert!(range_contains(body, "1E4C9B93F3F0682250B6CF8331B7EE68FD8"));
+        assert!(range_contains(body, "1e4c9b93f3f0682250b6cf8331b7ee68fd8"));
+        // パディング行（COUNT = 0）は一致とみなさない
+        assert!(!range_contains(body, "011053FD0102E94D6AE2F8B83D76FAF94F6"));
+        assert!(!range_contains(body, "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"));
+    }
+
+    fn service_with(source: BreachSource) -> PasswordPolicyService {
+        let mut env: std::collections::HashMap<String, String> = [
+            ("DATABASE_URL", "postgres://localhost/oxgate"),
+            ("HYDRA_ADMIN_URL", "http://localhost:4445"),
+            ("TOTP_ISSUER", "oxgate"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        match source {
+            BreachSource::Dataset(path) => env.insert(
+                "PASSWORD_BREACH_DATASET_PATH".to_string(),
+                path.display().to_string(),
+            ),
+            BreachSource::Api(url) => env.insert("PASSWORD_BREACH_CHECK_URL".to_string(), url),
+        };
+        let config: Config = envy::from_iter(env).unwrap();
+        PasswordPolicyService::new(SharedConfig::new(config))
+    }
+
+    #[tokio::test]
+    async fn test_breach_check_with_local_dataset() {
+        let dir = std::env::temp_dir().join(format!("oxgate-pwned-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("5BAA6.txt"),
+            "1E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\n",
+        )
+        .unwrap();
+
+        let service = service_with(BreachSource::Dataset(dir.clone()));
+
+        let result = service.validate("password", None).await;
+        assert!(matches!(
+            result,
+            Err(AppError::PasswordPolicy(ref v)) if v == &[PasswordPolicyViolation::Breached]
+        ));
+        // データセットにないプレフィックスは通過する
+        assert!(service.validate("not-in-the-dataset", None).await.is_ok());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_breach_check_with_range_api() {
+        use axum::{Router, extract::Path as AxumPath, http::HeaderMap, routing::get};
+
+        let app = Router::new().route(
+            "/range/{prefix}",
+            get(
+                |AxumPath(prefix): AxumPath<String>, headers: HeaderMap| async move {
+                    assert_eq!(headers["add-padding"], "true");
+                    if prefix == "5BAA6" {
+                        "1E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824\r\nAAAA:0".to_string()
+                    } else {
+                        "AAAA:0".to_string()
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let service = service_with(BreachSource::Api(format!("http://{}/", addr)));
+
+        assert!(service.validate("password", None).await.is_err());
+        assert!(
+            service
+                .validate("unbreached-passphrase", None)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_breach_check_fails_open() {
+        // 到達できない照会先ではチェックをスキップする
+        let service = service_with(BreachSource::Api("http://127.0.0.1:9".to_string()));
+        assert!(service.validate("password", None).await.is_ok());
+    }
+}
@@ -3,18 +3,20 @@ use std::sync::Arc;
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use sha2::{Digest, Sha256};
 use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
 
 use crate::config::Config;
 use crate::error::AppError;
+use crate::models::EmailMessage;
 use crate::repositories::{PasswordResetTokenRepository, UserRepository};
-use crate::services::{EmailService, auth::hash_password};
+use crate::services::{PasswordPolicyService, auth::PasswordHashService};
 
 /// パスワードリセットサービス
 #[derive(Clone)]
 pub struct PasswordResetService {
     user_repo: UserRepository,
     token_repo: PasswordResetTokenRepository,
-    email_service: EmailService,
+    password_policy: PasswordPolicyService,
     config: Arc<Config>,
 }
 
@@ -23,13 +25,13 @@ impl PasswordResetService {
     pub fn new(
         user_repo: UserRepository,
         token_repo: PasswordResetTokenRepository,
-        email_service: EmailService,
+        password_policy: PasswordPolicyService,
         config: Arc<Config>,
     ) -> Self {
         Self {
             user_repo,
             token_repo,
-            email_service,
+            password_policy,
             config,
         }
     }
@@ -39,7 +41,10 @@ impl PasswordResetService {
     /// # Security
     /// - ユーザーが存在しない場合も常に成功を返す（情報漏洩防止）
     /// - トークン（平文）はログに出力しない
-    pub async fn request_reset(&self, email: &str) -> Result<(), AppError> {
+    ///
+    /// # Returns
+    /// リセットメールを送信したユーザーのID（ユーザー不在の場合は None）
+    pub async fn request_reset(&self, email: &str) -> Result<Option<Uuid>, AppError> {
         tracing::info!(email = %email, "パスワードリセットリクエスト");
 
         // ユーザー検索
@@ -50,7 +55,7 @@ impl PasswordResetService {
             Some(u) => u,
             None => {
                 tracing::info!(email = %email, "パスワードリセット: ユーザー不在（成功レスポンス返却）");
-                return Ok(());
+                return Ok(None);
             }
         };
 
@@ -64,29 +69,32 @@ impl PasswordResetService {
         let expires_at = OffsetDateTime::now_utc()
             + Duration::seconds(self.config.password_reset_token_ttl_secs);
 
-        // トークンをDBに保存
-        self.token_repo
-            .create(user.id, &token_hash, expires_at)
-            .await?;
-
         // リセットURLを構築
         let reset_url = self.build_reset_url(&token);
 
-        // メール送信
-        self.email_service
-            .send_password_reset_email(email, &reset_url)
+        // トークンの保存とメールの送信キューへの追加を同じトランザクションで行う
+        // （SMTP への送信はワーカーが行うため、ユーザーの有無で応答時間が変わらない）
+        let message = EmailMessage::PasswordReset {
+            to: user.email.clone(),
+            reset_url,
+        };
+        self.token_repo
+            .create(user.id, &token_hash, expires_at, &message.into())
             .await?;
 
-        tracing::info!(email = %email, "パスワードリセットメール送信完了");
+        tracing::info!(email = %email, "パスワードリセットメールを送信キューに追加");
 
-        Ok(())
+        Ok(Some(user.id))
     }
 
     /// パスワードをリセット
     ///
     /// # Security
     /// - トークン・新パスワードはログに出力しない
-    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<(), AppError> {
+    ///
+    /// # Returns
+    /// パスワードを更新したユーザーのID
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<Uuid, AppError> {
         // トークンをSHA256ハッシュ化
         let token_hash = self.hash_token(token);
 
@@ -109,20 +117,32 @@ impl PasswordResetService {
             return Err(AppError::TokenExpired);
         }
 
+        // パスワードポリシー（メールアドレスの構成要素・漏洩チェックを含む）
+        let user = self
+            .user_repo
+            .find_by_id(reset_token.user_id)
+            .await?
+            .ok_or(AppError::TokenNotFound)?;
+        self.password_policy
+            .validate(new_password, Some(&user.email))
+            .await?;
+
         // パスワードをargon2ハッシュ化
-        let password_hash = hash_password(new_password)?;
+        let password_hash = PasswordHashService::from_config(&self.config)?.hash(new_password)?;
 
         // パスワードを更新
         self.user_repo
             .update_password(reset_token.user_id, &password_hash)
             .await?;
 
-        // トークンを使用済みにマーク
-        self.token_repo.mark_as_used(reset_token.id).await?;
+        // トークンを使用済みにマーク（同じユーザーの未使用トークンも全て無効化）
+        self.token_repo
+            .invalidate_all_for_user(reset_token.user_id)
+            .await?;
 
         tracing::info!(user_id = %reset_token.user_id, "パスワードリセット完了");
 
-        Ok(())
+        Ok(reset_token.user_id)
     }
 
     /// 32バイトのランダムトークンを生成
@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::UserProfile;
+use crate::repositories::UserProfileRepository;
+use crate::services::hydra::ConsentSession;
+use crate::services::oauth::OAuthUserInfo;
+
+/// プロフィールのクレームを ID トークンに含めるスコープ（OIDC）
+const PROFILE_SCOPE: &str = "profile";
+
+/// 名前項目の最大文字数
+const MAX_NAME_LENGTH: usize = 255;
+
+/// プロフィール画像 URL の最大長
+const MAX_PICTURE_URL_LENGTH: usize = 2048;
+
+/// ロケールの最大長（BCP 47 言語タグ）
+const MAX_LOCALE_LENGTH: usize = 35;
+
+/// タイムゾーン名の最大長
+const MAX_ZONEINFO_LENGTH: usize = 64;
+
+/// メタデータのキーの最大文字数
+const MAX_METADATA_KEY_LENGTH: usize = 64;
+
+/// メタデータの最大サイズ（JSON シリアライズ後のバイト数）
+const MAX_METADATA_BYTES: usize = 16 * 1024;
+
+/// プロフィールの部分更新
+///
+/// 省略した項目は変更しない。空文字を指定すると未設定に戻す。
+/// `metadata` は指定したキーのみ上書きし、値が null のキーは削除する
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileUpdate {
+    pub display_name: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    pub picture_url: Option<String>,
+    pub locale: Option<String>,
+    pub zoneinfo: Option<String>,
+    pub metadata: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl ProfileUpdate {
+    /// 変更をプロフィールに反映し、反映後の内容を検証
+    pub fn apply(self, profile: &mut UserProfile) -> Result<(), AppError> {
+        let fields = [
+            (&mut profile.display_name, self.display_name),
+            (&mut profile.given_name, self.given_name),
+            (&mut profile.family_name, self.family_name),
+            (&mut profile.picture_url, self.picture_url),
+            (&mut profile.locale, self.locale),
+            (&mut profile.zoneinfo, self.zoneinfo),
+        ];
+        for (field, value) in fields {
+            if let Some(value) = value {
+                let value = value.trim();
+                *field = (!value.is_empty()).then(|| value.to_string());
+            }
+        }
+
+        if let Some(changes) = self.metadata {
+            if !profile.metadata.is_object() {
+                profile.metadata = serde_json::Value::Object(serde_json::Map::new());
+            }
+            if let serde_json::Value::Object(metadata) = &mut profile.metadata {
+                for (key, value) in changes {
+                    if value.is_null() {
+                        metadata.remove(&key);
+                    } else {
+                        metadata.insert(key, value);
+                    }
+                }
+            }
+        }
+
+        validate_profile(profile)
+    }
+}
+
+/// プロフィールサービス
+#[derive(Clone)]
+pub struct ProfileService {
+    profile_repo: UserProfileRepository,
+}
+
+impl ProfileService {
+    /// 新しい ProfileService を作成
+    pub fn new(profile_repo: UserProfileRepository) -> Self {
+        Self { profile_repo }
+    }
+
+    /// ユーザーのプロフィールを取得（未設定の場合は空のプロフィール）
+    pub async fn profile(&self, user_id: Uuid) -> Result<UserProfile, AppError> {
+        Ok(self
+            .profile_repo
+            .find_by_user_id(user_id)
+            .await?
+            .unwrap_or_else(|| UserProfile::empty(user_id)))
+    }
+
+    /// プロフィールを部分更新
+    ///
+    /// 同時に更新された場合に一方の変更（`metadata` のキーを含む）が失われないよう、
+    /// 行ロックを取った上で現在の内容に変更を反映する
+    pub async fn update(
+        &self,
+        user_id: Uuid,
+        update: ProfileUpdate,
+    ) -> Result<UserProfile, AppError> {
+        let profile = self
+            .profile_repo
+            .modify(user_id, |profile| update.apply(profile))
+            .await?;
+        tracing::info!(user_id = %user_id, "プロフィールを更新");
+        Ok(profile)
+    }
+
+    /// 同意承認時に Hydra に渡すセッション
+    ///
+    /// `profile` スコープが許可された場合のみ、設定済みのプロフィールを ID トークンの
+    /// 標準クレームとして含める（それ以外、または設定済みの項目がない場合は None）
+    pub async fn consent_session(
+        &self,
+        subject: &str,
+        granted_scope: &[String],
+    ) -> Result<Option<ConsentSession>, AppError> {
+        if !granted_scope.iter().any(|scope| scope == PROFILE_SCOPE) {
+            return Ok(None);
+        }
+        let Ok(user_id) = Uuid::parse_str(subject) else {
+            return Ok(None);
+        };
+
+        let id_token = id_token_claims(&self.profile(user_id).await?);
+        Ok((!id_token.is_empty()).then(|| ConsentSession {
+            id_token,
+            ..Default::default()
+        }))
+    }
+
+    /// ソーシャルログインのユーザー情報で未設定の項目を補完
+    ///
+    /// プロバイダーの値が不正な場合はその項目を無視する。
+    /// 失敗してもログインは継続する（ログに残す）
+    pub async fn fill_from_oauth(&self, user_id: Uuid, user_info: &OAuthUserInfo) {
+        let profile = profile_from_oauth(user_id, user_info);
+        if let Err(e) = self.profile_repo.fill_missing(&profile).await {
+            tracing::warn!(user_id = %user_id, error = ?e, "プロフィールの補完に失敗");
+        }
+    }
+}
+
+/// プロフィールを OIDC の標準クレームに変換（未設定の項目は含めない）
+fn id_token_claims(profile: &UserProfile) -> HashMap<String, serde_json::Value> {
+    [
+        ("name", &profile.display_name),
+        ("given_name", &profile.given_name),
+        ("family_name", &profile.family_name),
+        ("picture", &profile.picture_url),
+        ("locale", &profile.locale),
+        ("zoneinfo", &profile.zoneinfo),
+    ]
+    .into_iter()
+    .filter_map(|(claim, value)| Some((claim.to_string(), value.clone()?.into())))
+    .collect()
+}
+
+/// ソーシャルログインのユーザー情報からプロフィールを構築（不正な値は除外）
+fn profile_from_oauth(user_id: Uuid, user_info: &OAuthUserInfo) -> UserProfile {
+    let valid = |value: &Option<String>, check: fn(&str) -> Result<(), AppError>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty() && check(v).is_ok())
+            .map(str::to_string)
+    };
+
+    UserProfile {
+        display_name: valid(&user_info.name, validate_name),
+        given_name: valid(&user_info.given_name, validate_name),
+        family_name: valid(&user_info.family_name, validate_name),
+        picture_url: valid(&user_info.picture, validate_picture_url),
+        locale: valid(&user_info.locale, validate_locale),
+        ..UserProfile::empty(user_id)
+    }
+}
+
+/// プロフィール全体の検証
+fn validate_profile(profile: &UserProfile) -> Result<(), AppError> {
+    for name in [
+        &profile.display_name,
+        &profile.given_name,
+        &profile.family_name,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        validate_name(name)?;
+    }
+    if let Some(url) = &profile.picture_url {
+        validate_picture_url(url)?;
+    }
+    if let Some(locale) = &profile.locale {
+        validate_locale(locale)?;
+    }
+    if let Some(zoneinfo) = &profile.zoneinfo {
+        validate_zoneinfo(zoneinfo)?;
+    }
+    validate_metadata(&profile.metadata)
+}
+
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::Validation(format!(
+            "名前は {} 文字以内で入力してください",
+            MAX_NAME_LENGTH
+        )));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(AppError::Validation(
+            "名前に制御文字は使用できません".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_picture_url(url: &str) -> Result<(), AppError> {
+    let valid = url.len() <= MAX_PICTURE_URL_LENGTH
+        && Url::parse(url)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some());
+    if !valid {
+        return Err(AppError::Validation(
+            "picture_url には http(s) URL を指定してください".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// BCP 47 言語タグの簡易検証（例: ja, ja-JP, zh-Hant-TW）
+fn validate_locale(locale: &str) -> Result<(), AppError> {
+    let mut subtags = locale.split(['-', '_']);
+    let language_ok = subtags
+        .next()
+        .is_some_and(|s| (2..=3).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphabetic()));
+    let rest_ok =
+        subtags.all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    if locale.len() > MAX_LOCALE_LENGTH || !language_ok || !rest_ok {
+        return Err(AppError::Validation(
+            "locale には言語タグ（例: ja-JP）を指定してください".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// IANA タイムゾーン名の形式チェック（例: Asia/Tokyo, UTC）
+fn validate_zoneinfo(zoneinfo: &str) -> Result<(), AppError> {
+    let valid = zoneinfo.len() <= MAX_ZONEINFO_LENGTH
+        && zoneinfo.split('/').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+'))
+        });
+    if !valid {
+        return Err(AppError::Validation(
+            "zoneinfo にはタイムゾーン名（例: Asia/Tokyo）を指定してください".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_metadata(metadata: &serde_json::Value) -> Result<(), AppError> {
+    let Some(map) = metadata.as_object() else {
+        return Err(AppError::Validation(
+            "metadata は JSON オブジェクトで指定してください".to_string(),
+        ));
+    };
+    if map
+        .keys()
+        .any(|key| key.is_empty() || key.chars().count() > MAX_METADATA_KEY_LENGTH)
+    {
+        return Err(AppError::Validation(format!(
+            "metadata のキーは 1-{} 文字で指定してください",
+            MAX_METADATA_KEY_LENGTH
+        )));
+    }
+    if metadata.to_string().len() > MAX_METADATA_BYTES {
+        return Err(AppError::Validation(format!(
+            "metadata は {} バイト以内にしてください",
+            MAX_METADATA_BYTES
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn update(value: serde_json::Value) -> ProfileUpdate {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_apply_sets_and_clears_fields() {
+        let mut profile = UserProfile::empty(Uuid::new_v4());
+        profile.given_name = Some("Taro".to_string());
+
+        update(json!({ "display_name": "  Taro Yamada ", "given_name": "", "locale": "ja-JP" }))
+            .apply(&mut profile)
+            .unwrap();
+
+        assert_eq!(profile.display_name.as_deref(), Some("Taro Yamada"));
+        assert_eq!(profile.given_name, None);
+        assert_eq!(profile.locale.as_deref(), Some("ja-JP"));
+    }
+
+    #[test]
+    fn test_id_token_claims() {
+        let mut profile = UserProfile::empty(Uuid::new_v4());
+        assert!(id_token_claims(&profile).is_empty());
+
+        profile.display_name = Some("Taro Yamada".to_string());
+        profile.given_name = Some("Taro".to_string());
+        profile.family_name = Some("Yamada".to_string());
+        profile.picture_url = Some("https://example.com/taro.png".to_string());
+        profile.locale = Some("ja-JP".to_string());
+        profile.zoneinfo = Some("Asia/Tokyo".to_string());
+        profile.metadata = json!({ "plan": "pro" });
+
+        let claims = id_token_claims(&profile);
+        assert_eq!(
+            serde_json::to_value(&claims).unwrap(),
+            json!({
+                "name": "Taro Yamada",
+                "given_name": "Taro",
+                "family_name": "Yamada",
+                "picture": "https://example.com/taro.png",
+                "locale": "ja-JP",
+                "zoneinfo": "Asia/Tokyo",
+            })
+        );
+
+        profile.given_name = None;
+        assert!(!id_token_claims(&profile).contains_key("given_name"));
+    }
+
+    #[test]
+    fn test_apply_merges_metadata() {
+        let mut profile = UserProfile::empty(Uuid::new_v4());
+        profile.metadata = json!({ "theme": "dark", "plan": "free" });
+
+        update(json!({ "metadata": { "plan": "pro", "theme": null, "beta": true } }))
+            .apply(&mut profile)
+            .unwrap();
+
+        assert_eq!(profile.metadata, json!({ "plan": "pro", "beta": true }));
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_values() {
+        let invalid = [
+            json!({ "picture_url": "javascript:alert(1)" }),
+            json!({ "locale": "not a locale" }),
+            json!({ "zoneinfo": "Asia/../Tokyo " }),
+            json!({ "display_name": "a".repeat(MAX_NAME_LENGTH + 1) }),
+            json!({ "metadata": { "blob": "a".repeat(MAX_METADATA_BYTES) } }),
+        ];
+        for value in invalid {
+            let mut profile = UserProfile::empty(Uuid::new_v4());
+            assert!(
+                update(value.clone()).apply(&mut profile).is_err(),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        assert!(
+            serde_json::from_value::<ProfileUpdate>(json!({ "email": "x@example.com" })).is_err()
+        );
+    }
+
+    #[test]
+    fn test_profile_from_oauth_skips_invalid_values() {
+        let user_info = OAuthUserInfo {
+            id: "1".to_string(),
+            email: "user@example.com".to_string(),
+            name: Some("Taro Yamada".to_string()),
+            given_name: None,
+            family_name: Some(" ".to_string()),
+            picture: Some("ftp://example.com/a.png".to_string()),
+            locale: Some("ja".to_string()),
+        };
+
+        let profile = profile_from_oauth(Uuid::new_v4(), &user_info);
+
+        assert_eq!(profile.display_name.as_deref(), Some("Taro Yamada"));
+        assert_eq!(profile.family_name, None);
+        assert_eq!(profile.picture_url, None);
+        assert_eq!(profile.locale.as_deref(), Some("ja"));
+    }
+}
@@ -0,0 +1,344 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+use crate::config::SharedConfig;
+use crate::error::AppError;
+use crate::migrations::{self, SchemaError};
+use crate::services::hydra::HydraClient;
+use crate::services::oauth::{GITHUB_AUTH_URL, GOOGLE_DISCOVERY_URL};
+
+/// 各依存先の確認のタイムアウト
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 確認結果を再利用する期間（プローブのたびに依存先へ問い合わせない）
+const CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// キャッシュした確認結果と確認した時刻
+type CachedReport = Option<(Instant, Arc<ReadinessReport>)>;
+
+/// 依存先ごとの確認結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Error,
+}
+
+/// 全体の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadinessStatus {
+    /// 全ての依存先が利用可能
+    Ok,
+    /// 任意の依存先（SMTP・ソーシャルログインプロバイダー）が利用できない
+    Degraded,
+    /// 必須の依存先（Postgres・Hydra）が利用できない
+    Unavailable,
+}
+
+/// 依存先 1 件の確認結果
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyCheck {
+    pub name: &'static str,
+    /// 利用できない場合にリクエストを受け付けられないか
+    pub required: bool,
+    pub status: CheckStatus,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// レディネスの確認結果
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    pub status: ReadinessStatus,
+    #[serde(with = "time::serde::rfc3339")]
+    pub checked_at: OffsetDateTime,
+    pub checks: Vec<DependencyCheck>,
+}
+
+impl ReadinessReport {
+    /// 依存先ごとの結果から全体の状態を判定
+    fn new(checks: Vec<DependencyCheck>) -> Self {
+        let failed = |required: bool| {
+            checks
+                .iter()
+                .any(|check| check.required == required && check.status == CheckStatus::Error)
+        };
+        let status = if failed(true) {
+            ReadinessStatus::Unavailable
+        } else if failed(false) {
+            ReadinessStatus::Degraded
+        } else {
+            ReadinessStatus::Ok
+        };
+
+        Self {
+            status,
+            checked_at: OffsetDateTime::now_utc(),
+            checks,
+        }
+    }
+}
+
+/// レディネス（リクエストを処理できる状態か）の確認サービス
+///
+/// 依存先を並行して確認し、結果を `CACHE_TTL` の間キャッシュする
+#[derive(Clone)]
+pub struct ReadinessService {
+    db_pool: PgPool,
+    /// リードレプリカ（設定されている場合のみ）
+    db_read_pool: Option<PgPool>,
+    hydra_client: HydraClient,
+    config: SharedConfig,
+    http_client: reqwest::Client,
+    cache: Arc<Mutex<CachedReport>>,
+}
+
+impl ReadinessService {
+    /// 新しい ReadinessService を作成
+    pub fn new(
+        db_pool: PgPool,
+        db_read_pool: Option<PgPool>,
+        hydra_client: HydraClient,
+        config: SharedConfig,
+    ) -> Result<Self, AppError> {
+        let http_client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(CHECK_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            db_pool,
+            db_read_pool,
+            hydra_client,
+            config,
+            http_client,
+            cache: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// 依存先を確認（キャッシュが有効な場合はキャッシュを返す）
+    ///
+    /// 確認中に届いたプローブは、確認の完了を待って同じ結果を使う
+    pub async fn check(&self) -> Arc<ReadinessReport> {
+        let mut cache = self.cache.lock().await;
+        if let Some((checked, report)) = cache.as_ref()
+            && checked.elapsed() < CACHE_TTL
+        {
+            return report.clone();
+        }
+
+        let report = Arc::new(self.check_dependencies().await);
+        if report.status != ReadinessStatus::Ok {
+            let failed: Vec<_> = report
+                .checks
+                .iter()
+                .filter(|check| check.status == CheckStatus::Error)
+                .map(|check| check.name)
+                .collect();
+            tracing::warn!(status = ?report.status, failed = ?failed, "依存先の確認に失敗");
+        }
+        *cache = Some((Instant::now(), report.clone()));
+        report
+    }
+
+    /// 全ての依存先を並行して確認
+    async fn check_dependencies(&self) -> ReadinessReport {
+        let config = self.config.load();
+
+        let (postgres, replica, hydra, smtp, google, github) = tokio::join!(
+            run_check("postgres", true, self.check_postgres()),
+            optional(self.db_read_pool.as_ref(), |pool| {
+                run_check("postgres_replica", true, check_replica(pool))
+            }),
+            run_check("hydra", true, self.check_hydra()),
+            optional(
+                config
+                    .smtp_host
+                    .clone()
+                    .map(|host| (host, config.smtp_port)),
+                |(host, port)| run_check("smtp", false, check_smtp(host, port)),
+            ),
+            optional(config.google_client_id.as_ref(), |_| {
+                run_check(
+                    "google",
+                    false,
+                    self.check_provider(GOOGLE_DISCOVERY_URL, true),
+                )
+            }),
+            optional(config.github_client_id.as_ref(), |_| {
+                run_check("github", false, self.check_provider(GITHUB_AUTH_URL, false))
+            }),
+        );
+
+        let checks = [Some(postgres), replica, Some(hydra), smtp, google, github]
+            .into_iter()
+            .flatten()
+            .collect();
+        ReadinessReport::new(checks)
+    }
+
+    /// DB に接続でき、スキーマがこのバイナリのマイグレーションと一致するか
+    async fn check_postgres(&self) -> Result<(), String> {
+        migrations::check_schema(&self.db_pool)
+            .await
+            .map_err(|e| match e {
+                // 接続情報を含むことがあるため詳細は返さない
+                SchemaError::Migrate(e) => {
+                    tracing::warn!(error = %e, "データベースの確認に失敗");
+                    "データベースに接続できません".to_string()
+                }
+                e => e.to_string(),
+            })
+    }
+
+    /// Hydra の `/health/ready`
+    async fn check_hydra(&self) -> Result<(), String> {
+        self.hydra_client.check_ready().await.map_err(|e| {
+            tracing::warn!(error = ?e, "Hydra の確認に失敗");
+            "Hydra が応答しません".to_string()
+        })
+    }
+
+    /// ソーシャルログインプロバイダーへの疎通
+    ///
+    /// # Arguments
+    /// * `require_success` - 2xx の応答を必須とするか（false の場合は 5xx 以外を許容）
+    async fn check_provider(&self, url: &str, require_success: bool) -> Result<(), String> {
+        let response = self
+            .http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("接続できません: {}", e.without_url()))?;
+
+        let status = response.status();
+        if status.is_server_error() || (require_success && !status.is_success()) {
+            return Err(format!("予期しない応答: {}", status));
+        }
+        Ok(())
+    }
+}
+
+/// リードレプリカに接続できるか
+async fn check_replica(pool: &PgPool) -> Result<(), String> {
+    sqlx::query("SELECT 1")
+        .execute(pool)
+        .await
+        .map(drop)
+        .map_err(|e| {
+            tracing::warn!(error = %e, "リードレプリカの確認に失敗");
+            "リードレプリカに接続できません".to_string()
+        })
+}
+
+/// SMTP サーバーに TCP 接続できるか
+async fn check_smtp(host: String, port: u16) -> Result<(), String> {
+    TcpStream::connect((host.as_str(), port))
+        .await
+        .map(drop)
+        .map_err(|e| format!("接続できません: {}", e))
+}
+
+/// 確認を実行し、所要時間とあわせて記録
+async fn run_check(
+    name: &'static str,
+    required: bool,
+    check: impl Future<Output = Result<(), String>>,
+) -> DependencyCheck {
+    let started = Instant::now();
+    let result = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(result) => result,
+        Err(_) => Err("タイムアウトしました".to_string()),
+    };
+
+    DependencyCheck {
+        name,
+        required,
+        status: if result.is_ok() {
+            CheckStatus::Ok
+        } else {
+            CheckStatus::Error
+        },
+        latency_ms: started.elapsed().as_millis() as u64,
+        error: result.err(),
+    }
+}
+
+/// 設定されている場合のみ確認を実行
+async fn optional<T, F, Fut>(value: Option<T>, check: F) -> Option<DependencyCheck>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = DependencyCheck>,
+{
+    match value {
+        Some(value) => Some(check(value).await),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &'static str, required: bool, ok: bool) -> DependencyCheck {
+        DependencyCheck {
+            name,
+            required,
+            status: if ok {
+                CheckStatus::Ok
+            } else {
+                CheckStatus::Error
+            },
+            latency_ms: 1,
+            error: (!ok).then(|| "error".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_status_all_ok() {
+        let report = ReadinessReport::new(vec![
+            check("postgres", true, true),
+            check("smtp", false, true),
+        ]);
+        assert_eq!(report.status, ReadinessStatus::Ok);
+    }
+
+    #[test]
+    fn test_status_optional_failure_is_degraded() {
+        let report = ReadinessReport::new(vec![
+            check("postgres", true, true),
+            check("google", false, false),
+        ]);
+        assert_eq!(report.status, ReadinessStatus::Degraded);
+    }
+
+    #[test]
+    fn test_status_required_failure_is_unavailable() {
+        let report = ReadinessReport::new(vec![
+            check("postgres", true, true),
+            check("hydra", true, false),
+            check("google", false, false),
+        ]);
+        assert_eq!(report.status, ReadinessStatus::Unavailable);
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let report = ReadinessReport::new(vec![check("smtp", false, false)]);
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["status"], "degraded");
+        assert_eq!(json["checks"][0]["name"], "smtp");
+        assert_eq!(json["checks"][0]["status"], "error");
+        assert_eq!(json["checks"][0]["required"], false);
+    }
+}
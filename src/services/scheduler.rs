@@ -0,0 +1,250 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use sqlx::PgPool;
+use time::OffsetDateTime;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::error::AppError;
+use crate::repositories::ScheduledJobRepository;
+
+/// 定期ジョブの戻り値
+type JobFuture = Pin<Box<dyn Future<Output = Result<(), AppError>> + Send>>;
+
+/// 定期ジョブ
+#[derive(Clone)]
+struct Job {
+    name: &'static str,
+    interval: Duration,
+    run: Arc<dyn Fn() -> JobFuture + Send + Sync>,
+}
+
+/// プロセス内の定期ジョブスケジューラー
+///
+/// 複数レプリカで起動しても、各ジョブは Postgres のアドバイザリロックで同時に 1 つだけ実行し、
+/// `scheduled_jobs` の最終開始日時を共有して実行間隔内に 2 回以上実行しない
+pub struct Scheduler {
+    pool: PgPool,
+    job_repo: ScheduledJobRepository,
+    jobs: Vec<Job>,
+}
+
+/// 起動したスケジューラー（`shutdown` で停止）
+pub struct SchedulerHandle {
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Scheduler {
+    /// 新しい Scheduler を作成
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            job_repo: ScheduledJobRepository::new(pool.clone()),
+            pool,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// 定期ジョブを登録
+    ///
+    /// # Arguments
+    /// * `name` - ジョブ名（アドバイザリロックのキーと `scheduled_jobs` の主キー）
+    /// * `interval` - 実行間隔
+    pub fn job<F, Fut>(mut self, name: &'static str, interval: Duration, run: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+    {
+        self.jobs.push(Job {
+            name,
+            interval,
+            run: Arc::new(move || Box::pin(run())),
+        });
+        self
+    }
+
+    /// 各ジョブを起動
+    pub fn start(self) -> SchedulerHandle {
+        let (shutdown, _) = watch::channel(false);
+
+        let tasks = self
+            .jobs
+            .iter()
+            .map(|job| {
+                let runner = JobRunner {
+                    pool: self.pool.clone(),
+                    job_repo: self.job_repo.clone(),
+                    job: job.clone(),
+                };
+                tokio::spawn(runner.run(shutdown.subscribe()))
+            })
+            .collect();
+
+        tracing::info!(jobs = self.jobs.len(), "スケジューラーを起動");
+        SchedulerHandle { shutdown, tasks }
+    }
+}
+
+impl SchedulerHandle {
+    /// 新しいジョブの開始を止め、実行中のジョブの完了を待つ
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        for task in self.tasks {
+            if let Err(e) = task.await {
+                tracing::error!(error = ?e, "ジョブタスクが異常終了");
+            }
+        }
+        tracing::info!("スケジューラーを停止");
+    }
+}
+
+/// 1 つのジョブの実行ループ
+struct JobRunner {
+    pool: PgPool,
+    job_repo: ScheduledJobRepository,
+    job: Job,
+}
+
+impl JobRunner {
+    async fn run(self, mut shutdown: watch::Receiver<bool>) {
+        let mut interval = tokio::time::interval(self.job.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            // 実行中のジョブはキャンセルせず、次の実行前に停止を確認する
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.changed() => break,
+            }
+            if *shutdown.borrow() {
+                break;
+            }
+
+            if let Err(e) = self.run_once().await {
+                tracing::error!(job = self.job.name, error = ?e, "ジョブの実行に失敗");
+            }
+        }
+    }
+
+    /// アドバイザリロックを取得できた場合のみジョブを実行
+    ///
+    /// # Returns
+    /// 実行した場合は true（他のレプリカが実行中・実行間隔内の場合は false）
+    async fn run_once(&self) -> Result<bool, AppError> {
+        let name = self.job.name;
+        let key = advisory_lock_key(name);
+
+        // ロックはセッション単位のため、取得と解放は同じ接続で行う
+        let mut conn = self.pool.acquire().await?;
+        let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(key)
+            .fetch_one(&mut *conn)
+            .await?;
+        if !locked {
+            tracing::debug!(job = name, "他のレプリカが実行中のためスキップ");
+            return Ok(false);
+        }
+
+        let result = self.run_locked().await;
+
+        if let Err(e) = sqlx::query("SELECT pg_advisory_unlock($1)")
+            .bind(key)
+            .execute(&mut *conn)
+            .await
+        {
+            // 接続を破棄すればロックは解放される
+            tracing::warn!(job = name, error = ?e, "アドバイザリロックの解放に失敗");
+            conn.detach();
+        }
+
+        result
+    }
+
+    async fn run_locked(&self) -> Result<bool, AppError> {
+        let name = self.job.name;
+
+        if let Some(last_started_at) = self.job_repo.last_started_at(name).await?
+            && !is_due(
+                last_started_at,
+                OffsetDateTime::now_utc(),
+                self.job.interval,
+            )
+        {
+            tracing::debug!(job = name, "実行間隔内のためスキップ");
+            return Ok(false);
+        }
+
+        self.job_repo.record_started(name).await?;
+        tracing::debug!(job = name, "ジョブを開始");
+
+        // ジョブの panic でループが止まらないよう別タスクで実行
+        let outcome = match tokio::spawn((self.job.run)()).await {
+            Ok(outcome) => outcome,
+            Err(e) => Err(AppError::Internal(anyhow::anyhow!("job panicked: {}", e))),
+        };
+
+        let error = outcome.as_ref().err().map(|e| e.to_string());
+        self.job_repo
+            .record_finished(name, error.as_deref())
+            .await?;
+
+        outcome.map(|_| true)
+    }
+}
+
+/// 前回の開始から実行間隔が経過したか
+///
+/// レプリカ間のタイマーのずれで実行が 1 周期遅れないよう、間隔の 1 割を許容する
+fn is_due(last_started_at: OffsetDateTime, now: OffsetDateTime, interval: Duration) -> bool {
+    let elapsed = now - last_started_at;
+    elapsed >= interval.mul_f64(0.9)
+}
+
+/// ジョブ名からアドバイザリロックのキーを生成（FNV-1a、レプリカ間で同じ値になる）
+fn advisory_lock_key(name: &str) -> i64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = "oxgate:job:"
+        .bytes()
+        .chain(name.bytes())
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        });
+    hash as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due() {
+        let now = OffsetDateTime::now_utc();
+        let hour = Duration::from_secs(3600);
+
+        assert!(is_due(now - time::Duration::hours(2), now, hour));
+        assert!(is_due(now - time::Duration::hours(1), now, hour));
+        // タイマーのずれは許容
+        assert!(is_due(now - time::Duration::minutes(55), now, hour));
+        assert!(!is_due(now - time::Duration::minutes(30), now, hour));
+        // 時計のずれで未来の日時が記録されていても実行しない
+        assert!(!is_due(now + time::Duration::minutes(1), now, hour));
+    }
+
+    #[test]
+    fn test_advisory_lock_key() {
+        assert_eq!(
+            advisory_lock_key("expired_tokens"),
+            advisory_lock_key("expired_tokens")
+        );
+        assert_ne!(
+            advisory_lock_key("expired_tokens"),
+            advisory_lock_key("audit_retention")
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use std::net::IpAddr;
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::client_info::ClientInfo;
+use crate::config::SharedConfig;
+use crate::error::AppError;
+use crate::models::{AuditEvent, EmailMessage, User, UserNotificationSettings};
+use crate::repositories::{
+    AccountLockTokenRepository, UserNotificationSettingsRepository, UserRepository,
+};
+use crate::services::AuditService;
+use crate::services::hydra::HydraClient;
+
+/// 「心当たりがない」リンクでロックした場合のロック理由
+const LOCK_REASON_USER_REPORTED: &str = "user_reported";
+
+/// 既知の端末として扱うログイン履歴の期間（日）
+const KNOWN_DEVICE_WINDOW_DAYS: i64 = 90;
+
+/// 既知の端末の判定に使うログイン履歴の件数
+const KNOWN_DEVICE_HISTORY_LIMIT: i64 = 100;
+
+/// 同一ネットワークとみなすプレフィックス長（IPv4 / IPv6）
+const IPV4_NETWORK_PREFIX: u32 = 24;
+const IPV6_NETWORK_PREFIX: u32 = 48;
+
+/// セキュリティ通知の対象となる操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityEvent {
+    /// 新しい端末・場所からのログイン
+    NewDeviceLogin {
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    },
+    PasswordChanged,
+    PasswordReset,
+    TwoFactorEnabled,
+    TwoFactorDisabled,
+    SocialAccountLinked {
+        provider: String,
+    },
+    EmailChanged {
+        new_email: String,
+    },
+}
+
+impl SecurityEvent {
+    /// リクエスト元クライアントから新しい端末からのログイン通知を作成
+    pub fn new_device_login(client: &ClientInfo) -> Self {
+        Self::NewDeviceLogin {
+            ip_address: client.ip.map(|ip| ip.to_string()),
+            user_agent: client.user_agent.clone(),
+        }
+    }
+
+    /// ユーザーがこの種類の通知を受信する設定か
+    pub fn is_enabled(&self, settings: &UserNotificationSettings) -> bool {
+        match self {
+            Self::NewDeviceLogin { .. } => settings.new_device_login,
+            Self::PasswordChanged | Self::PasswordReset => settings.password_changed,
+            Self::TwoFactorEnabled | Self::TwoFactorDisabled => settings.two_factor_changed,
+            Self::SocialAccountLinked { .. } => settings.social_account_changed,
+            Self::EmailChanged { .. } => settings.email_changed,
+        }
+    }
+
+    /// 通知メールの本文
+    pub fn summary(&self) -> String {
+        match self {
+            Self::NewDeviceLogin {
+                ip_address,
+                user_agent,
+            } => format!(
+                "新しい端末からログインがありました（IP: {}、端末: {}）",
+                ip_address.as_deref().unwrap_or("不明"),
+                user_agent.as_deref().unwrap_or("不明")
+            ),
+            Self::PasswordChanged => "パスワードが変更されました".to_string(),
+            Self::PasswordReset => "パスワードがリセットされました".to_string(),
+            Self::TwoFactorEnabled => "二要素認証が有効になりました".to_string(),
+            Self::TwoFactorDisabled => "二要素認証が無効になりました".to_string(),
+            Self::SocialAccountLinked { provider } => {
+                format!("{} アカウントが連携されました", provider)
+            }
+            Self::EmailChanged { new_email } => {
+                format!("メールアドレスが {} に変更されました", new_email)
+            }
+        }
+    }
+}
+
+/// セキュリティ通知サービス
+///
+/// アカウントの重要な変更をメールで通知し、通知に含める「心当たりがない」リンク
+/// （アカウントロック）を処理する
+#[derive(Clone)]
+pub struct SecurityNotificationService {
+    user_repo: UserRepository,
+    settings_repo: UserNotificationSettingsRepository,
+    lock_token_repo: AccountLockTokenRepository,
+    audit_service: AuditService,
+    hydra_client: HydraClient,
+    config: SharedConfig,
+}
+
+impl SecurityNotificationService {
+    /// 新しい SecurityNotificationService を作成
+    pub fn new(
+        user_repo: UserRepository,
+        settings_repo: UserNotificationSettingsRepository,
+        lock_token_repo: AccountLockTokenRepository,
+        audit_service: AuditService,
+        hydra_client: HydraClient,
+        config: SharedConfig,
+    ) -> Self {
+        Self {
+            user_repo,
+            settings_repo,
+            lock_token_repo,
+            audit_service,
+            hydra_client,
+            config,
+        }
+    }
+
+    /// セキュリティ通知を送信
+    ///
+    /// 通知の失敗で本来の処理を失敗させないよう、エラーはログに残して無視する
+    pub async fn notify(&self, user: &User, event: SecurityEvent) {
+        if let Err(e) = self.try_notify(user, &event).await {
+            tracing::warn!(user_id = %user.id, error = ?e, "セキュリティ通知メールの送信キューへの追加に失敗");
+        }
+    }
+
+    async fn try_notify(&self, user: &User, event: &SecurityEvent) -> Result<(), AppError> {
+        let settings = self.settings(user.id).await?;
+        if !event.is_enabled(&settings) {
+            tracing::debug!(user_id = %user.id, "セキュリティ通知は無効に設定されています");
+            return Ok(());
+        }
+
+        // 「心当たりがない」リンク用のトークンを発行
+        let token = generate_token();
+        let config = self.config.load();
+        let expires_at =
+            OffsetDateTime::now_utc() + Duration::seconds(config.account_lock_token_ttl_secs);
+        let lock_url = build_lock_url(config.account_lock_url_base.as_deref(), &token);
+
+        // トークンの保存とメールの送信キューへの追加を同じトランザクションで行う
+        let message = EmailMessage::SecurityNotification {
+            to: user.email.clone(),
+            summary: event.summary(),
+            lock_url,
+        };
+        self.lock_token_repo
+            .create(user.id, &hash_token(&token), expires_at, &message.into())
+            .await?;
+        Ok(())
+    }
+
+    /// 通知設定を取得（未設定の場合はデフォルト）
+    pub async fn settings(&self, user_id: Uuid) -> Result<UserNotificationSettings, AppError> {
+        Ok(self
+            .settings_repo
+            .find_by_user_id(user_id)
+            .await?
+            .unwrap_or_else(|| UserNotificationSettings::defaults(user_id)))
+    }
+
+    /// 通知設定を保存
+    pub async fn update_settings(
+        &self,
+        settings: &UserNotificationSettings,
+    ) -> Result<UserNotificationSettings, AppError> {
+        Ok(self.settings_repo.upsert(settings).await?)
+    }
+
+    /// 新しい端末・場所からのログインか
+    ///
+    /// 直近のログイン履歴に同じ User-Agent かつ同一ネットワークからのログインがなければ
+    /// 新しい端末とみなす。初回ログインや判定できない場合は false。
+    /// 今回のログインを監査ログに記録する前に呼び出すこと
+    pub async fn is_new_device(&self, user_id: Uuid, client: &ClientInfo) -> bool {
+        if client.ip.is_none() && client.user_agent.is_none() {
+            return false;
+        }
+
+        let since = OffsetDateTime::now_utc() - Duration::days(KNOWN_DEVICE_WINDOW_DAYS);
+        match self
+            .audit_service
+            .recent_logins(user_id, since, KNOWN_DEVICE_HISTORY_LIMIT)
+            .await
+        {
+            Ok(history) => !history.is_empty() && !is_known_device(&history, client),
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = ?e, "ログイン履歴の取得に失敗");
+                false
+            }
+        }
+    }
+
+    /// 「心当たりがない」リンクからアカウントをロック
+    ///
+    /// アカウントをロックし、Hydra のログインセッションと同意を失効させる。
+    /// ロック解除は管理者が行う
+    ///
+    /// # Returns
+    /// ロックしたユーザーのID
+    ///
+    /// # Security
+    /// - トークンはログに出力しない
+    pub async fn lock_account(&self, token: &str) -> Result<Uuid, AppError> {
+        let token_hash = hash_token(token);
+        let Some(user_id) = self.lock_token_repo.consume(&token_hash).await? else {
+            // 使用できなかった理由をエラーで区別する
+            let lock_token = self
+                .lock_token_repo
+                .find_by_token_hash(&token_hash)
+                .await?
+                .ok_or(AppError::TokenNotFound)?;
+            tracing::warn!(token_id = %lock_token.id, "使用済みまたは期限切れのロックトークン");
+            return Err(AppError::TokenExpired);
+        };
+
+        self.user_repo
+            .lock(user_id, Some(LOCK_REASON_USER_REPORTED), None)
+            .await?;
+        self.lock_token_repo
+            .invalidate_all_for_user(user_id)
+            .await?;
+
+        tracing::warn!(user_id = %user_id, "ユーザー操作によりアカウントをロック");
+
+        // ロックは確定済みのため、失効の失敗はログに残す（ログインはロックで拒否される）
+        if let Err(e) = self
+            .hydra_client
+            .revoke_subject_sessions(&user_id.to_string())
+            .await
+        {
+            tracing::error!(user_id = %user_id, error = ?e, "アカウントロック後のセッション失効に失敗");
+        }
+
+        Ok(user_id)
+    }
+}
+
+/// 32バイトのランダムトークンを生成
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// トークンをSHA256でハッシュ化
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// アカウントロックURLを構築
+fn build_lock_url(base: Option<&str>, token: &str) -> String {
+    match base {
+        Some(base) => format!("{}?token={}", base, token),
+        None => format!("http://localhost:3000/account/lock?token={}", token),
+    }
+}
+
+/// ログイン履歴に同じ端末（User-Agent）・同一ネットワークからのログインがあるか
+fn is_known_device(history: &[AuditEvent], client: &ClientInfo) -> bool {
+    history.iter().any(|event| {
+        let same_agent = event.user_agent == client.user_agent;
+        let same_network = match (
+            event
+                .ip_address
+                .as_deref()
+                .and_then(|ip| ip.parse::<IpAddr>().ok()),
+            client.ip,
+        ) {
+            (Some(previous), Some(current)) => same_network(previous, current),
+            (None, None) => true,
+            _ => false,
+        };
+        same_agent && same_network
+    })
+}
+
+/// 同一ネットワーク（IPv4 /24、IPv6 /48）のアドレスか
+fn same_network(a: IpAddr, b: IpAddr) -> bool {
+    match (a, b) {
+        (IpAddr::V4(a), IpAddr::V4(b)) => {
+            let mask = u32::MAX << (32 - IPV4_NETWORK_PREFIX);
+            u32::from(a) & mask == u32::from(b) & mask
+        }
+        (IpAddr::V6(a), IpAddr::V6(b)) => {
+            let mask = u128::MAX << (128 - IPV6_NETWORK_PREFIX);
+            u128::from(a) & mask == u128::from(b) & mask
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn login_event(ip: Option<&str>, user_agent: Option<&str>) -> AuditEvent {
+        AuditEvent {
+            id: Uuid::new_v4(),
+            user_id: Some(Uuid::new_v4()),
+            actor: None,
+            event_type: "login".to_string(),
+            outcome: "success".to_string(),
+            ip_address: ip.map(str::to_string),
+            user_agent: user_agent.map(str::to_string),
+            metadata: serde_json::json!({}),
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    fn client(ip: &str, user_agent: &str) -> ClientInfo {
+        ClientInfo {
+            ip: Some(ip.parse().unwrap()),
+            user_agent: Some(user_agent.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_same_network() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        assert!(same_network(ip("203.0.113.7"), ip("203.0.113.200")));
+        assert!(!same_network(ip("203.0.113.7"), ip("203.0.114.7")));
+        assert!(same_network(ip("2001:db8:1::1"), ip("2001:db8:1:ffff::2")));
+        assert!(!same_network(ip("2001:db8:1::1"), ip("2001:db8:2::1")));
+        assert!(!same_network(ip("203.0.113.7"), ip("::ffff:203.0.113.7")));
+    }
+
+    #[test]
+    fn test_known_device() {
+        let history = vec![
+            login_event(Some("203.0.113.7"), Some("Firefox")),
+            login_event(Some("198.51.100.1"), Some("Safari")),
+        ];
+
+        // 同じ端末・同一ネットワーク（IP は変わっている）
+        assert!(is_known_device(
+            &history,
+            &client("203.0.113.50", "Firefox")
+        ));
+        // 同じ端末だが別のネットワーク
+        assert!(!is_known_device(&history, &client("192.0.2.1", "Firefox")));
+        // 既知のネットワークだが別の端末
+        assert!(!is_known_device(&history, &client("203.0.113.7", "Chrome")));
+    }
+
+    #[test]
+    fn test_event_toggles() {
+        let mut settings = UserNotificationSettings::defaults(Uuid::new_v4());
+        assert!(SecurityEvent::PasswordReset.is_enabled(&settings));
+
+        settings.password_changed = false;
+        assert!(!SecurityEvent::PasswordChanged.is_enabled(&settings));
+        assert!(!SecurityEvent::PasswordReset.is_enabled(&settings));
+        assert!(SecurityEvent::TwoFactorDisabled.is_enabled(&settings));
+    }
+
+    #[test]
+    fn test_build_lock_url() {
+        assert_eq!(
+            build_lock_url(Some("https://example.com/account/lock"), "abc"),
+            "https://example.com/account/lock?token=abc"
+        );
+        assert!(build_lock_url(None, "abc").ends_with("?token=abc"));
+    }
+
+    #[test]
+    fn test_token_hash_is_deterministic() {
+        let token = generate_token();
+        assert_eq!(token.len(), 43);
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), hash_token(&generate_token()));
+    }
+}
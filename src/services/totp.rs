@@ -1,22 +1,20 @@
-use aes_gcm::{
-    Aes256Gcm, KeyInit, Nonce,
-    aead::{Aead, OsRng},
-};
 use data_encoding::BASE32;
 use rand::RngCore;
 use totp_rs::{Algorithm, TOTP};
 
 use crate::error::AppError;
+use crate::services::keyring::Keyring;
 
 /// TOTP (Time-based One-Time Password) サービス
 ///
 /// # Security
 /// - シークレットはAES-256-GCMで暗号化してDB保存
+/// - 暗号文にはキーIDが付与され、キーローテーションに対応
 /// - シークレット平文はログに出力しない
 #[derive(Clone)]
 pub struct TotpService {
     issuer: String,
-    encryption_key: [u8; 32],
+    keyring: Keyring,
 }
 
 impl TotpService {
@@ -26,31 +24,19 @@ impl TotpService {
     /// * `issuer` - TOTP発行者名（アプリ名）
     /// * `encryption_key_base64` - Base64エンコードされた32バイトの暗号化キー
     pub fn new(issuer: String, encryption_key_base64: &str) -> Result<Self, AppError> {
-        use base64::{Engine as _, engine::general_purpose::STANDARD};
-
-        let key_bytes = STANDARD.decode(encryption_key_base64).map_err(|e| {
-            tracing::error!(error = ?e, "TOTP暗号化キーのBase64デコードエラー");
-            AppError::Internal(anyhow::anyhow!("invalid encryption key format"))
-        })?;
-
-        if key_bytes.len() != 32 {
-            tracing::error!(
-                expected = 32,
-                actual = key_bytes.len(),
-                "TOTP暗号化キーの長さが不正"
-            );
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "encryption key must be 32 bytes"
-            )));
-        }
-
-        let mut encryption_key = [0u8; 32];
-        encryption_key.copy_from_slice(&key_bytes);
-
-        Ok(Self {
+        Ok(Self::with_keyring(
             issuer,
-            encryption_key,
-        })
+            Keyring::from_single(encryption_key_base64)?,
+        ))
+    }
+
+    /// キーリングを指定して TotpService を作成
+    ///
+    /// # Arguments
+    /// * `issuer` - TOTP発行者名（アプリ名）
+    /// * `keyring` - シークレット暗号化用キーリング（最新キーで暗号化）
+    pub fn with_keyring(issuer: String, keyring: Keyring) -> Self {
+        Self { issuer, keyring }
     }
 
     /// 20バイトのランダムシークレットを生成し、Base32でエンコード
@@ -63,50 +49,17 @@ impl TotpService {
     /// シークレットをAES-256-GCMで暗号化
     ///
     /// # Returns
-    /// 96ビットnonce (12バイト) + 暗号文
+    /// キーID (1バイト) + 96ビットnonce (12バイト) + 暗号文
     pub fn encrypt_secret(&self, secret: &str) -> Result<Vec<u8>, AppError> {
-        let cipher = Aes256Gcm::new_from_slice(&self.encryption_key).map_err(|e| {
-            tracing::error!(error = ?e, "AES-GCM暗号化器の初期化エラー");
-            AppError::Internal(anyhow::anyhow!("cipher initialization error"))
-        })?;
-
-        // 96ビット (12バイト) のランダムnonce生成
-        let mut nonce_bytes = [0u8; 12];
-        OsRng.fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
-
-        let ciphertext = cipher.encrypt(nonce, secret.as_bytes()).map_err(|e| {
-            tracing::error!(error = ?e, "シークレット暗号化エラー");
-            AppError::Internal(anyhow::anyhow!("encryption error"))
-        })?;
-
-        // nonce + ciphertext を結合
-        let mut result = Vec::with_capacity(12 + ciphertext.len());
-        result.extend_from_slice(&nonce_bytes);
-        result.extend_from_slice(&ciphertext);
-
-        Ok(result)
+        self.keyring.encrypt(secret.as_bytes())
     }
 
     /// 暗号化されたシークレットを復号
+    ///
+    /// キーリング内の全キーを試す（ローテーション前の暗号文にも対応）
     pub fn decrypt_secret(&self, encrypted: &[u8]) -> Result<String, AppError> {
-        if encrypted.len() < 12 {
-            tracing::error!(len = encrypted.len(), "暗号化データが短すぎる");
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "encrypted data too short"
-            )));
-        }
-
-        let cipher = Aes256Gcm::new_from_slice(&self.encryption_key).map_err(|e| {
-            tracing::error!(error = ?e, "AES-GCM暗号化器の初期化エラー");
-            AppError::Internal(anyhow::anyhow!("cipher initialization error"))
-        })?;
-
-        let (nonce_bytes, ciphertext) = encrypted.split_at(12);
-        let nonce = Nonce::from_slice(nonce_bytes);
-
-        let plaintext = cipher.decrypt(nonce, ciphertext).map_err(|e| {
-            tracing::error!(error = ?e, "シークレット復号エラー");
+        let (plaintext, _) = self.keyring.decrypt(encrypted).ok_or_else(|| {
+            tracing::error!(len = encrypted.len(), "シークレット復号エラー");
             AppError::Internal(anyhow::anyhow!("decryption error"))
         })?;
 
@@ -116,6 +69,17 @@ impl TotpService {
         })
     }
 
+    /// 暗号文が最新キー以外で暗号化されているか
+    pub fn needs_reencryption(&self, encrypted: &[u8]) -> bool {
+        self.keyring.needs_reencryption(encrypted)
+    }
+
+    /// シークレットを最新キーで再暗号化
+    pub fn reencrypt_secret(&self, encrypted: &[u8]) -> Result<Vec<u8>, AppError> {
+        let secret = self.decrypt_secret(encrypted)?;
+        self.encrypt_secret(&secret)
+    }
+
     /// QRコードを生成（PNG形式、Base64エンコード）
     ///
     /// # Arguments
@@ -240,6 +204,27 @@ mod tests {
         assert_eq!(original, decrypted);
     }
 
+    #[test]
+    fn test_reencrypt_secret_with_rotated_key() {
+        let old_keyring = Keyring::parse(&format!("1:{}", STANDARD.encode([1u8; 32]))).unwrap();
+        let old_service = TotpService::with_keyring("TestApp".to_string(), old_keyring);
+        let original = TotpService::generate_secret();
+        let encrypted = old_service.encrypt_secret(&original).unwrap();
+
+        let keyring = Keyring::parse(&format!(
+            "1:{},2:{}",
+            STANDARD.encode([1u8; 32]),
+            STANDARD.encode([2u8; 32])
+        ))
+        .unwrap();
+        let service = TotpService::with_keyring("TestApp".to_string(), keyring);
+        assert!(service.needs_reencryption(&encrypted));
+
+        let reencrypted = service.reencrypt_secret(&encrypted).unwrap();
+        assert!(!service.needs_reencryption(&reencrypted));
+        assert_eq!(service.decrypt_secret(&reencrypted).unwrap(), original);
+    }
+
     #[test]
     fn test_generate_qr_code() {
         let service = create_test_service();
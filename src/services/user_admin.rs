@@ -0,0 +1,294 @@
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{EmailMessage, User, UserStatus};
+use crate::repositories::{User2faSecretRepository, UserRepository, UserSocialAccountRepository};
+use crate::services::hydra::HydraClient;
+use crate::services::{OutboxService, PasswordResetService};
+
+/// 管理 API のユーザー一覧の 1 件
+#[derive(Debug, Serialize)]
+pub struct AdminUserSummary {
+    pub id: Uuid,
+    pub email: String,
+    pub has_password: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub deletion_scheduled_at: Option<OffsetDateTime>,
+    /// 現在のアカウント状態（期限切れのロックは active）
+    pub status: UserStatus,
+    pub lock_reason: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub locked_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub locked_until: Option<OffsetDateTime>,
+}
+
+impl From<&User> for AdminUserSummary {
+    fn from(user: &User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email.clone(),
+            has_password: user.password_hash.is_some(),
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            deletion_scheduled_at: user.deletion_scheduled_at,
+            status: user.current_status(),
+            lock_reason: user.lock_reason.clone(),
+            locked_at: user.locked_at,
+            locked_until: user.locked_until,
+        }
+    }
+}
+
+/// 管理 API のユーザー詳細
+#[derive(Debug, Serialize)]
+pub struct AdminUserDetail {
+    #[serde(flatten)]
+    pub user: AdminUserSummary,
+    pub two_factor: AdminTwoFactorState,
+    pub social_accounts: Vec<AdminSocialAccount>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTwoFactorState {
+    /// 2FA シークレットが登録済みか（未検証を含む）
+    pub configured: bool,
+    pub enabled: bool,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub configured_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSocialAccount {
+    pub provider: String,
+    pub email: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub linked_at: OffsetDateTime,
+}
+
+/// ユーザー検索結果
+#[derive(Debug)]
+pub struct UserPage {
+    pub users: Vec<AdminUserSummary>,
+    pub total: i64,
+}
+
+/// 管理者によるユーザー管理サービス
+#[derive(Clone)]
+pub struct UserAdminService {
+    user_repo: UserRepository,
+    user_2fa_repo: User2faSecretRepository,
+    social_account_repo: UserSocialAccountRepository,
+    hydra_client: HydraClient,
+    outbox_service: OutboxService,
+    password_reset_service: PasswordResetService,
+}
+
+impl UserAdminService {
+    /// 新しい UserAdminService を作成
+    pub fn new(
+        user_repo: UserRepository,
+        user_2fa_repo: User2faSecretRepository,
+        social_account_repo: UserSocialAccountRepository,
+        hydra_client: HydraClient,
+        outbox_service: OutboxService,
+        password_reset_service: PasswordResetService,
+    ) -> Self {
+        Self {
+            user_repo,
+            user_2fa_repo,
+            social_account_repo,
+            hydra_client,
+            outbox_service,
+            password_reset_service,
+        }
+    }
+
+    /// ユーザーを検索（メールアドレスの部分一致またはユーザーID、アカウント状態）
+    pub async fn search(
+        &self,
+        query: Option<&str>,
+        status: Option<UserStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<UserPage, AppError> {
+        let users = self.user_repo.search(query, status, limit, offset).await?;
+        let total = self.user_repo.count(query, status).await?;
+
+        Ok(UserPage {
+            users: users.iter().map(Into::into).collect(),
+            total,
+        })
+    }
+
+    /// ユーザーを取得
+    pub async fn find_user(&self, user_id: Uuid) -> Result<User, AppError> {
+        self.user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::UserNotFound)
+    }
+
+    /// ユーザー詳細（2FA・ソーシャルアカウントの連携状態を含む）
+    pub async fn detail(&self, user: &User) -> Result<AdminUserDetail, AppError> {
+        let two_factor = match self.user_2fa_repo.find_by_user_id(user.id).await? {
+            Some(secret) => AdminTwoFactorState {
+                configured: true,
+                enabled: secret.enabled,
+                configured_at: Some(secret.created_at),
+            },
+            None => AdminTwoFactorState {
+                configured: false,
+                enabled: false,
+                configured_at: None,
+            },
+        };
+
+        let social_accounts = self
+            .social_account_repo
+            .find_by_user_id(user.id)
+            .await?
+            .into_iter()
+            .map(|account| AdminSocialAccount {
+                provider: account.provider,
+                email: account.email,
+                linked_at: account.created_at,
+            })
+            .collect();
+
+        Ok(AdminUserDetail {
+            user: user.into(),
+            two_factor,
+            social_accounts,
+        })
+    }
+
+    /// アカウントをロックし、ログインセッションと同意を失効
+    ///
+    /// `until` を指定した場合はその日時に自動で解除される
+    ///
+    /// # Returns
+    /// ロックした場合は true（既にロック済み・無効化済みの場合は false）
+    pub async fn lock(
+        &self,
+        user: &User,
+        reason: Option<&str>,
+        until: Option<OffsetDateTime>,
+    ) -> Result<bool, AppError> {
+        let locked = self.user_repo.lock(user.id, reason, until).await?;
+        // ロック済みでも失効は再試行する（前回の失効失敗に備える）
+        self.revoke_sessions(user).await?;
+
+        if locked {
+            tracing::info!(user_id = %user.id, "管理者がアカウントをロック");
+        }
+        Ok(locked)
+    }
+
+    /// アカウントのロックを解除
+    ///
+    /// # Returns
+    /// 解除した場合は true（ロックされていない場合は false）
+    pub async fn unlock(&self, user: &User) -> Result<bool, AppError> {
+        let unlocked = self.user_repo.unlock(user.id).await?;
+        if unlocked {
+            tracing::info!(user_id = %user.id, "管理者がアカウントのロックを解除");
+        }
+        Ok(unlocked)
+    }
+
+    /// アカウントを無効化し、ログインセッションと同意を失効
+    ///
+    /// # Returns
+    /// 無効化した場合は true（既に無効化済みの場合は false）
+    pub async fn disable(&self, user: &User, reason: Option<&str>) -> Result<bool, AppError> {
+        let disabled = self.user_repo.disable(user.id, reason).await?;
+        self.revoke_sessions(user).await?;
+
+        if disabled {
+            tracing::info!(user_id = %user.id, "管理者がアカウントを無効化");
+        }
+        Ok(disabled)
+    }
+
+    /// 無効化したアカウントを有効に戻す
+    ///
+    /// # Returns
+    /// 有効化した場合は true（無効化されていない場合は false）
+    pub async fn enable(&self, user: &User) -> Result<bool, AppError> {
+        let enabled = self.user_repo.enable(user.id).await?;
+        if enabled {
+            tracing::info!(user_id = %user.id, "管理者がアカウントを有効化");
+        }
+        Ok(enabled)
+    }
+
+    /// ログインセッションと同意を全て失効（強制ログアウト）
+    pub async fn revoke_sessions(&self, user: &User) -> Result<(), AppError> {
+        self.hydra_client
+            .revoke_subject_sessions(&user.id.to_string())
+            .await
+    }
+
+    /// 2FA をリセット（シークレットを削除し、次回から再設定を可能にする）
+    ///
+    /// # Returns
+    /// 2FA が設定されていた場合は true
+    pub async fn reset_two_factor(&self, user: &User) -> Result<bool, AppError> {
+        if self.user_2fa_repo.find_by_user_id(user.id).await?.is_none() {
+            return Ok(false);
+        }
+
+        self.user_2fa_repo.delete(user.id).await?;
+        tracing::info!(user_id = %user.id, "管理者が2FAをリセット");
+        Ok(true)
+    }
+
+    /// パスワードリセットを強制
+    ///
+    /// 現在のパスワードを無効化してセッションを失効させ、リセットメールを送信する
+    pub async fn force_password_reset(&self, user: &User) -> Result<(), AppError> {
+        self.user_repo.clear_password(user.id).await?;
+        self.revoke_sessions(user).await?;
+        self.password_reset_service
+            .request_reset(&user.email)
+            .await?;
+
+        tracing::info!(user_id = %user.id, "管理者がパスワードリセットを強制");
+        Ok(())
+    }
+
+    /// アカウントを即時削除（猶予期間なし）
+    ///
+    /// Hydra の失効に失敗した場合は削除しない（トークンが残らないよう再試行させる）
+    ///
+    /// # Returns
+    /// 削除した場合は true
+    pub async fn delete(&self, user: &User) -> Result<bool, AppError> {
+        self.revoke_sessions(user).await?;
+
+        if !self.user_repo.delete(user.id).await? {
+            return Ok(false);
+        }
+
+        tracing::info!(user_id = %user.id, "管理者がアカウントを削除");
+
+        if let Err(e) = self
+            .outbox_service
+            .enqueue(EmailMessage::AccountDeleted {
+                to: user.email.clone(),
+            })
+            .await
+        {
+            tracing::warn!(user_id = %user.id, error = ?e, "アカウント削除完了メールの送信キューへの追加に失敗");
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,382 @@
+use std::time::Duration;
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use data_encoding::HEXLOWER;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::{
+    WebhookDelivery, WebhookDeliveryStatus, WebhookEvent, WebhookEventType, WebhookSubscription,
+};
+use crate::repositories::WebhookRepository;
+use crate::services::keyring::Keyring;
+
+/// 1 回の配信のタイムアウト
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 署名ヘッダー（`t=<UNIX 時刻>,v1=<HMAC-SHA256 の16進数>`）
+pub const SIGNATURE_HEADER: &str = "X-Oxgate-Signature";
+
+/// イベント種別ヘッダー
+pub const EVENT_HEADER: &str = "X-Oxgate-Event";
+
+/// 配信IDヘッダー
+pub const DELIVERY_HEADER: &str = "X-Oxgate-Delivery";
+
+/// URL の最大文字数
+const MAX_URL_LENGTH: usize = 2048;
+
+/// 作成した購読設定と署名用シークレット（シークレットは作成時のみ返却）
+#[derive(Debug)]
+pub struct CreatedWebhook {
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+/// Webhook サービス
+///
+/// ライフサイクルイベントを購読設定ごとの配信ログにし、送信キュー経由で配信する。
+/// 再試行・バックオフは `OutboxWorker` が行う
+#[derive(Clone)]
+pub struct WebhookService {
+    repo: WebhookRepository,
+    keyring: Keyring,
+    http_client: reqwest::Client,
+}
+
+impl WebhookService {
+    /// 新しい WebhookService を作成
+    ///
+    /// # Arguments
+    /// * `keyring` - 署名用シークレットの暗号化キーリング（`ENCRYPTION_KEYS`）
+    pub fn new(repo: WebhookRepository, keyring: Keyring) -> Result<Self, AppError> {
+        // リダイレクト先に署名付きのペイロードを送らない（3xx は失敗として再試行）
+        let http_client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+
+        Ok(Self {
+            repo,
+            keyring,
+            http_client,
+        })
+    }
+
+    /// イベントを通知（購読設定ごとに送信キューに追加）
+    ///
+    /// 通知の失敗で本来の処理を失敗させないよう、エラーはログに残して無視する
+    pub async fn emit(&self, event: WebhookEvent) {
+        match self.repo.create_deliveries(&event).await {
+            Ok(delivery_ids) if !delivery_ids.is_empty() => {
+                tracing::debug!(
+                    event_id = %event.id,
+                    event_type = event.event_type.as_str(),
+                    deliveries = delivery_ids.len(),
+                    "Webhook イベントを送信キューに追加"
+                );
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(
+                    event_id = %event.id,
+                    event_type = event.event_type.as_str(),
+                    error = ?e,
+                    "Webhook イベントの送信キューへの追加に失敗"
+                );
+            }
+        }
+    }
+
+    /// 購読設定を作成し、署名用シークレットを生成
+    pub async fn create_subscription(
+        &self,
+        url: &str,
+        event_types: &[WebhookEventType],
+        description: Option<&str>,
+    ) -> Result<CreatedWebhook, AppError> {
+        validate_url(url)?;
+        if event_types.is_empty() {
+            return Err(AppError::Validation(
+                "イベント種別を 1 つ以上指定してください".to_string(),
+            ));
+        }
+
+        let mut names: Vec<String> = event_types.iter().map(|t| t.as_str().to_string()).collect();
+        names.sort();
+        names.dedup();
+
+        let secret = generate_secret();
+        let secret_encrypted = self.keyring.encrypt(secret.as_bytes())?;
+        let subscription = self
+            .repo
+            .create_subscription(url, &names, &secret_encrypted, description)
+            .await?;
+
+        tracing::info!(subscription_id = %subscription.id, "Webhook の購読設定を作成");
+        Ok(CreatedWebhook {
+            subscription,
+            secret,
+        })
+    }
+
+    /// 購読設定の一覧
+    pub async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, AppError> {
+        Ok(self.repo.list_subscriptions().await?)
+    }
+
+    /// 購読設定の有効・無効を切り替え
+    pub async fn set_enabled(
+        &self,
+        id: Uuid,
+        enabled: bool,
+    ) -> Result<WebhookSubscription, AppError> {
+        let subscription = self
+            .repo
+            .set_enabled(id, enabled)
+            .await?
+            .ok_or(AppError::WebhookNotFound)?;
+        tracing::info!(subscription_id = %id, enabled, "Webhook の購読設定を更新");
+        Ok(subscription)
+    }
+
+    /// 購読設定と配信ログを削除
+    pub async fn delete_subscription(&self, id: Uuid) -> Result<(), AppError> {
+        if !self.repo.delete_subscription(id).await? {
+            return Err(AppError::WebhookNotFound);
+        }
+        tracing::info!(subscription_id = %id, "Webhook の購読設定を削除");
+        Ok(())
+    }
+
+    /// 配信ログを新しい順に取得
+    pub async fn list_deliveries(
+        &self,
+        subscription_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, AppError> {
+        Ok(self
+            .repo
+            .list_deliveries(subscription_id, limit, offset)
+            .await?)
+    }
+
+    /// 配信済み・失敗した配信を同じイベントで再送
+    ///
+    /// # Returns
+    /// 新しい配信ログ（イベントIDは元の配信と同じ）
+    pub async fn replay(&self, delivery_id: Uuid) -> Result<WebhookDelivery, AppError> {
+        let delivery = self
+            .repo
+            .replay_delivery(delivery_id)
+            .await?
+            .ok_or(AppError::WebhookNotFound)?;
+        tracing::info!(
+            delivery_id = %delivery_id,
+            replay_id = %delivery.id,
+            "Webhook の配信を再送"
+        );
+        Ok(delivery)
+    }
+
+    /// 指定日時より前の配信ログを削除（配信待ちのものは残す）
+    pub async fn purge_deliveries(&self, before: OffsetDateTime) -> Result<u64, AppError> {
+        let deleted = self.repo.delete_deliveries_before(before).await?;
+        if deleted > 0 {
+            tracing::info!(deleted, "古い Webhook の配信ログを削除");
+        }
+        Ok(deleted)
+    }
+
+    /// 配信ログのイベントを購読先に送信（送信キューのワーカーから呼び出す）
+    ///
+    /// 2xx 以外の応答・接続エラーはエラーを返し、ワーカーが再試行する
+    ///
+    /// # Security
+    /// - 署名用シークレットはログに出力しない
+    pub async fn deliver(&self, delivery_id: Uuid) -> Result<(), AppError> {
+        // 購読設定ごと削除された・配信済みの場合は何もしない
+        let Some(delivery) = self.repo.find_delivery(delivery_id).await? else {
+            return Ok(());
+        };
+        if delivery.status == WebhookDeliveryStatus::Succeeded.as_str() {
+            return Ok(());
+        }
+        let Some(subscription) = self
+            .repo
+            .find_subscription(delivery.subscription_id)
+            .await?
+        else {
+            return Ok(());
+        };
+        if !subscription.enabled {
+            tracing::info!(delivery_id = %delivery_id, "無効な購読設定のため配信しません");
+            self.repo.mark_failed(delivery_id).await?;
+            return Ok(());
+        }
+
+        let (secret, _) = self
+            .keyring
+            .decrypt(&subscription.secret_encrypted)
+            .ok_or_else(|| {
+                tracing::error!(subscription_id = %subscription.id, "Webhook シークレット復号エラー");
+                AppError::Internal(anyhow::anyhow!("webhook secret decryption error"))
+            })?;
+
+        let body = serde_json::to_vec(&delivery.payload)
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("webhook payload error: {}", e)))?;
+        let signature = sign(&secret, OffsetDateTime::now_utc().unix_timestamp(), &body);
+
+        let result = self
+            .http_client
+            .post(&subscription.url)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, &delivery.event_type)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .timeout(DELIVERY_TIMEOUT)
+            .body(body)
+            .send()
+            .await;
+
+        let (response_status, error) = match &result {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16()), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16()),
+                Some(format!("HTTP {}", response.status())),
+            ),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        self.repo
+            .record_attempt(
+                delivery_id,
+                response_status.map(i32::from),
+                error.as_deref(),
+            )
+            .await?;
+
+        match error {
+            None => Ok(()),
+            Some(error) => Err(AppError::Internal(anyhow::anyhow!(
+                "webhook delivery failed: {}",
+                error
+            ))),
+        }
+    }
+
+    /// 再試行回数を超えた配信を失敗にする（送信キューのワーカーから呼び出す）
+    pub async fn mark_failed(&self, delivery_id: Uuid) -> Result<(), AppError> {
+        Ok(self.repo.mark_failed(delivery_id).await?)
+    }
+}
+
+/// 配信先 URL を検証（http / https のみ）
+fn validate_url(url: &str) -> Result<(), AppError> {
+    if url.len() > MAX_URL_LENGTH {
+        return Err(AppError::Validation(format!(
+            "URL は {} 文字以内で指定してください",
+            MAX_URL_LENGTH
+        )));
+    }
+
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|_| AppError::Validation("URL の形式が正しくありません".to_string()))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(AppError::Validation(
+            "URL は http または https で指定してください".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 署名ヘッダーの値を計算
+///
+/// 署名対象は `<UNIX 時刻>.<リクエストボディ>`。受信側は時刻を確認してリプレイを防ぐ
+fn sign(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!(
+        "t={},v1={}",
+        timestamp,
+        HEXLOWER.encode(&mac.finalize().into_bytes())
+    )
+}
+
+/// 署名用シークレットを生成（32バイトのランダム値、Base64URL）
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        // HMAC-SHA256("secret", "1700000000.{}")
+        assert_eq!(
+            sign(b"secret", 1700000000, b"{}"),
+            "t=1700000000,v1=b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+        );
+        assert_ne!(
+            sign(b"secret", 1700000001, b"{}"),
+            sign(b"secret", 1700000000, b"{}")
+        );
+    }
+
+    #[test]
+    fn test_validate_url() {
+        assert!(validate_url("https://example.com/hooks/oxgate").is_ok());
+        assert!(validate_url("http://localhost:8080/hook").is_ok());
+        assert!(validate_url("ftp://example.com/hook").is_err());
+        assert!(validate_url("not a url").is_err());
+        assert!(validate_url(&format!("https://example.com/{}", "a".repeat(2048))).is_err());
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 43);
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[test]
+    fn test_event_schema() {
+        let user_id = Uuid::new_v4();
+        let event = WebhookEvent::new(WebhookEventType::UserEmailChanged, user_id)
+            .email("new@example.com")
+            .previous_email("old@example.com");
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "user.email_changed");
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["data"]["user_id"], user_id.to_string());
+        assert_eq!(json["data"]["email"], "new@example.com");
+        assert_eq!(json["data"]["previous_email"], "old@example.com");
+        // 該当しない項目は出力しない
+        assert!(json["data"].get("provider").is_none());
+
+        let decoded: WebhookEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, event);
+    }
+
+    #[test]
+    fn test_event_type_names() {
+        for event_type in WebhookEventType::ALL {
+            assert_eq!(
+                serde_json::to_value(event_type).unwrap(),
+                event_type.as_str()
+            );
+        }
+    }
+}
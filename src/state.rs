@@ -1,80 +1,50 @@
 use std::sync::Arc;
 
+use arc_swap::ArcSwap;
 use sqlx::PgPool;
 
-use crate::config::Config;
+use crate::config::{Config, SharedConfig};
+use crate::email_address::DisposableDomains;
 use crate::error::AppError;
 use crate::repositories::{
-    PasswordResetTokenRepository, User2faSecretRepository, UserRepository,
-    UserSocialAccountRepository,
+    AccountLockTokenRepository, AuditEventRepository, OutboxRepository,
+    PasswordResetTokenRepository, User2faSecretRepository, UserNotificationSettingsRepository,
+    UserProfileRepository, UserRepository, UserSocialAccountRepository, WebhookRepository,
 };
 use crate::services::hydra::HydraClient;
-use crate::services::{EmailService, GitHubOAuthService, OAuthService, TotpService};
+use crate::services::{
+    AuditService, EmailService, GitHubOAuthService, OAuthService, OutboxService,
+    PasswordPolicyService, ProfileService, ReadinessService, SecurityNotificationService,
+    TotpService, WebhookService,
+};
 use secrecy::ExposeSecret;
 
-/// アプリケーション共有状態
-///
-/// axum の State として全ハンドラーで共有される。
-/// Clone は必須（axum が内部で clone するため）。
-#[derive(Clone)]
-pub struct AppState {
-    /// PostgreSQL コネクションプール
-    pub db_pool: PgPool,
-    /// Hydra Admin API クライアント
-    pub hydra_client: HydraClient,
-    /// アプリケーション設定（Arc で共有）
-    pub config: Arc<Config>,
-    /// ユーザーリポジトリ
-    pub user_repo: UserRepository,
-    /// パスワードリセットトークンリポジトリ
-    pub token_repo: PasswordResetTokenRepository,
-    /// メールサービス
-    pub email_service: EmailService,
-    /// 2FAシークレットリポジトリ
-    pub user_2fa_repo: User2faSecretRepository,
-    /// TOTPサービス
-    pub totp_service: TotpService,
-    /// ソーシャルアカウントリポジトリ
-    pub social_account_repo: UserSocialAccountRepository,
+/// ソーシャルログインプロバイダー（設定リロード時に差し替え可能）
+#[derive(Clone, Default)]
+pub struct OAuthProviders {
     /// Google OAuth サービス（設定されている場合のみ）
-    pub google_oauth_service: Option<OAuthService>,
+    pub google: Option<OAuthService>,
     /// GitHub OAuth サービス（設定されている場合のみ）
-    pub github_oauth_service: Option<GitHubOAuthService>,
+    pub github: Option<GitHubOAuthService>,
 }
 
-impl AppState {
-    /// 新しい AppState を作成
-    pub fn new(
-        db_pool: PgPool,
-        hydra_client: HydraClient,
-        config: Config,
-    ) -> Result<Self, AppError> {
-        let config = Arc::new(config);
-        let user_repo = UserRepository::new(db_pool.clone());
-        let token_repo = PasswordResetTokenRepository::new(db_pool.clone());
-        let email_service = EmailService::new(config.clone());
-        let user_2fa_repo = User2faSecretRepository::new(db_pool.clone());
-        let totp_service = TotpService::new(
-            config.totp_issuer.clone(),
-            config.encryption_key.expose_secret(),
-        )?;
-
-        let social_account_repo = UserSocialAccountRepository::new(db_pool.clone());
-
+impl OAuthProviders {
+    /// 設定からプロバイダーを構築
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
         // Google OAuth サービス（設定されている場合のみ初期化）
-        let google_oauth_service = match (
+        let google = match (
             &config.google_client_id,
             &config.google_client_secret,
             &config.google_redirect_uri,
         ) {
             (Some(client_id), Some(client_secret), Some(redirect_uri)) => {
                 tracing::info!("Google OAuth サービスを初期化");
-                Some(OAuthService::new(
+                Some(OAuthService::with_keyring(
                     client_id.clone(),
                     client_secret.expose_secret().clone(),
                     redirect_uri.clone(),
-                    config.oauth_state_secret.expose_secret(),
-                )?)
+                    config.oauth_state_keyring()?,
+                ))
             }
             _ => {
                 tracing::info!("Google OAuth 未設定（スキップ）");
@@ -83,19 +53,19 @@ impl AppState {
         };
 
         // GitHub OAuth サービス（設定されている場合のみ初期化）
-        let github_oauth_service = match (
+        let github = match (
             &config.github_client_id,
             &config.github_client_secret,
             &config.github_redirect_uri,
         ) {
             (Some(client_id), Some(client_secret), Some(redirect_uri)) => {
                 tracing::info!("GitHub OAuth サービスを初期化");
-                Some(GitHubOAuthService::new(
+                Some(GitHubOAuthService::with_keyring(
                     client_id.clone(),
                     client_secret.expose_secret().clone(),
                     redirect_uri.clone(),
-                    config.oauth_state_secret.expose_secret(),
-                )?)
+                    config.oauth_state_keyring()?,
+                ))
             }
             _ => {
                 tracing::info!("GitHub OAuth 未設定（スキップ）");
@@ -103,18 +73,124 @@ impl AppState {
             }
         };
 
+        Ok(Self { google, github })
+    }
+}
+
+/// アプリケーション共有状態
+///
+/// axum の State として全ハンドラーで共有される。
+/// Clone は必須（axum が内部で clone するため）。
+#[derive(Clone)]
+pub struct AppState {
+    /// PostgreSQL コネクションプール
+    pub db_pool: PgPool,
+    /// 読み取り専用クエリ用のコネクションプール（リードレプリカ未設定の場合は `db_pool` と同じ）
+    pub db_read_pool: PgPool,
+    /// Hydra Admin API クライアント
+    pub hydra_client: HydraClient,
+    /// アプリケーション設定（ホットリロードで差し替え可能）
+    pub config: SharedConfig,
+    /// ユーザーリポジトリ
+    pub user_repo: UserRepository,
+    /// パスワードリセットトークンリポジトリ
+    pub token_repo: PasswordResetTokenRepository,
+    /// メールサービス（送信キューの配信ワーカーが使用）
+    pub email_service: EmailService,
+    /// 送信キュー（outbox）サービス
+    pub outbox_service: OutboxService,
+    /// Webhook サービス
+    pub webhook_service: WebhookService,
+    /// 2FAシークレットリポジトリ
+    pub user_2fa_repo: User2faSecretRepository,
+    /// TOTPサービス
+    pub totp_service: TotpService,
+    /// ソーシャルアカウントリポジトリ
+    pub social_account_repo: UserSocialAccountRepository,
+    /// ソーシャルログインプロバイダー（ホットリロードで差し替え可能）
+    pub oauth_providers: Arc<ArcSwap<OAuthProviders>>,
+    /// パスワードポリシーサービス
+    pub password_policy: PasswordPolicyService,
+    /// 監査ログサービス
+    pub audit_service: AuditService,
+    /// セキュリティ通知サービス
+    pub security_notifications: SecurityNotificationService,
+    /// プロフィールサービス
+    pub profile_service: ProfileService,
+    /// 使い捨てメールドメインリスト（設定リロード時に再読み込み）
+    pub disposable_domains: Arc<ArcSwap<DisposableDomains>>,
+    /// レディネス確認サービス
+    pub readiness: ReadinessService,
+}
+
+impl AppState {
+    /// 新しい AppState を作成
+    ///
+    /// # Arguments
+    /// * `db_read_pool` - リードレプリカのプール（None の場合は `db_pool` を使う）
+    pub fn new(
+        db_pool: PgPool,
+        db_read_pool: Option<PgPool>,
+        hydra_client: HydraClient,
+        config: Config,
+    ) -> Result<Self, AppError> {
+        let read_pool = db_read_pool.clone().unwrap_or_else(|| db_pool.clone());
+        let user_repo = UserRepository::new(db_pool.clone()).with_read_pool(read_pool.clone());
+        let token_repo = PasswordResetTokenRepository::new(db_pool.clone());
+        let user_2fa_repo = User2faSecretRepository::new(db_pool.clone());
+        // TOTP シークレット・Webhook の署名用シークレットは同じキーリングで暗号化する
+        let encryption_keyring = config.totp_keyring()?;
+        let totp_service =
+            TotpService::with_keyring(config.totp_issuer.clone(), encryption_keyring.clone());
+
+        let social_account_repo = UserSocialAccountRepository::new(db_pool.clone());
+
+        let oauth_providers = OAuthProviders::from_config(&config)?;
+        let disposable_domains = DisposableDomains::from_config(&config)?;
+        let audit_service = AuditService::new(AuditEventRepository::new(db_pool.clone()));
+
+        let config = SharedConfig::new(config);
+        let email_service = EmailService::new(config.clone());
+        let password_policy = PasswordPolicyService::new(config.clone());
+        let security_notifications = SecurityNotificationService::new(
+            user_repo.clone(),
+            UserNotificationSettingsRepository::new(db_pool.clone()),
+            AccountLockTokenRepository::new(db_pool.clone()),
+            audit_service.clone(),
+            hydra_client.clone(),
+            config.clone(),
+        );
+        let profile_service = ProfileService::new(UserProfileRepository::new(db_pool.clone()));
+        let outbox_service = OutboxService::new(OutboxRepository::new(db_pool.clone()));
+        let webhook_service =
+            WebhookService::new(WebhookRepository::new(db_pool.clone()), encryption_keyring)?;
+        let readiness = ReadinessService::new(
+            db_pool.clone(),
+            db_read_pool,
+            hydra_client.clone(),
+            config.clone(),
+        )?;
+
         Ok(Self {
             db_pool,
+            db_read_pool: read_pool,
             hydra_client,
             config,
             user_repo,
             token_repo,
             email_service,
+            outbox_service,
+            webhook_service,
             user_2fa_repo,
             totp_service,
             social_account_repo,
-            google_oauth_service,
-            github_oauth_service,
+            oauth_providers: Arc::new(ArcSwap::from_pointee(oauth_providers)),
+            password_policy,
+            audit_service,
+            security_notifications,
+            profile_service,
+            disposable_domains: Arc::new(ArcSwap::from_pointee(disposable_domains)),
+            readiness,
         })
     }
 }
@@ -0,0 +1,278 @@
+//! ログ・分散トレーシング（OpenTelemetry）
+//!
+//! リクエストごとに `http_request` スパンを作成し、リクエストID と W3C Trace Context
+//! （`traceparent`）を引き継ぐ。`OTEL_EXPORTER_OTLP_ENDPOINT` が設定されている場合は
+//! スパンを OTLP/HTTP でコレクターに送信する。
+//! Hydra・ソーシャルログインプロバイダーの呼び出しと DB クエリは子スパンとして記録される
+
+use std::time::Duration;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::{
+    Context, global,
+    trace::{TraceContextExt, TracerProvider as _},
+};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    propagation::TraceContextPropagator,
+    trace::{Sampler, SdkTracer, SdkTracerProvider},
+};
+use tracing::{Instrument, Span, field};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::{
+    EnvFilter, Registry, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+use uuid::Uuid;
+
+use crate::config::Config;
+
+/// リクエストID のヘッダー
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// 受け付けるリクエストID の最大長
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// コレクターへの送信のタイムアウト
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+type OtelLayer = OpenTelemetryLayer<Registry, SdkTracer>;
+
+/// 初期化済みのログ出力（OTLP 送信は設定読み込み後に `enable_otlp` で有効化する）
+pub struct Tracing {
+    otel: reload::Handle<Option<OtelLayer>, Registry>,
+}
+
+/// OTLP 送信の停止用（`shutdown` で未送信のスパンを送信する）
+pub struct OtlpExporter {
+    provider: SdkTracerProvider,
+}
+
+/// ログ出力を初期化（JSON形式、環境変数 `RUST_LOG` でレベル制御）
+///
+/// 設定の読み込み前にログを出力できるよう、OTLP 送信は後から差し込む
+pub fn init_tracing() -> Tracing {
+    let env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info,oxgate=debug"));
+    let (otel, handle) = reload::Layer::new(None);
+
+    tracing_subscriber::registry()
+        .with(otel)
+        .with(env_filter)
+        .with(tracing_subscriber::fmt::layer().json())
+        .init();
+
+    // OTLP 送信の有無に関わらず traceparent を引き継ぐ（ログの trace_id に使う）
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    Tracing { otel: handle }
+}
+
+impl Tracing {
+    /// 設定に従って OTLP 送信を有効化
+    ///
+    /// # Returns
+    /// 送信先が設定されていない場合は None
+    pub fn enable_otlp(&self, config: &Config) -> anyhow::Result<Option<OtlpExporter>> {
+        let Some(endpoint) = &config.otel_exporter_otlp_endpoint else {
+            return Ok(None);
+        };
+
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(traces_endpoint(endpoint))
+            .with_timeout(EXPORT_TIMEOUT)
+            .build()?;
+
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                config.otel_traces_sample_ratio,
+            ))))
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(config.otel_service_name.clone())
+                    .build(),
+            )
+            .build();
+
+        let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+        self.otel
+            .reload(Some(tracing_opentelemetry::layer().with_tracer(tracer)))?;
+
+        tracing::info!(
+            endpoint = %endpoint,
+            service_name = %config.otel_service_name,
+            sample_ratio = config.otel_traces_sample_ratio,
+            "OTLP トレース送信を有効化"
+        );
+        Ok(Some(OtlpExporter { provider }))
+    }
+}
+
+impl OtlpExporter {
+    /// 未送信のスパンを送信して停止
+    pub async fn shutdown(self) {
+        let provider = self.provider;
+        let result = tokio::task::spawn_blocking(move || provider.shutdown()).await;
+        match result {
+            Ok(Ok(())) => tracing::info!("OTLP トレース送信を停止"),
+            Ok(Err(e)) => tracing::warn!(error = %e, "OTLP トレース送信の停止に失敗"),
+            Err(e) => tracing::warn!(error = ?e, "OTLP トレース送信の停止に失敗"),
+        }
+    }
+}
+
+/// ベース URL からトレースの送信先 URL を組み立てる
+fn traces_endpoint(endpoint: &str) -> String {
+    format!("{}/v1/traces", endpoint.trim_end_matches('/'))
+}
+
+/// リクエストごとのスパンを作成するミドルウェア
+///
+/// - `X-Request-Id` を引き継ぐ（ないか不正な場合は生成）し、レスポンスにも付ける
+/// - `traceparent` があれば呼び出し元のトレースの子スパンにする
+pub async fn trace_request(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(parse_request_id)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let span = tracing::info_span!(
+        "http_request",
+        otel.name = %request.method(),
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+        http.request.method = %request.method(),
+        url.path = %request.uri().path(),
+        http.route = field::Empty,
+        http.response.status_code = field::Empty,
+        request_id = %request_id,
+        trace_id = field::Empty,
+    );
+
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    // OTLP 送信が無効な場合は失敗するが、trace_id は traceparent から引き継ぐ
+    let _ = span.set_parent(parent.clone());
+    if let Some(trace_id) = trace_id(&span.context()).or_else(|| trace_id(&parent)) {
+        span.record("trace_id", trace_id);
+    }
+
+    let mut response = next.run(request).instrument(span.clone()).await;
+
+    let status = response.status();
+    span.record("http.response.status_code", status.as_u16());
+    if status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// マッチしたルートをリクエストのスパンに記録するミドルウェア
+///
+/// `MatchedPath` はルーティング後にしか取得できないため、`route_layer` で適用する
+pub async fn record_route(request: Request, next: Next) -> Response {
+    if let Some(route) = request.extensions().get::<MatchedPath>() {
+        let span = Span::current();
+        span.record("http.route", route.as_str());
+        span.record(
+            "otel.name",
+            format!("{} {}", request.method(), route.as_str()),
+        );
+    }
+    next.run(request).await
+}
+
+/// 外部サービスに送るトレースコンテキストのヘッダー（`traceparent`）
+///
+/// # Security
+/// 内部のサービス（Hydra）にのみ付ける。外部のプロバイダーにはトレースID を送らない
+pub fn trace_context_headers(span: &Span) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&span.context(), &mut HeaderInjector(&mut headers))
+    });
+    headers
+}
+
+/// コンテキストのトレースID（有効な場合のみ）
+fn trace_id(cx: &Context) -> Option<String> {
+    let span = cx.span();
+    let span_context = span.span_context();
+    span_context
+        .is_valid()
+        .then(|| span_context.trace_id().to_string())
+}
+
+/// 受け取ったリクエストID を検証
+///
+/// ログに出力するため、空白・制御文字を含むものや長すぎるものは受け付けない
+fn parse_request_id(value: &HeaderValue) -> Option<String> {
+    let value = value.to_str().ok()?;
+    let valid = !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LENGTH
+        && value.chars().all(|c| c.is_ascii_graphic());
+    valid.then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::propagation::TextMapPropagator;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_request_id() {
+        let parse = |value: &str| parse_request_id(&HeaderValue::from_str(value).unwrap());
+
+        assert_eq!(parse("abc-123").as_deref(), Some("abc-123"));
+        assert_eq!(
+            parse("5b0c6f1e-8d0a-4a53-9b3e-2f1d7c1e9a10").as_deref(),
+            Some("5b0c6f1e-8d0a-4a53-9b3e-2f1d7c1e9a10")
+        );
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("has space"), None);
+        assert_eq!(parse(&"a".repeat(MAX_REQUEST_ID_LENGTH + 1)), None);
+    }
+
+    #[test]
+    fn test_traces_endpoint() {
+        assert_eq!(
+            traces_endpoint("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("http://collector:4318/"),
+            "http://collector:4318/v1/traces"
+        );
+    }
+
+    #[test]
+    fn test_trace_id_from_traceparent() {
+        let propagator = TraceContextPropagator::new();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+        );
+
+        let cx = propagator.extract(&HeaderExtractor(&headers));
+        assert_eq!(
+            trace_id(&cx).as_deref(),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+        assert_eq!(trace_id(&Context::new()), None);
+    }
+}
@@ -0,0 +1,306 @@
+//! TLS 終端（rustls）
+//!
+//! 証明書チェーンと秘密鍵（PEM）を読み込み、ALPN で HTTP/2 と HTTP/1.1 をネゴシエートする。
+//! 証明書ファイルは `RELOAD_INTERVAL` ごとに更新を確認し、変更されていれば再起動せずに
+//! 差し替える（読み込みに失敗した場合は現在の証明書を使い続ける）。
+//! 管理 API のリスナーではクライアント証明書を必須にできる（mTLS）
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use arc_swap::ArcSwap;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+use crate::config::Config;
+
+/// 証明書ファイルの更新を確認する間隔
+const RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// ALPN で提示するプロトコル（優先順）
+const ALPN_PROTOCOLS: [&[u8]; 2] = [b"h2", b"http/1.1"];
+
+/// TLS 設定エラー
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("ファイルの読み込みに失敗: {path}: {message}")]
+    Load { path: String, message: String },
+
+    #[error("TLS 設定が不正です: {0}")]
+    Rustls(#[from] rustls::Error),
+
+    #[error("クライアント証明書の検証設定が不正です: {0}")]
+    ClientVerifier(#[from] rustls::server::VerifierBuilderError),
+}
+
+impl TlsError {
+    fn load(path: &Path, message: impl ToString) -> Self {
+        Self::Load {
+            path: path.display().to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// サーバー証明書（ファイルの更新に追従する）
+#[derive(Debug)]
+pub struct CertResolver {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    provider: Arc<CryptoProvider>,
+    current: ArcSwap<CertifiedKey>,
+    /// 読み込んだ時点の証明書・秘密鍵ファイルの更新日時
+    modified: Mutex<(Option<SystemTime>, Option<SystemTime>)>,
+}
+
+impl CertResolver {
+    fn load(
+        cert_path: PathBuf,
+        key_path: PathBuf,
+        provider: Arc<CryptoProvider>,
+    ) -> Result<Self, TlsError> {
+        let modified = modified_times(&cert_path, &key_path);
+        let key = load_certified_key(&cert_path, &key_path, &provider)?;
+
+        Ok(Self {
+            cert_path,
+            key_path,
+            provider,
+            current: ArcSwap::from_pointee(key),
+            modified: Mutex::new(modified),
+        })
+    }
+
+    /// 証明書・秘密鍵ファイルが更新されていれば読み込み直す
+    ///
+    /// # Returns
+    /// 差し替えた場合は true
+    ///
+    /// # Errors
+    /// 読み込みに失敗した場合は現在の証明書を使い続け、次回の確認で再試行する
+    /// （証明書と秘密鍵の更新の間に確認した場合など）
+    pub fn reload_if_changed(&self) -> Result<bool, TlsError> {
+        let modified = modified_times(&self.cert_path, &self.key_path);
+        let mut last = self.modified.lock().unwrap_or_else(|e| e.into_inner());
+        if *last == modified {
+            return Ok(false);
+        }
+
+        let key = load_certified_key(&self.cert_path, &self.key_path, &self.provider)?;
+        self.current.store(Arc::new(key));
+        *last = modified;
+        Ok(true)
+    }
+}
+
+impl ResolvesServerCert for CertResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.load_full())
+    }
+}
+
+/// リスナーの TLS 設定
+#[derive(Clone)]
+pub struct Tls {
+    resolver: Arc<CertResolver>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl Tls {
+    /// 設定から証明書を読み込む
+    ///
+    /// # Returns
+    /// `TLS_CERT_PATH` / `TLS_KEY_PATH` が設定されていない場合は None（平文の HTTP で待ち受ける）
+    pub fn from_config(config: &Config) -> Result<Option<Self>, TlsError> {
+        let (Some(cert_path), Some(key_path)) = (&config.tls_cert_path, &config.tls_key_path)
+        else {
+            return Ok(None);
+        };
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let resolver = CertResolver::load(cert_path.into(), key_path.into(), provider.clone())?;
+        Ok(Some(Self {
+            resolver: Arc::new(resolver),
+            provider,
+        }))
+    }
+
+    /// クライアント証明書を要求しない TLS の acceptor
+    pub fn acceptor(&self) -> Result<TlsAcceptor, TlsError> {
+        let config = ServerConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_cert_resolver(self.resolver.clone());
+        Ok(acceptor(config))
+    }
+
+    /// クライアント証明書を必須とする TLS（mTLS）の acceptor
+    ///
+    /// # Arguments
+    /// * `client_ca_path` - クライアント証明書を発行した CA 証明書（PEM、複数可）
+    pub fn acceptor_with_client_auth(&self, client_ca_path: &str) -> Result<TlsAcceptor, TlsError> {
+        let path = Path::new(client_ca_path);
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_file_iter(path).map_err(|e| TlsError::load(path, e))? {
+            roots.add(cert.map_err(|e| TlsError::load(path, e))?)?;
+        }
+
+        let verifier =
+            WebPkiClientVerifier::builder_with_provider(Arc::new(roots), self.provider.clone())
+                .build()?;
+        let config = ServerConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()?
+            .with_client_cert_verifier(verifier)
+            .with_cert_resolver(self.resolver.clone());
+        Ok(acceptor(config))
+    }
+
+    /// 証明書ファイルの更新を定期的に確認するタスクを起動
+    pub fn spawn_reloader(&self) {
+        let resolver = self.resolver.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                match resolver.reload_if_changed() {
+                    Ok(true) => tracing::info!(
+                        path = %resolver.cert_path.display(),
+                        "TLS 証明書を再読み込み"
+                    ),
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!(
+                        error = %e,
+                        "TLS 証明書の再読み込みに失敗（現在の証明書を継続使用）"
+                    ),
+                }
+            }
+        });
+    }
+}
+
+fn acceptor(mut config: ServerConfig) -> TlsAcceptor {
+    config.alpn_protocols = ALPN_PROTOCOLS.iter().map(|p| p.to_vec()).collect();
+    TlsAcceptor::from(Arc::new(config))
+}
+
+/// 証明書チェーンと秘密鍵を読み込み、対応していることを確認する
+fn load_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+    provider: &CryptoProvider,
+) -> Result<CertifiedKey, TlsError> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| TlsError::load(cert_path, e))?;
+    if certs.is_empty() {
+        return Err(TlsError::load(cert_path, "証明書が含まれていません"));
+    }
+
+    // 秘密鍵のパースエラーには鍵の内容を含めない
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|_| TlsError::load(key_path, "PEM 形式の秘密鍵として読み込めません"))?;
+    let signing_key = provider
+        .key_provider
+        .load_private_key(key)
+        .map_err(|e| TlsError::load(key_path, e))?;
+
+    let certified = CertifiedKey::new(certs, signing_key);
+    certified
+        .keys_match()
+        .map_err(|e| TlsError::load(cert_path, e))?;
+    Ok(certified)
+}
+
+fn modified_times(cert_path: &Path, key_path: &Path) -> (Option<SystemTime>, Option<SystemTime>) {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    (modified(cert_path), modified(key_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rcgen::{CertificateParams, KeyPair};
+
+    use super::*;
+
+    /// 自己署名証明書を一時ディレクトリに書き出す
+    fn write_cert(dir: &Path, name: &str) -> (PathBuf, PathBuf) {
+        let key = KeyPair::generate().unwrap();
+        let cert = CertificateParams::new(vec![name.to_string()])
+            .unwrap()
+            .self_signed(&key)
+            .unwrap();
+
+        let cert_path = dir.join("cert.pem");
+        let key_path = dir.join("key.pem");
+        fs::write(&cert_path, cert.pem()).unwrap();
+        fs::write(&key_path, key.serialize_pem()).unwrap();
+        (cert_path, key_path)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("oxgate-tls-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn provider() -> Arc<CryptoProvider> {
+        Arc::new(rustls::crypto::ring::default_provider())
+    }
+
+    #[test]
+    fn test_reload_if_changed() {
+        let dir = temp_dir("reload");
+        let (cert_path, key_path) = write_cert(&dir, "a.example.com");
+        let resolver = CertResolver::load(cert_path.clone(), key_path.clone(), provider()).unwrap();
+        let original = resolver.current.load_full();
+
+        assert!(!resolver.reload_if_changed().unwrap());
+
+        // 更新日時の比較が確実に変わるよう、記録済みの値を消してから差し替える
+        write_cert(&dir, "b.example.com");
+        *resolver.modified.lock().unwrap() = (None, None);
+        assert!(resolver.reload_if_changed().unwrap());
+        assert_ne!(resolver.current.load().cert, original.cert);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_reload_keeps_current_certificate_on_error() {
+        let dir = temp_dir("invalid");
+        let (cert_path, key_path) = write_cert(&dir, "a.example.com");
+        let resolver = CertResolver::load(cert_path.clone(), key_path, provider()).unwrap();
+        let original = resolver.current.load_full();
+
+        fs::write(&cert_path, "not a certificate").unwrap();
+        *resolver.modified.lock().unwrap() = (None, None);
+        assert!(resolver.reload_if_changed().is_err());
+        assert_eq!(resolver.current.load().cert, original.cert);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_mismatched_key_is_rejected() {
+        let dir = temp_dir("mismatch");
+        let (cert_path, _) = write_cert(&dir, "a.example.com");
+        let other = temp_dir("mismatch-other");
+        let (_, other_key_path) = write_cert(&other, "b.example.com");
+
+        let result = CertResolver::load(cert_path, other_key_path, provider());
+        assert!(matches!(result, Err(TlsError::Load { .. })));
+
+        fs::remove_dir_all(dir).unwrap();
+        fs::remove_dir_all(other).unwrap();
+    }
+}